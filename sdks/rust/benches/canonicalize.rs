@@ -0,0 +1,61 @@
+//! Canonicalizing a typed bundle through a `serde_json::Value` tree vs.
+//! the single-pass [`dcp_ai::fast_canon`] serializer, with plain
+//! `serde_json::to_string` as the floor.
+//!
+//! ```text
+//! cargo bench --bench canonicalize --features fast-canon
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use dcp_ai::bundle::{AuditEntryFields, BundleBuilder};
+use dcp_ai::canonicalize;
+use dcp_ai::fast_canon::to_canonical_string;
+use dcp_ai::fixtures::{generate_fixtures, GOLDEN_SEED};
+use dcp_ai::types::{AuditEvidence, CitizenshipBundle};
+
+fn bundle(entries: usize) -> CitizenshipBundle {
+    let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+    let base: CitizenshipBundle =
+        serde_json::from_value(set.get("citizenship_bundle").unwrap().value.clone()).unwrap();
+    let mut builder = BundleBuilder::new()
+        .responsible_principal_record(base.responsible_principal_record)
+        .agent_passport(base.agent_passport.clone())
+        .intent(base.intent)
+        .policy_decision(base.policy_decision);
+    for i in 0..entries {
+        builder = builder
+            .create_audit_entry(AuditEntryFields {
+                audit_id: format!("audit-{:05}", i),
+                timestamp: "2026-01-01T00:00:00Z".into(),
+                agent_id: base.agent_passport.agent_id.clone(),
+                human_id: base.agent_passport.principal_binding_reference.clone(),
+                policy_decision: "approved".into(),
+                outcome: "success".into(),
+                evidence: AuditEvidence { tool: Some("bench".into()), result_ref: None, tombstone: None },
+            })
+            .unwrap();
+    }
+    builder.build().unwrap()
+}
+
+fn bench_canonicalize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("canonicalize");
+    for entries in [10, 100, 1000, 10_000] {
+        let b = bundle(entries);
+        assert_eq!(to_canonical_string(&b).unwrap(), canonicalize(&serde_json::to_value(&b).unwrap()));
+        group.bench_with_input(BenchmarkId::new("value_tree", entries), &b, |bench, b| {
+            bench.iter(|| canonicalize(&serde_json::to_value(black_box(b)).unwrap()))
+        });
+        group.bench_with_input(BenchmarkId::new("fast_canon", entries), &b, |bench, b| {
+            bench.iter(|| to_canonical_string(black_box(b)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("to_string", entries), &b, |bench, b| {
+            bench.iter(|| serde_json::to_string(black_box(b)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_canonicalize);
+criterion_main!(benches);
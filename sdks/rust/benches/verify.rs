@@ -0,0 +1,62 @@
+//! Full verification vs. the `quick_verify` pre-filter, and a reused
+//! [`Verifier`] with pooled canonicalization buffers.
+//!
+//! ```text
+//! cargo bench --bench verify
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use serde_json::Value;
+
+use dcp_ai::bundle::{sign_bundle, AuditEntryFields, BundleBuilder, SignOptions};
+use dcp_ai::crypto::generate_keypair;
+use dcp_ai::fixtures::{generate_fixtures, GOLDEN_SEED};
+use dcp_ai::types::{AuditEvidence, CitizenshipBundle};
+use dcp_ai::{quick_verify, verify_signed_bundle, Verifier};
+
+fn signed_bundle(entries: usize) -> Value {
+    let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+    let base: CitizenshipBundle =
+        serde_json::from_value(set.get("citizenship_bundle").unwrap().value.clone()).unwrap();
+    let mut builder = BundleBuilder::new()
+        .responsible_principal_record(base.responsible_principal_record)
+        .agent_passport(base.agent_passport.clone())
+        .intent(base.intent)
+        .policy_decision(base.policy_decision);
+    for i in 0..entries {
+        builder = builder
+            .create_audit_entry(AuditEntryFields {
+                audit_id: format!("audit-{:05}", i),
+                timestamp: "2026-01-01T00:00:00Z".into(),
+                agent_id: base.agent_passport.agent_id.clone(),
+                human_id: base.agent_passport.principal_binding_reference.clone(),
+                policy_decision: "approved".into(),
+                outcome: "success".into(),
+                evidence: AuditEvidence { tool: Some("bench".into()), result_ref: None, tombstone: None },
+            })
+            .unwrap();
+    }
+    let (_, sk) = generate_keypair();
+    serde_json::to_value(sign_bundle(builder.build().unwrap(), &SignOptions::new(&sk)).unwrap()).unwrap()
+}
+
+fn bench_verify(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verify");
+    let verifier = Verifier::default();
+    for entries in [10, 100, 1000] {
+        let sb = signed_bundle(entries);
+        group.bench_with_input(BenchmarkId::new("full", entries), &sb, |b, sb| {
+            b.iter(|| verify_signed_bundle(black_box(sb), None))
+        });
+        group.bench_with_input(BenchmarkId::new("pooled", entries), &sb, |b, sb| {
+            b.iter(|| verifier.verify(black_box(sb), None))
+        });
+        group.bench_with_input(BenchmarkId::new("quick", entries), &sb, |b, sb| {
+            b.iter(|| quick_verify(black_box(sb), None))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_verify);
+criterion_main!(benches);
@@ -0,0 +1,126 @@
+//! Legal documents anchored to a Responsible Principal Record.
+//!
+//! A v1.1 record can list the paperwork behind the binding (terms of
+//! liability, an insurance certificate) in
+//! [`ResponsiblePrincipalRecord::agreements`], each with the SHA-256 of
+//! the document. The record signature covers the list, so
+//! [`verify_agreements`] only has to fetch each document and compare
+//! hashes. Fetching is pluggable: HTTP, an object store, or a local
+//! archive all implement [`DocumentFetcher`].
+//!
+//! ```
+//! use dcp_ai::agreements::verify_agreements;
+//! use dcp_ai::types::Agreement;
+//!
+//! let terms = b"The principal accepts liability for ...";
+//! let agreement = Agreement::for_document("terms_of_liability", "https://example.com/terms.pdf", terms);
+//! let fetch = |_uri: &str| Ok::<_, String>(terms.to_vec());
+//! assert!(verify_agreements(&[agreement], &fetch).verified);
+//! ```
+
+use crate::types::{Agreement, ResponsiblePrincipalRecord, VerificationFailure, VerificationResult, VerifyError};
+use crate::v2::dual_hash::sha256_hex;
+
+/// Retrieves the bytes of a document by its [`Agreement::uri`].
+pub trait DocumentFetcher {
+    fn fetch(&self, uri: &str) -> Result<Vec<u8>, String>;
+}
+
+impl<F> DocumentFetcher for F
+where
+    F: Fn(&str) -> Result<Vec<u8>, String>,
+{
+    fn fetch(&self, uri: &str) -> Result<Vec<u8>, String> {
+        self(uri)
+    }
+}
+
+/// Fetch every agreement and check it against its recorded hash.
+///
+/// A document that hashes differently fails with `AGREEMENT MISMATCH`;
+/// one that cannot be fetched fails with `AGREEMENT UNAVAILABLE`, which a
+/// caller may retry. Pointers are relative to the record
+/// (`/agreements/<i>`).
+pub fn verify_agreements(agreements: &[Agreement], fetcher: &dyn DocumentFetcher) -> VerificationResult {
+    let mut failures = Vec::new();
+    for (i, agreement) in agreements.iter().enumerate() {
+        let pointer = format!("/agreements/{}", i);
+        match fetcher.fetch(&agreement.uri) {
+            Ok(bytes) => {
+                let actual = sha256_hex(&bytes);
+                if !actual.eq_ignore_ascii_case(&agreement.sha256) {
+                    failures.push(
+                        VerificationFailure::new(
+                            VerifyError::AgreementMismatch,
+                            format!("{} at {}", agreement.doc_type, agreement.uri),
+                            format!("{}/sha256", pointer),
+                        )
+                        .expected(agreement.sha256.clone())
+                        .actual(actual),
+                    );
+                }
+            }
+            Err(e) => failures.push(VerificationFailure::new(
+                VerifyError::AgreementUnavailable,
+                format!("{} at {}: {}", agreement.doc_type, agreement.uri, e),
+                format!("{}/uri", pointer),
+            )),
+        }
+    }
+    if failures.is_empty() {
+        VerificationResult::ok()
+    } else {
+        VerificationResult::fail_at(failures)
+    }
+}
+
+/// [`verify_agreements`] over a record's agreements.
+pub fn verify_record_agreements(
+    record: &ResponsiblePrincipalRecord,
+    fetcher: &dyn DocumentFetcher,
+) -> VerificationResult {
+    verify_agreements(&record.agreements, fetcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::crypto::generate_keypair;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+    use crate::issue::{sign_record, verify_record_signature};
+    use crate::types::CitizenshipBundle;
+
+    #[test]
+    fn test_fetched_documents_must_match_recorded_hashes() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let bundle: CitizenshipBundle =
+            serde_json::from_value(set.get("citizenship_bundle").unwrap().value.clone()).unwrap();
+        let (pk, sk) = generate_keypair();
+        let mut record = bundle
+            .responsible_principal_record
+            .with_agreement(Agreement::for_document("terms_of_liability", "mem://terms", b"terms v1"))
+            .with_agreement(Agreement::for_document("insurance_certificate", "mem://insurance", b"policy 42"));
+        record.signature = sign_record(&record, &sk).unwrap();
+        assert!(verify_record_signature(&record, &pk));
+
+        let mut store: HashMap<&'static str, &'static [u8]> = HashMap::from([
+            ("mem://terms", &b"terms v1"[..]),
+            ("mem://insurance", &b"policy 42"[..]),
+        ]);
+        fn fetch(store: &HashMap<&'static str, &'static [u8]>) -> impl Fn(&str) -> Result<Vec<u8>, String> {
+            let store = store.clone();
+            move |uri| store.get(uri).map(|b| b.to_vec()).ok_or_else(|| "not found".to_string())
+        }
+        assert!(verify_record_agreements(&record, &fetch(&store)).verified);
+
+        store.insert("mem://terms", b"terms v2");
+        store.remove("mem://insurance");
+        let result = verify_record_agreements(&record, &fetch(&store));
+        let errors = result.errors.unwrap();
+        assert!(errors[0].starts_with("AGREEMENT MISMATCH: terms_of_liability"));
+        assert!(errors[1].starts_with("AGREEMENT UNAVAILABLE: insurance_certificate"));
+        assert_eq!(result.failures.unwrap()[0].pointer, "/agreements/0/sha256");
+    }
+}
@@ -0,0 +1,120 @@
+//! Intent amendments (v1.1).
+//!
+//! An agent that revises an intent mid-flight records an
+//! [`IntentAmendment`]: the fields it changed, the intent hash before and
+//! after, and its signature. The bundle keeps the original `intent` (the
+//! one the policy decision covered) and lists amendments in
+//! `intent_amendments`, oldest first.
+//!
+//! Each amendment is announced in the audit chain by an entry with outcome
+//! [`AMENDED_OUTCOME`] and `evidence.result_ref` set to the amendment id.
+//! That entry still carries the pre-amendment `intent_hash`; entries after
+//! it carry the amended one. [`check_lineage`] verifies the amendment chain
+//! itself; the verifier enforces the audit-entry ordering.
+//!
+//! `dcp_version`, `intent_id`, `agent_id`, and `human_id` cannot be
+//! amended, and the validity window is always read from the original
+//! intent.
+
+use serde_json::{Map, Value};
+
+use crate::crypto::{intent_hash, Canon, Canonicalizer};
+use crate::issue::{sign_record, verify_record_signature};
+use crate::types::{Intent, IntentAmendment, SignatureB64, VerificationFailure, VerifyError};
+use crate::v2::lifecycle::utc_now_iso_pub as utc_now_iso;
+
+/// Outcome of the audit entry announcing an amendment.
+pub const AMENDED_OUTCOME: &str = "intent_amended";
+
+const FROZEN_FIELDS: &[&str] = &["dcp_version", "intent_id", "agent_id", "human_id"];
+
+/// `intent` with `changes` applied to its top-level fields.
+pub fn apply_changes(intent: &Value, changes: &Map<String, Value>) -> Result<Value, String> {
+    if let Some(field) = changes.keys().find(|k| FROZEN_FIELDS.contains(&k.as_str())) {
+        return Err(format!("{} cannot be amended", field));
+    }
+    let mut amended = intent.clone();
+    let obj = amended.as_object_mut().ok_or("intent is not an object")?;
+    for (k, v) in changes {
+        obj.insert(k.clone(), v.clone());
+    }
+    Ok(amended)
+}
+
+/// Amend `current` (the latest revision of the intent), signing with the
+/// agent's key. Returns the amended intent and the amendment.
+pub fn create_amendment(
+    current: &Intent,
+    amendment_id: &str,
+    changes: Map<String, Value>,
+    agent_secret_key_b64: &str,
+) -> Result<(Intent, IntentAmendment), String> {
+    let before = serde_json::to_value(current).map_err(|e| e.to_string())?;
+    let after = apply_changes(&before, &changes)?;
+    let amended: Intent = serde_json::from_value(after.clone()).map_err(|e| format!("amended intent: {}", e))?;
+    let mut amendment = IntentAmendment {
+        dcp_version: "1.0".into(),
+        amendment_id: amendment_id.to_string(),
+        intent_id: current.intent_id.clone(),
+        prev_intent_hash: intent_hash(&before),
+        changes,
+        new_intent_hash: intent_hash(&after),
+        amended_at: utc_now_iso(),
+        signature: SignatureB64::default(),
+    };
+    amendment.signature = sign_record(&amendment, agent_secret_key_b64)?;
+    Ok((amended, amendment))
+}
+
+/// Verify the bundle's amendment lineage: each amendment names the
+/// intent, links to the previous revision's hash, hashes to what its
+/// changes produce, and is signed by the passport's agent key. Returns
+/// `(amendment_id, new_intent_hash)` in order.
+pub fn check_lineage(bundle: &Value) -> Result<Vec<(String, String)>, VerificationFailure> {
+    check_lineage_in(bundle, &mut Canonicalizer::new(), Canon::V1)
+}
+
+/// [`check_lineage`] hashing revisions under `canon`.
+pub(crate) fn check_lineage_in(
+    bundle: &Value,
+    buffers: &mut Canonicalizer,
+    canon: Canon,
+) -> Result<Vec<(String, String)>, VerificationFailure> {
+    let Some(amendments) = bundle.get("intent_amendments").and_then(|v| v.as_array()) else {
+        return Ok(Vec::new());
+    };
+    let intent = &bundle["intent"];
+    let agent_key = bundle.pointer("/agent_passport/public_key").and_then(|v| v.as_str()).unwrap_or("");
+    let mut current = intent.clone();
+    let mut lineage = Vec::with_capacity(amendments.len());
+    for (i, raw) in amendments.iter().enumerate() {
+        let pointer = format!("/bundle/intent_amendments/{}", i);
+        let fail = |what: String, field: &str| {
+            let kind = VerifyError::AmendmentInvalid { entry: None };
+            VerificationFailure::new(kind, format!("amendment {}: {}", i, what), format!("{}{}", pointer, field))
+        };
+        let amendment: IntentAmendment =
+            serde_json::from_value(raw.clone()).map_err(|e| fail(e.to_string(), ""))?;
+        if Some(amendment.intent_id.as_str()) != intent.get("intent_id").and_then(|v| v.as_str()) {
+            return Err(fail("names another intent".into(), "/intent_id"));
+        }
+        let prev = buffers.intent_hash_in(canon, &current);
+        if amendment.prev_intent_hash != prev {
+            return Err(fail("does not follow the previous revision".into(), "/prev_intent_hash")
+                .expected(prev)
+                .actual(amendment.prev_intent_hash.as_str()));
+        }
+        current = apply_changes(&current, &amendment.changes).map_err(|e| fail(e, "/changes"))?;
+        let next = buffers.intent_hash_in(canon, &current);
+        if amendment.new_intent_hash != next {
+            return Err(fail("new_intent_hash does not match its changes".into(), "/new_intent_hash")
+                .expected(next)
+                .actual(amendment.new_intent_hash.as_str()));
+        }
+        if !verify_record_signature(&amendment, agent_key) {
+            return Err(fail("not signed by the agent".into(), "/signature"));
+        }
+        lineage.push((amendment.amendment_id, next));
+    }
+    Ok(lineage)
+}
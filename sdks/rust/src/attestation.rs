@@ -0,0 +1,227 @@
+//! Hardware key attestation for agent passports (feature = "attestation").
+//!
+//! An agent key generated inside a TPM or secure enclave can carry the
+//! platform's attestation statement in [`AgentPassport::attestation`].
+//! [`AttestationVerifier`] checks that statement against the platform CA
+//! roots the deployment trusts and confirms it covers the passport's own
+//! key, so high-risk-tier agents can be required to sign with keys that
+//! cannot be exported.
+//!
+//! Statement formats differ per platform, so each is plugged in as an
+//! [`AttestationFormat`]; the verifier owns the roots and the policy.
+//!
+//! ```ignore
+//! let verifier = AttestationVerifier::new()
+//!     .with_format(Tpm2Format)
+//!     .with_root("tpm2", vendor_root_der);
+//! let result = verifier.verify_bundle(&signed_bundle);
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::types::{AgentPassport, KeyAttestation, VerificationResult, VerifyError};
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum AttestationError {
+    #[error("statement is malformed: {0}")]
+    Malformed(String),
+    #[error("certificate chain does not reach a trusted root: {0}")]
+    Untrusted(String),
+    #[error("statement signature does not verify")]
+    BadSignature,
+}
+
+/// What a verified statement says about the key it covers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AttestedKey {
+    pub public_key_b64: String,
+    /// The platform vouches the key was generated in hardware and is
+    /// not exportable.
+    pub hardware_bound: bool,
+}
+
+impl AttestedKey {
+    pub fn new(public_key_b64: impl Into<String>, hardware_bound: bool) -> Self {
+        Self { public_key_b64: public_key_b64.into(), hardware_bound }
+    }
+}
+
+/// Verifies one platform's statement format.
+pub trait AttestationFormat: Send + Sync {
+    /// Value of [`KeyAttestation::format`] this implementation handles.
+    fn format(&self) -> &str;
+
+    /// Check `attestation`'s chain against `roots` (DER) and return the
+    /// key it attests.
+    fn verify(&self, attestation: &KeyAttestation, roots: &[Vec<u8>]) -> Result<AttestedKey, AttestationError>;
+}
+
+/// Platform roots, formats, and the risk tiers that must be attested.
+#[derive(Clone)]
+pub struct AttestationVerifier {
+    formats: HashMap<String, Arc<dyn AttestationFormat>>,
+    roots: HashMap<String, Vec<Vec<u8>>>,
+    required_tiers: Vec<String>,
+}
+
+impl std::fmt::Debug for AttestationVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut formats: Vec<&String> = self.formats.keys().collect();
+        formats.sort();
+        f.debug_struct("AttestationVerifier")
+            .field("formats", &formats)
+            .field("required_tiers", &self.required_tiers)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for AttestationVerifier {
+    /// No formats or roots; passports with `risk_tier` `high` must be
+    /// attested.
+    fn default() -> Self {
+        Self { formats: HashMap::new(), roots: HashMap::new(), required_tiers: vec!["high".into()] }
+    }
+}
+
+impl AttestationVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_format(mut self, format: impl AttestationFormat + 'static) -> Self {
+        self.formats.insert(format.format().to_string(), Arc::new(format));
+        self
+    }
+
+    /// Trust `root_der` for statements in `format`.
+    pub fn with_root(mut self, format: &str, root_der: Vec<u8>) -> Self {
+        self.roots.entry(format.to_string()).or_default().push(root_der);
+        self
+    }
+
+    /// Replace the risk tiers whose passports must carry an attestation.
+    pub fn require_for_tiers<I, S>(mut self, tiers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.required_tiers = tiers.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Check `passport`'s attestation. Passports outside the required
+    /// tiers may omit it, but one that is present must verify.
+    pub fn verify_passport(&self, passport: &AgentPassport) -> VerificationResult {
+        let Some(attestation) = &passport.attestation else {
+            return match passport.risk_tier.as_deref().filter(|t| self.required_tiers.iter().any(|r| r == t)) {
+                Some(tier) => VerificationResult::fail(
+                    VerifyError::AttestationMissing,
+                    format!("agent {} has risk tier {} and no hardware attestation", passport.agent_id, tier),
+                ),
+                None => VerificationResult::ok(),
+            };
+        };
+        let Some(format) = self.formats.get(&attestation.format) else {
+            return VerificationResult::fail(
+                VerifyError::AttestationInvalid,
+                format!("unsupported format {}", attestation.format),
+            );
+        };
+        let roots = self.roots.get(&attestation.format).map(Vec::as_slice).unwrap_or(&[]);
+        if roots.is_empty() {
+            return VerificationResult::fail(
+                VerifyError::AttestationInvalid,
+                format!("no platform roots configured for {}", attestation.format),
+            );
+        }
+        match format.verify(attestation, roots) {
+            Err(e) => VerificationResult::fail(VerifyError::AttestationInvalid, e.to_string()),
+            Ok(key) if !key.hardware_bound => VerificationResult::fail(
+                VerifyError::KeyNotAttested,
+                format!("{} statement reports a software key for agent {}", attestation.format, passport.agent_id),
+            ),
+            Ok(key) if key.public_key_b64 != passport.public_key.as_str() => VerificationResult::fail(
+                VerifyError::KeyNotAttested,
+                format!("statement covers another key than the passport of agent {}", passport.agent_id),
+            ),
+            Ok(_) => VerificationResult::ok(),
+        }
+    }
+
+    /// [`Self::verify_passport`] on the agent passport of a signed bundle.
+    /// Does not verify the bundle itself.
+    pub fn verify_bundle(&self, signed_bundle: &Value) -> VerificationResult {
+        let Some(passport) = signed_bundle.pointer("/bundle/agent_passport") else {
+            return VerificationResult::fail(VerifyError::missing("agent_passport"), "");
+        };
+        match serde_json::from_value::<AgentPassport>(passport.clone()) {
+            Ok(passport) => self.verify_passport(&passport),
+            Err(e) => VerificationResult::fail(VerifyError::missing("agent_passport"), e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+
+    /// Statement is the attested key itself; the chain's only certificate
+    /// must equal a root byte for byte.
+    struct EchoFormat;
+
+    impl AttestationFormat for EchoFormat {
+        fn format(&self) -> &str {
+            "echo"
+        }
+
+        fn verify(&self, attestation: &KeyAttestation, roots: &[Vec<u8>]) -> Result<AttestedKey, AttestationError> {
+            let leaf = attestation.cert_chain_b64.first().ok_or_else(|| AttestationError::Malformed("empty chain".into()))?;
+            if !roots.iter().any(|r| r.as_slice() == leaf.as_bytes()) {
+                return Err(AttestationError::Untrusted(leaf.clone()));
+            }
+            Ok(AttestedKey::new(attestation.statement_b64.clone(), true))
+        }
+    }
+
+    fn passport(tier: &str) -> AgentPassport {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let mut passport: AgentPassport = serde_json::from_value(set.get("agent_passport").unwrap().value.clone()).unwrap();
+        passport.risk_tier = Some(tier.into());
+        passport
+    }
+
+    fn attest(passport: &mut AgentPassport, key: &str, cert: &str) {
+        passport.attestation = Some(KeyAttestation {
+            format: "echo".into(),
+            statement_b64: key.into(),
+            cert_chain_b64: vec![cert.into()],
+        });
+    }
+
+    #[test]
+    fn test_high_tier_requires_attested_passport_key() {
+        let verifier = AttestationVerifier::new().with_format(EchoFormat).with_root("echo", b"vendor-root".to_vec());
+        assert!(verifier.verify_passport(&passport("low")).verified);
+
+        let mut high = passport("high");
+        let errors = verifier.verify_passport(&high).errors.unwrap();
+        assert!(errors[0].starts_with("ATTESTATION MISSING"));
+
+        let key = high.public_key.to_string();
+        attest(&mut high, &key, "vendor-root");
+        assert!(verifier.verify_passport(&high).verified);
+
+        attest(&mut high, &key, "self-signed");
+        assert!(verifier.verify_passport(&high).errors.unwrap()[0].starts_with("ATTESTATION INVALID"));
+
+        attest(&mut high, &generate_keypair().0, "vendor-root");
+        assert!(verifier.verify_passport(&high).errors.unwrap()[0].starts_with("KEY NOT ATTESTED"));
+    }
+}
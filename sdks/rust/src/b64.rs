@@ -0,0 +1,88 @@
+//! Strict standard base64 (RFC 4648 §4, padded) with typed errors.
+//!
+//! Every key and signature decode in the crate goes through here, so a
+//! malformed input reports *what* is wrong — a base64url character, a
+//! missing `=`, or a 31-byte key — instead of a generic decode failure.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::{DecodeError, Engine};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum B64Error {
+    /// A character outside the standard alphabet.
+    #[error("invalid base64 character {byte:?} at offset {offset}{}", url_safe_hint(*.byte))]
+    WrongAlphabet { offset: usize, byte: char },
+    /// Missing, excess, or misplaced `=` padding, or a truncated final group.
+    #[error("bad base64 padding in {input_len}-character input")]
+    BadPadding { input_len: usize },
+    /// Decoded to the wrong number of bytes.
+    #[error("expected {expected} bytes, got {got}")]
+    WrongLength { expected: usize, got: usize },
+}
+
+fn url_safe_hint(byte: char) -> &'static str {
+    if byte == '-' || byte == '_' {
+        " (input looks like base64url; use standard base64)"
+    } else {
+        ""
+    }
+}
+
+/// Encode with the standard padded alphabet.
+pub fn encode(bytes: impl AsRef<[u8]>) -> String {
+    STANDARD.encode(bytes)
+}
+
+/// Decode standard padded base64.
+pub fn decode(input: &str) -> Result<Vec<u8>, B64Error> {
+    STANDARD.decode(input).map_err(|e| match e {
+        DecodeError::InvalidByte(offset, b) if b != b'=' => B64Error::WrongAlphabet { offset, byte: b as char },
+        DecodeError::InvalidByte(..)
+        | DecodeError::InvalidLength(_)
+        | DecodeError::InvalidLastSymbol(..)
+        | DecodeError::InvalidPadding => B64Error::BadPadding { input_len: input.len() },
+    })
+}
+
+/// Decode to exactly `N` bytes.
+pub fn decode_exact<const N: usize>(input: &str) -> Result<[u8; N], B64Error> {
+    let bytes = decode(input)?;
+    let got = bytes.len();
+    bytes.try_into().map_err(|_| B64Error::WrongLength { expected: N, got })
+}
+
+/// Decode an Ed25519 secret key: a 32-byte seed or the 64-byte
+/// seed-plus-public-key encoding. Returns the seed.
+pub fn decode_ed25519_secret(input: &str) -> Result<[u8; 32], B64Error> {
+    let bytes = decode(input)?;
+    match bytes.len() {
+        32 | 64 => {
+            let mut seed = [0u8; 32];
+            seed.copy_from_slice(&bytes[..32]);
+            Ok(seed)
+        }
+        got => Err(B64Error::WrongLength { expected: 64, got }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        assert_eq!(decode(&encode(b"dcp")).unwrap(), b"dcp");
+        assert_eq!(decode_exact::<3>("ZGNw").unwrap(), *b"dcp");
+    }
+
+    #[test]
+    fn test_typed_errors() {
+        assert_eq!(decode("ab-d"), Err(B64Error::WrongAlphabet { offset: 2, byte: '-' }));
+        assert!(decode("ab-d").unwrap_err().to_string().contains("base64url"));
+        assert_eq!(decode("ZGM"), Err(B64Error::BadPadding { input_len: 3 }));
+        assert_eq!(decode("ZG=w"), Err(B64Error::BadPadding { input_len: 4 }));
+        assert_eq!(decode_exact::<32>("ZGNw"), Err(B64Error::WrongLength { expected: 32, got: 3 }));
+        assert_eq!(decode_ed25519_secret("ZGNw"), Err(B64Error::WrongLength { expected: 64, got: 3 }));
+    }
+}
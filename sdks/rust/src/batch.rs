@@ -0,0 +1,184 @@
+//! Batch verification of newline-delimited signed bundles (JSON Lines).
+//!
+//! [`verify_jsonl`] reads one signed bundle per line, verifies them across
+//! a pool of worker threads, and writes one JSON result per input line in
+//! input order, followed by a summary line:
+//!
+//! ```text
+//! {"line":1,"verified":true}
+//! {"line":2,"verified":false,"errors":["SIGNATURE INVALID"]}
+//! {"summary":{"total":2,"verified":1,"failed":1,"elapsed_ms":3}}
+//! ```
+//!
+//! Blank lines are skipped. A line whose verification panics is reported
+//! as failed (`VERIFIER INTERNAL ERROR`) and the batch goes on. The
+//! `dcp verify-jsonl` command wraps this.
+
+use std::io::{self, BufRead, Write};
+use std::time::Instant;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::types::{VerificationResult, VerifyError};
+use crate::verify::{verify_untrusted, VerifierInternalError, VerifyOptions};
+
+/// Options for [`verify_jsonl`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BatchOptions {
+    /// Worker threads. Defaults to available parallelism.
+    pub jobs: usize,
+    /// Lines read and verified per round.
+    pub chunk_size: usize,
+    /// Verify every bundle against this key instead of its embedded signer key.
+    pub public_key_b64: Option<String>,
+    pub verify: VerifyOptions,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            jobs: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            chunk_size: 256,
+            public_key_b64: None,
+            verify: VerifyOptions::default(),
+        }
+    }
+}
+
+/// Result line for one input bundle.
+#[derive(Debug, Clone, Serialize)]
+pub struct LineResult {
+    /// 1-based input line number.
+    pub line: usize,
+    pub verified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<String>>,
+}
+
+/// Totals written as the final line.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BatchSummary {
+    pub total: usize,
+    pub verified: usize,
+    pub failed: usize,
+    pub elapsed_ms: u128,
+}
+
+fn verify_line(text: &str, opts: &BatchOptions) -> VerificationResult {
+    match serde_json::from_str::<Value>(text) {
+        Ok(sb) => verify_untrusted(&sb, opts.public_key_b64.as_deref(), &opts.verify)
+            .unwrap_or_else(VerifierInternalError::into_result),
+        Err(e) => VerificationResult::fail(VerifyError::InvalidValue { entry: None }, format!("invalid JSON: {}", e)),
+    }
+}
+
+fn verify_chunk(chunk: &[(usize, String)], opts: &BatchOptions) -> Vec<LineResult> {
+    let jobs = opts.jobs.max(1).min(chunk.len().max(1));
+    let per_worker = chunk.len().div_ceil(jobs).max(1);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = chunk
+            .chunks(per_worker)
+            .map(|part| {
+                let handle = scope.spawn(move || {
+                    part.iter()
+                        .map(|(line, text)| {
+                            let r = verify_line(text, opts);
+                            LineResult { line: *line, verified: r.verified, errors: r.errors }
+                        })
+                        .collect::<Vec<_>>()
+                });
+                (part, handle)
+            })
+            .collect();
+        // Panics are contained per line; a worker that dies anyway fails
+        // its lines rather than the batch.
+        handles
+            .into_iter()
+            .flat_map(|(part, handle)| {
+                handle.join().unwrap_or_else(|_| {
+                    let failed = VerifierInternalError { message: "verification worker panicked".into() }.into_result();
+                    let fail_line = |&(line, _): &(usize, String)| LineResult {
+                        line,
+                        verified: false,
+                        errors: failed.errors.clone(),
+                    };
+                    part.iter().map(fail_line).collect()
+                })
+            })
+            .collect()
+    })
+}
+
+/// Verify every bundle in `input`, writing result lines and a summary
+/// line to `output`. Returns the summary.
+pub fn verify_jsonl<R: BufRead, W: Write>(input: R, mut output: W, opts: &BatchOptions) -> io::Result<BatchSummary> {
+    let started = Instant::now();
+    let mut summary = BatchSummary::default();
+    let mut lines = input.lines().enumerate();
+    let chunk_size = opts.chunk_size.max(1);
+
+    loop {
+        let mut chunk = Vec::with_capacity(chunk_size);
+        for (i, line) in lines.by_ref() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            chunk.push((i + 1, line));
+            if chunk.len() == chunk_size {
+                break;
+            }
+        }
+        if chunk.is_empty() {
+            break;
+        }
+        for result in verify_chunk(&chunk, opts) {
+            summary.total += 1;
+            if result.verified {
+                summary.verified += 1;
+            } else {
+                summary.failed += 1;
+            }
+            serde_json::to_writer(&mut output, &result)?;
+            output.write_all(b"\n")?;
+        }
+    }
+
+    summary.elapsed_ms = started.elapsed().as_millis();
+    serde_json::to_writer(&mut output, &serde_json::json!({ "summary": summary }))?;
+    output.write_all(b"\n")?;
+    output.flush()?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+
+    #[test]
+    fn test_results_in_input_order() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let good = serde_json::to_string(&set.get("citizenship_bundle.signed").unwrap().value).unwrap();
+        let input = format!("{good}\n\nnot json\n{good}\n");
+
+        let opts = BatchOptions { jobs: 2, chunk_size: 2, ..Default::default() };
+        let mut out = Vec::new();
+        let summary = verify_jsonl(input.as_bytes(), &mut out, &opts).unwrap();
+        assert_eq!((summary.total, summary.verified, summary.failed), (3, 2, 1));
+
+        let lines: Vec<Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0]["line"], 1);
+        assert_eq!(lines[1]["line"], 3);
+        assert_eq!(lines[1]["verified"], false);
+        assert_eq!(lines[2]["line"], 4);
+        assert_eq!(lines[3]["summary"]["total"], 3);
+    }
+}
@@ -0,0 +1,135 @@
+//! `dcp` command-line tool.
+//!
+//! ```text
+//! dcp verify-jsonl [FILE|-] [--jobs N] [--key PUBLIC_KEY_B64]
+//! dcp gen-fixtures [OUT_DIR]
+//! dcp gen-interop [OUT_DIR]
+//! dcp check-interop SUITE RESULTS
+//! dcp gen-types [OUT_DIR]               (feature = "codegen")
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use dcp_ai::batch::{verify_jsonl, BatchOptions};
+use dcp_ai::fixtures::{generate_fixtures, write_fixtures, GOLDEN_SEED};
+use dcp_ai::interop::{generate_suite, verify_fixture_suite, write_suite, FixtureSuite, SuiteResults};
+
+const USAGE: &str = "usage:
+  dcp verify-jsonl [FILE|-] [--jobs N] [--key PUBLIC_KEY_B64]
+  dcp gen-fixtures [OUT_DIR]
+  dcp gen-interop [OUT_DIR]
+  dcp check-interop SUITE RESULTS
+  dcp gen-types [OUT_DIR]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("verify-jsonl") => cmd_verify_jsonl(&args[1..]),
+        Some("gen-fixtures") => cmd_gen_fixtures(&args[1..]),
+        Some("gen-interop") => cmd_gen_interop(&args[1..]),
+        Some("check-interop") => cmd_check_interop(&args[1..]),
+        #[cfg(feature = "codegen")]
+        Some("gen-types") => cmd_gen_types(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("dcp: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn cmd_verify_jsonl(args: &[String]) -> Result<ExitCode, String> {
+    let mut opts = BatchOptions::default();
+    let mut path: Option<&str> = None;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--jobs" => {
+                let n = it.next().ok_or("--jobs needs a value")?;
+                opts.jobs = n.parse().map_err(|_| format!("invalid --jobs: {}", n))?;
+            }
+            "--key" => opts.public_key_b64 = Some(it.next().ok_or("--key needs a value")?.clone()),
+            p if path.is_none() => path = Some(p),
+            other => return Err(format!("unexpected argument: {}\n{}", other, USAGE)),
+        }
+    }
+
+    let stdout = io::stdout().lock();
+    let summary = match path {
+        None | Some("-") => verify_jsonl(io::stdin().lock(), stdout, &opts),
+        Some(p) => {
+            let file = File::open(p).map_err(|e| format!("{}: {}", p, e))?;
+            verify_jsonl(BufReader::new(file), stdout, &opts)
+        }
+    }
+    .map_err(|e| e.to_string())?;
+
+    Ok(if summary.failed == 0 { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+fn cmd_gen_fixtures(args: &[String]) -> Result<ExitCode, String> {
+    let dir = args.first().map(PathBuf::from).unwrap_or_else(|| {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/conformance/golden")
+    });
+    let set = generate_fixtures(&GOLDEN_SEED)?;
+    for path in write_fixtures(&set, &dir).map_err(|e| format!("{}: {}", dir.display(), e))? {
+        println!("{}", path.display());
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(feature = "codegen")]
+fn cmd_gen_types(args: &[String]) -> Result<ExitCode, String> {
+    let dir = args
+        .first()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../playground/js/types"));
+    for path in dcp_ai::codegen::write_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))? {
+        println!("{}", path.display());
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn cmd_gen_interop(args: &[String]) -> Result<ExitCode, String> {
+    let dir = args.first().map(PathBuf::from).unwrap_or_else(|| {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("interop")
+    });
+    let suite = generate_suite()?;
+    let path = write_suite(&suite, &dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    println!("{}", path.display());
+    Ok(ExitCode::SUCCESS)
+}
+
+fn cmd_check_interop(args: &[String]) -> Result<ExitCode, String> {
+    let [suite_path, results_path] = args else {
+        return Err(USAGE.to_string());
+    };
+    let suite: FixtureSuite = read_json(suite_path)?;
+    let results: SuiteResults = read_json(results_path)?;
+    let report = verify_fixture_suite(&suite, &results)?;
+    for m in &report.mismatches {
+        println!(
+            "MISMATCH {} {}: expected {:?}, got {:?}",
+            m.case_id, m.field, m.expected, m.actual.as_deref().unwrap_or("<missing>")
+        );
+    }
+    println!(
+        "{}: {}/{} cases match suite v{}",
+        report.implementation,
+        report.cases - report.mismatches.iter().map(|m| &m.case_id).collect::<std::collections::HashSet<_>>().len(),
+        report.cases,
+        report.suite_version
+    );
+    Ok(if report.certified() { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &str) -> Result<T, String> {
+    let file = File::open(path).map_err(|e| format!("{}: {}", path, e))?;
+    serde_json::from_reader(BufReader::new(file)).map_err(|e| format!("{}: {}", path, e))
+}
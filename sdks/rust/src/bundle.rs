@@ -0,0 +1,541 @@
+//! Building and signing DCP v1 Citizenship Bundles.
+//!
+//! Rust port of `sdks/typescript/src/bundle/builder.ts` and `signer.ts`.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::{Map, Value};
+
+use crate::amendment::{create_amendment, AMENDED_OUTCOME};
+use crate::crypto::{
+    accepted_intent_hashes, canonicalize_with, hash_canonical, intent_hash, merkle_root_from_hex_leaves,
+    public_key_from_secret, sign_canonical, CANON_V1,
+};
+use crate::overrides::OverrideRecord;
+use crate::patch::{self, touched_paths};
+use crate::merkle::LeafOrder;
+use crate::retention;
+use crate::types::{
+    AgentPassport, AuditEntry, AuditEvidence, BundlePatch, BundleSignature, CitizenshipBundle, Intent, IntentAmendment,
+    PolicyDecision, PublicKeyB64, ResponsiblePrincipalRecord, SignatureB64, SignedBundle, Signer,
+};
+use crate::v2::advisory::{duration_secs, now_epoch_secs, parse_iso_epoch_secs};
+use crate::v2::lifecycle::{civil_from_secs, utc_now_iso_pub as utc_now_iso};
+
+/// Receives producer-side events from a [`BundleBuilder`] as they happen,
+/// so a host can mirror entries to a database, queue, or UI while the
+/// agent runs. Both methods default to no-ops.
+pub trait AuditObserver: Send + Sync {
+    /// Called after an entry is appended to the audit chain.
+    fn on_entry_appended(&self, _entry: &AuditEntry) {}
+
+    /// Called once [`BundleBuilder::build`] has assembled the bundle.
+    fn on_bundle_finalized(&self, _bundle: &CitizenshipBundle) {}
+}
+
+#[derive(Clone, Default)]
+struct Observers(Vec<Arc<dyn AuditObserver>>);
+
+impl fmt::Debug for Observers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} observer(s)", self.0.len())
+    }
+}
+
+/// Builder for [`CitizenshipBundle`].
+#[derive(Debug, Clone, Default)]
+pub struct BundleBuilder {
+    rpr: Option<ResponsiblePrincipalRecord>,
+    passport: Option<AgentPassport>,
+    intent: Option<Intent>,
+    policy: Option<PolicyDecision>,
+    audit_entries: Vec<AuditEntry>,
+    amendments: Vec<IntentAmendment>,
+    /// Latest revision of the intent once amended.
+    amended_intent: Option<Intent>,
+    /// `seq` of the next created entry, once [`BundleBuilder::sequenced`].
+    next_seq: Option<u64>,
+    /// Stamp created entries with the active span, once [`BundleBuilder::traced`].
+    #[cfg(feature = "trace-context")]
+    traced: bool,
+    observers: Observers,
+}
+
+/// Fields of an audit entry the builder does not compute itself.
+#[derive(Debug, Clone)]
+pub struct AuditEntryFields {
+    pub audit_id: String,
+    pub timestamp: String,
+    pub agent_id: String,
+    pub human_id: String,
+    pub policy_decision: String,
+    pub outcome: String,
+    pub evidence: AuditEvidence,
+}
+
+impl BundleBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an observer for entry and finalization events.
+    pub fn observer(mut self, observer: Arc<dyn AuditObserver>) -> Self {
+        self.observers.0.push(observer);
+        self
+    }
+
+    fn push_entry(&mut self, entry: AuditEntry) {
+        for o in &self.observers.0 {
+            o.on_entry_appended(&entry);
+        }
+        self.audit_entries.push(entry);
+    }
+
+    /// Number entries made by [`Self::create_audit_entry`] with `seq`,
+    /// continuing after the last pre-built entry's `seq`, or from 0.
+    pub fn sequenced(mut self) -> Self {
+        let last = self.audit_entries.iter().rev().find_map(|e| e.seq);
+        self.next_seq = Some(last.map_or(0, |s| s + 1));
+        self
+    }
+
+    /// Stamp entries made by [`Self::create_audit_entry`] with the
+    /// `trace_id` / `span_id` of the `tracing` span active when each is
+    /// created (see [`crate::trace_context`]).
+    #[cfg(feature = "trace-context")]
+    pub fn traced(mut self) -> Self {
+        self.traced = true;
+        self
+    }
+
+    /// Set the Responsible Principal Record (DCP-01).
+    pub fn responsible_principal_record(mut self, rpr: ResponsiblePrincipalRecord) -> Self {
+        self.rpr = Some(rpr);
+        self
+    }
+
+    /// Set the Agent Passport (DCP-01).
+    pub fn agent_passport(mut self, passport: AgentPassport) -> Self {
+        self.passport = Some(passport);
+        self
+    }
+
+    /// Set the Intent declaration (DCP-02).
+    pub fn intent(mut self, intent: Intent) -> Self {
+        self.intent = Some(intent);
+        self
+    }
+
+    /// Set the Policy Decision (DCP-02).
+    pub fn policy_decision(mut self, policy: PolicyDecision) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Add a pre-built audit entry.
+    pub fn add_audit_entry(mut self, entry: AuditEntry) -> Self {
+        self.push_entry(entry);
+        self
+    }
+
+    /// Append an audit entry with `intent_hash` and `prev_hash` computed
+    /// from the intent and the chain so far. Fails if the entry's timestamp
+    /// is outside the intent's validity window.
+    pub fn create_audit_entry(mut self, fields: AuditEntryFields) -> Result<Self, String> {
+        let intent = self
+            .intent
+            .as_ref()
+            .ok_or("Intent must be set before creating audit entries")?;
+        if !intent.is_valid_at(&fields.timestamp) {
+            return Err(format!(
+                "Audit entry {} at {} is outside the validity window of intent {}",
+                fields.audit_id, fields.timestamp, intent.intent_id
+            ));
+        }
+        // After an amendment, entries carry the hash of the amended revision.
+        let current = self.amended_intent.as_ref().unwrap_or(intent);
+        let intent_hash = intent_hash(&serde_json::to_value(current).map_err(|e| e.to_string())?);
+        let prev_hash = match self.audit_entries.last() {
+            None => "GENESIS".to_string(),
+            Some(last) => retention::entry_hash(&serde_json::to_value(last).map_err(|e| e.to_string())?),
+        };
+        let mut entry = AuditEntry::new(fields, intent.intent_id.clone(), intent_hash, prev_hash);
+        entry.seq = self.next_seq;
+        #[cfg(feature = "trace-context")]
+        if self.traced {
+            entry.stamp_current_span();
+        }
+        self.next_seq = self.next_seq.map(|s| s + 1);
+        self.push_entry(entry);
+        Ok(self)
+    }
+
+    /// Amend the intent (see [`crate::amendment`]) and append the audit
+    /// entry announcing it. `fields.outcome` and `fields.evidence.result_ref`
+    /// are overwritten; entries created afterwards carry the amended
+    /// intent's hash.
+    pub fn amend_intent(
+        self,
+        amendment_id: &str,
+        changes: Map<String, Value>,
+        mut fields: AuditEntryFields,
+        agent_secret_key_b64: &str,
+    ) -> Result<Self, String> {
+        let intent = self
+            .intent
+            .as_ref()
+            .ok_or("Intent must be set before amending it")?;
+        let current = self.amended_intent.as_ref().unwrap_or(intent);
+        let (amended, amendment) = create_amendment(current, amendment_id, changes, agent_secret_key_b64)?;
+        fields.outcome = AMENDED_OUTCOME.into();
+        fields.evidence.result_ref = Some(amendment_id.to_string());
+        let mut builder = self.create_audit_entry(fields)?;
+        builder.amendments.push(amendment);
+        builder.amended_intent = Some(amended);
+        Ok(builder)
+    }
+
+    /// Append an audit entry recording a signed human override; see
+    /// [`crate::overrides`].
+    pub fn create_override_entry(self, audit_id: &str, record: &OverrideRecord) -> Result<Self, String> {
+        let fields = record.audit_fields(audit_id)?;
+        self.create_audit_entry(fields)
+    }
+
+    /// Build the bundle. Fails if any required artifact is missing.
+    pub fn build(self) -> Result<CitizenshipBundle, String> {
+        let responsible_principal_record = self.rpr.ok_or("Missing responsible_principal_record")?;
+        let agent_passport = self.passport.ok_or("Missing agent_passport")?;
+        let intent = self.intent.ok_or("Missing intent")?;
+        let policy_decision = self.policy.ok_or("Missing policy_decision")?;
+        if self.audit_entries.is_empty() {
+            return Err("At least one audit entry is required".into());
+        }
+        let bundle = CitizenshipBundle {
+            responsible_principal_record,
+            agent_passport,
+            intent,
+            policy_decision,
+            audit_entries: self.audit_entries,
+            intent_amendments: self.amendments,
+            patches: Vec::new(),
+        };
+        for o in &self.observers.0 {
+            o.on_bundle_finalized(&bundle);
+        }
+        Ok(bundle)
+    }
+
+    /// [`Self::build`], refusing if the oldest audit entry is more than
+    /// `max_entry_age` old, so agents sign while their entries are fresh
+    /// rather than batching them indefinitely. Verifiers flag the same
+    /// window with [`crate::verify::VerifyOptions::max_entry_age`].
+    pub fn finalize_with_freshness(self, max_entry_age: Duration) -> Result<CitizenshipBundle, String> {
+        let now = now_epoch_secs();
+        for entry in &self.audit_entries {
+            let Some(at) = parse_iso_epoch_secs(&entry.timestamp) else {
+                return Err(format!("Audit entry {} has unparseable timestamp {}", entry.audit_id, entry.timestamp));
+            };
+            let age = now.saturating_sub(at);
+            if age > duration_secs(max_entry_age) {
+                return Err(format!(
+                    "Audit entry {} is {}s old, beyond the {}s freshness window",
+                    entry.audit_id,
+                    age,
+                    max_entry_age.as_secs()
+                ));
+            }
+        }
+        self.build()
+    }
+}
+
+/// One fact from an event-sourced agent log, replayed by [`from_events`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum DcpEvent {
+    PrincipalRegistered(ResponsiblePrincipalRecord),
+    PassportIssued(AgentPassport),
+    IntentDeclared(Intent),
+    DecisionMade(PolicyDecision),
+    /// Becomes an audit entry chained and bound to the declared intent.
+    ActionExecuted(AuditEntryFields),
+}
+
+/// Rebuild a bundle from an event stream, for systems that event-source
+/// agent activity and emit DCP bundles at retention time.
+///
+/// Events apply in order: each `ActionExecuted` needs a prior
+/// `IntentDeclared`, and a bundle covers exactly one intent and one
+/// decision, which must name that intent.
+pub fn from_events(events: impl IntoIterator<Item = DcpEvent>) -> Result<CitizenshipBundle, String> {
+    let mut builder = BundleBuilder::new();
+    for event in events {
+        builder = match event {
+            DcpEvent::PrincipalRegistered(rpr) => builder.responsible_principal_record(rpr),
+            DcpEvent::PassportIssued(passport) => builder.agent_passport(passport),
+            DcpEvent::IntentDeclared(intent) => {
+                if let Some(existing) = &builder.intent {
+                    return Err(format!(
+                        "Intent {} declared after intent {}; a bundle covers one intent",
+                        intent.intent_id, existing.intent_id
+                    ));
+                }
+                builder.intent(intent)
+            }
+            DcpEvent::DecisionMade(decision) => {
+                match &builder.intent {
+                    Some(intent) if intent.intent_id == decision.intent_id => {}
+                    Some(intent) => {
+                        return Err(format!(
+                            "Decision for intent {} does not match declared intent {}",
+                            decision.intent_id, intent.intent_id
+                        ))
+                    }
+                    None => return Err(format!("Decision for intent {} precedes its declaration", decision.intent_id)),
+                }
+                if builder.policy.is_some() {
+                    return Err(format!("Second decision for intent {}", decision.intent_id));
+                }
+                builder.policy_decision(decision)
+            }
+            DcpEvent::ActionExecuted(fields) => builder.create_audit_entry(fields)?,
+        };
+    }
+    builder.build()
+}
+
+/// Options for [`sign_bundle`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct SignOptions<'a> {
+    /// Ed25519 secret key (base64).
+    pub secret_key_b64: &'a str,
+    /// Signer type: `human` or `organization`.
+    pub signer_type: &'a str,
+    /// Signer identifier (e.g. DID). Defaults to the RPR `human_id`.
+    pub signer_id: Option<&'a str>,
+    /// Signature `created_at`. Defaults to now; fix it for reproducible output.
+    pub created_at: Option<&'a str>,
+    /// Order of the merkle leaves, declared as `signature.leaf_order`.
+    /// Defaults to chain order, with the field left out.
+    pub leaf_order: Option<LeafOrder>,
+}
+
+impl<'a> SignOptions<'a> {
+    pub fn new(secret_key_b64: &'a str) -> Self {
+        Self { secret_key_b64, signer_type: "human", signer_id: None, created_at: None, leaf_order: None }
+    }
+}
+
+/// Sign a Citizenship Bundle and produce a Signed Bundle.
+pub fn sign_bundle(bundle: CitizenshipBundle, opts: &SignOptions<'_>) -> Result<SignedBundle, String> {
+    let bundle_value = serde_json::to_value(&bundle).map_err(|e| e.to_string())?;
+    let canon = canonicalize_with(CANON_V1, &bundle_value).ok_or("Unknown canonicalization")?;
+    let public_key_b64 = public_key_from_secret(opts.secret_key_b64)?
+        .parse::<PublicKeyB64>()
+        .map_err(|e| format!("public key: {}", e))?;
+
+    let entries = bundle_value["audit_entries"].as_array().map(Vec::as_slice).unwrap_or(&[]);
+    let hashes: Vec<String> = entries.iter().map(retention::entry_hash).collect();
+    let leaves = opts.leaf_order.unwrap_or_default().arrange(entries, &hashes);
+    let merkle_root = merkle_root_from_hex_leaves(&leaves).map(|r| format!("sha256:{}", r));
+    let sig_b64 = SignatureB64::from_signer(sign_canonical(&canon, opts.secret_key_b64)?);
+
+    let signer_id = opts
+        .signer_id
+        .map(str::to_string)
+        .unwrap_or_else(|| bundle.responsible_principal_record.human_id.clone());
+
+    Ok(SignedBundle {
+        signature: BundleSignature {
+            alg: "ed25519".into(),
+            created_at: opts.created_at.map(str::to_string).unwrap_or_else(utc_now_iso),
+            signer: Signer {
+                signer_type: opts.signer_type.to_string(),
+                id: signer_id,
+                public_key_b64,
+            },
+            bundle_hash: format!("sha256:{}", hash_canonical(&canon)),
+            merkle_root,
+            sig_b64,
+            canon: Some(CANON_V1.to_string()),
+            leaf_order: opts.leaf_order,
+        },
+        bundle,
+    })
+}
+
+/// Correct a signed bundle with an RFC 6902 patch and sign the result.
+///
+/// The patch applies to the unsigned bundle. Audit entries that carried
+/// the old intent's hash are moved to the patched intent's, the
+/// `prev_hash` chain is relinked, and [`sign_bundle`] recomputes
+/// `bundle_hash` and `merkle_root`. A [`BundlePatch`] recording the
+/// operations and the previous `bundle_hash` is appended to `patches`,
+/// so the correction stays auditable.
+///
+/// `patches` itself cannot be patched, nor can the intent of a bundle
+/// with `intent_amendments` (the amendments sign its hash).
+pub fn apply_patch(
+    signed_bundle: &SignedBundle,
+    json_patch: &Value,
+    signer: &SignOptions<'_>,
+) -> Result<SignedBundle, String> {
+    if let Some(path) = touched_paths(json_patch)
+        .into_iter()
+        .find(|p| p.is_empty() || *p == "/patches" || p.starts_with("/patches/"))
+    {
+        return Err(format!("{:?} cannot be patched", path));
+    }
+    let before = &signed_bundle.bundle;
+    let mut value = serde_json::to_value(before).map_err(|e| e.to_string())?;
+    patch::apply(&mut value, json_patch).map_err(|e| format!("patch: {}", e))?;
+    let mut bundle: CitizenshipBundle = serde_json::from_value(value).map_err(|e| format!("patched bundle: {}", e))?;
+
+    let intent_value = |intent: &Intent| serde_json::to_value(intent).map_err(|e| e.to_string());
+    let (old_intent, new_intent) = (intent_value(&before.intent)?, intent_value(&bundle.intent)?);
+    let old_intent_hashes = accepted_intent_hashes(&old_intent);
+    let new_intent_hash = intent_hash(&new_intent);
+    if old_intent_hashes[0] != new_intent_hash && !bundle.intent_amendments.is_empty() {
+        return Err("The intent of an amended bundle cannot be patched".into());
+    }
+    let mut prev_hash: Option<String> = None;
+    for entry in &mut bundle.audit_entries {
+        if old_intent_hashes.contains(&entry.intent_hash) {
+            entry.intent_hash = new_intent_hash.clone();
+        }
+        if let Some(prev) = prev_hash.take() {
+            entry.prev_hash = prev;
+        }
+        prev_hash = Some(retention::entry_hash(&serde_json::to_value(&*entry).map_err(|e| e.to_string())?));
+    }
+
+    let applied_by = signer
+        .signer_id
+        .map(str::to_string)
+        .unwrap_or_else(|| bundle.responsible_principal_record.human_id.clone());
+    bundle.patches.push(BundlePatch {
+        prev_bundle_hash: signed_bundle.signature.bundle_hash.clone(),
+        operations: json_patch.clone(),
+        applied_at: signer.created_at.map(str::to_string).unwrap_or_else(utc_now_iso),
+        applied_by,
+    });
+    sign_bundle(bundle, signer)
+}
+
+/// Options for [`normalize_with`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct NormalizeOptions {
+    /// Remove object members whose value is `null`. The Rust types omit
+    /// absent optionals, while some producers serialize them as `null`;
+    /// the two canonicalize differently. Default `true`.
+    pub drop_nulls: bool,
+    /// Trim surrounding whitespace from identifier strings: `id`, keys
+    /// ending in `_id`, and keys ending in `_reference`. Default `true`.
+    pub trim_ids: bool,
+    /// Rewrite RFC 3339 timestamps with a numeric offset (`+02:00`,
+    /// `-05:00`, `+00:00`) to the same instant in UTC with a `Z` suffix.
+    /// Applies to `timestamp`, keys ending in `_at`, `not_before`, and
+    /// `valid_until`. Fractional seconds are kept as written. Default `true`.
+    pub utc_timestamps: bool,
+}
+
+impl Default for NormalizeOptions {
+    fn default() -> Self {
+        Self { drop_nulls: true, trim_ids: true, utc_timestamps: true }
+    }
+}
+
+/// Normalize a bundle (or any DCP artifact) in place with the default
+/// [`NormalizeOptions`], so producers on different stacks reach
+/// byte-identical canonical forms before signing.
+///
+/// Run this before [`sign_bundle`] or hashing, never on a bundle that is
+/// already signed: it changes the bytes the signature covers.
+pub fn normalize(value: &mut Value) {
+    normalize_with(value, &NormalizeOptions::default());
+}
+
+/// [`normalize`] with explicit options.
+pub fn normalize_with(value: &mut Value, opts: &NormalizeOptions) {
+    match value {
+        Value::Object(map) => {
+            if opts.drop_nulls {
+                map.retain(|_, v| !v.is_null());
+            }
+            for (k, v) in map.iter_mut() {
+                match v {
+                    Value::String(s) if opts.trim_ids && is_id_key(k) => {
+                        let trimmed = s.trim();
+                        if trimmed.len() != s.len() {
+                            *s = trimmed.to_string();
+                        }
+                    }
+                    Value::String(s) if opts.utc_timestamps && is_timestamp_key(k) => {
+                        if let Some(utc) = timestamp_to_utc(s) {
+                            *s = utc;
+                        }
+                    }
+                    _ => normalize_with(v, opts),
+                }
+            }
+        }
+        Value::Array(arr) => arr.iter_mut().for_each(|v| normalize_with(v, opts)),
+        _ => {}
+    }
+}
+
+fn is_id_key(key: &str) -> bool {
+    key == "id" || key.ends_with("_id") || key.ends_with("_reference")
+}
+
+fn is_timestamp_key(key: &str) -> bool {
+    key == "timestamp" || key == "not_before" || key == "valid_until" || key.ends_with("_at")
+}
+
+/// `YYYY-MM-DDTHH:MM:SS[.frac]±HH:MM` → UTC with `Z`. Returns `None` for
+/// strings already in `Z` form or not in this shape.
+fn timestamp_to_utc(ts: &str) -> Option<String> {
+    let b = ts.as_bytes();
+    if !ts.is_ascii() || b.len() < 25 || b[4] != b'-' || b[7] != b'-' || b[10] != b'T' || b[13] != b':' || b[16] != b':' {
+        return None;
+    }
+    let (body, offset) = ts.split_at(ts.len() - 6);
+    let ob = offset.as_bytes();
+    let sign = match ob[0] {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    if ob[3] != b':' {
+        return None;
+    }
+    let num = |s: &str| if s.bytes().all(|c| c.is_ascii_digit()) { s.parse::<i64>().ok() } else { None };
+    let offset_secs = sign * (num(&offset[1..3])? * 3600 + num(&offset[4..6])? * 60);
+
+    let (year, month, day) = (num(&body[0..4])?, num(&body[5..7])?, num(&body[8..10])?);
+    let (hour, minute, second) = (num(&body[11..13])?, num(&body[14..16])?, num(&body[17..19])?);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    let frac = &body[19..];
+    if !(frac.is_empty() || (frac.starts_with('.') && frac.len() > 1 && frac[1..].bytes().all(|c| c.is_ascii_digit()))) {
+        return None;
+    }
+
+    // Days from civil (year, month, day) — Howard Hinnant.
+    let m = if month <= 2 { month + 9 } else { month - 3 };
+    let ym = if month <= 2 { year - 1 } else { year };
+    let era = ym.div_euclid(400);
+    let yoe = ym - era * 400;
+    let doy = (153 * m + 2) / 5 + day - 1;
+    let days = era * 146_097 + yoe * 365 + yoe / 4 - yoe / 100 + doy - 719_468;
+
+    let secs = days * 86_400 + hour * 3600 + minute * 60 + second - offset_secs;
+    let (y, mo, d, h, mi, s) = civil_from_secs(secs);
+    Some(format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}Z", y, mo, d, h, mi, s, frac))
+}
@@ -0,0 +1,309 @@
+//! Dual-control key ceremonies for notary and organization keys.
+//!
+//! At least two operators each contribute 32 bytes of entropy. The key
+//! seed is `SHA-256("dcp-ceremony/v1" || os_random || c_1 || ... || c_n)`,
+//! so no single operator — nor the host RNG alone — determines the key.
+//! The ceremony emits a [`CeremonyTranscript`] recording each operator's
+//! commitment (SHA-256 of their contribution, never the contribution
+//! itself), timestamps, and the resulting public key. The new key signs
+//! the transcript as proof of possession; every operator then
+//! countersigns it with [`countersign`] before it goes to the archive.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::crypto::{keypair_from_seed, sign_object, verify_object};
+use crate::entropy::{repetition_count_test, RCT_CUTOFF};
+use crate::types::{VerificationFailure, VerificationResult, VerifyError};
+use crate::v2::lifecycle::utc_now_iso_pub as utc_now_iso;
+
+/// Ceremony transcript format identifier.
+pub const CEREMONY_VERSION: &str = "dcp-ceremony/v1";
+
+/// Minimum number of distinct operators.
+pub const MIN_OPERATORS: usize = 2;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CeremonyError {
+    #[error("Operator {0} already contributed")]
+    DuplicateOperator(String),
+    #[error("Operator {0} reuses another operator's public key")]
+    DuplicateOperatorKey(String),
+    #[error("Contribution from {0} failed the entropy health test")]
+    WeakContribution(String),
+    #[error("Need at least two operators, have {0}")]
+    NotEnoughOperators(usize),
+    #[error("Operator {0} is not a participant")]
+    UnknownOperator(String),
+    #[error("Random source error: {0}")]
+    Rng(String),
+    #[error("Signing failed: {0}")]
+    Signing(String),
+}
+
+/// One operator's entry in the transcript.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CeremonyParticipant {
+    pub operator_id: String,
+    pub operator_public_key_b64: String,
+    /// SHA-256 hex of the operator's entropy contribution.
+    pub commitment: String,
+    pub contributed_at: String,
+    /// Operator's countersignature over the transcript payload.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+/// Compliance record of a key ceremony.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CeremonyTranscript {
+    pub ceremony_version: String,
+    pub ceremony_id: String,
+    /// What the key is for, e.g. `notary` or `organization`.
+    pub purpose: String,
+    pub participants: Vec<CeremonyParticipant>,
+    pub public_key_b64: String,
+    pub started_at: String,
+    pub completed_at: String,
+    /// The generated key's signature over the transcript payload.
+    pub key_signature: String,
+}
+
+impl CeremonyTranscript {
+    /// The signed payload: the transcript without `key_signature` and
+    /// operator signatures.
+    pub fn payload(&self) -> Value {
+        let mut v = serde_json::to_value(self).unwrap_or(Value::Null);
+        if let Some(obj) = v.as_object_mut() {
+            obj.remove("key_signature");
+            if let Some(Value::Array(participants)) = obj.get_mut("participants") {
+                for p in participants.iter_mut().filter_map(Value::as_object_mut) {
+                    p.remove("signature");
+                }
+            }
+        }
+        v
+    }
+}
+
+struct Contribution {
+    participant: CeremonyParticipant,
+    entropy: Zeroizing<[u8; 32]>,
+}
+
+/// A ceremony in progress.
+pub struct KeyCeremony {
+    ceremony_id: String,
+    purpose: String,
+    started_at: String,
+    contributions: Vec<Contribution>,
+}
+
+impl KeyCeremony {
+    pub fn new(ceremony_id: impl Into<String>, purpose: impl Into<String>) -> Self {
+        Self {
+            ceremony_id: ceremony_id.into(),
+            purpose: purpose.into(),
+            started_at: utc_now_iso(),
+            contributions: Vec::new(),
+        }
+    }
+
+    /// Record an operator's entropy contribution.
+    pub fn contribute(
+        &mut self,
+        operator_id: &str,
+        operator_public_key_b64: &str,
+        entropy: [u8; 32],
+    ) -> Result<(), CeremonyError> {
+        let entropy = Zeroizing::new(entropy);
+        if self.contributions.iter().any(|c| c.participant.operator_id == operator_id) {
+            return Err(CeremonyError::DuplicateOperator(operator_id.to_string()));
+        }
+        // One key countersigning as two operators is single control.
+        if self.contributions.iter().any(|c| c.participant.operator_public_key_b64 == operator_public_key_b64) {
+            return Err(CeremonyError::DuplicateOperatorKey(operator_id.to_string()));
+        }
+        if repetition_count_test(&entropy[..], RCT_CUTOFF).is_err() {
+            return Err(CeremonyError::WeakContribution(operator_id.to_string()));
+        }
+        self.contributions.push(Contribution {
+            participant: CeremonyParticipant {
+                operator_id: operator_id.to_string(),
+                operator_public_key_b64: operator_public_key_b64.to_string(),
+                commitment: hex::encode(Sha256::digest(&entropy[..])),
+                contributed_at: utc_now_iso(),
+                signature: None,
+            },
+            entropy,
+        });
+        Ok(())
+    }
+
+    /// Generate the key and the key-signed transcript. Returns the
+    /// transcript and the base64 secret key.
+    pub fn finish(self) -> Result<(CeremonyTranscript, Zeroizing<String>), CeremonyError> {
+        if self.contributions.len() < MIN_OPERATORS {
+            return Err(CeremonyError::NotEnoughOperators(self.contributions.len()));
+        }
+        let mut os_random = Zeroizing::new([0u8; 32]);
+        rand::RngCore::try_fill_bytes(&mut rand::rngs::OsRng, &mut os_random[..])
+            .map_err(|e| CeremonyError::Rng(e.to_string()))?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(CEREMONY_VERSION.as_bytes());
+        hasher.update(&os_random[..]);
+        for c in &self.contributions {
+            hasher.update(&c.entropy[..]);
+        }
+        let seed = Zeroizing::new(<[u8; 32]>::from(hasher.finalize()));
+        let (public_key_b64, secret_key_b64) = keypair_from_seed(&seed);
+        let secret_key_b64 = Zeroizing::new(secret_key_b64);
+
+        let mut transcript = CeremonyTranscript {
+            ceremony_version: CEREMONY_VERSION.to_string(),
+            ceremony_id: self.ceremony_id,
+            purpose: self.purpose,
+            participants: self.contributions.into_iter().map(|c| c.participant).collect(),
+            public_key_b64,
+            started_at: self.started_at,
+            completed_at: utc_now_iso(),
+            key_signature: String::new(),
+        };
+        transcript.key_signature =
+            sign_object(&transcript.payload(), &secret_key_b64).map_err(CeremonyError::Signing)?;
+        Ok((transcript, secret_key_b64))
+    }
+}
+
+/// Add `operator_id`'s countersignature to the transcript.
+pub fn countersign(
+    transcript: &mut CeremonyTranscript,
+    operator_id: &str,
+    operator_secret_key_b64: &str,
+) -> Result<(), CeremonyError> {
+    let payload = transcript.payload();
+    let participant = transcript
+        .participants
+        .iter_mut()
+        .find(|p| p.operator_id == operator_id)
+        .ok_or_else(|| CeremonyError::UnknownOperator(operator_id.to_string()))?;
+    participant.signature = Some(sign_object(&payload, operator_secret_key_b64).map_err(CeremonyError::Signing)?);
+    Ok(())
+}
+
+/// Verify a ceremony transcript: the generated key's signature, at least
+/// [`MIN_OPERATORS`] distinct operators, no operator key listed twice,
+/// and every operator's countersignature.
+pub fn verify_ceremony_transcript(transcript: &CeremonyTranscript) -> VerificationResult {
+    let payload = transcript.payload();
+    let mut errors = Vec::new();
+    if !matches!(verify_object(&payload, &transcript.key_signature, &transcript.public_key_b64), Ok(true)) {
+        errors.push("key_signature invalid".to_string());
+    }
+    let mut operators: Vec<&str> = transcript.participants.iter().map(|p| p.operator_id.as_str()).collect();
+    operators.sort_unstable();
+    operators.dedup();
+    if operators.len() < MIN_OPERATORS {
+        errors.push(format!("{} distinct operators, need {}", operators.len(), MIN_OPERATORS));
+    }
+    for (i, p) in transcript.participants.iter().enumerate() {
+        let earlier = &transcript.participants[..i];
+        if let Some(first) = earlier.iter().find(|q| q.operator_public_key_b64 == p.operator_public_key_b64) {
+            errors.push(format!("operator {}: public key already used by {}", p.operator_id, first.operator_id));
+        }
+    }
+    for p in &transcript.participants {
+        match &p.signature {
+            Some(sig) if matches!(verify_object(&payload, sig, &p.operator_public_key_b64), Ok(true)) => {}
+            Some(_) => errors.push(format!("operator {}: countersignature invalid", p.operator_id)),
+            None => errors.push(format!("operator {}: countersignature missing", p.operator_id)),
+        }
+    }
+    if errors.is_empty() {
+        VerificationResult::ok()
+    } else {
+        VerificationResult::fail_at(
+            errors.into_iter().map(|e| VerificationFailure::new(VerifyError::CeremonyInvalid, e, "")).collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+    use rand::RngCore;
+
+    fn entropy() -> [u8; 32] {
+        let mut e = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut e);
+        e
+    }
+
+    #[test]
+    fn test_dual_control_ceremony() {
+        let (a_pk, a_sk) = generate_keypair();
+        let (b_pk, b_sk) = generate_keypair();
+        let mut ceremony = KeyCeremony::new("cer-1", "notary");
+        ceremony.contribute("alice", &a_pk, entropy()).unwrap();
+        assert_eq!(ceremony.finish().err(), Some(CeremonyError::NotEnoughOperators(1)));
+
+        let mut ceremony = KeyCeremony::new("cer-2", "notary");
+        ceremony.contribute("alice", &a_pk, entropy()).unwrap();
+        assert!(matches!(
+            ceremony.contribute("alice", &a_pk, entropy()),
+            Err(CeremonyError::DuplicateOperator(_))
+        ));
+        ceremony.contribute("bob", &b_pk, entropy()).unwrap();
+        let (mut transcript, sk) = ceremony.finish().unwrap();
+        assert!(!verify_ceremony_transcript(&transcript).verified);
+
+        countersign(&mut transcript, "alice", &a_sk).unwrap();
+        countersign(&mut transcript, "bob", &b_sk).unwrap();
+        let result = verify_ceremony_transcript(&transcript);
+        assert!(result.verified, "{:?}", result.errors);
+
+        let sig = sign_object(&serde_json::json!({"x": 1}), &sk).unwrap();
+        assert_eq!(verify_object(&serde_json::json!({"x": 1}), &sig, &transcript.public_key_b64), Ok(true));
+    }
+
+    #[test]
+    fn test_stuck_contribution_rejected() {
+        let (pk, _) = generate_keypair();
+        let mut ceremony = KeyCeremony::new("cer-3", "organization");
+        assert_eq!(
+            ceremony.contribute("alice", &pk, [0u8; 32]),
+            Err(CeremonyError::WeakContribution("alice".into()))
+        );
+    }
+
+    #[test]
+    fn test_repeated_operator_key_rejected() {
+        let (a_pk, a_sk) = generate_keypair();
+        let (b_pk, _) = generate_keypair();
+        let mut ceremony = KeyCeremony::new("cer-4", "notary");
+        ceremony.contribute("alice", &a_pk, entropy()).unwrap();
+        assert_eq!(
+            ceremony.contribute("mallory", &a_pk, entropy()),
+            Err(CeremonyError::DuplicateOperatorKey("mallory".into()))
+        );
+
+        // A transcript edited to list one key under two names fails.
+        ceremony.contribute("bob", &b_pk, entropy()).unwrap();
+        let (mut transcript, sk) = ceremony.finish().unwrap();
+        transcript.participants[1].operator_public_key_b64 = a_pk;
+        transcript.key_signature = sign_object(&transcript.payload(), &sk).unwrap();
+        countersign(&mut transcript, "alice", &a_sk).unwrap();
+        countersign(&mut transcript, "bob", &a_sk).unwrap();
+        let result = verify_ceremony_transcript(&transcript);
+        assert!(!result.verified);
+        assert_eq!(
+            result.errors.unwrap(),
+            ["CEREMONY INVALID: operator bob: public key already used by alice".to_string()]
+        );
+    }
+}
@@ -0,0 +1,183 @@
+//! Human-binding possession challenges.
+//!
+//! Periodic proof that the human behind a Responsible Principal Record is
+//! still reachable and in control of its key. The relying party issues a
+//! [`Challenge`] bound to the record's hash with a fresh nonce and a short
+//! expiry; the human signs it with [`respond`]; [`verify_challenge_response`]
+//! checks the response against the challenge and confirms the responding
+//! key is the one that signed the record.
+//!
+//! The signed payload carries a fixed `purpose`, so a challenge response
+//! can never be replayed as any other DCP signature.
+
+use std::time::Duration;
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::crypto::{sign_object, verify_object};
+use crate::issue::{record_hash, verify_record_signature};
+use crate::types::{ResponsiblePrincipalRecord, SignatureB64, VerificationFailure, VerificationResult, VerifyError};
+use crate::v2::lifecycle::{civil_from_secs, utc_now_iso_pub as utc_now_iso};
+
+/// `purpose` of every signed challenge payload.
+pub const CHALLENGE_PURPOSE: &str = "DCP-AI.v1.HumanControlChallenge";
+
+/// Issued by a relying party to the human behind a binding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Challenge {
+    pub challenge_id: String,
+    pub human_id: String,
+    /// `sha256:<hex>` of the challenged record.
+    pub rpr_hash: String,
+    /// 32 random bytes, hex.
+    pub nonce: String,
+    pub issued_at: String,
+    pub expires_at: String,
+}
+
+/// The human's signed answer to a [`Challenge`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChallengeResponse {
+    pub challenge_id: String,
+    pub responded_at: String,
+    /// Ed25519 over [`response_payload`].
+    pub signature: SignatureB64,
+}
+
+impl Challenge {
+    /// A fresh challenge for `rpr`, answerable for `ttl`.
+    pub fn new(rpr: &ResponsiblePrincipalRecord, ttl: Duration) -> Result<Self, String> {
+        let mut nonce = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let nonce = hex::encode(nonce);
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        Ok(Self {
+            challenge_id: format!("challenge-{}", &nonce[..16]),
+            human_id: rpr.human_id.clone(),
+            rpr_hash: record_hash(rpr)?,
+            nonce,
+            issued_at: iso_from_secs(now),
+            expires_at: iso_from_secs(now + ttl.as_secs() as i64),
+        })
+    }
+}
+
+pub(crate) fn iso_from_secs(secs: i64) -> String {
+    let (y, mo, d, h, mi, s) = civil_from_secs(secs);
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", y, mo, d, h, mi, s)
+}
+
+/// What the human signs: the purpose, the whole challenge, and the
+/// response time.
+pub fn response_payload(challenge: &Challenge, responded_at: &str) -> Value {
+    json!({
+        "purpose": CHALLENGE_PURPOSE,
+        "challenge": challenge,
+        "responded_at": responded_at,
+    })
+}
+
+/// Answer `challenge` with the human's secret key.
+pub fn respond(challenge: &Challenge, secret_key_b64: &str) -> Result<ChallengeResponse, String> {
+    let responded_at = utc_now_iso();
+    Ok(ChallengeResponse {
+        challenge_id: challenge.challenge_id.clone(),
+        signature: SignatureB64::from_signer(sign_object(&response_payload(challenge, &responded_at), secret_key_b64)?),
+        responded_at,
+    })
+}
+
+/// Verify `response` to `challenge` for `rpr`.
+///
+/// Passes when the response names the challenge, the challenge targets
+/// this record, the response came before `expires_at` and it is not yet
+/// past, the signature verifies under `public_key_b64`, and that same key
+/// signed `rpr`. ISO-8601 UTC timestamps are compared lexicographically.
+pub fn verify_challenge_response(
+    challenge: &Challenge,
+    response: &ChallengeResponse,
+    rpr: &ResponsiblePrincipalRecord,
+    public_key_b64: &str,
+) -> VerificationResult {
+    let failure = |kind, detail: String| VerificationFailure::new(kind, detail, "");
+    let mut failures = Vec::new();
+    if response.challenge_id != challenge.challenge_id {
+        failures.push(failure(
+            VerifyError::ChallengeMismatch,
+            format!("response is for {}, not {}", response.challenge_id, challenge.challenge_id),
+        ));
+    }
+    if challenge.human_id != rpr.human_id || record_hash(rpr).ok().as_deref() != Some(challenge.rpr_hash.as_str()) {
+        failures.push(failure(
+            VerifyError::ChallengeMismatch,
+            format!("challenge does not target the record of {}", rpr.human_id),
+        ));
+    }
+    if response.responded_at.as_str() > challenge.expires_at.as_str() || utc_now_iso() > challenge.expires_at {
+        failures.push(failure(VerifyError::ChallengeExpired, format!("challenge expired at {}", challenge.expires_at)));
+    }
+    let payload = response_payload(challenge, &response.responded_at);
+    if !matches!(verify_object(&payload, response.signature.as_str(), public_key_b64), Ok(true)) {
+        failures.push(failure(VerifyError::SignatureInvalid, "challenge response".into()));
+    }
+    if !verify_record_signature(rpr, public_key_b64) {
+        failures.push(failure(
+            VerifyError::KeyNotBound,
+            format!("responding key did not sign the record of {}", rpr.human_id),
+        ));
+    }
+    if failures.is_empty() {
+        VerificationResult::ok()
+    } else {
+        VerificationResult::fail_at(failures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+    use crate::issue::sign_record;
+
+    fn bound_rpr() -> (ResponsiblePrincipalRecord, String, String) {
+        let (pk, sk) = generate_keypair();
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let mut rpr: ResponsiblePrincipalRecord =
+            serde_json::from_value(set.get("responsible_principal_record").unwrap().value.clone()).unwrap();
+        rpr.signature = sign_record(&rpr, &sk).unwrap();
+        (rpr, pk, sk)
+    }
+
+    #[test]
+    fn test_bound_human_passes_challenge() {
+        let (rpr, pk, sk) = bound_rpr();
+        let challenge = Challenge::new(&rpr, Duration::from_secs(300)).unwrap();
+        let response = respond(&challenge, &sk).unwrap();
+        let result = verify_challenge_response(&challenge, &response, &rpr, &pk);
+        assert!(result.verified, "{:?}", result.errors);
+    }
+
+    #[test]
+    fn test_unbound_key_and_expired_challenge_fail() {
+        let (rpr, _, _) = bound_rpr();
+        let (other_pk, other_sk) = generate_keypair();
+        let challenge = Challenge::new(&rpr, Duration::from_secs(300)).unwrap();
+        let response = respond(&challenge, &other_sk).unwrap();
+        let errors = verify_challenge_response(&challenge, &response, &rpr, &other_pk).errors.unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("KEY NOT BOUND"));
+
+        let (rpr, pk, sk) = bound_rpr();
+        let mut stale = Challenge::new(&rpr, Duration::from_secs(300)).unwrap();
+        stale.expires_at = "2020-01-01T00:00:00Z".into();
+        let response = respond(&stale, &sk).unwrap();
+        let errors = verify_challenge_response(&stale, &response, &rpr, &pk).errors.unwrap();
+        assert!(errors[0].starts_with("CHALLENGE EXPIRED"));
+    }
+}
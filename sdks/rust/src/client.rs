@@ -0,0 +1,235 @@
+//! Submitting signed bundles to a DCP server (feature = "client").
+//!
+//! [`submit_bundle`] POSTs a signed bundle with an `Idempotency-Key`
+//! derived from its `bundle_hash`, so a retried submission of the same
+//! bundle is recognizable to the server as a repeat, not a second bundle.
+//! Transient failures (connection errors, `408`, `429`, `5xx`) are retried
+//! with exponential backoff; anything else is returned as a typed
+//! [`SubmitResponse`] or [`SubmitError`].
+//!
+//! ```ignore
+//! match client::submit_bundle("https://dcp.example/v1/bundles", &signed_bundle)? {
+//!     SubmitResponse::Accepted { .. } => {}
+//!     SubmitResponse::Duplicate { .. } => {} // an earlier attempt got through
+//! }
+//! ```
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::crypto::hash_object;
+
+/// Header carrying [`idempotency_key`].
+pub const IDEMPOTENCY_HEADER: &str = "Idempotency-Key";
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum SubmitError {
+    #[error("submit: signed bundle has no bundle")]
+    MissingBundle,
+    #[error("submit encoding: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The server refused the bundle; retrying will not help.
+    #[error("server rejected bundle with {status}: {body}")]
+    Rejected { status: u16, body: String },
+    /// Every attempt failed transiently.
+    #[error("submit failed after {attempts} attempts: {last}")]
+    Exhausted { attempts: u32, last: String },
+}
+
+/// What the server answered.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum SubmitResponse {
+    /// `2xx`: the bundle was accepted. `body` is the server's JSON
+    /// response (`null` if it sent none).
+    Accepted { status: u16, body: Value },
+    /// `409`: the server already holds a bundle under this idempotency
+    /// key, typically from an earlier attempt whose response was lost.
+    Duplicate { body: Value },
+}
+
+/// A raw HTTP response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Sends submission requests. The default [`HttpTransport`] POSTs over
+/// HTTP(S); substitute your own to route through an existing client.
+pub trait SubmitTransport: Send + Sync {
+    /// POST `body`. `Err` means no response was received.
+    fn post(&self, url: &str, headers: &[(&str, String)], body: &str) -> Result<HttpResponse, String>;
+}
+
+/// Blocking HTTP(S) transport.
+#[derive(Debug, Clone)]
+pub struct HttpTransport {
+    agent: ureq::Agent,
+}
+
+impl HttpTransport {
+    pub fn new(timeout: Duration) -> Self {
+        Self { agent: ureq::AgentBuilder::new().timeout(timeout).build() }
+    }
+}
+
+impl Default for HttpTransport {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(30))
+    }
+}
+
+impl SubmitTransport for HttpTransport {
+    fn post(&self, url: &str, headers: &[(&str, String)], body: &str) -> Result<HttpResponse, String> {
+        let mut req = self.agent.post(url).set("Content-Type", "application/json");
+        for (name, value) in headers {
+            req = req.set(name, value);
+        }
+        let response = match req.send_string(body) {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => return Err(e.to_string()),
+        };
+        let status = response.status();
+        Ok(HttpResponse { status, body: response.into_string().map_err(|e| e.to_string())? })
+    }
+}
+
+/// How often and how patiently to retry transient failures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts in total, including the first. At least one is made.
+    pub max_attempts: u32,
+    /// Wait before the first retry; doubled for each one after.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { max_attempts: 5, initial_backoff: Duration::from_millis(250), max_backoff: Duration::from_secs(10) }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before retry number `retry` (from 0).
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff.saturating_mul(1u32 << retry.min(16)).min(self.max_backoff)
+    }
+}
+
+/// The `Idempotency-Key` of `signed_bundle`: its `signature.bundle_hash`,
+/// or the SHA-256 of the canonical bundle when it has none, so the same
+/// bundle always gets the same key.
+pub fn idempotency_key(signed_bundle: &Value) -> Result<String, SubmitError> {
+    if let Some(bundle_hash) = signed_bundle.pointer("/signature/bundle_hash").and_then(Value::as_str) {
+        return Ok(bundle_hash.to_string());
+    }
+    let bundle = signed_bundle.get("bundle").ok_or(SubmitError::MissingBundle)?;
+    Ok(format!("sha256:{}", hash_object(bundle)))
+}
+
+/// Submits bundles with a configurable transport and retry policy.
+#[derive(Clone)]
+pub struct Submitter {
+    transport: Arc<dyn SubmitTransport>,
+    retry: RetryPolicy,
+}
+
+impl Default for Submitter {
+    fn default() -> Self {
+        Self { transport: Arc::new(HttpTransport::default()), retry: RetryPolicy::default() }
+    }
+}
+
+impl Submitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn transport(mut self, transport: impl SubmitTransport + 'static) -> Self {
+        self.transport = Arc::new(transport);
+        self
+    }
+
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// POST `signed_bundle` to `endpoint`, retrying transient failures.
+    pub fn submit(&self, endpoint: &str, signed_bundle: &Value) -> Result<SubmitResponse, SubmitError> {
+        let headers = [(IDEMPOTENCY_HEADER, idempotency_key(signed_bundle)?)];
+        let body = serde_json::to_string(signed_bundle)?;
+        let attempts = self.retry.max_attempts.max(1);
+        let mut last = String::new();
+        for attempt in 0..attempts {
+            if attempt > 0 {
+                std::thread::sleep(self.retry.backoff(attempt - 1));
+            }
+            let response = match self.transport.post(endpoint, &headers, &body) {
+                Ok(response) => response,
+                Err(e) => {
+                    last = e;
+                    continue;
+                }
+            };
+            let json = || serde_json::from_str(&response.body).unwrap_or(Value::Null);
+            match response.status {
+                200..=299 => return Ok(SubmitResponse::Accepted { status: response.status, body: json() }),
+                409 => return Ok(SubmitResponse::Duplicate { body: json() }),
+                408 | 429 | 500..=599 => last = format!("{}: {}", response.status, response.body),
+                status => return Err(SubmitError::Rejected { status, body: response.body }),
+            }
+        }
+        Err(SubmitError::Exhausted { attempts, last })
+    }
+}
+
+/// [`Submitter::submit`] with the default transport and retry policy.
+pub fn submit_bundle(endpoint: &str, signed_bundle: &Value) -> Result<SubmitResponse, SubmitError> {
+    Submitter::default().submit(endpoint, signed_bundle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+
+    /// Answers with `statuses` in turn, recording the idempotency keys.
+    struct Scripted {
+        statuses: Mutex<Vec<u16>>,
+        keys: Mutex<Vec<String>>,
+    }
+
+    impl SubmitTransport for Arc<Scripted> {
+        fn post(&self, _url: &str, headers: &[(&str, String)], _body: &str) -> Result<HttpResponse, String> {
+            self.keys.lock().unwrap().push(headers[0].1.clone());
+            let status = self.statuses.lock().unwrap().remove(0);
+            Ok(HttpResponse { status, body: r#"{"receipt_id":"r1"}"#.into() })
+        }
+    }
+
+    #[test]
+    fn test_retries_transient_errors_under_one_key() {
+        let signed = generate_fixtures(&GOLDEN_SEED).unwrap().get("citizenship_bundle.signed").unwrap().value.clone();
+        let scripted = Arc::new(Scripted { statuses: Mutex::new(vec![503, 429, 201, 400]), keys: Mutex::default() });
+        let retry = RetryPolicy { initial_backoff: Duration::ZERO, ..RetryPolicy::default() };
+        let submitter = Submitter::new().transport(scripted.clone()).retry(retry);
+
+        let response = submitter.submit("https://dcp.example/v1/bundles", &signed).unwrap();
+        assert_eq!(response, SubmitResponse::Accepted { status: 201, body: serde_json::json!({"receipt_id": "r1"}) });
+        let keys = scripted.keys.lock().unwrap().clone();
+        assert_eq!(keys.len(), 3);
+        assert!(keys.iter().all(|k| *k == signed["signature"]["bundle_hash"]));
+
+        let err = submitter.submit("https://dcp.example/v1/bundles", &signed).unwrap_err();
+        assert!(matches!(err, SubmitError::Rejected { status: 400, .. }), "{}", err);
+    }
+}
@@ -0,0 +1,117 @@
+//! TypeScript definitions and JSON Schemas for the wire types (feature =
+//! "codegen").
+//!
+//! [`crate::types`] is the source of truth for the shape of DCP
+//! artifacts. [`write_all`] derives from it a `.ts` declaration and a
+//! `.schema.json` for every type there, so the browser playground and JS
+//! consumers are regenerated rather than kept in sync by hand:
+//!
+//! ```text
+//! cargo run --features codegen --bin dcp -- gen-types ../../playground/js/types
+//! ```
+//!
+//! Both follow the serde attributes, so they describe the JSON the types
+//! (de)serialize, not the Rust structs.
+
+use std::path::{Path, PathBuf};
+
+use schemars::JsonSchema;
+use ts_rs::TS;
+
+use crate::merkle::LeafOrder;
+use crate::types::*;
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum CodegenError {
+    #[error("codegen I/O: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("codegen encoding: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("TypeScript export of {name}: {reason}")]
+    TypeScript { name: String, reason: String },
+}
+
+/// Write `ts/<Type>.ts` and `schema/<Type>.schema.json` under `dir`
+/// (created if missing) for every wire type. Returns the written paths.
+pub fn write_all(dir: &Path) -> Result<Vec<PathBuf>, CodegenError> {
+    let mut written = Vec::new();
+    macro_rules! write_types {
+        ($($t:ty),* $(,)?) => {
+            $(write_type::<$t>(dir, &mut written)?;)*
+        };
+    }
+    write_types!(
+        PublicKeyB64,
+        SignatureB64,
+        ResponsiblePrincipalRecord,
+        Agreement,
+        AgentPassport,
+        PassportUpgrade,
+        CapabilityUpgradeRequest,
+        CapabilityUpgradeApproval,
+        KeyAttestation,
+        IntentTarget,
+        Impact,
+        ImpactAssessment,
+        ImpactCategory,
+        ImpactMagnitude,
+        Intent,
+        PolicyDecision,
+        RiskFactor,
+        RiskAssessment,
+        AuditEvidence,
+        EvidenceTombstone,
+        Outcome,
+        AuditEntry,
+        IntentAmendment,
+        CitizenshipBundle,
+        BundlePatch,
+        Signer,
+        BundleSignature,
+        LeafOrder,
+        SignedBundle,
+        DetachedSignature,
+        VerificationFailure,
+        VerifyError,
+        VerificationResult,
+        CheckStatus,
+        CheckResult,
+        VerificationReport,
+    );
+    Ok(written)
+}
+
+fn write_type<T: TS + JsonSchema + 'static>(dir: &Path, written: &mut Vec<PathBuf>) -> Result<(), CodegenError> {
+    let name = T::ident();
+    let ts = T::export_to_string().map_err(|e| CodegenError::TypeScript { name: name.clone(), reason: e.to_string() })?;
+    let mut schema = serde_json::to_string_pretty(&schemars::schema_for!(T))?;
+    schema.push('\n');
+    for (sub, file, text) in [("ts", format!("{}.ts", name), ts), ("schema", format!("{}.schema.json", name), schema)] {
+        std::fs::create_dir_all(dir.join(sub))?;
+        let path = dir.join(sub).join(file);
+        std::fs::write(&path, text)?;
+        written.push(path);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writes_every_type() {
+        let nanos = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos();
+        let dir = std::env::temp_dir().join(format!("dcp-codegen-{}-{}", std::process::id(), nanos));
+        let written = write_all(&dir).unwrap();
+        assert_eq!(written.len(), 2 * 36);
+
+        let ts = std::fs::read_to_string(dir.join("ts/SignedBundle.ts")).unwrap();
+        assert!(ts.contains("bundle: CitizenshipBundle"), "{}", ts);
+        let schema = std::fs::read_to_string(dir.join("schema/SignedBundle.schema.json")).unwrap();
+        let schema: serde_json::Value = serde_json::from_str(&schema).unwrap();
+        assert_eq!(schema["required"], serde_json::json!(["bundle", "signature"]));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
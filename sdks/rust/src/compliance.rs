@@ -0,0 +1,210 @@
+//! Spec compliance levels.
+//!
+//! Relying parties state requirements as a level ("accept L3+") instead
+//! of a set of [`VerifyOptions`]. Each level adds checks to the one
+//! below:
+//!
+//! | Level | Adds |
+//! |-------|------|
+//! | L1 | a valid signature |
+//! | L2 | `bundle_hash`, `merkle_root`, and the intent and `prev_hash` chains |
+//! | L3 | cross-artifact references, identifier consistency, and consent |
+//! | L4 | a revocation check against a registry |
+//!
+//! A bundle is at the highest level whose checks, and those of every
+//! level below, all pass. [`report`] lists what blocks each level.
+//!
+//! ```ignore
+//! let report = compliance::report(&signed, Some(&revocation_state))?;
+//! if report.level < ComplianceLevel::L3 {
+//!     return Err(report.findings_at(ComplianceLevel::L3).collect());
+//! }
+//! ```
+
+use serde_json::Value;
+
+use crate::dataclasses::{intent_categories, RegulatoryCategory};
+use crate::types::{CheckStatus, SignedBundle, VerificationFailure, VerificationResult, VerifyError};
+use crate::verify::{verify_with_report, VerifyOptions};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ComplianceLevel {
+    /// Not even the signature verifies.
+    L0,
+    L1,
+    L2,
+    L3,
+    L4,
+}
+
+/// Revocation lookup for L4. Returns a failed result (`REVOKED: ...`)
+/// when the bundle's agent or human is revoked.
+pub trait RevocationCheck {
+    fn check(&self, signed_bundle: &Value) -> VerificationResult;
+}
+
+impl<F: Fn(&Value) -> VerificationResult> RevocationCheck for F {
+    fn check(&self, signed_bundle: &Value) -> VerificationResult {
+        self(signed_bundle)
+    }
+}
+
+#[cfg(feature = "registry")]
+impl RevocationCheck for crate::registry::RevocationState {
+    fn check(&self, signed_bundle: &Value) -> VerificationResult {
+        crate::registry::RevocationState::check(self, signed_bundle)
+    }
+}
+
+/// Verifier checks by the level they belong to. Those marked `true` must
+/// pass; the rest only must not fail (they are skipped when the bundle
+/// has nothing for them to check).
+const LEVEL_CHECKS: &[(ComplianceLevel, &str, bool)] = &[
+    (ComplianceLevel::L1, "signature_policy", true),
+    (ComplianceLevel::L1, "signature", true),
+    (ComplianceLevel::L1, "signer_binding", false),
+    (ComplianceLevel::L1, "signature_age", false),
+    (ComplianceLevel::L1, "binding_expiry", false),
+    (ComplianceLevel::L2, "bundle_hash", true),
+    (ComplianceLevel::L2, "merkle_root", true),
+    (ComplianceLevel::L2, "intent_hash", true),
+    (ComplianceLevel::L2, "prev_hash", true),
+    (ComplianceLevel::L2, "sequence", false),
+    (ComplianceLevel::L2, "trace_context", false),
+    (ComplianceLevel::L3, "expiry", false),
+    (ComplianceLevel::L3, "references", true),
+    (ComplianceLevel::L3, "identifiers", true),
+    (ComplianceLevel::L3, "decision_coverage", false),
+    (ComplianceLevel::L3, "hashed_targets", false),
+    (ComplianceLevel::L3, "policy_snapshot", false),
+];
+
+/// What keeps a bundle from one level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComplianceFinding {
+    pub level: ComplianceLevel,
+    pub failure: VerificationFailure,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComplianceReport {
+    pub level: ComplianceLevel,
+    /// Every finding, for every level, lowest level first.
+    pub findings: Vec<ComplianceFinding>,
+    /// Regulatory categories the intent's data classes engage, by the
+    /// [`crate::dataclasses::MAPPING_VERSION`] table.
+    pub regulatory_categories: Vec<RegulatoryCategory>,
+}
+
+impl ComplianceReport {
+    /// Whether the bundle is at `level` or above.
+    pub fn meets(&self, level: ComplianceLevel) -> bool {
+        self.level >= level
+    }
+
+    /// Findings that block exactly `level`.
+    pub fn findings_at(&self, level: ComplianceLevel) -> impl Iterator<Item = &VerificationFailure> {
+        self.findings.iter().filter(move |f| f.level == level).map(|f| &f.failure)
+    }
+}
+
+/// The level of `signed_bundle`, without a revocation check (so at most
+/// L3).
+pub fn assess(signed_bundle: &SignedBundle) -> Result<ComplianceLevel, String> {
+    Ok(report(signed_bundle, None)?.level)
+}
+
+/// Assess `signed_bundle` against every level, with `revocation`
+/// deciding L4. Verification runs with default options plus
+/// [`VerifyOptions::strict_identifiers`].
+pub fn report(
+    signed_bundle: &SignedBundle,
+    revocation: Option<&dyn RevocationCheck>,
+) -> Result<ComplianceReport, String> {
+    let value = serde_json::to_value(signed_bundle).map_err(|e| e.to_string())?;
+    let opts = VerifyOptions { strict_identifiers: true, ..Default::default() };
+    let verification = verify_with_report(&value, None, &opts);
+
+    // Verification stops at the first failing check. A failure no check
+    // claims blocks L1.
+    let failed_level = LEVEL_CHECKS
+        .iter()
+        .find(|(_, name, _)| verification.check(name).is_some_and(|c| c.status == CheckStatus::Failed))
+        .map_or(ComplianceLevel::L1, |&(level, _, _)| level);
+    let failures = verification.result.failures.iter().flatten();
+    let mut findings: Vec<ComplianceFinding> = failures
+        .map(|failure| ComplianceFinding { level: failed_level, failure: failure.clone() })
+        .collect();
+    for &(level, name, _) in LEVEL_CHECKS.iter().filter(|c| c.2) {
+        let Some(check) = verification.check(name).filter(|c| c.status == CheckStatus::Skipped) else { continue };
+        let reason = check.skipped_reason.as_deref().unwrap_or("skipped");
+        let failure = VerificationFailure::new(VerifyError::NotVerified, format!("{} ({})", name, reason), "");
+        findings.push(ComplianceFinding { level, failure });
+    }
+
+    let intent = &signed_bundle.bundle.intent;
+    let decision = &signed_bundle.bundle.policy_decision.decision;
+    if intent.requires_consent == Some(true) && decision == "approve" {
+        let failure = VerificationFailure::new(
+            VerifyError::ConsentMissing,
+            "intent requires consent but was approved without escalation",
+            "/bundle/policy_decision/decision",
+        )
+        .expected("escalate or block")
+        .actual(decision.as_str());
+        findings.push(ComplianceFinding { level: ComplianceLevel::L3, failure });
+    }
+
+    match revocation {
+        Some(registry) => {
+            let result = registry.check(&value);
+            let failures = result.failures.into_iter().flatten();
+            findings.extend(failures.map(|failure| ComplianceFinding { level: ComplianceLevel::L4, failure }));
+        }
+        None => findings.push(ComplianceFinding {
+            level: ComplianceLevel::L4,
+            failure: VerificationFailure::new(VerifyError::NotVerified, "revocation (no registry given)", ""),
+        }),
+    }
+
+    findings.sort_by_key(|f| f.level);
+    let level = match findings.first() {
+        Some(f) => match f.level {
+            ComplianceLevel::L0 | ComplianceLevel::L1 => ComplianceLevel::L0,
+            ComplianceLevel::L2 => ComplianceLevel::L1,
+            ComplianceLevel::L3 => ComplianceLevel::L2,
+            ComplianceLevel::L4 => ComplianceLevel::L3,
+        },
+        None => ComplianceLevel::L4,
+    };
+    let regulatory_categories = intent_categories(&signed_bundle.bundle.intent);
+    Ok(ComplianceReport { level, findings, regulatory_categories })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+
+    #[test]
+    fn test_levels() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let signed: SignedBundle =
+            serde_json::from_value(set.get("citizenship_bundle.signed").unwrap().value.clone()).unwrap();
+        assert_eq!(assess(&signed).unwrap(), ComplianceLevel::L3);
+        let not_revoked = |_: &Value| VerificationResult::ok();
+        let full = report(&signed, Some(&not_revoked)).unwrap();
+        assert!(full.meets(ComplianceLevel::L4));
+        assert_eq!(full.regulatory_categories, vec![RegulatoryCategory::GdprPersonalData]);
+
+        // The signature covers the bundle, not the hashes in the signature block.
+        let mut tampered = signed.clone();
+        tampered.signature.bundle_hash = format!("sha256:{}", "0".repeat(64));
+        let found = report(&tampered, Some(&not_revoked)).unwrap();
+        assert_eq!(found.level, ComplianceLevel::L1);
+        assert!(found.findings_at(ComplianceLevel::L2).next().unwrap().message.starts_with("BUNDLE HASH MISMATCH"));
+
+        tampered.bundle.audit_entries[0].outcome = "rewritten".into();
+        assert_eq!(assess(&tampered).unwrap(), ComplianceLevel::L0);
+    }
+}
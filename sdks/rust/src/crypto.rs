@@ -4,64 +4,337 @@ use std::time::Instant;
 
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD as BASE64;
-use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey, Signature};
-use sha2::{Sha256, Digest};
+use ed25519_dalek::SigningKey;
 use serde_json::Value;
+use rand::{CryptoRng, RngCore};
 
+use crate::b64;
+use crate::entropy::{health_check, EntropyError};
+use crate::hash::{digest_hex, digest_pair, HashAlgorithm};
 use crate::observability::{attrs, dcp_telemetry, SpanStatus};
 
+/// Identifier of the canonicalization implemented by [`canonicalize`],
+/// recorded as `signature.canon` by signers.
+pub const CANON_V1: &str = "dcp-c14n/1";
+
+/// A canonicalization implementation, as named by `signature.canon`.
+/// Verifiers resolve the identifier once and then canonicalize and hash
+/// everything in the bundle with it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Canon {
+    /// [`CANON_V1`].
+    #[default]
+    V1,
+}
+
+impl Canon {
+    /// `None` for identifiers this SDK does not know.
+    pub fn parse(id: &str) -> Option<Self> {
+        match id {
+            CANON_V1 => Some(Canon::V1),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Canon::V1 => CANON_V1,
+        }
+    }
+}
+
+/// Canonicalize with the implementation named by `canon`. Returns `None`
+/// for identifiers this SDK does not know, so verifiers can reject rather
+/// than silently fall back. Already-signed bundles keep verifying with the
+/// implementation they were signed under when newer ones are added.
+pub fn canonicalize_with(canon: &str, obj: &Value) -> Option<String> {
+    Canon::parse(canon).map(|canon| Canonicalizer::new().canonicalize_in(canon, obj).to_string())
+}
+
+/// Canonicalization knobs. `Default` follows the spec profile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CanonicalizeOptions {
+    /// Omit object members whose value is `null`, so `{"contact": null}`
+    /// canonicalizes like `{}`. Array slots keep their `null`s. Default
+    /// `false`: spec Rule 5 preserves nulls. Only for comparing against
+    /// producers that skip `None` fields (see
+    /// [`crate::verify::VerifyOptions::accept_null_as_absent`]).
+    pub treat_null_as_absent: bool,
+}
+
 /// Canonical JSON serialization (sorted keys, compact).
 pub fn canonicalize(obj: &Value) -> String {
+    canonicalize_opts(obj, &CanonicalizeOptions::default())
+}
+
+/// [`canonicalize`] with explicit [`CanonicalizeOptions`].
+pub fn canonicalize_opts(obj: &Value, opts: &CanonicalizeOptions) -> String {
+    let mut out = Vec::new();
+    write_canonical(obj, opts, &mut out);
+    String::from_utf8(out).expect("canonical JSON is UTF-8")
+}
+
+/// Canonicalizer that keeps its output buffer between calls, so
+/// high-throughput verifiers canonicalize without allocating per call
+/// once the buffer has grown to their bundle size. Produces exactly the
+/// bytes of [`canonicalize_opts`].
+#[derive(Debug, Clone, Default)]
+pub struct Canonicalizer {
+    buf: Vec<u8>,
+    opts: CanonicalizeOptions,
+}
+
+impl Canonicalizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(opts: CanonicalizeOptions) -> Self {
+        Self { buf: Vec::new(), opts }
+    }
+
+    /// Canonical form of `obj`, valid until the next call.
+    pub fn canonicalize(&mut self, obj: &Value) -> &str {
+        self.buf.clear();
+        write_canonical(obj, &self.opts, &mut self.buf);
+        std::str::from_utf8(&self.buf).expect("canonical JSON is UTF-8")
+    }
+
+    /// Buffered counterpart of [`canonicalize_with`].
+    pub fn canonicalize_with(&mut self, canon: &str, obj: &Value) -> Option<&str> {
+        Canon::parse(canon).map(|canon| self.canonicalize_in(canon, obj))
+    }
+
+    /// Canonical form of `obj` under `canon`, valid until the next call.
+    pub fn canonicalize_in(&mut self, canon: Canon, obj: &Value) -> &str {
+        match canon {
+            Canon::V1 => self.canonicalize(obj),
+        }
+    }
+
+    /// Buffered counterpart of [`hash_object`].
+    pub fn hash(&mut self, obj: &Value) -> String {
+        self.hash_in(Canon::V1, obj)
+    }
+
+    /// SHA-256 hex of the canonical form of `obj` under `canon`.
+    pub fn hash_in(&mut self, canon: Canon, obj: &Value) -> String {
+        hash_canonical(self.canonicalize_in(canon, obj))
+    }
+
+    /// [`intent_hash`] under `canon`.
+    pub fn intent_hash_in(&mut self, canon: Canon, intent: &Value) -> String {
+        self.hash_in(canon, intent)
+    }
+
+    /// [`accepted_intent_hashes`] under `canon`.
+    pub fn accepted_intent_hashes_in(&mut self, canon: Canon, intent: &Value) -> [String; 2] {
+        let mut normalized = intent.clone();
+        normalize_set_fields(&mut normalized);
+        [self.intent_hash_in(canon, intent), self.hash_in(canon, &normalized)]
+    }
+
+    /// Bytes currently reserved by the buffer.
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+}
+
+fn write_canonical(obj: &Value, opts: &CanonicalizeOptions, out: &mut Vec<u8>) {
     match obj {
         Value::Object(map) => {
-            let mut keys: Vec<&String> = map.keys().collect();
-            keys.sort();
-            let pairs: Vec<String> = keys
-                .iter()
-                .map(|k| format!("{}:{}", serde_json::to_string(k).unwrap(), canonicalize(&map[*k])))
-                .collect();
-            format!("{{{}}}", pairs.join(","))
+            let keep = |v: &Value| !(opts.treat_null_as_absent && v.is_null());
+            let write_member = |i: usize, k: &String, v: &Value, out: &mut Vec<u8>| {
+                if i > 0 {
+                    out.push(b',');
+                }
+                serde_json::to_writer(&mut *out, k).expect("writing to a Vec cannot fail");
+                out.push(b':');
+                write_canonical(v, opts, out);
+            };
+            out.push(b'{');
+            // serde_json's default map is already sorted; only an
+            // insertion-ordered map (`preserve_order`) needs the sort.
+            if map.keys().zip(map.keys().skip(1)).all(|(a, b)| a <= b) {
+                for (i, (k, v)) in map.iter().filter(|&(_, v)| keep(v)).enumerate() {
+                    write_member(i, k, v, out);
+                }
+            } else {
+                let mut members: Vec<(&String, &Value)> = map.iter().filter(|&(_, v)| keep(v)).collect();
+                members.sort_by(|a, b| a.0.cmp(b.0));
+                for (i, (k, v)) in members.into_iter().enumerate() {
+                    write_member(i, k, v, out);
+                }
+            }
+            out.push(b'}');
         }
         Value::Array(arr) => {
-            let items: Vec<String> = arr.iter().map(canonicalize).collect();
-            format!("[{}]", items.join(","))
+            out.push(b'[');
+            for (i, v) in arr.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_canonical(v, opts, out);
+            }
+            out.push(b']');
         }
-        _ => serde_json::to_string(obj).unwrap(),
+        _ => serde_json::to_writer(&mut *out, obj).expect("writing to a Vec cannot fail"),
     }
 }
 
 /// Compute SHA-256 hash of canonical JSON. Returns hex string.
 pub fn hash_object(obj: &Value) -> String {
-    let canon = canonicalize(obj);
-    let mut hasher = Sha256::new();
-    hasher.update(canon.as_bytes());
-    hex::encode(hasher.finalize())
+    hash_canonical(&canonicalize(obj))
+}
+
+/// SHA-256 hex of an already-canonicalized JSON string.
+pub fn hash_canonical(canon: &str) -> String {
+    digest_hex(HashAlgorithm::Sha256, canon.as_bytes())
+}
+
+/// Fields whose array values have set semantics: element order carries no
+/// meaning, so `["pii","financial"]` and `["financial","pii"]` describe the
+/// same intent. Typed canonical hashing sorts and de-duplicates them.
+pub const SET_VALUED_FIELDS: &[&str] = &["data_classes"];
+
+/// Apply set semantics to [`SET_VALUED_FIELDS`] anywhere in `value`:
+/// string arrays under those keys are sorted (byte order) and de-duplicated.
+/// Arrays containing non-string elements are left untouched.
+pub fn normalize_set_fields(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (k, v) in map.iter_mut() {
+                if SET_VALUED_FIELDS.contains(&k.as_str()) {
+                    if let Value::Array(arr) = v {
+                        if arr.iter().all(Value::is_string) {
+                            arr.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+                            arr.dedup();
+                            continue;
+                        }
+                    }
+                }
+                normalize_set_fields(v);
+            }
+        }
+        Value::Array(arr) => arr.iter_mut().for_each(normalize_set_fields),
+        _ => {}
+    }
+}
+
+/// SHA-256 of the canonical form after [`normalize_set_fields`].
+pub fn hash_object_normalized(obj: &Value) -> String {
+    let mut normalized = obj.clone();
+    normalize_set_fields(&mut normalized);
+    hash_object(&normalized)
+}
+
+/// `intent_hash` of an intent revision: SHA-256 of its canonical form, as
+/// the spec and every SDK compute it. Audit entries, amendments and
+/// patches all hash intents with this.
+pub fn intent_hash(intent: &Value) -> String {
+    Canonicalizer::new().intent_hash_in(Canon::V1, intent)
+}
+
+/// The `intent_hash` values an audit entry may carry for `intent`:
+/// [`intent_hash`], then the set-normalized form
+/// ([`hash_object_normalized`]) from producers that sort `data_classes`
+/// before hashing.
+pub fn accepted_intent_hashes(intent: &Value) -> [String; 2] {
+    Canonicalizer::new().accepted_intent_hashes_in(Canon::V1, intent)
+}
+
+/// Typed canonical hashing: serialize a DCP type and hash it with set
+/// semantics applied, so SDKs that order `data_classes` differently agree.
+pub fn hash_typed<T: serde::Serialize>(value: &T) -> Result<String, String> {
+    let v = serde_json::to_value(value).map_err(|e| e.to_string())?;
+    Ok(hash_object_normalized(&v))
 }
 
 /// Generate a new Ed25519 keypair. Returns (public_key_b64, secret_key_b64).
 pub fn generate_keypair() -> (String, String) {
-    let mut rng = rand::thread_rng();
-    let signing_key = SigningKey::generate(&mut rng);
+    generate_keypair_with(&mut rand::thread_rng())
+}
+
+/// Generate an Ed25519 keypair from a caller-supplied cryptographic RNG
+/// (HSM-backed, hardware, or a seeded test RNG).
+pub fn generate_keypair_with<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> (String, String) {
+    let mut seed = zeroize::Zeroizing::new([0u8; 32]);
+    rng.fill_bytes(seed.as_mut());
+    let signing_key = SigningKey::from_bytes(&seed);
     let verifying_key = signing_key.verifying_key();
     let secret_b64 = BASE64.encode(signing_key.to_keypair_bytes());
     let public_b64 = BASE64.encode(verifying_key.to_bytes());
     (public_b64, secret_b64)
 }
 
+/// Deterministic Ed25519 keypair from a 32-byte seed. For fixtures and
+/// tests only; production keys must come from a CSPRNG.
+pub fn keypair_from_seed(seed: &[u8; 32]) -> (String, String) {
+    let signing_key = SigningKey::from_bytes(seed);
+    let public_b64 = BASE64.encode(signing_key.verifying_key().to_bytes());
+    (public_b64, BASE64.encode(signing_key.to_keypair_bytes()))
+}
+
+/// Like [`generate_keypair_with`], but first runs the SP 800-90B health
+/// tests in [`crate::entropy`] on `rng` and refuses to generate a key from
+/// a source that fails them.
+pub fn generate_keypair_checked<R: RngCore + CryptoRng + ?Sized>(
+    rng: &mut R,
+) -> Result<(String, String), EntropyError> {
+    health_check(rng)?;
+    Ok(generate_keypair_with(rng))
+}
+
+/// Derive the base64 Ed25519 public key from a base64 secret key (32-byte
+/// seed or 64-byte keypair encoding).
+pub fn public_key_from_secret(secret_key_b64: &str) -> Result<String, String> {
+    let key_bytes = b64::decode_ed25519_secret(secret_key_b64).map_err(|e| format!("secret key: {}", e))?;
+    let signing_key = SigningKey::from_bytes(&key_bytes);
+    Ok(BASE64.encode(signing_key.verifying_key().to_bytes()))
+}
+
 /// Sign a JSON value with Ed25519 (detached). Returns base64 signature.
 pub fn sign_object(obj: &Value, secret_key_b64: &str) -> Result<String, String> {
+    sign_canonical(&canonicalize(obj), secret_key_b64)
+}
+
+/// Sign an already-canonicalized JSON string with Ed25519 (detached).
+pub fn sign_canonical(canon: &str, secret_key_b64: &str) -> Result<String, String> {
+    sign_ed25519(canon.as_bytes(), secret_key_b64)
+}
+
+/// Prefix of the message signed by [`sign_digest`]. Canonical JSON starts
+/// with `{`, so a digest signature never verifies as a signature over an
+/// object, or the reverse.
+pub const DIGEST_SIGNING_CONTEXT: &[u8] = b"DCP-AI.v1.Digest\0";
+
+fn digest_message(digest: &[u8; 32]) -> Vec<u8> {
+    [DIGEST_SIGNING_CONTEXT, digest.as_slice()].concat()
+}
+
+/// Sign a SHA-256 digest computed elsewhere (e.g. by object storage for
+/// an artifact too large to pass through the SDK). Returns a base64
+/// signature over [`DIGEST_SIGNING_CONTEXT`] followed by the digest; see
+/// [`crate::types::DetachedSignature`] for the envelope.
+pub fn sign_digest(digest: &[u8; 32], secret_key_b64: &str) -> Result<String, String> {
+    sign_ed25519(&digest_message(digest), secret_key_b64)
+}
+
+/// Verify a [`sign_digest`] signature, strictly as [`verify_object`] does.
+pub fn verify_digest(digest: &[u8; 32], signature_b64: &str, public_key_b64: &str) -> Result<bool, String> {
+    verify_ed25519(&digest_message(digest), signature_b64, public_key_b64, true)
+}
+
+fn sign_ed25519(msg: &[u8], secret_key_b64: &str) -> Result<String, String> {
     let tel = dcp_telemetry();
     let span_id = tel.start_span("dcp.sign", attrs([("algorithm", "ed25519")]));
     let t0 = Instant::now();
 
     let inner = || -> Result<String, String> {
-        let canon = canonicalize(obj);
-        let sk_bytes = BASE64.decode(secret_key_b64).map_err(|e| e.to_string())?;
-        let key_bytes: [u8; 32] =
-            sk_bytes[..32].try_into().map_err(|_| "invalid key length".to_string())?;
-        let signing_key = SigningKey::from_bytes(&key_bytes);
-        let sig = signing_key.sign(canon.as_bytes());
-        Ok(BASE64.encode(sig.to_bytes()))
+        let key_bytes = b64::decode_ed25519_secret(secret_key_b64).map_err(|e| format!("secret key: {}", e))?;
+        Ok(BASE64.encode(backend::sign(&key_bytes, msg)))
     };
 
     match inner() {
@@ -79,25 +352,74 @@ pub fn sign_object(obj: &Value, secret_key_b64: &str) -> Result<String, String>
 }
 
 /// Verify an Ed25519 detached signature on a JSON value.
+///
+/// Verification is strict ([`ed25519_dalek::VerifyingKey::verify_strict`],
+/// or the same checks with [`CRYPTO_BACKEND`] `"ring"`): besides the
+/// non-canonical `S` values every mode rejects, signatures whose `R` or
+/// public key has small order are refused, so no two conforming verifiers
+/// disagree on the same bytes.
 pub fn verify_object(obj: &Value, signature_b64: &str, public_key_b64: &str) -> Result<bool, String> {
+    verify_canonical(&canonicalize(obj), signature_b64, public_key_b64)
+}
+
+/// Verify a signature on a JSON value against several candidate keys,
+/// e.g. the old and new key while a rotation is in flight. Returns the
+/// index of the first key that verifies, or one reason per key.
+pub fn verify_object_multi<K: AsRef<str>>(obj: &Value, signature_b64: &str, keys: &[K]) -> Result<usize, Vec<String>> {
+    verify_canonical_multi(&canonicalize(obj), signature_b64, keys)
+}
+
+/// [`verify_object_multi`] over an already-canonicalized JSON string.
+pub fn verify_canonical_multi<K: AsRef<str>>(canon: &str, signature_b64: &str, keys: &[K]) -> Result<usize, Vec<String>> {
+    verify_canonical_multi_with(canon, signature_b64, keys, true)
+}
+
+/// [`verify_canonical_multi`], choosing strict or RFC 8032 cofactorless
+/// (`strict = false`) verification; see [`verify_canonical_lenient`].
+pub fn verify_canonical_multi_with<K: AsRef<str>>(
+    canon: &str,
+    signature_b64: &str,
+    keys: &[K],
+    strict: bool,
+) -> Result<usize, Vec<String>> {
+    if keys.is_empty() {
+        return Err(vec!["no candidate keys".to_string()]);
+    }
+    let mut reasons = Vec::with_capacity(keys.len());
+    for (i, key) in keys.iter().enumerate() {
+        match verify_ed25519(canon.as_bytes(), signature_b64, key.as_ref(), strict) {
+            Ok(true) => return Ok(i),
+            Ok(false) => reasons.push(format!("key {}: signature mismatch", i)),
+            Err(e) => reasons.push(format!("key {}: {}", i, e)),
+        }
+    }
+    Err(reasons)
+}
+
+/// Verify an Ed25519 detached signature over an already-canonicalized
+/// JSON string. Lets callers that also hash the canonical form avoid
+/// serializing the same value twice.
+pub fn verify_canonical(canon: &str, signature_b64: &str, public_key_b64: &str) -> Result<bool, String> {
+    verify_ed25519(canon.as_bytes(), signature_b64, public_key_b64, true)
+}
+
+/// [`verify_canonical`] without the small-order checks of strict
+/// verification. Accepts signatures under weak (small-order) keys that
+/// verify for any message; only for interop with verifiers known to be
+/// lenient. Non-canonical `S` is still rejected.
+pub fn verify_canonical_lenient(canon: &str, signature_b64: &str, public_key_b64: &str) -> Result<bool, String> {
+    verify_ed25519(canon.as_bytes(), signature_b64, public_key_b64, false)
+}
+
+fn verify_ed25519(msg: &[u8], signature_b64: &str, public_key_b64: &str, strict: bool) -> Result<bool, String> {
     let tel = dcp_telemetry();
     let span_id = tel.start_span("dcp.verify", attrs([("algorithm", "ed25519")]));
     let t0 = Instant::now();
 
     let inner = || -> Result<bool, String> {
-        let canon = canonicalize(obj);
-        let sig_bytes = BASE64.decode(signature_b64).map_err(|e| e.to_string())?;
-        let pk_bytes = BASE64.decode(public_key_b64).map_err(|e| e.to_string())?;
-
-        let pk_array: [u8; 32] =
-            pk_bytes.try_into().map_err(|_| "invalid public key length".to_string())?;
-        let sig_array: [u8; 64] =
-            sig_bytes.try_into().map_err(|_| "invalid signature length".to_string())?;
-
-        let verifying_key = VerifyingKey::from_bytes(&pk_array).map_err(|e| e.to_string())?;
-        let signature = Signature::from_bytes(&sig_array);
-
-        Ok(verifying_key.verify(canon.as_bytes(), &signature).is_ok())
+        let sig_array = b64::decode_exact::<64>(signature_b64).map_err(|e| format!("signature: {}", e))?;
+        let pk_array = b64::decode_exact::<32>(public_key_b64).map_err(|e| format!("public key: {}", e))?;
+        backend::verify(&pk_array, &sig_array, msg, strict)
     };
 
     match inner() {
@@ -114,28 +436,77 @@ pub fn verify_object(obj: &Value, signature_b64: &str, public_key_b64: &str) ->
     }
 }
 
-/// Compute Merkle root from hex leaf hashes.
+/// Ed25519 implementation behind [`sign_object`] and [`verify_object`]:
+/// `"ring"` with the `ring-backend` feature, otherwise `"ed25519-dalek"`.
+/// Key generation always uses ed25519-dalek. Both backends produce and
+/// accept the same bytes; `tests/nist_kat.rs` runs the RFC 8032 vectors
+/// through whichever is compiled in.
+#[cfg(feature = "ring-backend")]
+pub const CRYPTO_BACKEND: &str = "ring";
+#[cfg(not(feature = "ring-backend"))]
+pub const CRYPTO_BACKEND: &str = "ed25519-dalek";
+
+#[cfg(not(feature = "ring-backend"))]
+mod backend {
+    use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+
+    pub fn sign(seed: &[u8; 32], msg: &[u8]) -> [u8; 64] {
+        SigningKey::from_bytes(seed).sign(msg).to_bytes()
+    }
+
+    pub fn verify(pk: &[u8; 32], sig: &[u8; 64], msg: &[u8], strict: bool) -> Result<bool, String> {
+        let verifying_key = VerifyingKey::from_bytes(pk).map_err(|e| e.to_string())?;
+        let signature = Signature::from_bytes(sig);
+        if strict {
+            Ok(verifying_key.verify_strict(msg, &signature).is_ok())
+        } else {
+            Ok(verifying_key.verify(msg, &signature).is_ok())
+        }
+    }
+}
+
+#[cfg(feature = "ring-backend")]
+mod backend {
+    use ed25519_dalek::VerifyingKey;
+    use ring::signature::{Ed25519KeyPair, UnparsedPublicKey, ED25519};
+
+    pub fn sign(seed: &[u8; 32], msg: &[u8]) -> [u8; 64] {
+        let pair = Ed25519KeyPair::from_seed_unchecked(seed).expect("any 32-byte seed is a valid Ed25519 key");
+        let mut sig = [0u8; 64];
+        sig.copy_from_slice(pair.sign(msg).as_ref());
+        sig
+    }
+
+    pub fn verify(pk: &[u8; 32], sig: &[u8; 64], msg: &[u8], strict: bool) -> Result<bool, String> {
+        // Decode the key as the dalek backend does, so both report the
+        // same error for bytes that are not a curve point.
+        let verifying_key = VerifyingKey::from_bytes(pk).map_err(|e| e.to_string())?;
+        if strict {
+            // ring does not refuse small-order keys or R; do what
+            // `verify_strict` does.
+            let r_bytes: [u8; 32] = sig[..32].try_into().expect("64-byte signature");
+            let r_small = VerifyingKey::from_bytes(&r_bytes).map_or(true, |r| r.is_weak());
+            if verifying_key.is_weak() || r_small {
+                return Ok(false);
+            }
+        }
+        Ok(UnparsedPublicKey::new(&ED25519, pk).verify(msg, sig).is_ok())
+    }
+}
+
+/// Compute Merkle root from hex leaf hashes. `None` if there are no
+/// leaves or one is not hex.
 pub fn merkle_root_from_hex_leaves(leaves: &[String]) -> Option<String> {
     if leaves.is_empty() {
         return None;
     }
-    let mut layer: Vec<String> = leaves.to_vec();
+    let mut layer: Vec<Vec<u8>> = leaves.iter().map(hex::decode).collect::<Result<_, _>>().ok()?;
     while layer.len() > 1 {
         if layer.len() % 2 == 1 {
             let last = layer.last().unwrap().clone();
             layer.push(last);
         }
-        let mut next = Vec::new();
-        for i in (0..layer.len()).step_by(2) {
-            let left = hex::decode(&layer[i]).unwrap();
-            let right = hex::decode(&layer[i + 1]).unwrap();
-            let mut combined = left;
-            combined.extend_from_slice(&right);
-            let mut hasher = Sha256::new();
-            hasher.update(&combined);
-            next.push(hex::encode(hasher.finalize()));
-        }
-        layer = next;
+        layer = layer.chunks(2).map(|pair| digest_pair(HashAlgorithm::Sha256, &pair[0], &pair[1])).collect();
     }
-    Some(layer[0].clone())
+    Some(hex::encode(&layer[0]))
 }
@@ -0,0 +1,180 @@
+//! Regulatory categories of DCP data classes.
+//!
+//! An intent's `data_classes` say what data an action touches; which
+//! laws that engages depends on the jurisdiction. [`MAPPING`] is the
+//! crate's table from each [`DataClass`] to the [`RegulatoryCategory`]s it
+//! falls under (GDPR personal and special-category data, HIPAA PHI, PCI
+//! cardholder data, COPPA children's data). The table is versioned by
+//! [`MAPPING_VERSION`]; a change to it is a new version, so a stored
+//! compliance report can say which table classified it.
+//!
+//! ```
+//! use dcp_ai::dataclasses::{lookup, lookup_in, Jurisdiction, RegulatoryCategory};
+//!
+//! let categories = lookup("health_data").unwrap();
+//! assert!(categories.contains(&RegulatoryCategory::HipaaPhi));
+//! assert_eq!(lookup_in("health_data", Jurisdiction::Us), vec![RegulatoryCategory::HipaaPhi]);
+//! assert!(lookup("telemetry").is_none());
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::spec::DataClass;
+use crate::types::Intent;
+
+/// Version of [`MAPPING`]; the date the table last changed.
+pub const MAPPING_VERSION: &str = "2026-10-01";
+
+/// Where a regulatory category applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Jurisdiction {
+    Eu,
+    Us,
+    /// Contractual regimes that apply wherever cards are accepted.
+    Global,
+}
+
+/// A category of data some regulation attaches obligations to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum RegulatoryCategory {
+    /// GDPR Art. 4(1).
+    #[serde(rename = "gdpr:personal_data")]
+    GdprPersonalData,
+    /// GDPR Art. 9: health, biometric, and other special categories.
+    #[serde(rename = "gdpr:special_category")]
+    GdprSpecialCategory,
+    /// HIPAA protected health information.
+    #[serde(rename = "hipaa:phi")]
+    HipaaPhi,
+    /// PCI DSS cardholder data.
+    #[serde(rename = "pci:cardholder_data")]
+    PciCardholderData,
+    /// COPPA personal information of children under 13.
+    #[serde(rename = "coppa:child_data")]
+    CoppaChildData,
+}
+
+impl RegulatoryCategory {
+    pub const ALL: &'static [RegulatoryCategory] = &[
+        RegulatoryCategory::GdprPersonalData,
+        RegulatoryCategory::GdprSpecialCategory,
+        RegulatoryCategory::HipaaPhi,
+        RegulatoryCategory::PciCardholderData,
+        RegulatoryCategory::CoppaChildData,
+    ];
+
+    /// The `<regime>:<category>` name, as serialized.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RegulatoryCategory::GdprPersonalData => "gdpr:personal_data",
+            RegulatoryCategory::GdprSpecialCategory => "gdpr:special_category",
+            RegulatoryCategory::HipaaPhi => "hipaa:phi",
+            RegulatoryCategory::PciCardholderData => "pci:cardholder_data",
+            RegulatoryCategory::CoppaChildData => "coppa:child_data",
+        }
+    }
+
+    pub fn jurisdiction(&self) -> Jurisdiction {
+        match self {
+            RegulatoryCategory::GdprPersonalData | RegulatoryCategory::GdprSpecialCategory => Jurisdiction::Eu,
+            RegulatoryCategory::HipaaPhi | RegulatoryCategory::CoppaChildData => Jurisdiction::Us,
+            RegulatoryCategory::PciCardholderData => Jurisdiction::Global,
+        }
+    }
+}
+
+impl std::str::FromStr for RegulatoryCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let found = Self::ALL.iter().copied().find(|c| c.as_str() == s);
+        found.ok_or_else(|| format!("unknown regulatory category {:?}", s))
+    }
+}
+
+impl std::fmt::Display for RegulatoryCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+use RegulatoryCategory::*;
+
+/// Every data class and the categories it falls under, as of
+/// [`MAPPING_VERSION`].
+pub const MAPPING: &[(DataClass, &[RegulatoryCategory])] = &[
+    (DataClass::NoData, &[]),
+    (DataClass::ContactInfo, &[GdprPersonalData]),
+    (DataClass::Pii, &[GdprPersonalData]),
+    (DataClass::Credentials, &[GdprPersonalData]),
+    (DataClass::FinancialData, &[GdprPersonalData, PciCardholderData]),
+    (DataClass::HealthData, &[GdprPersonalData, GdprSpecialCategory, HipaaPhi]),
+    (DataClass::ChildrenData, &[GdprPersonalData, CoppaChildData]),
+    (DataClass::CompanyConfidential, &[]),
+];
+
+/// The categories `class` falls under.
+pub fn categories(class: DataClass) -> &'static [RegulatoryCategory] {
+    MAPPING.iter().find(|(c, _)| *c == class).map_or(&[], |(_, categories)| categories)
+}
+
+/// The categories of a `data_classes` wire value, or `None` when it is
+/// not a spec data class.
+pub fn lookup(data_class: &str) -> Option<&'static [RegulatoryCategory]> {
+    data_class.parse().ok().map(categories)
+}
+
+/// The categories of `data_class` that apply in `jurisdiction`; global
+/// categories apply in every jurisdiction.
+pub fn lookup_in(data_class: &str, jurisdiction: Jurisdiction) -> Vec<RegulatoryCategory> {
+    let categories = lookup(data_class).unwrap_or_default().iter().copied();
+    categories.filter(|c| c.jurisdiction() == jurisdiction || c.jurisdiction() == Jurisdiction::Global).collect()
+}
+
+/// Every category engaged by `intent`'s data classes, sorted and without
+/// duplicates. Non-spec data classes engage none.
+pub fn intent_categories(intent: &Intent) -> Vec<RegulatoryCategory> {
+    let mut found: Vec<RegulatoryCategory> =
+        intent.data_classes.iter().filter_map(|c| lookup(c)).flatten().copied().collect();
+    found.sort();
+    found.dedup();
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mapping_covers_every_data_class() {
+        for class in DataClass::ALL {
+            assert_eq!(MAPPING.iter().filter(|(c, _)| c == class).count(), 1, "{}", class);
+        }
+        for category in RegulatoryCategory::ALL {
+            assert_eq!(category.as_str().parse::<RegulatoryCategory>().unwrap(), *category);
+            assert_eq!(serde_json::to_value(category).unwrap(), category.as_str());
+        }
+        assert_eq!(lookup_in("financial_data", Jurisdiction::Us), vec![PciCardholderData]);
+        assert_eq!(lookup_in("health_data", Jurisdiction::Eu), vec![GdprPersonalData, GdprSpecialCategory]);
+    }
+
+    #[test]
+    fn test_policy_rule_matches_engaged_categories() {
+        use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+        use crate::policy::PolicyRule;
+
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let bundle = &set.get("citizenship_bundle.signed").unwrap().value["bundle"];
+        let mut intent: Intent = serde_json::from_value(bundle["intent"].clone()).unwrap();
+        let when = serde_json::json!({"regulatory_categories": ["hipaa:phi"]});
+        let when = when.as_object().unwrap().clone();
+        let rule = PolicyRule { rule_id: "phi".into(), effect: "escalate".into(), when };
+        assert!(!rule.matches_regulatory_categories(&intent));
+        intent.data_classes.push("health_data".into());
+        assert_eq!(intent_categories(&intent), vec![GdprPersonalData, GdprSpecialCategory, HipaaPhi]);
+        assert!(rule.matches_regulatory_categories(&intent));
+    }
+}
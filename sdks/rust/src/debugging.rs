@@ -0,0 +1,224 @@
+//! Minimal reproducers for verification failures.
+//!
+//! A bundle that fails verification is often too large, or too sensitive,
+//! to attach to a bug report. [`minimize_failing_bundle`] shrinks it:
+//! audit entries, then fields and array elements anywhere in the bundle,
+//! are removed one at a time, and a removal is kept only if the bundle
+//! still fails with the same first failure. It repeats until nothing more
+//! can go. The search is deterministic, so the same input always yields
+//! the same reproducer.
+//!
+//! Removing anything changes the bundle hash and breaks the signature. So
+//! that only the failure under study can surface, each candidate has its
+//! `merkle_root`, `bundle_hash`, and signature recomputed with a fixed,
+//! public throwaway key ([`REPRO_SEED`]), which also replaces the agent's
+//! passport key. Failures about those fields themselves (`SIGNATURE
+//! INVALID`, `BUNDLE HASH MISMATCH`, `MERKLE ROOT MISMATCH`, and the key
+//! binding checks) are preserved by leaving them as they are instead.
+//!
+//! ```ignore
+//! if let Some(repro) = minimize_failing_bundle(&signed, &opts) {
+//!     std::fs::write("repro.json", serde_json::to_vec_pretty(&repro.signed_bundle)?)?;
+//! }
+//! ```
+
+use serde_json::Value;
+
+use crate::crypto::{canonicalize, hash_canonical, keypair_from_seed, merkle_root_from_hex_leaves, sign_canonical};
+use crate::merkle::LeafOrder;
+use crate::retention::tombstoned_hash;
+use crate::verify::{verify_signed_bundle_with_options, VerifyOptions};
+
+/// Seed of the key reproducers are re-signed with. It is public: the
+/// signature on a reproducer proves nothing.
+pub const REPRO_SEED: [u8; 32] = *b"dcp-ai minimal reproducer key!!!";
+
+/// Failures about the signature fields themselves, which recomputing
+/// them would hide.
+const SIGNATURE_FAILURES: &[&str] = &[
+    "SIGNATURE INVALID",
+    "BUNDLE HASH MISMATCH",
+    "KEY NOT BOUND",
+    "SIGNER INVALID",
+    "Missing signature",
+    "Missing sig_b64",
+];
+
+/// Result of [`minimize_failing_bundle`].
+#[derive(Debug, Clone)]
+pub struct Minimized {
+    /// The smallest signed bundle found that fails like the original.
+    pub signed_bundle: Value,
+    /// The original's first failure.
+    pub failure: String,
+    /// Whether `signed_bundle` was re-signed with the [`REPRO_SEED`] key.
+    pub resigned: bool,
+    pub entries_removed: usize,
+    /// Fields and array elements removed, besides whole audit entries.
+    pub fields_removed: usize,
+}
+
+/// Shrink `signed_bundle` while it keeps failing verification under
+/// `opts` with the same first failure. `None` if it verifies.
+pub fn minimize_failing_bundle(signed_bundle: &Value, opts: &VerifyOptions) -> Option<Minimized> {
+    let failure = first_failure(signed_bundle, opts)?;
+    let tag = failure_tag(&failure);
+    let fails_same = |candidate: &Value| first_failure(candidate, opts).is_some_and(|f| failure_tag(&f) == tag);
+
+    let mut current = signed_bundle.clone();
+    let mut resigned = false;
+    if !SIGNATURE_FAILURES.contains(&tag) {
+        let mut candidate = current.clone();
+        rederive(&mut candidate, tag, opts);
+        if fails_same(&candidate) {
+            current = candidate;
+            resigned = true;
+        }
+    }
+    let try_removal = |current: &Value, pointer: &str| -> Option<Value> {
+        let mut candidate = current.clone();
+        remove_at(&mut candidate, pointer)?;
+        if resigned {
+            rederive(&mut candidate, tag, opts);
+        }
+        fails_same(&candidate).then_some(candidate)
+    };
+
+    let (mut entries_removed, mut fields_removed) = (0, 0);
+    loop {
+        let mut progressed = false;
+        let entries = current.pointer("/bundle/audit_entries").and_then(Value::as_array).map_or(0, Vec::len);
+        for i in (0..entries).rev() {
+            if let Some(candidate) = try_removal(&current, &format!("/bundle/audit_entries/{}", i)) {
+                current = candidate;
+                entries_removed += 1;
+                progressed = true;
+            }
+        }
+        let mut pointers = Vec::new();
+        collect_pointers(&current["bundle"], "/bundle", &mut pointers);
+        for pointer in pointers {
+            if current.pointer(&pointer).is_none() {
+                continue; // Under a member removed earlier in this pass.
+            }
+            if let Some(candidate) = try_removal(&current, &pointer) {
+                current = candidate;
+                fields_removed += 1;
+                progressed = true;
+            }
+        }
+        if !progressed {
+            break;
+        }
+    }
+    Some(Minimized { signed_bundle: current, failure, resigned, entries_removed, fields_removed })
+}
+
+fn first_failure(signed_bundle: &Value, opts: &VerifyOptions) -> Option<String> {
+    let result = verify_signed_bundle_with_options(signed_bundle, None, opts);
+    if result.verified {
+        return None;
+    }
+    Some(result.errors.and_then(|e| e.into_iter().next()).unwrap_or_default())
+}
+
+/// The leading part of a failure message that names the failure, without
+/// the positions and values after it (`prev_hash chain (entry 2): ...`
+/// gives `prev_hash chain`).
+fn failure_tag(message: &str) -> &str {
+    message.split([':', '(']).next().unwrap_or(message).trim_end()
+}
+
+/// JSON Pointers of every member and element under `value`, parents
+/// before their children. Top-level audit entries are left to the
+/// entry pass.
+fn collect_pointers(value: &Value, at: &str, out: &mut Vec<String>) {
+    let children: Vec<(String, &Value)> = match value {
+        Value::Object(map) => map.iter().map(|(k, v)| (k.replace('~', "~0").replace('/', "~1"), v)).collect(),
+        Value::Array(items) => items.iter().enumerate().map(|(i, v)| (i.to_string(), v)).collect(),
+        _ => return,
+    };
+    for (token, child) in children {
+        let pointer = format!("{}/{}", at, token);
+        if at != "/bundle/audit_entries" {
+            out.push(pointer.clone());
+        }
+        collect_pointers(child, &pointer, out);
+    }
+}
+
+fn remove_at(value: &mut Value, pointer: &str) -> Option<()> {
+    let (parent, token) = pointer.rsplit_once('/')?;
+    match value.pointer_mut(parent)? {
+        Value::Object(map) => map.remove(&token.replace("~1", "/").replace("~0", "~")).map(drop),
+        Value::Array(items) => {
+            let i: usize = token.parse().ok()?;
+            (i < items.len()).then(|| drop(items.remove(i)))
+        }
+        _ => None,
+    }
+}
+
+/// Recompute `merkle_root` (unless it is the failure), `bundle_hash`,
+/// and the signature with the [`REPRO_SEED`] key.
+fn rederive(signed_bundle: &mut Value, tag: &str, opts: &VerifyOptions) {
+    let (pk, sk) = keypair_from_seed(&REPRO_SEED);
+    if let Some(key) = signed_bundle.pointer_mut("/bundle/agent_passport/public_key") {
+        *key = pk.clone().into();
+    }
+    let entries = signed_bundle.pointer("/bundle/audit_entries").and_then(Value::as_array).cloned().unwrap_or_default();
+    let Some(signature) = signed_bundle.get("signature") else {
+        return;
+    };
+    let mut root = None;
+    if tag != "MERKLE ROOT MISMATCH" && signature.get("merkle_root").is_some() {
+        let volatile = &opts.volatile_audit_fields;
+        let hashes: Vec<String> = entries
+            .iter()
+            .map(|e| tombstoned_hash(e).map_or_else(|| volatile.entry_hash(e), str::to_string))
+            .collect();
+        let order = signature.get("leaf_order").and_then(Value::as_str).and_then(LeafOrder::parse).unwrap_or_default();
+        if hashes.iter().all(|h| hex::decode(h).is_ok()) {
+            root = merkle_root_from_hex_leaves(&order.arrange(&entries, &hashes));
+        }
+    }
+    let canon = canonicalize(&signed_bundle["bundle"]);
+    let Ok(sig_b64) = sign_canonical(&canon, &sk) else {
+        return;
+    };
+    let signature = &mut signed_bundle["signature"];
+    if let Some(root) = root {
+        signature["merkle_root"] = format!("sha256:{}", root).into();
+    }
+    signature["bundle_hash"] = format!("sha256:{}", hash_canonical(&canon)).into();
+    signature["sig_b64"] = sig_b64.into();
+    if let Some(key) = signature.pointer_mut("/signer/public_key_b64") {
+        *key = pk.into();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+
+    #[test]
+    fn test_minimizes_broken_chain() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let mut signed = set.get("citizenship_bundle.signed").unwrap().value.clone();
+        let opts = VerifyOptions::default();
+        assert!(minimize_failing_bundle(&signed, &opts).is_none());
+
+        signed["bundle"]["audit_entries"][0]["intent_hash"] = "0".repeat(64).into();
+        rederive(&mut signed, "", &opts);
+        let repro = minimize_failing_bundle(&signed, &opts).unwrap();
+        assert!(repro.failure.starts_with("intent_hash"), "{}", repro.failure);
+        assert!(repro.resigned && repro.fields_removed > 0);
+        assert!(first_failure(&repro.signed_bundle, &opts).unwrap().starts_with("intent_hash"));
+        assert_eq!(repro.signed_bundle["bundle"]["audit_entries"].as_array().unwrap().len(), 1);
+        assert!(canonicalize(&repro.signed_bundle).len() < canonicalize(&signed).len());
+
+        let again = minimize_failing_bundle(&signed, &opts).unwrap();
+        assert_eq!(again.signed_bundle, repro.signed_bundle);
+    }
+}
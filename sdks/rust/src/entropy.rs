@@ -0,0 +1,136 @@
+//! Entropy health checks for key generation (NIST SP 800-90B §4.4).
+//!
+//! Before generating production keys from a caller-supplied random source,
+//! [`health_check`] draws a sample and runs the two approved continuous
+//! health tests:
+//!
+//! - **Repetition Count Test** — fails on a run of [`RCT_CUTOFF`] identical
+//!   bytes (a stuck source).
+//! - **Adaptive Proportion Test** — fails when one byte value occurs
+//!   [`APT_CUTOFF`] or more times in a [`APT_WINDOW`]-byte window (a
+//!   heavily biased source).
+//!
+//! Cutoffs assume an assessed min-entropy of 4 bits per byte and a false
+//! positive rate of α = 2^-20; a healthy CSPRNG (8 bits per byte) passes
+//! with overwhelming margin.
+
+use rand::RngCore;
+
+/// Repetition Count Test cutoff: `1 + ceil(20 / H)` with H = 4.
+pub const RCT_CUTOFF: usize = 6;
+/// Adaptive Proportion Test window for non-binary sources.
+pub const APT_WINDOW: usize = 512;
+/// Adaptive Proportion Test cutoff for H = 4, W = 512, α = 2^-20.
+pub const APT_CUTOFF: usize = 62;
+/// Bytes drawn by [`health_check`].
+pub const HEALTH_SAMPLE_BYTES: usize = 2 * APT_WINDOW;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EntropyError {
+    #[error("Repetition count test failed: {run} identical bytes (cutoff {cutoff})")]
+    RepetitionCount { run: usize, cutoff: usize },
+    #[error("Adaptive proportion test failed: value seen {count} times in {window} bytes (cutoff {cutoff})")]
+    AdaptiveProportion { count: usize, window: usize, cutoff: usize },
+    #[error("Random source error: {0}")]
+    Source(String),
+}
+
+/// Fail if any byte value repeats `cutoff` or more times consecutively.
+pub fn repetition_count_test(samples: &[u8], cutoff: usize) -> Result<(), EntropyError> {
+    let mut run = 1;
+    for pair in samples.windows(2) {
+        if pair[0] == pair[1] {
+            run += 1;
+            if run >= cutoff {
+                return Err(EntropyError::RepetitionCount { run, cutoff });
+            }
+        } else {
+            run = 1;
+        }
+    }
+    Ok(())
+}
+
+/// Fail if, in any window, the window's first value occurs `cutoff` or
+/// more times.
+pub fn adaptive_proportion_test(samples: &[u8], window: usize, cutoff: usize) -> Result<(), EntropyError> {
+    for chunk in samples.chunks(window) {
+        let first = chunk[0];
+        let count = chunk.iter().filter(|&&b| b == first).count();
+        if count >= cutoff {
+            return Err(EntropyError::AdaptiveProportion { count, window, cutoff });
+        }
+    }
+    Ok(())
+}
+
+/// Draw [`HEALTH_SAMPLE_BYTES`] from `rng` and run both health tests.
+pub fn health_check<R: RngCore + ?Sized>(rng: &mut R) -> Result<(), EntropyError> {
+    let mut sample = [0u8; HEALTH_SAMPLE_BYTES];
+    rng.try_fill_bytes(&mut sample).map_err(|e| EntropyError::Source(e.to_string()))?;
+    repetition_count_test(&sample, RCT_CUTOFF)?;
+    adaptive_proportion_test(&sample, APT_WINDOW, APT_CUTOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    struct StuckRng;
+
+    impl RngCore for StuckRng {
+        fn next_u32(&mut self) -> u32 {
+            0
+        }
+        fn next_u64(&mut self) -> u64 {
+            0
+        }
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            dest.fill(0x42);
+        }
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl rand::CryptoRng for StuckRng {}
+
+    #[test]
+    fn test_healthy_source_passes() {
+        assert_eq!(health_check(&mut StdRng::seed_from_u64(7)), Ok(()));
+        assert_eq!(health_check(&mut rand::thread_rng()), Ok(()));
+    }
+
+    #[test]
+    fn test_stuck_source_fails_rct() {
+        assert_eq!(
+            health_check(&mut StuckRng),
+            Err(EntropyError::RepetitionCount { run: RCT_CUTOFF, cutoff: RCT_CUTOFF })
+        );
+    }
+
+    #[test]
+    fn test_biased_source_fails_apt() {
+        // Alternating bytes defeat the RCT but not the APT.
+        let samples: Vec<u8> = (0..APT_WINDOW).map(|i| if i % 2 == 0 { 7 } else { i as u8 }).collect();
+        assert!(repetition_count_test(&samples, RCT_CUTOFF).is_ok());
+        assert!(matches!(
+            adaptive_proportion_test(&samples, APT_WINDOW, APT_CUTOFF),
+            Err(EntropyError::AdaptiveProportion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_keygen_from_supplied_rng() {
+        use crate::crypto::{generate_keypair_checked, generate_keypair_with};
+        let a = generate_keypair_with(&mut StdRng::seed_from_u64(42));
+        let b = generate_keypair_with(&mut StdRng::seed_from_u64(42));
+        assert_eq!(a, b);
+        assert!(generate_keypair_checked(&mut StdRng::seed_from_u64(42)).is_ok());
+        assert!(generate_keypair_checked(&mut StuckRng).is_err());
+    }
+}
@@ -0,0 +1,242 @@
+//! Flat export of audit chains for analytics.
+//!
+//! Each signed bundle is verified, then every audit entry becomes one row
+//! carrying the bundle's verdict, so a warehouse query can filter on
+//! `bundle_verified` without re-running the SDK. [`to_csv`] writes RFC
+//! 4180 CSV with a header row of [`COLUMNS`]; with the `arrow` feature,
+//! `to_parquet` writes the same columns to a Parquet file.
+//!
+//! ```ignore
+//! let bundles: Vec<Value> = load_day_of_bundles()?;
+//! let file = std::fs::File::create("audit.csv")?;
+//! let rows = export::to_csv(&bundles, &VerifyOptions::default(), file)?;
+//! ```
+
+use std::io::{self, Write};
+
+use serde_json::Value;
+
+use crate::verify::{verify_signed_bundle_with_options, VerifyOptions};
+
+/// Column names, in output order.
+pub const COLUMNS: &[&str] = &[
+    "bundle_index",
+    "bundle_verified",
+    "verification_error",
+    "entry_index",
+    "audit_id",
+    "seq",
+    "timestamp",
+    "agent_id",
+    "human_id",
+    "intent_id",
+    "intent_hash",
+    "prev_hash",
+    "policy_decision",
+    "outcome",
+    "tool",
+    "result_ref",
+    "evidence_deleted",
+    "trace_id",
+    "span_id",
+];
+
+/// One audit entry with its bundle's verdict. String fields missing from
+/// the entry (or not strings) are `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditRow {
+    pub bundle_index: u64,
+    pub bundle_verified: bool,
+    /// First error of the bundle's verification, if it failed.
+    pub verification_error: Option<String>,
+    pub entry_index: u64,
+    pub audit_id: Option<String>,
+    pub seq: Option<u64>,
+    pub timestamp: Option<String>,
+    pub agent_id: Option<String>,
+    pub human_id: Option<String>,
+    pub intent_id: Option<String>,
+    pub intent_hash: Option<String>,
+    pub prev_hash: Option<String>,
+    pub policy_decision: Option<String>,
+    pub outcome: Option<String>,
+    pub tool: Option<String>,
+    pub result_ref: Option<String>,
+    /// Evidence was removed under a retention policy ([`crate::retention`]).
+    pub evidence_deleted: bool,
+    pub trace_id: Option<String>,
+    pub span_id: Option<String>,
+}
+
+/// Verify each of `signed_bundles` under `opts` and flatten their audit
+/// entries, in bundle then chain order. A bundle without an
+/// `audit_entries` array contributes no rows.
+pub fn rows(signed_bundles: &[Value], opts: &VerifyOptions) -> Vec<AuditRow> {
+    let mut rows = Vec::new();
+    for (bundle_index, signed) in signed_bundles.iter().enumerate() {
+        let result = verify_signed_bundle_with_options(signed, None, opts);
+        let error = result.errors.as_ref().and_then(|e| e.first()).cloned();
+        let entries = signed.pointer("/bundle/audit_entries").and_then(Value::as_array);
+        for (entry_index, entry) in entries.into_iter().flatten().enumerate() {
+            let text = |pointer: &str| entry.pointer(pointer).and_then(Value::as_str).map(str::to_string);
+            rows.push(AuditRow {
+                bundle_index: bundle_index as u64,
+                bundle_verified: result.verified,
+                verification_error: if result.verified { None } else { error.clone() },
+                entry_index: entry_index as u64,
+                audit_id: text("/audit_id"),
+                seq: entry.get("seq").and_then(Value::as_u64),
+                timestamp: text("/timestamp"),
+                agent_id: text("/agent_id"),
+                human_id: text("/human_id"),
+                intent_id: text("/intent_id"),
+                intent_hash: text("/intent_hash"),
+                prev_hash: text("/prev_hash"),
+                policy_decision: text("/policy_decision"),
+                outcome: text("/outcome"),
+                tool: text("/evidence/tool"),
+                result_ref: text("/evidence/result_ref"),
+                evidence_deleted: entry.pointer("/evidence/tombstone").is_some_and(|t| !t.is_null()),
+                trace_id: text("/trace_id"),
+                span_id: text("/span_id"),
+            });
+        }
+    }
+    rows
+}
+
+impl AuditRow {
+    /// Field values in [`COLUMNS`] order; `None` is an empty cell.
+    pub fn values(&self) -> Vec<Option<String>> {
+        vec![
+            Some(self.bundle_index.to_string()),
+            Some(self.bundle_verified.to_string()),
+            self.verification_error.clone(),
+            Some(self.entry_index.to_string()),
+            self.audit_id.clone(),
+            self.seq.map(|s| s.to_string()),
+            self.timestamp.clone(),
+            self.agent_id.clone(),
+            self.human_id.clone(),
+            self.intent_id.clone(),
+            self.intent_hash.clone(),
+            self.prev_hash.clone(),
+            self.policy_decision.clone(),
+            self.outcome.clone(),
+            self.tool.clone(),
+            self.result_ref.clone(),
+            Some(self.evidence_deleted.to_string()),
+            self.trace_id.clone(),
+            self.span_id.clone(),
+        ]
+    }
+}
+
+/// Write [`rows`] of `signed_bundles` as CSV, header first. Returns the
+/// number of data rows.
+pub fn to_csv<W: Write>(signed_bundles: &[Value], opts: &VerifyOptions, writer: W) -> io::Result<usize> {
+    let mut out = io::BufWriter::new(writer);
+    write_record(&mut out, COLUMNS.iter().map(|c| Some(*c)))?;
+    let rows = rows(signed_bundles, opts);
+    for row in &rows {
+        let values = row.values();
+        write_record(&mut out, values.iter().map(Option::as_deref))?;
+    }
+    out.flush()?;
+    Ok(rows.len())
+}
+
+fn write_record<'a, W: Write>(out: &mut W, fields: impl Iterator<Item = Option<&'a str>>) -> io::Result<()> {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            out.write_all(b",")?;
+        }
+        let field = field.unwrap_or_default();
+        if field.contains([',', '"', '\r', '\n']) {
+            write!(out, "\"{}\"", field.replace('"', "\"\""))?;
+        } else {
+            out.write_all(field.as_bytes())?;
+        }
+    }
+    out.write_all(b"\r\n")
+}
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ExportError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("parquet: {0}")]
+    Parquet(String),
+}
+
+/// Write [`rows`] of `signed_bundles` to a Parquet file at `path` with
+/// [`COLUMNS`] as the schema: `bundle_index`, `entry_index`, and `seq`
+/// are UInt64, the `bundle_verified` and `evidence_deleted` flags
+/// Boolean, the rest nullable Utf8. Returns the number of rows.
+#[cfg(feature = "arrow")]
+pub fn to_parquet(
+    signed_bundles: &[Value],
+    opts: &VerifyOptions,
+    path: impl AsRef<std::path::Path>,
+) -> Result<usize, ExportError> {
+    use std::sync::Arc;
+
+    use arrow_array::{ArrayRef, BooleanArray, RecordBatch, StringArray, UInt64Array};
+    use arrow_schema::{Field, Schema};
+    use parquet::arrow::ArrowWriter;
+
+    let rows = rows(signed_bundles, opts);
+    let values: Vec<Vec<Option<String>>> = rows.iter().map(AuditRow::values).collect();
+    let parquet_err = |e: &dyn std::fmt::Display| ExportError::Parquet(e.to_string());
+
+    let mut fields = Vec::with_capacity(COLUMNS.len());
+    let mut columns: Vec<ArrayRef> = Vec::with_capacity(COLUMNS.len());
+    for (i, name) in COLUMNS.iter().enumerate() {
+        let column: ArrayRef = match *name {
+            "bundle_index" => Arc::new(UInt64Array::from_iter_values(rows.iter().map(|r| r.bundle_index))),
+            "entry_index" => Arc::new(UInt64Array::from_iter_values(rows.iter().map(|r| r.entry_index))),
+            "seq" => Arc::new(rows.iter().map(|r| r.seq).collect::<UInt64Array>()),
+            "bundle_verified" => Arc::new(rows.iter().map(|r| Some(r.bundle_verified)).collect::<BooleanArray>()),
+            "evidence_deleted" => Arc::new(rows.iter().map(|r| Some(r.evidence_deleted)).collect::<BooleanArray>()),
+            _ => Arc::new(values.iter().map(|v| v[i].as_deref()).collect::<StringArray>()),
+        };
+        let nullable = !matches!(*name, "bundle_index" | "entry_index" | "bundle_verified" | "evidence_deleted");
+        fields.push(Field::new(*name, column.data_type().clone(), nullable));
+        columns.push(column);
+    }
+    let schema = Arc::new(Schema::new(fields));
+    let batch = RecordBatch::try_new(schema.clone(), columns).map_err(|e| parquet_err(&e))?;
+
+    let file = std::fs::File::create(path)?;
+    let mut writer = ArrowWriter::try_new(file, schema, None).map_err(|e| parquet_err(&e))?;
+    writer.write(&batch).map_err(|e| parquet_err(&e))?;
+    writer.close().map_err(|e| parquet_err(&e))?;
+    Ok(rows.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+
+    #[test]
+    fn test_csv_rows_carry_bundle_verdict() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let signed = set.get("citizenship_bundle.signed").unwrap().value.clone();
+        let mut tampered = signed.clone();
+        tampered["bundle"]["audit_entries"][0]["outcome"] = "rewritten, \"quietly\"".into();
+
+        let mut out = Vec::new();
+        let n = to_csv(&[signed.clone(), tampered], &VerifyOptions::default(), &mut out).unwrap();
+        let entries = signed["bundle"]["audit_entries"].as_array().unwrap().len();
+        assert_eq!(n, 2 * entries);
+
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = csv.split("\r\n").filter(|l| !l.is_empty()).collect();
+        assert_eq!(lines[0], COLUMNS.join(","));
+        assert!(lines[1].starts_with("0,true,,0,"));
+        assert!(lines[1 + entries].starts_with("1,false,"));
+        assert!(lines[1 + entries].contains(",\"rewritten, \"\"quietly\"\"\","));
+    }
+}
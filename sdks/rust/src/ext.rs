@@ -0,0 +1,47 @@
+//! Extension methods on bundle types.
+//!
+//! The traits are sealed: they cannot be implemented outside this crate,
+//! so new methods can be added without breaking downstream builds.
+
+use serde_json::Value;
+
+use crate::types::{SignedBundle, VerificationResult};
+use crate::verify::{verify_signed_bundle, verify_signed_bundle_typed};
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for serde_json::Value {}
+    impl Sealed for crate::types::SignedBundle {}
+}
+
+/// Verification helpers on a parsed [`SignedBundle`].
+pub trait SignedBundleExt: sealed::Sealed {
+    /// Verify this bundle; see [`verify_signed_bundle_typed`].
+    fn verify(&self, public_key_b64: Option<&str>) -> VerificationResult;
+}
+
+impl SignedBundleExt for SignedBundle {
+    fn verify(&self, public_key_b64: Option<&str>) -> VerificationResult {
+        verify_signed_bundle_typed(self, public_key_b64)
+    }
+}
+
+/// DCP helpers on raw JSON.
+pub trait DcpValueExt: sealed::Sealed {
+    /// Protocol version of this artifact; see [`crate::detect_dcp_version`].
+    fn dcp_version(&self) -> Option<&str>;
+
+    /// Verify this value as a signed bundle; see [`verify_signed_bundle`].
+    fn verify_signed_bundle(&self, public_key_b64: Option<&str>) -> VerificationResult;
+}
+
+impl DcpValueExt for Value {
+    fn dcp_version(&self) -> Option<&str> {
+        crate::detect_dcp_version(self)
+    }
+
+    fn verify_signed_bundle(&self, public_key_b64: Option<&str>) -> VerificationResult {
+        verify_signed_bundle(self, public_key_b64)
+    }
+}
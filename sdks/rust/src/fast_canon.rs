@@ -0,0 +1,439 @@
+//! Single-pass canonicalization of typed values (feature = "fast-canon").
+//!
+//! [`crate::crypto::canonicalize`] works on a [`serde_json::Value`], so
+//! hashing a typed bundle first builds the whole value tree with
+//! `serde_json::to_value` and then walks it again. [`to_canonical_string`]
+//! is a [`serde::Serializer`] that writes `dcp-c14n/1` output directly
+//! from the typed value, sorting each object's members as it closes
+//! instead of materializing the tree.
+//!
+//! Output is byte-identical to
+//! `canonicalize(&serde_json::to_value(value)?)`; only the spec profile
+//! is supported ([`crate::crypto::CanonicalizeOptions::default`]).
+//!
+//! ```text
+//! cargo bench --bench canonicalize --features fast-canon
+//! ```
+
+use std::borrow::Cow;
+
+use serde::ser::{self, Error as _, Serialize};
+use serde_json::{Error, Value};
+
+use crate::crypto::hash_canonical;
+
+/// Canonical JSON of `value`, as [`crate::crypto::canonicalize`] would
+/// produce from `serde_json::to_value(value)`.
+pub fn to_canonical_string<T: Serialize + ?Sized>(value: &T) -> Result<String, Error> {
+    let mut out = Vec::new();
+    to_canonical_writer(&mut out, value)?;
+    Ok(String::from_utf8(out).expect("canonical JSON is UTF-8"))
+}
+
+/// Append the canonical JSON of `value` to `out`.
+pub fn to_canonical_writer<T: Serialize + ?Sized>(out: &mut Vec<u8>, value: &T) -> Result<(), Error> {
+    value.serialize(Canon { out })
+}
+
+/// SHA-256 hex of the canonical JSON of `value`; the typed counterpart of
+/// [`crate::crypto::hash_object`].
+pub fn hash_serialize<T: Serialize + ?Sized>(value: &T) -> Result<String, Error> {
+    Ok(hash_canonical(&to_canonical_string(value)?))
+}
+
+fn write_json<T: Serialize + ?Sized>(out: &mut Vec<u8>, v: &T) -> Result<(), Error> {
+    serde_json::to_writer(out, v)
+}
+
+struct Canon<'a> {
+    out: &'a mut Vec<u8>,
+}
+
+impl<'a> ser::Serializer for Canon<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Seq<'a>;
+    type SerializeTuple = Seq<'a>;
+    type SerializeTupleStruct = Seq<'a>;
+    type SerializeTupleVariant = Seq<'a>;
+    type SerializeMap = Object<'a>;
+    type SerializeStruct = Object<'a>;
+    type SerializeStructVariant = Object<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        write_json(self.out, &v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        write_json(self.out, &v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        write_json(self.out, &v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        write_json(self.out, &v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        write_json(self.out, &v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        write_json(self.out, &v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        write_json(self.out, &v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        write_json(self.out, &v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        write_json(self.out, &v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        // `to_value` widens to f64 before formatting.
+        write_json(self.out, &Value::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        write_json(self.out, &Value::from(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        write_json(self.out, &v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        write_json(self.out, v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        write_json(self.out, v)
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.out.extend_from_slice(b"null");
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<(), Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.out.push(b'{');
+        write_json(self.out, variant)?;
+        self.out.push(b':');
+        value.serialize(Canon { out: &mut *self.out })?;
+        self.out.push(b'}');
+        Ok(())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Seq<'a>, Error> {
+        self.out.push(b'[');
+        Ok(Seq { out: self.out, first: true, close: "]" })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Seq<'a>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Seq<'a>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Seq<'a>, Error> {
+        self.out.push(b'{');
+        write_json(self.out, variant)?;
+        self.out.extend_from_slice(b":[");
+        Ok(Seq { out: self.out, first: true, close: "]}" })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Object<'a>, Error> {
+        Ok(Object::new(self.out, None))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Object<'a>, Error> {
+        Ok(Object::new(self.out, None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Object<'a>, Error> {
+        Ok(Object::new(self.out, Some(variant)))
+    }
+}
+
+struct Seq<'a> {
+    out: &'a mut Vec<u8>,
+    first: bool,
+    close: &'static str,
+}
+
+impl Seq<'_> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        if !std::mem::take(&mut self.first) {
+            self.out.push(b',');
+        }
+        value.serialize(Canon { out: &mut *self.out })
+    }
+
+    fn finish(self) -> Result<(), Error> {
+        self.out.extend_from_slice(self.close.as_bytes());
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for Seq<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for Seq<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for Seq<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for Seq<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+/// An object being written: member values are serialized into `scratch`
+/// in arrival order and copied out in key order on `end`.
+struct Object<'a> {
+    out: &'a mut Vec<u8>,
+    /// Wraps the object as `{"variant":{...}}`.
+    variant: Option<&'static str>,
+    members: Vec<(Cow<'static, str>, std::ops::Range<usize>)>,
+    scratch: Vec<u8>,
+    key: Option<String>,
+}
+
+impl<'a> Object<'a> {
+    fn new(out: &'a mut Vec<u8>, variant: Option<&'static str>) -> Self {
+        Self { out, variant, members: Vec::new(), scratch: Vec::new(), key: None }
+    }
+
+    fn member<T: Serialize + ?Sized>(&mut self, key: Cow<'static, str>, value: &T) -> Result<(), Error> {
+        let start = self.scratch.len();
+        value.serialize(Canon { out: &mut self.scratch })?;
+        self.members.push((key, start..self.scratch.len()));
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<(), Error> {
+        // Stable sort, then keep the last of equal keys, as a `Value` map
+        // would after a repeated insert.
+        self.members.sort_by(|a, b| a.0.cmp(&b.0));
+        if let Some(variant) = self.variant {
+            self.out.push(b'{');
+            write_json(self.out, variant)?;
+            self.out.push(b':');
+        }
+        self.out.push(b'{');
+        let mut first = true;
+        for (i, (key, range)) in self.members.iter().enumerate() {
+            if self.members.get(i + 1).is_some_and(|next| next.0 == *key) {
+                continue;
+            }
+            if !std::mem::take(&mut first) {
+                self.out.push(b',');
+            }
+            write_json(self.out, &**key)?;
+            self.out.push(b':');
+            self.out.extend_from_slice(&self.scratch[range.clone()]);
+        }
+        self.out.push(b'}');
+        if self.variant.is_some() {
+            self.out.push(b'}');
+        }
+        Ok(())
+    }
+}
+
+/// Map keys as `to_value` accepts them: strings, and numbers and bools
+/// written as their JSON text.
+fn key_string<T: Serialize + ?Sized>(key: &T) -> Result<String, Error> {
+    match serde_json::to_value(key)? {
+        Value::String(s) => Ok(s),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Bool(b) => Ok(b.to_string()),
+        _ => Err(Error::custom("key must be a string")),
+    }
+}
+
+impl ser::SerializeMap for Object<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(key_string(key)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self.key.take().ok_or_else(|| Error::custom("serialize_value called before serialize_key"))?;
+        self.member(Cow::Owned(key), value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for Object<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        self.member(Cow::Borrowed(key), value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for Object<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        self.member(Cow::Borrowed(key), value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde::Serialize;
+
+    use super::*;
+    use crate::crypto::canonicalize;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+    use crate::types::CitizenshipBundle;
+
+    #[derive(Serialize)]
+    enum Shape {
+        Unit,
+        Newtype(u8),
+        Tuple(i32, f64),
+        Struct { z: bool, a: Option<String> },
+    }
+
+    #[derive(Serialize)]
+    struct Mixed {
+        zeta: Vec<Shape>,
+        alpha: HashMap<u32, &'static str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        skipped: Option<u8>,
+        ratio: f32,
+        nothing: (),
+        text: &'static str,
+    }
+
+    fn slow<T: Serialize>(value: &T) -> String {
+        canonicalize(&serde_json::to_value(value).unwrap())
+    }
+
+    #[test]
+    fn test_matches_value_canonicalization() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let bundle: CitizenshipBundle =
+            serde_json::from_value(set.get("citizenship_bundle").unwrap().value.clone()).unwrap();
+        assert_eq!(to_canonical_string(&bundle).unwrap(), slow(&bundle));
+        let value = serde_json::to_value(&bundle).unwrap();
+        assert_eq!(hash_serialize(&bundle).unwrap(), crate::crypto::hash_object(&value));
+
+        let mixed = Mixed {
+            zeta: vec![Shape::Unit, Shape::Newtype(7), Shape::Tuple(-1, 0.5), Shape::Struct { z: true, a: None }],
+            alpha: HashMap::from([(10, "ten"), (2, "two\n\"quoted\"")]),
+            skipped: None,
+            ratio: 0.1,
+            nothing: (),
+            text: "ünïcödé",
+        };
+        assert_eq!(to_canonical_string(&mixed).unwrap(), slow(&mixed));
+    }
+}
@@ -0,0 +1,238 @@
+//! Deterministic golden fixtures.
+//!
+//! [`generate_fixtures`] rebuilds the canonical v1 artifacts and a signed
+//! Citizenship Bundle from a seed: keys come from the seed, every
+//! timestamp is fixed, and Ed25519 signatures are deterministic, so the
+//! same seed always yields byte-identical output. After a protocol change,
+//! regenerate with
+//!
+//! ```text
+//! cargo run --bin dcp -- gen-fixtures <out_dir>
+//! ```
+//!
+//! and review the JSON and `expected_hashes.json` diffs. The files are
+//! checked in under `tests/conformance/golden/`, and
+//! `tests/conformance.rs` fails when they no longer match.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::bundle::{sign_bundle, AuditEntryFields, BundleBuilder, SignOptions};
+use crate::crypto::{hash_object, keypair_from_seed};
+use crate::issue::sign_record;
+use crate::types::*;
+
+/// Seed for the checked-in golden fixtures.
+pub const GOLDEN_SEED: [u8; 32] = *b"dcp-ai golden fixtures seed v1!!";
+
+/// Fixed timestamp stamped on every generated artifact.
+pub const FIXTURE_TIME: &str = "2026-01-01T00:00:00Z";
+
+/// One generated artifact.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Fixture {
+    /// File stem, e.g. `citizenship_bundle.signed`.
+    pub name: String,
+    pub value: Value,
+    /// SHA-256 hex of the canonical JSON.
+    pub canonical_sha256: String,
+}
+
+/// Everything generated from one seed.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct FixtureSet {
+    pub human_public_key_b64: String,
+    pub agent_public_key_b64: String,
+    pub fixtures: Vec<Fixture>,
+}
+
+impl FixtureSet {
+    pub fn get(&self, name: &str) -> Option<&Fixture> {
+        self.fixtures.iter().find(|f| f.name == name)
+    }
+
+    /// `expected_hashes.json`: keys, per-fixture canonical hashes, and the
+    /// signed bundle's `bundle_hash` / `merkle_root` / `sig_b64`.
+    pub fn expected_hashes(&self) -> Value {
+        let hashes: BTreeMap<&str, &str> = self
+            .fixtures
+            .iter()
+            .map(|f| (f.name.as_str(), f.canonical_sha256.as_str()))
+            .collect();
+        let sig = self.get("citizenship_bundle.signed").map(|f| &f.value["signature"]);
+        json!({
+            "human_public_key_b64": self.human_public_key_b64,
+            "agent_public_key_b64": self.agent_public_key_b64,
+            "canonical_sha256": hashes,
+            "bundle_hash": sig.map(|s| s["bundle_hash"].clone()),
+            "merkle_root": sig.map(|s| s["merkle_root"].clone()),
+            "sig_b64": sig.map(|s| s["sig_b64"].clone()),
+        })
+    }
+}
+
+fn derive_seed(seed: &[u8; 32], label: &str) -> [u8; 32] {
+    let mut h = Sha256::new();
+    h.update(label.as_bytes());
+    h.update(seed);
+    h.finalize().into()
+}
+
+fn fixture<T: Serialize>(name: &str, artifact: &T) -> Result<Fixture, String> {
+    let value = serde_json::to_value(artifact).map_err(|e| e.to_string())?;
+    Ok(Fixture { name: name.to_string(), canonical_sha256: hash_object(&value), value })
+}
+
+/// Generate the golden fixture set for `seed`.
+///
+/// The human key is derived as `SHA-256("dcp-fixtures/human" || seed)` and
+/// the agent key as `SHA-256("dcp-fixtures/agent" || seed)`.
+pub fn generate_fixtures(seed: &[u8; 32]) -> Result<FixtureSet, String> {
+    let (human_pk, human_sk) = keypair_from_seed(&derive_seed(seed, "dcp-fixtures/human"));
+    let (agent_pk, _) = keypair_from_seed(&derive_seed(seed, "dcp-fixtures/agent"));
+
+    let mut rpr = ResponsiblePrincipalRecord {
+        dcp_version: "1.0".into(),
+        human_id: "did:human:fixture".into(),
+        legal_name: "Fixture Principal".into(),
+        entity_type: "natural_person".into(),
+        jurisdiction: "US".into(),
+        liability_mode: "owner_responsible".into(),
+        override_rights: true,
+        issued_at: FIXTURE_TIME.into(),
+        expires_at: None,
+        contact: None,
+        prev_record_hash: None,
+        issuer_binding_reference: None,
+        agreements: Vec::new(),
+        signature_alg: None,
+        signature: SignatureB64::default(),
+    };
+    rpr.signature = sign_record(&rpr, &human_sk)?;
+
+    let mut passport = AgentPassport {
+        dcp_version: "1.0".into(),
+        agent_id: "did:agent:fixture".into(),
+        public_key: agent_pk.parse::<PublicKeyB64>().map_err(|e| format!("agent key: {}", e))?,
+        principal_binding_reference: rpr.human_id.clone(),
+        capabilities: Some(vec!["email".into()]),
+        risk_tier: Some("low".into()),
+        created_at: FIXTURE_TIME.into(),
+        status: "active".into(),
+        attestation: None,
+        upgrade: None,
+        signature: SignatureB64::default(),
+    };
+    passport.signature = sign_record(&passport, &human_sk)?;
+
+    let intent = Intent {
+        dcp_version: "1.0".into(),
+        intent_id: "intent-fixture-001".into(),
+        agent_id: passport.agent_id.clone(),
+        human_id: rpr.human_id.clone(),
+        timestamp: FIXTURE_TIME.into(),
+        action_type: "send_email".into(),
+        target: IntentTarget { channel: "email".into(), to: Some("bob@example.com".into()), domain: None, url: None, salt_id: None },
+        data_classes: vec!["contact_info".into()],
+        estimated_impact: "low".into(),
+        requires_consent: None,
+        not_before: None,
+        valid_until: None,
+    };
+    let decision = PolicyDecision {
+        dcp_version: "1.0".into(),
+        intent_id: intent.intent_id.clone(),
+        decision: "approve".into(),
+        risk_score: 0.0,
+        reasons: vec!["low_risk".into()],
+        risk: None,
+        policy_snapshot_hash: None,
+    };
+
+    let mut builder = BundleBuilder::new()
+        .responsible_principal_record(rpr.clone())
+        .agent_passport(passport.clone())
+        .intent(intent.clone())
+        .policy_decision(decision.clone());
+    for (i, outcome) in ["policy_approved", "email_sent"].iter().enumerate() {
+        builder = builder.create_audit_entry(AuditEntryFields {
+            audit_id: format!("audit-fixture-{:03}", i + 1),
+            timestamp: FIXTURE_TIME.into(),
+            agent_id: passport.agent_id.clone(),
+            human_id: rpr.human_id.clone(),
+            policy_decision: "approved".into(),
+            outcome: outcome.to_string(),
+            evidence: AuditEvidence { tool: Some("smtp".into()), result_ref: None, tombstone: None },
+        })?;
+    }
+    let bundle = builder.build()?;
+
+    let mut opts = SignOptions::new(&human_sk);
+    opts.created_at = Some(FIXTURE_TIME);
+    let signed = sign_bundle(bundle.clone(), &opts)?;
+
+    Ok(FixtureSet {
+        human_public_key_b64: human_pk,
+        agent_public_key_b64: agent_pk,
+        fixtures: vec![
+            fixture("responsible_principal_record", &rpr)?,
+            fixture("agent_passport", &passport)?,
+            fixture("intent", &intent)?,
+            fixture("policy_decision", &decision)?,
+            fixture("audit_entry", &bundle.audit_entries[0])?,
+            fixture("citizenship_bundle", &bundle)?,
+            fixture("citizenship_bundle.signed", &signed)?,
+        ],
+    })
+}
+
+/// Write `<name>.json` for every fixture plus `expected_hashes.json` into
+/// `dir` (created if missing). JSON is pretty-printed with a trailing
+/// newline so diffs stay reviewable. Returns the written paths.
+pub fn write_fixtures(set: &FixtureSet, dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir)?;
+    let expected = set.expected_hashes();
+    let files = set
+        .fixtures
+        .iter()
+        .map(|f| (format!("{}.json", f.name), &f.value))
+        .chain(std::iter::once(("expected_hashes.json".to_string(), &expected)));
+    let mut written = Vec::new();
+    for (file, value) in files {
+        let path = dir.join(file);
+        let mut text = serde_json::to_string_pretty(value)?;
+        text.push('\n');
+        std::fs::write(&path, text)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::verify_signed_bundle;
+
+    #[test]
+    fn test_same_seed_same_fixtures() {
+        let a = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let b = generate_fixtures(&GOLDEN_SEED).unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, generate_fixtures(&[7u8; 32]).unwrap());
+    }
+
+    #[test]
+    fn test_signed_fixture_verifies() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let signed = &set.get("citizenship_bundle.signed").unwrap().value;
+        let result = verify_signed_bundle(signed, Some(&set.human_public_key_b64));
+        assert!(result.verified, "{:?}", result.errors);
+        assert_eq!(signed["signature"]["created_at"], FIXTURE_TIME);
+    }
+}
@@ -0,0 +1,199 @@
+//! Relationship graph over signed bundles.
+//!
+//! [`build`] turns a fleet of bundles into one [`DcpGraph`]: humans,
+//! agents, intents, and policy decisions as nodes; `bound_to`,
+//! `declared`, `decided`, and `executed` as edges. Nodes are deduplicated
+//! by kind and id, so an agent appearing in many bundles is one node;
+//! identical edges (the same audit entry seen twice) are kept once.
+//! Verify bundles before graphing them; `build` trusts its input.
+//!
+//! With the `petgraph` feature, [`DcpGraph::to_petgraph`] hands the graph
+//! to petgraph's algorithms.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::SignedBundle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum NodeKind {
+    Human,
+    Agent,
+    Intent,
+    Decision,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum EdgeKind {
+    /// Agent → human, from the passport's `principal_binding_reference`.
+    BoundTo,
+    /// Agent → intent it declared.
+    Declared,
+    /// Decision → intent it ruled on.
+    Decided,
+    /// Agent → intent, one per audit entry.
+    Executed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphNode {
+    pub kind: NodeKind,
+    pub id: String,
+    /// Short human-readable label (legal name, action type, decision).
+    pub label: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub kind: EdgeKind,
+    /// Index into [`DcpGraph::nodes`].
+    pub from: usize,
+    /// Index into [`DcpGraph::nodes`].
+    pub to: usize,
+    /// `audit_id` for `executed` edges.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audit_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+}
+
+/// Nodes and edges extracted from a set of bundles.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DcpGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    #[serde(skip)]
+    index: HashMap<(NodeKind, String), usize>,
+    #[serde(skip)]
+    seen_edges: HashSet<GraphEdge>,
+}
+
+impl DcpGraph {
+    fn node(&mut self, kind: NodeKind, id: &str, label: &str) -> usize {
+        if let Some(&i) = self.index.get(&(kind, id.to_string())) {
+            return i;
+        }
+        let i = self.nodes.len();
+        self.nodes.push(GraphNode { kind, id: id.to_string(), label: label.to_string() });
+        self.index.insert((kind, id.to_string()), i);
+        i
+    }
+
+    fn edge(&mut self, kind: EdgeKind, from: usize, to: usize, audit_id: Option<&str>, timestamp: Option<&str>) {
+        let edge = GraphEdge {
+            kind,
+            from,
+            to,
+            audit_id: audit_id.map(str::to_string),
+            timestamp: timestamp.map(str::to_string),
+        };
+        if self.seen_edges.insert(edge.clone()) {
+            self.edges.push(edge);
+        }
+    }
+
+    /// Index of the node with this kind and id.
+    pub fn find(&self, kind: NodeKind, id: &str) -> Option<usize> {
+        self.index.get(&(kind, id.to_string())).copied()
+    }
+
+    /// Nodes reachable from `node` over one edge of `kind`.
+    pub fn outgoing(&self, node: usize, kind: EdgeKind) -> Vec<&GraphNode> {
+        self.edges
+            .iter()
+            .filter(|e| e.from == node && e.kind == kind)
+            .map(|e| &self.nodes[e.to])
+            .collect()
+    }
+
+    /// Nodes with an edge of `kind` into `node`.
+    pub fn incoming(&self, node: usize, kind: EdgeKind) -> Vec<&GraphNode> {
+        self.edges
+            .iter()
+            .filter(|e| e.to == node && e.kind == kind)
+            .map(|e| &self.nodes[e.from])
+            .collect()
+    }
+
+    /// Agents bound to `human_id`.
+    pub fn agents_of(&self, human_id: &str) -> Vec<&GraphNode> {
+        match self.find(NodeKind::Human, human_id) {
+            Some(h) => self.incoming(h, EdgeKind::BoundTo),
+            None => Vec::new(),
+        }
+    }
+
+    /// Convert to a petgraph directed graph. Node and edge indices match
+    /// positions in [`Self::nodes`] and [`Self::edges`].
+    #[cfg(feature = "petgraph")]
+    pub fn to_petgraph(&self) -> petgraph::graph::DiGraph<GraphNode, GraphEdge> {
+        let mut g = petgraph::graph::DiGraph::with_capacity(self.nodes.len(), self.edges.len());
+        let ids: Vec<_> = self.nodes.iter().map(|n| g.add_node(n.clone())).collect();
+        for e in &self.edges {
+            g.add_edge(ids[e.from], ids[e.to], e.clone());
+        }
+        g
+    }
+}
+
+/// Build the relationship graph of `bundles`.
+pub fn build(bundles: &[SignedBundle]) -> DcpGraph {
+    let mut g = DcpGraph::default();
+    for sb in bundles {
+        let b = &sb.bundle;
+        let rpr = &b.responsible_principal_record;
+        let human = g.node(NodeKind::Human, &rpr.human_id, &rpr.legal_name);
+        let bound = g.node(NodeKind::Human, b.agent_passport.principal_human_id(), "");
+        let agent = g.node(NodeKind::Agent, &b.agent_passport.agent_id, &b.agent_passport.status);
+        let intent = g.node(NodeKind::Intent, &b.intent.intent_id, &b.intent.action_type);
+        let decision_id = format!("{}#decision", b.policy_decision.intent_id);
+        let decision = g.node(NodeKind::Decision, &decision_id, &b.policy_decision.decision);
+
+        g.edge(EdgeKind::BoundTo, agent, bound, None, None);
+        if bound != human {
+            g.edge(EdgeKind::BoundTo, agent, human, None, None);
+        }
+        g.edge(EdgeKind::Declared, agent, intent, None, Some(&b.intent.timestamp));
+        g.edge(EdgeKind::Decided, decision, intent, None, None);
+        for entry in &b.audit_entries {
+            let actor = g.node(NodeKind::Agent, &entry.agent_id, "");
+            let target = g.node(NodeKind::Intent, &entry.intent_id, "");
+            g.edge(EdgeKind::Executed, actor, target, Some(&entry.audit_id), Some(&entry.timestamp));
+        }
+    }
+    g
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+
+    fn signed() -> SignedBundle {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        serde_json::from_value(set.get("citizenship_bundle.signed").unwrap().value.clone()).unwrap()
+    }
+
+    #[test]
+    fn test_nodes_deduplicated_across_bundles() {
+        let sb = signed();
+        let g = build(&[sb.clone(), sb]);
+        assert_eq!(g.nodes.len(), 4);
+        assert_eq!(g.edges.iter().filter(|e| e.kind == EdgeKind::Executed).count(), 2);
+        assert_eq!(g.edges.iter().filter(|e| e.kind == EdgeKind::Declared).count(), 1);
+    }
+
+    #[test]
+    fn test_agents_of_human() {
+        let g = build(&[signed()]);
+        let agents = g.agents_of("did:human:fixture");
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0].id, "did:agent:fixture");
+        assert!(g.agents_of("did:human:nobody").is_empty());
+    }
+}
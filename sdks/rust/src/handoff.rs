@@ -0,0 +1,335 @@
+//! Agent-to-agent intent handoff records.
+//!
+//! A [`Handoff`] transfers responsibility for one intent from agent A to
+//! agent B under the same human binding. A signs the offer, B signs its
+//! acceptance over the same payload. Audit entries for the intent dated at
+//! or after `effective_at` must then carry B's `agent_id`, and B may act
+//! only within the handoff's `scope`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::crypto::{sign_object, verify_object};
+use crate::types::{CitizenshipBundle, VerificationFailure, VerificationResult, VerifyError};
+use crate::v2::advisory::parse_iso_epoch_secs;
+
+/// Intent handoff artifact.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Handoff {
+    pub dcp_version: String,
+    pub handoff_id: String,
+    pub intent_id: String,
+    pub human_id: String,
+    pub from_agent_id: String,
+    pub to_agent_id: String,
+    /// Actions B may take under the transferred intent.
+    pub scope: Vec<String>,
+    pub effective_at: String,
+    /// A's Ed25519 signature over the handoff payload.
+    pub from_signature: String,
+    /// B's Ed25519 acceptance signature over the same payload.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_signature: Option<String>,
+}
+
+pub struct HandoffParams<'a> {
+    pub handoff_id: &'a str,
+    pub intent_id: &'a str,
+    pub human_id: &'a str,
+    pub from_agent_id: &'a str,
+    pub to_agent_id: &'a str,
+    pub scope: Vec<String>,
+    pub effective_at: &'a str,
+}
+
+impl Handoff {
+    /// The signed payload: every field except the two signatures.
+    pub fn payload(&self) -> Result<Value, String> {
+        let mut v = serde_json::to_value(self).map_err(|e| e.to_string())?;
+        if let Some(obj) = v.as_object_mut() {
+            obj.remove("from_signature");
+            obj.remove("to_signature");
+        }
+        Ok(v)
+    }
+}
+
+/// Create a handoff offer signed by the outgoing agent.
+pub fn create_handoff(params: HandoffParams<'_>, from_secret_key_b64: &str) -> Result<Handoff, String> {
+    let mut handoff = Handoff {
+        dcp_version: "1.0".into(),
+        handoff_id: params.handoff_id.into(),
+        intent_id: params.intent_id.into(),
+        human_id: params.human_id.into(),
+        from_agent_id: params.from_agent_id.into(),
+        to_agent_id: params.to_agent_id.into(),
+        scope: params.scope,
+        effective_at: params.effective_at.into(),
+        from_signature: String::new(),
+        to_signature: None,
+    };
+    handoff.from_signature = sign_object(&handoff.payload()?, from_secret_key_b64)?;
+    Ok(handoff)
+}
+
+/// Countersign a handoff as the receiving agent.
+pub fn accept_handoff(handoff: &mut Handoff, to_secret_key_b64: &str) -> Result<(), String> {
+    handoff.to_signature = Some(sign_object(&handoff.payload()?, to_secret_key_b64)?);
+    Ok(())
+}
+
+/// Verify both signatures on a handoff.
+pub fn verify_handoff(handoff: &Handoff, from_public_key_b64: &str, to_public_key_b64: &str) -> VerificationResult {
+    let payload = match handoff.payload() {
+        Ok(payload) => payload,
+        Err(e) => return handoff_result(vec![format!("handoff {}: payload: {}", handoff.handoff_id, e)]),
+    };
+    let mut errors = Vec::new();
+    if !matches!(verify_object(&payload, &handoff.from_signature, from_public_key_b64), Ok(true)) {
+        errors.push(format!("handoff {}: from_signature invalid", handoff.handoff_id));
+    }
+    match &handoff.to_signature {
+        Some(sig) => {
+            if !matches!(verify_object(&payload, sig, to_public_key_b64), Ok(true)) {
+                errors.push(format!("handoff {}: to_signature invalid", handoff.handoff_id));
+            }
+        }
+        None => errors.push(format!("handoff {}: not accepted by {}", handoff.handoff_id, handoff.to_agent_id)),
+    }
+    handoff_result(errors)
+}
+
+/// Check that a bundle's audit entries are attributed to the agent
+/// responsible for their intent at the time they were written.
+///
+/// Handoffs for the bundle's intent must form a chain starting at the
+/// passport agent (each `from_agent_id` equals the previous
+/// `to_agent_id`) under the bundle's `human_id`. Each hop's `scope` must
+/// cover the intent's `action_type` and stay within what the delegator
+/// holds: the passport's `capabilities` for the first hop, the previous
+/// hop's `scope` after that. Signatures are not re-checked here; call
+/// [`verify_handoff`] on each record first. Timestamps are compared as
+/// instants, to the second; an `effective_at` or entry `timestamp` that
+/// does not parse fails the check.
+pub fn verify_handoff_attribution(bundle: &CitizenshipBundle, handoffs: &[Handoff]) -> VerificationResult {
+    let intent = &bundle.intent;
+    let mut errors = Vec::new();
+    let mut timed: Vec<(i64, &Handoff)> = Vec::new();
+    for h in handoffs.iter().filter(|h| h.intent_id == intent.intent_id) {
+        match parse_iso_epoch_secs(&h.effective_at) {
+            Some(t) => timed.push((t, h)),
+            None => errors.push(format!("handoff {}: effective_at {:?} is unparseable", h.handoff_id, h.effective_at)),
+        }
+    }
+    if timed.is_empty() || !errors.is_empty() {
+        return handoff_result(errors);
+    }
+    timed.sort_by_key(|(t, _)| *t);
+    let chain: Vec<&Handoff> = timed.iter().map(|(_, h)| *h).collect();
+
+    let passport = &bundle.agent_passport;
+    if chain[0].from_agent_id != passport.agent_id {
+        errors.push(format!(
+            "handoff {} starts from {} but the passport agent is {}",
+            chain[0].handoff_id, chain[0].from_agent_id, passport.agent_id
+        ));
+    }
+    let human_id = &bundle.responsible_principal_record.human_id;
+    let mut delegator_holds: &[String] = passport.capabilities.as_deref().unwrap_or_default();
+    for (j, h) in chain.iter().enumerate() {
+        if j > 0 && h.from_agent_id != chain[j - 1].to_agent_id {
+            errors.push(format!(
+                "handoff chain broken at {}: from {} but previous handoff went to {}",
+                h.handoff_id, h.from_agent_id, chain[j - 1].to_agent_id
+            ));
+        }
+        if &h.human_id != human_id {
+            errors.push(format!("handoff {} is bound to {} but the bundle to {}", h.handoff_id, h.human_id, human_id));
+        }
+        if let Some(extra) = h.scope.iter().find(|s| !delegator_holds.contains(s)) {
+            errors.push(format!(
+                "handoff {}: scope {} exceeds what {} holds",
+                h.handoff_id, extra, h.from_agent_id
+            ));
+        }
+        if !h.scope.contains(&intent.action_type) {
+            errors.push(format!(
+                "handoff {}: scope does not cover the intent's action_type {}",
+                h.handoff_id, intent.action_type
+            ));
+        }
+        delegator_holds = &h.scope;
+    }
+
+    for (i, entry) in bundle.audit_entries.iter().enumerate().filter(|(_, e)| e.intent_id == intent.intent_id) {
+        let Some(at) = parse_iso_epoch_secs(&entry.timestamp) else {
+            errors.push(format!("entry {} ({}): timestamp {:?} is unparseable", i, entry.audit_id, entry.timestamp));
+            continue;
+        };
+        let responsible = timed
+            .iter()
+            .rev()
+            .find(|(t, _)| *t <= at)
+            .map_or(chain[0].from_agent_id.as_str(), |(_, h)| h.to_agent_id.as_str());
+        if entry.agent_id != responsible {
+            errors.push(format!(
+                "entry {} ({}): attributed to {} but {} was responsible at {}",
+                i, entry.audit_id, entry.agent_id, responsible, entry.timestamp
+            ));
+        }
+    }
+    handoff_result(errors)
+}
+
+fn handoff_result(errors: Vec<String>) -> VerificationResult {
+    if errors.is_empty() {
+        VerificationResult::ok()
+    } else {
+        VerificationResult::fail_at(
+            errors.into_iter().map(|e| VerificationFailure::new(VerifyError::HandoffInvalid, e, "")).collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+    use crate::types::{AuditEntry, AuditEvidence};
+
+    fn entry(audit_id: &str, agent_id: &str, ts: &str) -> AuditEntry {
+        AuditEntry {
+            dcp_version: "1.0".into(),
+            audit_id: audit_id.into(),
+            prev_hash: "GENESIS".into(),
+            timestamp: ts.into(),
+            agent_id: agent_id.into(),
+            human_id: "did:human:alice".into(),
+            intent_id: "intent001".into(),
+            intent_hash: String::new(),
+            policy_decision: "approved".into(),
+            outcome: "ok".into(),
+            evidence: AuditEvidence { tool: None, result_ref: None, tombstone: None },
+            seq: None,
+            trace_id: None,
+            span_id: None,
+        }
+    }
+
+    fn handoff(from_sk: &str) -> Handoff {
+        create_handoff(
+            HandoffParams {
+                handoff_id: "ho-1",
+                intent_id: "intent001",
+                human_id: "did:human:alice",
+                from_agent_id: "did:agent:a",
+                to_agent_id: "did:agent:b",
+                scope: vec!["send_email".into()],
+                effective_at: "2026-01-01T02:00:00Z",
+            },
+            from_sk,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_both_signatures_required() {
+        let (a_pk, a_sk) = generate_keypair();
+        let (b_pk, b_sk) = generate_keypair();
+        let mut h = handoff(&a_sk);
+        assert!(!verify_handoff(&h, &a_pk, &b_pk).verified);
+        accept_handoff(&mut h, &b_sk).unwrap();
+        assert!(verify_handoff(&h, &a_pk, &b_pk).verified);
+        assert!(!verify_handoff(&h, &b_pk, &a_pk).verified);
+    }
+
+    fn bundle(entries: Vec<AuditEntry>) -> CitizenshipBundle {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let mut bundle: CitizenshipBundle =
+            serde_json::from_value(set.get("citizenship_bundle").unwrap().value.clone()).unwrap();
+        bundle.responsible_principal_record.human_id = "did:human:alice".into();
+        bundle.agent_passport.agent_id = "did:agent:a".into();
+        bundle.agent_passport.capabilities = Some(vec!["send_email".into(), "read_inbox".into()]);
+        bundle.intent.intent_id = "intent001".into();
+        bundle.audit_entries = entries;
+        bundle
+    }
+
+    #[test]
+    fn test_attribution_switches_at_effective_time() {
+        let (_, a_sk) = generate_keypair();
+        let h = handoff(&a_sk);
+        let ok = bundle(vec![
+            entry("a1", "did:agent:a", "2026-01-01T01:00:00Z"),
+            entry("a2", "did:agent:b", "2026-01-01T03:00:00Z"),
+        ]);
+        assert!(verify_handoff_attribution(&ok, std::slice::from_ref(&h)).verified);
+
+        let bad = bundle(vec![entry("a3", "did:agent:a", "2026-01-01T03:00:00Z")]);
+        let result = verify_handoff_attribution(&bad, &[h]);
+        assert!(!result.verified);
+        assert_eq!(result.errors.as_ref().unwrap().len(), 1);
+        assert!(result.errors.unwrap()[0].contains("did:agent:b was responsible"));
+    }
+
+    #[test]
+    fn test_timestamps_compare_as_instants() {
+        let (_, a_sk) = generate_keypair();
+        let h = handoff(&a_sk);
+        // Lexicographically before `effective_at`, but the same second.
+        let ms = bundle(vec![entry("a1", "did:agent:b", "2026-01-01T02:00:00.500Z")]);
+        let result = verify_handoff_attribution(&ms, std::slice::from_ref(&h));
+        assert!(result.verified, "{:?}", result.errors);
+
+        let garbled = bundle(vec![entry("a1", "did:agent:b", "yesterday")]);
+        let result = verify_handoff_attribution(&garbled, std::slice::from_ref(&h));
+        assert!(result.errors.unwrap()[0].contains("timestamp \"yesterday\" is unparseable"));
+
+        let mut undated = h;
+        undated.effective_at = "later".into();
+        let result = verify_handoff_attribution(&ms, &[undated]);
+        assert!(result.errors.unwrap()[0].contains("effective_at \"later\" is unparseable"));
+    }
+
+    #[test]
+    fn test_chain_must_start_at_passport_agent_within_its_capabilities() {
+        let (_, a_sk) = generate_keypair();
+        let entries = vec![entry("a1", "did:agent:b", "2026-01-01T03:00:00Z")];
+        let h = handoff(&a_sk);
+
+        let mut other = bundle(entries.clone());
+        other.agent_passport.agent_id = "did:agent:z".into();
+        let result = verify_handoff_attribution(&other, std::slice::from_ref(&h));
+        assert!(result.errors.unwrap().iter().any(|e| e.contains("passport agent is did:agent:z")));
+
+        let mut widened = h.clone();
+        widened.scope.push("make_payment".into());
+        let result = verify_handoff_attribution(&bundle(entries.clone()), &[widened]);
+        assert_eq!(
+            result.errors.unwrap(),
+            ["HANDOFF INVALID: handoff ho-1: scope make_payment exceeds what did:agent:a holds"]
+        );
+
+        let mut narrowed = h.clone();
+        narrowed.scope = vec!["read_inbox".into()];
+        let result = verify_handoff_attribution(&bundle(entries.clone()), &[narrowed]);
+        assert!(result.errors.unwrap()[0].contains("does not cover the intent's action_type"));
+
+        // A second hop may not regain what the first one dropped.
+        let mut first = h.clone();
+        first.scope = vec!["send_email".into()];
+        let mut second = h;
+        second.handoff_id = "ho-2".into();
+        second.from_agent_id = "did:agent:b".into();
+        second.to_agent_id = "did:agent:c".into();
+        second.scope = vec!["send_email".into(), "read_inbox".into()];
+        second.effective_at = "2026-01-01T04:00:00Z".into();
+        let result = verify_handoff_attribution(&bundle(entries), &[first, second]);
+        assert_eq!(
+            result.errors.unwrap(),
+            ["HANDOFF INVALID: handoff ho-2: scope read_inbox exceeds what did:agent:b holds"]
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! Agent authentication at connection time.
+//!
+//! A relying party that accepts a connection from an agent sends a
+//! [`HandshakeChallenge`]: a fresh nonce, its own id as audience, the
+//! capabilities it is about to rely on, and a short expiry. The agent
+//! answers with [`respond`]: its passport and a signature over the
+//! challenge with the passport's key. [`verify_handshake`] checks the
+//! passport was issued by the principal and grants the capabilities, and
+//! that the agent holds the passport's key.
+//!
+//! ```ignore
+//! let challenge = HandshakeChallenge::new("did:web:rp.example", vec!["email".into()], Duration::from_secs(60));
+//! let response = respond(&challenge, &passport, &agent_sk)?;      // agent side
+//! assert!(verify_handshake(&challenge, &response, &human_pk).verified);
+//! ```
+//!
+//! The signed payload carries a fixed `purpose`, so a handshake response
+//! can never be replayed as any other DCP signature.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::challenge::iso_from_secs;
+use crate::crypto::{sign_object, verify_object};
+use crate::issue::{record_hash, verify_record_signature};
+use crate::types::{AgentPassport, SignatureB64, VerificationFailure, VerificationResult, VerifyError};
+use crate::v2::advisory::now_epoch_secs;
+use crate::v2::lifecycle::utc_now_iso_pub as utc_now_iso;
+use crate::v2::session_nonce::generate_session_nonce;
+
+/// `purpose` of every signed handshake payload.
+pub const HANDSHAKE_PURPOSE: &str = "DCP-AI.v1.AgentHandshake";
+
+/// Sent by a relying party to an agent that connects.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandshakeChallenge {
+    pub handshake_id: String,
+    /// Id of the relying party, so a response is useless to any other.
+    pub audience: String,
+    /// 32 random bytes, hex.
+    pub nonce: String,
+    /// Capabilities the agent's passport must grant.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_capabilities: Vec<String>,
+    pub issued_at: String,
+    pub expires_at: String,
+}
+
+/// The agent's answer to a [`HandshakeChallenge`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandshakeResponse {
+    pub handshake_id: String,
+    pub passport: AgentPassport,
+    pub responded_at: String,
+    /// Ed25519 over [`response_payload`] with the passport's key.
+    pub signature: SignatureB64,
+}
+
+impl HandshakeChallenge {
+    /// A fresh challenge from `audience`, answerable for `ttl`.
+    pub fn new(audience: &str, required_capabilities: Vec<String>, ttl: Duration) -> Self {
+        let nonce = generate_session_nonce();
+        let now = now_epoch_secs();
+        Self {
+            handshake_id: format!("handshake-{}", &nonce[..16]),
+            audience: audience.to_string(),
+            nonce,
+            required_capabilities,
+            issued_at: iso_from_secs(now),
+            expires_at: iso_from_secs(now + ttl.as_secs() as i64),
+        }
+    }
+}
+
+/// What the agent signs: the purpose, the whole challenge, the hash of
+/// the passport it presents, and the response time.
+pub fn response_payload(challenge: &HandshakeChallenge, passport_hash: &str, responded_at: &str) -> Value {
+    json!({
+        "purpose": HANDSHAKE_PURPOSE,
+        "challenge": challenge,
+        "passport_hash": passport_hash,
+        "responded_at": responded_at,
+    })
+}
+
+/// Answer `challenge` presenting `passport`, signing with the agent's
+/// secret key.
+pub fn respond(
+    challenge: &HandshakeChallenge,
+    passport: &AgentPassport,
+    secret_key_b64: &str,
+) -> Result<HandshakeResponse, String> {
+    let responded_at = utc_now_iso();
+    let payload = response_payload(challenge, &record_hash(passport)?, &responded_at);
+    Ok(HandshakeResponse {
+        handshake_id: challenge.handshake_id.clone(),
+        passport: passport.clone(),
+        signature: SignatureB64::from_signer(sign_object(&payload, secret_key_b64)?),
+        responded_at,
+    })
+}
+
+/// Verify `response` to `challenge`.
+///
+/// Passes when the response names the challenge, came before
+/// `expires_at` and it is not yet past, the passport is active, signed
+/// under `human_public_key_b64`, and grants every required capability,
+/// and the response is signed with the passport's key. ISO-8601 UTC
+/// timestamps are compared lexicographically.
+pub fn verify_handshake(
+    challenge: &HandshakeChallenge,
+    response: &HandshakeResponse,
+    human_public_key_b64: &str,
+) -> VerificationResult {
+    let passport = &response.passport;
+    let failure = |kind, detail: String| VerificationFailure::new(kind, detail, "");
+    let mut failures = Vec::new();
+    if response.handshake_id != challenge.handshake_id {
+        failures.push(failure(
+            VerifyError::HandshakeMismatch,
+            format!("response is for {}, not {}", response.handshake_id, challenge.handshake_id),
+        ));
+    }
+    if response.responded_at.as_str() > challenge.expires_at.as_str() || utc_now_iso() > challenge.expires_at {
+        failures.push(failure(VerifyError::HandshakeExpired, format!("challenge expired at {}", challenge.expires_at)));
+    }
+    if !verify_record_signature(passport, human_public_key_b64) {
+        failures.push(failure(
+            VerifyError::KeyNotBound,
+            format!("passport of {} is not signed by the principal", passport.agent_id),
+        ));
+    }
+    if passport.status != "active" {
+        let detail = format!("passport of {} is {}", passport.agent_id, passport.status);
+        failures.push(failure(VerifyError::Revoked, detail));
+    }
+    let granted = passport.capabilities.as_deref().unwrap_or_default();
+    for capability in challenge.required_capabilities.iter().filter(|c| !granted.contains(*c)) {
+        failures.push(failure(
+            VerifyError::CapabilityMissing,
+            format!("passport of {} does not grant {}", passport.agent_id, capability),
+        ));
+    }
+    let signed = record_hash(passport).is_ok_and(|hash| {
+        let payload = response_payload(challenge, &hash, &response.responded_at);
+        matches!(verify_object(&payload, response.signature.as_str(), passport.public_key.as_str()), Ok(true))
+    });
+    if !signed {
+        failures.push(failure(VerifyError::SignatureInvalid, "handshake response".into()));
+    }
+    if failures.is_empty() {
+        VerificationResult::ok()
+    } else {
+        VerificationResult::fail_at(failures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+    use crate::issue::sign_record;
+
+    fn passport() -> (AgentPassport, String, String) {
+        let (human_pk, human_sk) = generate_keypair();
+        let (agent_pk, agent_sk) = generate_keypair();
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let mut passport: AgentPassport =
+            serde_json::from_value(set.get("agent_passport").unwrap().value.clone()).unwrap();
+        passport.public_key = agent_pk.parse().unwrap();
+        passport.signature = sign_record(&passport, &human_sk).unwrap();
+        (passport, human_pk, agent_sk)
+    }
+
+    #[test]
+    fn test_agent_passes_handshake() {
+        let (passport, human_pk, agent_sk) = passport();
+        let challenge = HandshakeChallenge::new("did:web:rp.example", vec!["email".into()], Duration::from_secs(60));
+        let response = respond(&challenge, &passport, &agent_sk).unwrap();
+        let result = verify_handshake(&challenge, &response, &human_pk);
+        assert!(result.verified, "{:?}", result.errors);
+    }
+
+    #[test]
+    fn test_wrong_key_and_missing_capability_fail() {
+        let (passport, human_pk, _) = passport();
+        let challenge = HandshakeChallenge::new("did:web:rp.example", vec!["payments".into()], Duration::from_secs(60));
+        let response = respond(&challenge, &passport, &generate_keypair().1).unwrap();
+        let errors = verify_handshake(&challenge, &response, &human_pk).errors.unwrap();
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors[0].starts_with("CAPABILITY MISSING"));
+        assert!(errors[1].starts_with("SIGNATURE INVALID"));
+
+        let other = HandshakeChallenge::new("did:web:rp.example", Vec::new(), Duration::from_secs(60));
+        let errors = verify_handshake(&other, &response, &human_pk).errors.unwrap();
+        assert!(errors[0].starts_with("HANDSHAKE MISMATCH"));
+    }
+}
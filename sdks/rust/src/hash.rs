@@ -0,0 +1,165 @@
+//! Incremental digests.
+//!
+//! Every hash the SDK computes (canonical object hashes, `bundle_hash`,
+//! merkle nodes, the WASM hashing exports) goes through [`Hasher`], so
+//! choosing the algorithm for an `<alg>:<hex>` value happens here and
+//! nowhere else. Input can be fed in chunks, which lets callers hash a
+//! canonical form as it is streamed instead of buffering it.
+//!
+//! ```
+//! use dcp_ai::hash::{digest_hex, HashAlgorithm, Hasher};
+//!
+//! let mut hasher = Hasher::new(HashAlgorithm::Sha256);
+//! hasher.update(b"{\"a\":");
+//! hasher.update(b"1}");
+//! assert_eq!(hasher.finalize_hex(), digest_hex(HashAlgorithm::Sha256, b"{\"a\":1}"));
+//! ```
+
+use sha2::{Digest, Sha256};
+use sha3::Sha3_256;
+
+/// A digest algorithm, named as in `<alg>:<hex>` values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Sha3_256,
+}
+
+impl HashAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Sha3_256 => "sha3-256",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "sha256" => Some(HashAlgorithm::Sha256),
+            "sha3-256" => Some(HashAlgorithm::Sha3_256),
+            _ => None,
+        }
+    }
+
+    /// Split `<alg>:<hex>` into its algorithm and hex digest. `None` if
+    /// there is no prefix or the algorithm is unknown.
+    pub fn split_prefixed(value: &str) -> Option<(Self, &str)> {
+        let (alg, hex) = value.split_once(':')?;
+        Some((Self::parse(alg)?, hex))
+    }
+}
+
+#[derive(Clone)]
+enum State {
+    Sha256(Sha256),
+    Sha3_256(Box<Sha3_256>),
+}
+
+/// Streaming hasher: [`Hasher::new`], any number of [`Hasher::update`]s,
+/// then one of the `finalize` methods.
+#[derive(Clone)]
+pub struct Hasher {
+    state: State,
+}
+
+impl std::fmt::Debug for Hasher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Hasher").field("algorithm", &self.algorithm()).finish_non_exhaustive()
+    }
+}
+
+impl Hasher {
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        let state = match algorithm {
+            HashAlgorithm::Sha256 => State::Sha256(Sha256::new()),
+            HashAlgorithm::Sha3_256 => State::Sha3_256(Box::new(Sha3_256::new())),
+        };
+        Self { state }
+    }
+
+    pub fn sha256() -> Self {
+        Self::new(HashAlgorithm::Sha256)
+    }
+
+    pub fn algorithm(&self) -> HashAlgorithm {
+        match self.state {
+            State::Sha256(_) => HashAlgorithm::Sha256,
+            State::Sha3_256(_) => HashAlgorithm::Sha3_256,
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) -> &mut Self {
+        match &mut self.state {
+            State::Sha256(h) => h.update(data),
+            State::Sha3_256(h) => h.update(data),
+        }
+        self
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        match self.state {
+            State::Sha256(h) => h.finalize().to_vec(),
+            State::Sha3_256(h) => h.finalize().to_vec(),
+        }
+    }
+
+    pub fn finalize_hex(self) -> String {
+        hex::encode(self.finalize())
+    }
+
+    /// `<alg>:<hex>`, the form of `bundle_hash` and `merkle_root`.
+    pub fn finalize_prefixed(self) -> String {
+        let alg = self.algorithm();
+        format!("{}:{}", alg.as_str(), self.finalize_hex())
+    }
+}
+
+impl std::io::Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Hex digest of `data` in one call.
+pub fn digest_hex(algorithm: HashAlgorithm, data: &[u8]) -> String {
+    let mut hasher = Hasher::new(algorithm);
+    hasher.update(data);
+    hasher.finalize_hex()
+}
+
+/// Digest of two merkle children (raw bytes, left then right).
+pub fn digest_pair(algorithm: HashAlgorithm, left: &[u8], right: &[u8]) -> Vec<u8> {
+    let mut hasher = Hasher::new(algorithm);
+    hasher.update(left).update(right);
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunked_matches_one_shot() {
+        let data = b"{\"dcp_version\":\"1.0\",\"outcome\":\"ok\"}";
+        for alg in [HashAlgorithm::Sha256, HashAlgorithm::Sha3_256] {
+            let mut hasher = Hasher::new(alg);
+            data.chunks(5).for_each(|c| {
+                hasher.update(c);
+            });
+            assert_eq!(hasher.finalize_hex(), digest_hex(alg, data));
+        }
+        assert_eq!(
+            digest_hex(HashAlgorithm::Sha256, b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        let prefixed = Hasher::new(HashAlgorithm::Sha3_256).finalize_prefixed();
+        assert_eq!(HashAlgorithm::split_prefixed(&prefixed).map(|(a, _)| a), Some(HashAlgorithm::Sha3_256));
+    }
+}
@@ -0,0 +1,205 @@
+//! Verification health for services embedding the verifier.
+//!
+//! [`VerifierStatus`] is a cheap, shareable set of counters. Record every
+//! verification result (and, where the service has them, cache lookups
+//! and revocation refreshes), then serve [`VerifierStatus::to_prometheus_text`]
+//! from a `/metrics` route or [`VerifierStatus::snapshot`] as JSON from a
+//! status route.
+//!
+//! ```
+//! use dcp_ai::health::VerifierStatus;
+//! use dcp_ai::{VerificationResult, VerifyError};
+//!
+//! let status = VerifierStatus::new();
+//! status.record(&VerificationResult::fail(VerifyError::MerkleRootMismatch, ""));
+//! assert!(status.to_prometheus_text().contains(r#"dcp_verification_failures_total{code="DCP-E202"} 1"#));
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use serde::Serialize;
+
+use crate::types::VerificationResult;
+use crate::v2::advisory::parse_iso_epoch_secs;
+use crate::v2::error_codes::DcpErrorCode;
+
+/// Label for failures without a canonical code.
+pub const UNCLASSIFIED: &str = "unclassified";
+
+/// Live verification counters. Share it behind an `Arc`.
+#[derive(Debug, Default)]
+pub struct VerifierStatus {
+    verified: AtomicU64,
+    failed: AtomicU64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    failures_by_code: Mutex<BTreeMap<String, u64>>,
+    last_revocation_refresh: Mutex<Option<String>>,
+}
+
+/// Point-in-time copy of a [`VerifierStatus`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct StatusSnapshot {
+    pub bundles_verified: u64,
+    pub bundles_failed: u64,
+    /// Failed bundles by the canonical code of their first failure
+    /// (`DCP-E202`), or [`UNCLASSIFIED`].
+    pub failures_by_code: BTreeMap<String, u64>,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    /// ISO-8601 UTC time of the last revocation list refresh.
+    pub last_revocation_refresh: Option<String>,
+}
+
+impl StatusSnapshot {
+    /// Hits over lookups; `0.0` before the first lookup.
+    pub fn cache_hit_rate(&self) -> f64 {
+        let total = self.cache_hits + self.cache_misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.cache_hits as f64 / total as f64
+        }
+    }
+}
+
+impl VerifierStatus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one verification.
+    pub fn record(&self, result: &VerificationResult) {
+        if result.verified {
+            self.verified.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        self.failed.fetch_add(1, Ordering::Relaxed);
+        let code = result
+            .error_kinds
+            .as_ref()
+            .and_then(|e| e.first())
+            .and_then(DcpErrorCode::from_verify_error)
+            .map_or_else(|| UNCLASSIFIED.to_string(), |c| c.code().to_string());
+        *lock(&self.failures_by_code).entry(code).or_insert(0) += 1;
+    }
+
+    pub fn record_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_cache_miss(&self) {
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Note a completed revocation refresh at `at` (ISO-8601 UTC), e.g.
+    /// from `RevocationState::refreshed_at` with the `registry` feature.
+    pub fn record_revocation_refresh(&self, at: &str) {
+        *lock(&self.last_revocation_refresh) = Some(at.to_string());
+    }
+
+    pub fn snapshot(&self) -> StatusSnapshot {
+        StatusSnapshot {
+            bundles_verified: self.verified.load(Ordering::Relaxed),
+            bundles_failed: self.failed.load(Ordering::Relaxed),
+            failures_by_code: lock(&self.failures_by_code).clone(),
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+            cache_misses: self.cache_misses.load(Ordering::Relaxed),
+            last_revocation_refresh: lock(&self.last_revocation_refresh).clone(),
+        }
+    }
+
+    /// Prometheus text exposition format (version 0.0.4).
+    pub fn to_prometheus_text(&self) -> String {
+        let s = self.snapshot();
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: &[(String, String)]| {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+            for (labels, value) in samples {
+                let _ = writeln!(out, "{}{} {}", name, labels, value);
+            }
+        };
+        let plain = |v: String| vec![(String::new(), v)];
+
+        metric(
+            "dcp_bundles_verified_total",
+            "counter",
+            "Bundles that passed verification.",
+            &plain(s.bundles_verified.to_string()),
+        );
+        metric(
+            "dcp_bundles_failed_total",
+            "counter",
+            "Bundles that failed verification.",
+            &plain(s.bundles_failed.to_string()),
+        );
+        let by_code: Vec<(String, String)> = s
+            .failures_by_code
+            .iter()
+            .map(|(code, n)| (format!("{{code=\"{}\"}}", code), n.to_string()))
+            .collect();
+        metric(
+            "dcp_verification_failures_total",
+            "counter",
+            "Failed verifications by canonical error code of the first failure.",
+            &by_code,
+        );
+        metric("dcp_cache_hits_total", "counter", "Verification cache hits.", &plain(s.cache_hits.to_string()));
+        metric("dcp_cache_misses_total", "counter", "Verification cache misses.", &plain(s.cache_misses.to_string()));
+        metric(
+            "dcp_cache_hit_ratio",
+            "gauge",
+            "Cache hits over lookups.",
+            &plain(format!("{}", s.cache_hit_rate())),
+        );
+        if let Some(secs) = s.last_revocation_refresh.as_deref().and_then(parse_iso_epoch_secs) {
+            metric(
+                "dcp_revocation_last_refresh_timestamp_seconds",
+                "gauge",
+                "Unix time of the last revocation list refresh.",
+                &plain(secs.to_string()),
+            );
+        }
+        out
+    }
+}
+
+fn lock<T>(m: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    m.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::VerifyError;
+
+    #[test]
+    fn test_counters_and_prometheus_text() {
+        let status = VerifierStatus::new();
+        status.record(&VerificationResult::ok());
+        status.record(&VerificationResult::fail(VerifyError::SignatureInvalid, ""));
+        status.record(&VerificationResult::fail(VerifyError::AttestationMissing, ""));
+        status.record_cache_hit();
+        status.record_cache_hit();
+        status.record_cache_hit();
+        status.record_cache_miss();
+        status.record_revocation_refresh("2026-01-01T00:00:00Z");
+
+        let snap = status.snapshot();
+        assert_eq!((snap.bundles_verified, snap.bundles_failed), (1, 2));
+        assert_eq!(snap.failures_by_code.get("DCP-E100"), Some(&1));
+        assert_eq!(snap.failures_by_code.get(UNCLASSIFIED), Some(&1));
+        assert_eq!(snap.cache_hit_rate(), 0.75);
+
+        let text = status.to_prometheus_text();
+        assert!(text.contains("# TYPE dcp_bundles_verified_total counter\ndcp_bundles_verified_total 1\n"));
+        assert!(text.contains("dcp_verification_failures_total{code=\"unclassified\"} 1\n"));
+        assert!(text.contains("dcp_cache_hit_ratio 0.75\n"));
+        assert!(text.contains("dcp_revocation_last_refresh_timestamp_seconds 1767225600\n"));
+    }
+}
@@ -0,0 +1,307 @@
+//! Cross-SDK interop fixture suites with this crate as the reference.
+//!
+//! [`generate_suite`] emits a language-neutral, versioned JSON suite: for
+//! each case an input, the expected canonical bytes, their SHA-256, and an
+//! Ed25519 signature under a fixed, published test key. Signed-bundle
+//! cases also say whether the bundle must verify. Write it out with
+//!
+//! ```text
+//! cargo run --bin dcp -- gen-interop <out_dir>
+//! ```
+//!
+//! The current suite is checked in as `interop/suite-v<SUITE_VERSION>.json`
+//! in this crate (the default `<out_dir>`); a test fails when it no longer
+//! matches what this version generates.
+//!
+//! Another SDK (Go, Java, ...) runs every case through its own
+//! implementation and writes a [`SuiteResults`] file keyed by case id;
+//! [`verify_fixture_suite`] (or `dcp check-interop SUITE RESULTS`) then
+//! reports every field that differs from the Rust reference.
+//!
+//! The suite key is a test key. Never use it for anything else.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::crypto::{canonicalize, hash_canonical, keypair_from_seed, sign_canonical};
+use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+use crate::verify::verify_signed_bundle;
+
+/// `format` of every suite file.
+pub const SUITE_FORMAT: &str = "dcp-ai.interop-suite";
+
+/// Bumped whenever cases are added or expectations change. Results
+/// produced against another version are rejected.
+pub const SUITE_VERSION: &str = "1";
+
+const SUITE_KEY_SEED: [u8; 32] = *b"dcp-ai interop suite test key v1";
+
+/// A versioned set of interop cases.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct FixtureSuite {
+    pub format: String,
+    pub version: String,
+    /// Implementation that generated the suite, e.g. `dcp-ai-rust 2.0.0`.
+    pub generator: String,
+    /// Published test key; every `sig_b64` is deterministic under it.
+    pub secret_key_b64: String,
+    pub public_key_b64: String,
+    pub cases: Vec<FixtureCase>,
+}
+
+/// One input and everything a conforming SDK must derive from it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct FixtureCase {
+    /// Stable id, e.g. `canon/key-order` or `bundle/tampered`.
+    pub id: String,
+    pub input: Value,
+    /// Expected canonical JSON of `input`.
+    pub canonical: String,
+    /// SHA-256 hex of `canonical`.
+    pub sha256: String,
+    /// Ed25519 signature over `canonical` with the suite key.
+    pub sig_b64: String,
+    /// For signed-bundle cases: whether `input` must verify.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verifies: Option<bool>,
+}
+
+/// What an implementation under test produced for one case.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct CaseResult {
+    pub canonical: Option<String>,
+    pub sha256: Option<String>,
+    pub sig_b64: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified: Option<bool>,
+}
+
+/// Results file written by the implementation under test.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SuiteResults {
+    /// Name and version of the implementation, e.g. `dcp-ai-go 0.4.1`.
+    pub implementation: String,
+    /// [`FixtureSuite::version`] the results were produced against.
+    pub suite_version: String,
+    pub results: BTreeMap<String, CaseResult>,
+}
+
+/// One field where the implementation disagrees with the reference.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CaseMismatch {
+    pub case_id: String,
+    /// `canonical`, `sha256`, `sig_b64`, `verified`, or `result` when the
+    /// case is missing altogether.
+    pub field: &'static str,
+    pub expected: String,
+    pub actual: Option<String>,
+}
+
+/// Outcome of [`verify_fixture_suite`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct SuiteReport {
+    pub implementation: String,
+    pub suite_version: String,
+    pub cases: usize,
+    pub mismatches: Vec<CaseMismatch>,
+}
+
+impl SuiteReport {
+    /// Every case matched the reference.
+    pub fn certified(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Canonicalization edge cases every SDK gets wrong at least once.
+fn canonicalization_inputs() -> Vec<(&'static str, Value)> {
+    vec![
+        ("canon/key-order", json!({ "b": 1, "a": 2, "c": { "z": 0, "y": 1 } })),
+        ("canon/nested-arrays", json!({ "z": [3, { "b": true, "a": null }, []], "a": [[1], [2, 3]] })),
+        ("canon/unicode", json!({ "name": "Zoë ✓", "emoji": "🚀", "é": 1, "e": 2, "E": 3 })),
+        ("canon/escapes", json!({ "s": "line\nbreak \"quoted\" \\ tab\t", "ctrl": "\u{1}" })),
+        ("canon/numbers", json!({ "int": 42, "neg": -7, "zero": 0, "half": 0.5, "max_safe": 9007199254740991u64 })),
+        ("canon/empty", json!({ "o": {}, "a": [], "s": "" })),
+    ]
+}
+
+/// Build the current suite. Deterministic: the same crate version always
+/// yields the same suite.
+pub fn generate_suite() -> Result<FixtureSuite, String> {
+    let (public_key_b64, secret_key_b64) = keypair_from_seed(&SUITE_KEY_SEED);
+    let case = |id: &str, input: Value, verifies: Option<bool>| -> Result<FixtureCase, String> {
+        let canonical = canonicalize(&input);
+        Ok(FixtureCase {
+            id: id.to_string(),
+            sha256: hash_canonical(&canonical),
+            sig_b64: sign_canonical(&canonical, &secret_key_b64)?,
+            canonical,
+            input,
+            verifies,
+        })
+    };
+
+    let mut cases = Vec::new();
+    for (id, input) in canonicalization_inputs() {
+        cases.push(case(id, input, None)?);
+    }
+
+    let golden = generate_fixtures(&GOLDEN_SEED)?;
+    for fixture in golden.fixtures.iter().filter(|f| f.name != "citizenship_bundle.signed") {
+        cases.push(case(&format!("artifact/{}", fixture.name), fixture.value.clone(), None)?);
+    }
+
+    let signed = golden
+        .get("citizenship_bundle.signed")
+        .ok_or("golden fixtures lack citizenship_bundle.signed")?
+        .value
+        .clone();
+    let mut tampered = signed.clone();
+    tampered["bundle"]["intent"]["action_type"] = "wire_funds".into();
+    cases.push(case("bundle/valid", signed, Some(true))?);
+    cases.push(case("bundle/tampered", tampered, Some(false))?);
+
+    Ok(FixtureSuite {
+        format: SUITE_FORMAT.to_string(),
+        version: SUITE_VERSION.to_string(),
+        generator: format!("dcp-ai-rust {}", env!("CARGO_PKG_VERSION")),
+        secret_key_b64,
+        public_key_b64,
+        cases,
+    })
+}
+
+/// This crate's own answers to `suite`, in the format other SDKs write.
+/// Useful as a worked example of a results file.
+pub fn reference_results(suite: &FixtureSuite) -> Result<SuiteResults, String> {
+    let mut results = BTreeMap::new();
+    for case in &suite.cases {
+        let canonical = canonicalize(&case.input);
+        let result = CaseResult {
+            sha256: Some(hash_canonical(&canonical)),
+            sig_b64: Some(sign_canonical(&canonical, &suite.secret_key_b64)?),
+            verified: case.verifies.map(|_| verify_signed_bundle(&case.input, None).verified),
+            canonical: Some(canonical),
+        };
+        results.insert(case.id.clone(), result);
+    }
+    Ok(SuiteResults {
+        implementation: format!("dcp-ai-rust {}", env!("CARGO_PKG_VERSION")),
+        suite_version: suite.version.clone(),
+        results,
+    })
+}
+
+/// Compare another implementation's results with the reference suite.
+/// Fails outright if the results target a different suite version.
+pub fn verify_fixture_suite(suite: &FixtureSuite, results: &SuiteResults) -> Result<SuiteReport, String> {
+    if results.suite_version != suite.version {
+        return Err(format!(
+            "results are for suite version {}, this suite is version {}",
+            results.suite_version, suite.version
+        ));
+    }
+    let mut mismatches = Vec::new();
+    for case in &suite.cases {
+        let Some(got) = results.results.get(&case.id) else {
+            mismatches.push(CaseMismatch {
+                case_id: case.id.clone(),
+                field: "result",
+                expected: "a result".into(),
+                actual: None,
+            });
+            continue;
+        };
+        let mut compare = |field: &'static str, expected: &str, actual: Option<&str>| {
+            if actual != Some(expected) {
+                mismatches.push(CaseMismatch {
+                    case_id: case.id.clone(),
+                    field,
+                    expected: expected.to_string(),
+                    actual: actual.map(str::to_string),
+                });
+            }
+        };
+        compare("canonical", &case.canonical, got.canonical.as_deref());
+        compare("sha256", &case.sha256, got.sha256.as_deref());
+        compare("sig_b64", &case.sig_b64, got.sig_b64.as_deref());
+        if let Some(verifies) = case.verifies {
+            let actual = got.verified.map(|v| v.to_string());
+            compare("verified", &verifies.to_string(), actual.as_deref());
+        }
+    }
+    Ok(SuiteReport {
+        implementation: results.implementation.clone(),
+        suite_version: suite.version.clone(),
+        cases: suite.cases.len(),
+        mismatches,
+    })
+}
+
+/// Write `suite-v<version>.json` into `dir` (created if missing),
+/// pretty-printed with a trailing newline. Returns the written path.
+pub fn write_suite(suite: &FixtureSuite, dir: &Path) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!("suite-v{}.json", suite.version));
+    let mut text = serde_json::to_string_pretty(suite)?;
+    text.push('\n');
+    std::fs::write(&path, text)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_certifies_itself() {
+        let suite = generate_suite().unwrap();
+        assert_eq!(suite, generate_suite().unwrap());
+        let report = verify_fixture_suite(&suite, &reference_results(&suite).unwrap()).unwrap();
+        assert!(report.certified(), "{:?}", report.mismatches);
+        assert_eq!(report.cases, suite.cases.len());
+
+        let tampered = suite.cases.iter().find(|c| c.id == "bundle/tampered").unwrap();
+        assert_eq!(tampered.verifies, Some(false));
+    }
+
+    #[test]
+    fn test_mismatches_are_reported_per_field() {
+        let suite = generate_suite().unwrap();
+        let mut results = reference_results(&suite).unwrap();
+        results.results.get_mut("canon/unicode").unwrap().canonical = Some("{}".into());
+        results.results.remove("bundle/valid");
+
+        let report = verify_fixture_suite(&suite, &results).unwrap();
+        let found: Vec<(&str, &str)> = report.mismatches.iter().map(|m| (m.case_id.as_str(), m.field)).collect();
+        assert_eq!(found, [("canon/unicode", "canonical"), ("bundle/valid", "result")]);
+
+        results.suite_version = "0".into();
+        assert!(verify_fixture_suite(&suite, &results).is_err());
+    }
+
+    #[test]
+    fn test_checked_in_suite_is_current() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("interop/suite-v{}.json", SUITE_VERSION));
+        let text = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        let mut checked_in: FixtureSuite = serde_json::from_str(&text).unwrap();
+        let suite = generate_suite().unwrap();
+        // Only the generator line moves with the crate version.
+        checked_in.generator = suite.generator.clone();
+        assert!(
+            checked_in == suite,
+            "{} is stale; bump SUITE_VERSION if expectations changed and rerun `dcp gen-interop`",
+            path.display()
+        );
+        let report = verify_fixture_suite(&checked_in, &reference_results(&checked_in).unwrap()).unwrap();
+        assert!(report.certified(), "{:?}", report.mismatches);
+    }
+}
@@ -0,0 +1,403 @@
+//! Issuing and renewing DCP-01 records.
+//!
+//! v1 record signatures (`signature` on the Responsible Principal Record
+//! and Agent Passport) are Ed25519 over the canonical record with the
+//! `signature` field removed.
+//!
+//! Long-lived agents keep a continuous binding by renewing the Responsible
+//! Principal Record before it expires. Each renewal carries
+//! `prev_record_hash = "sha256:" + hash(previous record)`, so the full
+//! history forms a verifiable chain.
+//!
+//! Organizations nest: a record with `issuer_binding_reference` is issued
+//! and signed by the parent organization named there, up to a self-issued
+//! root. [`verify_issuance_chain`] walks agent → human → organization →
+//! root org.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::crypto::{hash_object, sign_object, verify_object};
+use crate::types::{
+    AgentPassport, ResponsiblePrincipalRecord, SignatureB64, VerificationFailure, VerificationResult, VerifyError,
+};
+use crate::v2::advisory::parse_iso_epoch_secs;
+use crate::v2::lifecycle::utc_now_iso_pub as utc_now_iso;
+
+/// The signed payload of a v1 record: the record without `signature`.
+pub fn record_payload<T: Serialize>(record: &T) -> Result<Value, String> {
+    let mut v = serde_json::to_value(record).map_err(|e| e.to_string())?;
+    if let Some(obj) = v.as_object_mut() {
+        obj.remove("signature");
+    }
+    Ok(v)
+}
+
+/// Sign a v1 record, returning the signature for its `signature` field.
+pub fn sign_record<T: Serialize>(record: &T, secret_key_b64: &str) -> Result<SignatureB64, String> {
+    sign_object(&record_payload(record)?, secret_key_b64).map(SignatureB64::from_signer)
+}
+
+/// Verify the `signature` field of a v1 record. Records whose
+/// `signature_alg` is `openpgp` are checked as OpenPGP signatures, with
+/// `public_key_b64` the signer's certificate (feature = "pgp"; rejected
+/// without it). Any other `signature_alg` is rejected.
+pub fn verify_record_signature<T: Serialize>(record: &T, public_key_b64: &str) -> bool {
+    let Ok(v) = serde_json::to_value(record) else {
+        return false;
+    };
+    let Some(sig) = v.get("signature").and_then(Value::as_str) else {
+        return false;
+    };
+    let Ok(payload) = record_payload(&v) else {
+        return false;
+    };
+    match v.get("signature_alg").and_then(Value::as_str) {
+        None | Some("ed25519") => matches!(verify_object(&payload, sig, public_key_b64), Ok(true)),
+        #[cfg(feature = "pgp")]
+        Some(crate::pgp::OPENPGP_ALG) => {
+            crate::pgp::verify_canonical(&crate::crypto::canonicalize(&payload), sig, public_key_b64)
+        }
+        Some(_) => false,
+    }
+}
+
+/// `sha256:<hex>` reference to a record, as stored in `prev_record_hash`.
+pub fn record_hash<T: Serialize>(record: &T) -> Result<String, String> {
+    let v = serde_json::to_value(record).map_err(|e| e.to_string())?;
+    Ok(format!("sha256:{}", hash_object(&v)))
+}
+
+/// Renew a Responsible Principal Record: same principal, fresh `issued_at`,
+/// `new_expiry`, and a `prev_record_hash` link to `old`, signed with the
+/// principal's key. Fails if `new_expiry` does not parse or is not after
+/// the new `issued_at`.
+pub fn renew_human_binding(
+    old: &ResponsiblePrincipalRecord,
+    new_expiry: &str,
+    secret_key_b64: &str,
+) -> Result<ResponsiblePrincipalRecord, String> {
+    let issued_at = utc_now_iso();
+    match (parse_iso_epoch_secs(new_expiry), parse_iso_epoch_secs(&issued_at)) {
+        (None, _) => return Err(format!("new_expiry {:?} is unparseable", new_expiry)),
+        (Some(expiry), Some(now)) if expiry > now => {}
+        _ => return Err(format!("new_expiry {} is not after issued_at {}", new_expiry, issued_at)),
+    }
+    let mut renewed = old.clone();
+    renewed.issued_at = issued_at;
+    renewed.expires_at = Some(new_expiry.to_string());
+    renewed.prev_record_hash = Some(record_hash(old)?);
+    renewed.signature = sign_record(&renewed, secret_key_b64)?;
+    Ok(renewed)
+}
+
+/// Verify a renewal chain, oldest record first.
+///
+/// Every record must be signed by `public_key_b64` and bind the same
+/// `human_id`; each renewal must link to its predecessor and be issued no
+/// later than the predecessor's expiry, so the binding has no gaps.
+/// Timestamps are compared as instants, to the second, whether or not they
+/// carry milliseconds; one that does not parse fails the chain.
+pub fn verify_renewal_chain(chain: &[ResponsiblePrincipalRecord], public_key_b64: &str) -> VerificationResult {
+    if chain.is_empty() {
+        return VerificationResult::fail(VerifyError::RenewalInvalid, "empty chain");
+    }
+    let mut errors = Vec::new();
+    for (i, record) in chain.iter().enumerate() {
+        if !verify_record_signature(record, public_key_b64) {
+            errors.push(format!("record {}: signature invalid", i));
+        }
+        if i == 0 {
+            continue;
+        }
+        let prev = &chain[i - 1];
+        if record.human_id != prev.human_id {
+            errors.push(format!("record {}: human_id {} differs from {}", i, record.human_id, prev.human_id));
+        }
+        match (&record.prev_record_hash, record_hash(prev)) {
+            (Some(link), Ok(expected)) if *link == expected => {}
+            (link, _) => errors.push(format!("record {}: prev_record_hash {:?} does not link to record {}", i, link, i - 1)),
+        }
+        let issued = parse_iso_epoch_secs(&record.issued_at);
+        match (issued, parse_iso_epoch_secs(&prev.issued_at)) {
+            (Some(t), Some(prev_t)) if t < prev_t => {
+                errors.push(format!("record {}: issued before its predecessor", i));
+            }
+            (Some(_), Some(_)) => {}
+            _ => errors.push(format!(
+                "record {}: issued_at {:?} or predecessor's {:?} is unparseable",
+                i, record.issued_at, prev.issued_at
+            )),
+        }
+        if let (Some(t), Some(prev_expiry)) = (issued, &prev.expires_at) {
+            match parse_iso_epoch_secs(prev_expiry) {
+                Some(expiry) if t > expiry => errors.push(format!(
+                    "record {}: issued at {} after predecessor expired at {}",
+                    i, record.issued_at, prev_expiry
+                )),
+                Some(_) => {}
+                None => errors.push(format!("record {}: predecessor expires_at {:?} is unparseable", i, prev_expiry)),
+            }
+        }
+    }
+    if errors.is_empty() {
+        VerificationResult::ok()
+    } else {
+        VerificationResult::fail_at(
+            errors.into_iter().map(|e| VerificationFailure::new(VerifyError::RenewalInvalid, e, "")).collect(),
+        )
+    }
+}
+
+/// Longest issuance chain [`verify_issuance_chain`] will walk.
+pub const MAX_ISSUANCE_DEPTH: usize = 16;
+
+/// Issue `record` under `issuer`: sets `issuer_binding_reference` to the
+/// issuer's `human_id` and signs with the issuer's key.
+pub fn issue_subordinate_record(
+    mut record: ResponsiblePrincipalRecord,
+    issuer: &ResponsiblePrincipalRecord,
+    issuer_secret_key_b64: &str,
+) -> Result<ResponsiblePrincipalRecord, String> {
+    if issuer.entity_type != "organization" {
+        return Err(format!("Issuer {} is not an organization", issuer.human_id));
+    }
+    record.issuer_binding_reference = Some(issuer.human_id.clone());
+    record.signature = sign_record(&record, issuer_secret_key_b64)?;
+    Ok(record)
+}
+
+/// Walk the issuance chain from an agent's passport to a root record.
+///
+/// `records` holds the Responsible Principal Records of the chain (in any
+/// order) and `public_keys` maps each `human_id` to its Ed25519 key. The
+/// passport must be signed by the principal it names. A record with
+/// `issuer_binding_reference` must be signed by that issuer, which must be
+/// an `organization` and must not have expired before the record was
+/// issued; a root record must be self-signed. Cycles and chains longer
+/// than [`MAX_ISSUANCE_DEPTH`] are rejected.
+pub fn verify_issuance_chain(
+    passport: &AgentPassport,
+    records: &[ResponsiblePrincipalRecord],
+    public_keys: &HashMap<String, String>,
+) -> VerificationResult {
+    let by_id: HashMap<&str, &ResponsiblePrincipalRecord> = records.iter().map(|r| (r.human_id.as_str(), r)).collect();
+    let mut errors = Vec::new();
+    let mut visited: Vec<&str> = Vec::new();
+    let mut current = passport.principal_human_id();
+    match public_keys.get(current) {
+        Some(pk) if verify_record_signature(passport, pk) => {}
+        Some(_) => errors.push(format!("passport {}: signature is not from principal {}", passport.agent_id, current)),
+        None => errors.push(format!("passport {}: no public key for principal {}", passport.agent_id, current)),
+    }
+
+    loop {
+        if visited.contains(&current) {
+            errors.push(format!("issuance cycle at {}", current));
+            break;
+        }
+        if visited.len() == MAX_ISSUANCE_DEPTH {
+            errors.push(format!("issuance chain longer than {}", MAX_ISSUANCE_DEPTH));
+            break;
+        }
+        visited.push(current);
+
+        let Some(record) = by_id.get(current) else {
+            errors.push(format!("no record for {}", current));
+            break;
+        };
+        let signer_id = record.issuer_binding_reference.as_deref().unwrap_or(current);
+        match public_keys.get(signer_id) {
+            Some(pk) if verify_record_signature(*record, pk) => {}
+            Some(_) => errors.push(format!("{}: signature is not from issuer {}", current, signer_id)),
+            None => errors.push(format!("{}: no public key for issuer {}", current, signer_id)),
+        }
+
+        let Some(issuer_id) = record.issuer_binding_reference.as_deref() else {
+            break;
+        };
+        if let Some(issuer) = by_id.get(issuer_id) {
+            if issuer.entity_type != "organization" {
+                errors.push(format!("{}: issuer {} is not an organization", current, issuer_id));
+            }
+            if let Some(expiry) = &issuer.expires_at {
+                match (parse_iso_epoch_secs(&record.issued_at), parse_iso_epoch_secs(expiry)) {
+                    (Some(issued), Some(expires)) if issued > expires => errors.push(format!(
+                        "{}: issued at {} after issuer {} expired at {}",
+                        current, record.issued_at, issuer_id, expiry
+                    )),
+                    (Some(_), Some(_)) => {}
+                    _ => errors.push(format!(
+                        "{}: issued_at {:?} or issuer {} expires_at {:?} is unparseable",
+                        current, record.issued_at, issuer_id, expiry
+                    )),
+                }
+            }
+        }
+        current = issuer_id;
+    }
+
+    if errors.is_empty() {
+        VerificationResult::ok()
+    } else {
+        VerificationResult::fail_at(
+            errors.into_iter().map(|e| VerificationFailure::new(VerifyError::IssuanceInvalid, e, "")).collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+    use crate::types::PublicKeyB64;
+
+    fn rpr(sk: &str) -> ResponsiblePrincipalRecord {
+        let mut r = ResponsiblePrincipalRecord {
+            dcp_version: "1.0".into(),
+            human_id: "did:human:alice".into(),
+            legal_name: "Alice Example".into(),
+            entity_type: "natural_person".into(),
+            jurisdiction: "US".into(),
+            liability_mode: "owner_responsible".into(),
+            override_rights: true,
+            issued_at: "2026-01-01T00:00:00Z".into(),
+            expires_at: Some("2099-01-01T00:00:00Z".into()),
+            contact: None,
+            prev_record_hash: None,
+            issuer_binding_reference: None,
+            agreements: Vec::new(),
+            signature_alg: None,
+            signature: SignatureB64::default(),
+        };
+        r.signature = sign_record(&r, sk).unwrap();
+        r
+    }
+
+    #[test]
+    fn test_renewal_chain_verifies() {
+        let (pk, sk) = generate_keypair();
+        let first = rpr(&sk);
+        let second = renew_human_binding(&first, "2100-01-01T00:00:00Z", &sk).unwrap();
+        let third = renew_human_binding(&second, "2101-01-01T00:00:00Z", &sk).unwrap();
+        assert_eq!(second.prev_record_hash, Some(record_hash(&first).unwrap()));
+        let result = verify_renewal_chain(&[first, second, third], &pk);
+        assert!(result.verified, "{:?}", result.errors);
+    }
+
+    #[test]
+    fn test_renewal_expiry_must_be_future() {
+        let (_, sk) = generate_keypair();
+        let first = rpr(&sk);
+        let past = renew_human_binding(&first, "2020-01-01T00:00:00Z", &sk).unwrap_err();
+        assert!(past.contains("is not after issued_at"), "{}", past);
+        let garbage = renew_human_binding(&first, "soon", &sk).unwrap_err();
+        assert_eq!(garbage, "new_expiry \"soon\" is unparseable");
+    }
+
+    #[test]
+    fn test_broken_link_rejected() {
+        let (pk, sk) = generate_keypair();
+        let first = rpr(&sk);
+        let mut second = renew_human_binding(&first, "2100-01-01T00:00:00Z", &sk).unwrap();
+        second.prev_record_hash = Some("sha256:00".into());
+        second.signature = sign_record(&second, &sk).unwrap();
+        assert!(!verify_renewal_chain(&[first, second], &pk).verified);
+    }
+
+    #[test]
+    fn test_gap_after_expiry_rejected() {
+        let (pk, sk) = generate_keypair();
+        let mut first = rpr(&sk);
+        first.expires_at = Some("2026-02-01T00:00:00Z".into());
+        first.signature = sign_record(&first, &sk).unwrap();
+        let second = renew_human_binding(&first, "2100-01-01T00:00:00Z", &sk).unwrap();
+        let result = verify_renewal_chain(&[first, second], &pk);
+        assert!(result.errors.unwrap().iter().any(|e| e.contains("after predecessor expired")));
+    }
+
+    #[test]
+    fn test_millisecond_timestamps_compare_as_instants() {
+        let (pk, sk) = generate_keypair();
+        let mut first = rpr(&sk);
+        first.expires_at = Some("2026-02-01T00:00:00.000Z".into());
+        first.signature = sign_record(&first, &sk).unwrap();
+        let mut second = renew_human_binding(&first, "2100-01-01T00:00:00Z", &sk).unwrap();
+        // Lexicographically after the expiry above, but the same instant.
+        second.issued_at = "2026-02-01T00:00:00Z".into();
+        second.signature = sign_record(&second, &sk).unwrap();
+        let result = verify_renewal_chain(&[first.clone(), second.clone()], &pk);
+        assert!(result.verified, "{:?}", result.errors);
+
+        second.issued_at = "2026-02-01T00:00:01.000Z".into();
+        second.signature = sign_record(&second, &sk).unwrap();
+        let result = verify_renewal_chain(&[first.clone(), second.clone()], &pk);
+        assert!(result.errors.unwrap().iter().any(|e| e.contains("after predecessor expired")));
+
+        second.issued_at = "next tuesday".into();
+        second.signature = sign_record(&second, &sk).unwrap();
+        let result = verify_renewal_chain(&[first, second], &pk);
+        assert!(result.errors.unwrap().iter().any(|e| e.contains("is unparseable")));
+    }
+
+    fn org(id: &str, sk: &str) -> ResponsiblePrincipalRecord {
+        let mut r = rpr(sk);
+        r.human_id = id.into();
+        r.entity_type = "organization".into();
+        r.signature = sign_record(&r, sk).unwrap();
+        r
+    }
+
+    #[test]
+    fn test_nested_issuance_chain() {
+        let (root_pk, root_sk) = generate_keypair();
+        let (div_pk, div_sk) = generate_keypair();
+        let (alice_pk, alice_sk) = generate_keypair();
+        let (agent_pk, _) = generate_keypair();
+        let root = org("did:org:acme", &root_sk);
+        let division = issue_subordinate_record(org("did:org:acme-eu", &div_sk), &root, &root_sk).unwrap();
+        let alice = issue_subordinate_record(rpr(&div_sk), &division, &div_sk).unwrap();
+        let mut passport = AgentPassport {
+            dcp_version: "1.0".into(),
+            agent_id: "did:agent:a".into(),
+            public_key: agent_pk.parse::<PublicKeyB64>().unwrap(),
+            principal_binding_reference: alice.human_id.clone(),
+            capabilities: None,
+            risk_tier: None,
+            created_at: "2026-01-01T00:00:00Z".into(),
+            status: "active".into(),
+            attestation: None,
+            upgrade: None,
+            signature: SignatureB64::default(),
+        };
+        passport.signature = sign_record(&passport, &alice_sk).unwrap();
+        let keys: HashMap<String, String> = [
+            ("did:org:acme".to_string(), root_pk),
+            ("did:org:acme-eu".to_string(), div_pk),
+            ("did:human:alice".to_string(), alice_pk),
+        ]
+        .into();
+
+        let chain = vec![alice.clone(), division.clone(), root.clone()];
+        let result = verify_issuance_chain(&passport, &chain, &keys);
+        assert!(result.verified, "{:?}", result.errors);
+
+        let result = verify_issuance_chain(&passport, &[alice.clone(), root], &keys);
+        assert!(result.errors.unwrap().iter().any(|e| e.contains("no record for did:org:acme-eu")));
+
+        assert!(issue_subordinate_record(rpr(&div_sk), &alice, &div_sk).is_err());
+
+        // A passport naming alice but signed by anyone else is not hers.
+        let (_, mallory_sk) = generate_keypair();
+        let mut forged = passport.clone();
+        forged.agent_id = "did:agent:mallory".into();
+        forged.signature = sign_record(&forged, &mallory_sk).unwrap();
+        let result = verify_issuance_chain(&forged, &chain, &keys);
+        assert_eq!(
+            result.errors.unwrap(),
+            ["ISSUANCE INVALID: passport did:agent:mallory: signature is not from principal did:human:alice"]
+        );
+    }
+}
@@ -0,0 +1,27 @@
+//! Agent key management.
+//!
+//! [`validate_rollover_plan`] rehearses a key rotation: it replays
+//! in-flight and archived bundles under the trust configuration before,
+//! during, and after the rotation and reports what would stop verifying,
+//! before anything is changed.
+//!
+//! With feature = "keyring", CLI and desktop agents keep their secret key
+//! in the macOS Keychain, Windows Credential Manager, or the Secret
+//! Service on Linux instead of a file on disk. Entries live under service
+//! [`SERVICE`] with the caller's label as the account name.
+//!
+//! ```ignore
+//! let (pk, sk) = generate_keypair();
+//! keys::store_in_os_keyring("agent-001", &sk)?;
+//! let sk = keys::load_from_os_keyring("agent-001")?;
+//! ```
+
+mod rollover;
+
+pub use rollover::{validate_rollover_plan, BundleRollover, RolloverPhase, RolloverReport};
+
+#[cfg(feature = "keyring")]
+mod os_keyring;
+
+#[cfg(feature = "keyring")]
+pub use os_keyring::{delete_from_os_keyring, load_from_os_keyring, store_in_os_keyring, KeyringError, SERVICE};
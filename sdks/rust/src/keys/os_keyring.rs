@@ -0,0 +1,68 @@
+//! Ed25519 secrets in the OS credential store (feature = "keyring").
+
+use zeroize::Zeroizing;
+
+use crate::crypto::public_key_from_secret;
+
+/// Service name of every entry this module writes.
+pub const SERVICE: &str = "dcp-ai";
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum KeyringError {
+    #[error("No key stored under {0:?}")]
+    NotFound(String),
+    #[error("Invalid secret key: {0}")]
+    InvalidKey(String),
+    #[error("OS keyring: {0}")]
+    Backend(String),
+}
+
+impl From<keyring::Error> for KeyringError {
+    fn from(e: keyring::Error) -> Self {
+        KeyringError::Backend(e.to_string())
+    }
+}
+
+fn entry(label: &str) -> Result<keyring::Entry, KeyringError> {
+    Ok(keyring::Entry::new(SERVICE, label)?)
+}
+
+/// Store a base64 Ed25519 secret key under `label`, replacing any key
+/// already there. Returns its public key.
+pub fn store_in_os_keyring(label: &str, secret_key_b64: &str) -> Result<String, KeyringError> {
+    let public_key_b64 = public_key_from_secret(secret_key_b64).map_err(KeyringError::InvalidKey)?;
+    entry(label)?.set_password(secret_key_b64)?;
+    Ok(public_key_b64)
+}
+
+/// The base64 secret key stored under `label`. A stored value that is
+/// not a valid Ed25519 secret fails with [`KeyringError::InvalidKey`].
+pub fn load_from_os_keyring(label: &str) -> Result<Zeroizing<String>, KeyringError> {
+    let secret = match entry(label)?.get_password() {
+        Ok(secret) => Zeroizing::new(secret),
+        Err(keyring::Error::NoEntry) => return Err(KeyringError::NotFound(label.to_string())),
+        Err(e) => return Err(e.into()),
+    };
+    public_key_from_secret(&secret).map_err(KeyringError::InvalidKey)?;
+    Ok(secret)
+}
+
+/// Remove the key stored under `label`.
+pub fn delete_from_os_keyring(label: &str) -> Result<(), KeyringError> {
+    match entry(label)?.delete_credential() {
+        Ok(()) => Ok(()),
+        Err(keyring::Error::NoEntry) => Err(KeyringError::NotFound(label.to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_secret_rejected_before_storing() {
+        assert!(matches!(store_in_os_keyring("dcp-ai-test", "not a key"), Err(KeyringError::InvalidKey(_))));
+    }
+}
@@ -0,0 +1,202 @@
+//! Key rollover rehearsal.
+
+use serde_json::Value;
+
+use crate::b64;
+use crate::providers::ed25519::Ed25519Provider;
+use crate::types::VerificationResult;
+use crate::v2::advisory::{now_epoch_secs, parse_iso_epoch_secs};
+use crate::v2::crypto_provider::derive_kid;
+use crate::v2::proof_of_possession::{verify_key_rotation, KeyRotationRecord};
+use crate::verify::{verify_signed_bundle_with_options, VerifyOptions};
+
+/// The keys a verifier trusts at one point of a rotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RolloverPhase {
+    /// Only the old key, as today.
+    Before,
+    /// Both keys, while bundles signed with the old key drain.
+    Transition,
+    /// Only the new key: the old key is retired.
+    After,
+}
+
+impl RolloverPhase {
+    pub const ALL: [RolloverPhase; 3] = [RolloverPhase::Before, RolloverPhase::Transition, RolloverPhase::After];
+
+    fn trusted(&self, old_key: &str, new_key: &str) -> Vec<String> {
+        match self {
+            RolloverPhase::Before => vec![old_key.to_string()],
+            RolloverPhase::Transition => vec![old_key.to_string(), new_key.to_string()],
+            RolloverPhase::After => vec![new_key.to_string()],
+        }
+    }
+}
+
+/// How one bundle fares through the rotation.
+#[derive(Debug, Clone)]
+pub struct BundleRollover {
+    /// Position in `pending_bundles`.
+    pub index: usize,
+    /// Verification in each phase, in [`RolloverPhase::ALL`] order.
+    pub results: [VerificationResult; 3],
+    /// The bundle's passport binds the old key, so the passport has to be
+    /// re-issued before bundles signed with the new key bind to it.
+    pub passport_binds_old_key: bool,
+}
+
+impl BundleRollover {
+    pub fn result(&self, phase: RolloverPhase) -> &VerificationResult {
+        &self.results[phase as usize]
+    }
+
+    /// The first phase in which the bundle stops verifying, if it
+    /// verifies today.
+    pub fn breaks_at(&self) -> Option<RolloverPhase> {
+        if !self.result(RolloverPhase::Before).verified {
+            return None;
+        }
+        RolloverPhase::ALL.into_iter().find(|&phase| !self.result(phase).verified)
+    }
+}
+
+/// Result of [`validate_rollover_plan`].
+#[derive(Debug, Clone)]
+pub struct RolloverReport {
+    /// What is wrong with the rotation record for these keys; empty if
+    /// it is valid.
+    pub record_errors: Vec<String>,
+    pub bundles: Vec<BundleRollover>,
+}
+
+impl RolloverReport {
+    /// The record is valid and nothing that verifies today stops
+    /// verifying while both keys are trusted.
+    pub fn transition_is_safe(&self) -> bool {
+        self.record_errors.is_empty() && self.breaking(RolloverPhase::Transition).next().is_none()
+    }
+
+    /// Bundles that verify today but not under `phase`'s trust.
+    pub fn breaking(&self, phase: RolloverPhase) -> impl Iterator<Item = &BundleRollover> {
+        self.bundles.iter().filter(move |b| b.result(RolloverPhase::Before).verified && !b.result(phase).verified)
+    }
+}
+
+/// Rehearse rotating from `old_key` to `new_key` (base64 Ed25519): check
+/// `rotation_record` authorizes exactly this rotation, then verify each of
+/// `pending_bundles` trusting the old key, both keys, and the new key
+/// alone. Nothing is changed.
+pub fn validate_rollover_plan(
+    old_key: &str,
+    new_key: &str,
+    rotation_record: &KeyRotationRecord,
+    pending_bundles: &[Value],
+) -> RolloverReport {
+    let bundles = pending_bundles
+        .iter()
+        .enumerate()
+        .map(|(index, signed_bundle)| {
+            let results = RolloverPhase::ALL.map(|phase| {
+                let opts = VerifyOptions { candidate_keys: phase.trusted(old_key, new_key), ..Default::default() };
+                verify_signed_bundle_with_options(signed_bundle, None, &opts)
+            });
+            let passport_key = signed_bundle.pointer("/bundle/agent_passport/public_key").and_then(Value::as_str);
+            BundleRollover { index, results, passport_binds_old_key: passport_key == Some(old_key) }
+        })
+        .collect();
+    RolloverReport { record_errors: check_record(old_key, new_key, rotation_record), bundles }
+}
+
+fn check_record(old_key: &str, new_key: &str, record: &KeyRotationRecord) -> Vec<String> {
+    let mut errors = Vec::new();
+    for alg in [&record.new_key.alg, &record.proof_of_possession.alg, &record.authorization_sig.alg] {
+        if alg != "ed25519" {
+            errors.push(format!("unsupported rotation algorithm {:?}", alg));
+            return errors;
+        }
+    }
+    if record.new_key.public_key_b64 != new_key {
+        errors.push("rotation record names a different new key".into());
+    }
+    for (field, kid, key) in [("old_kid", &record.old_kid, old_key), ("new_kid", &record.new_kid, new_key)] {
+        match b64::decode_exact::<32>(key) {
+            Ok(bytes) if derive_kid("ed25519", &bytes) == *kid => {}
+            Ok(_) => errors.push(format!("{} {} does not identify the key", field, kid)),
+            Err(e) => errors.push(format!("{}: invalid key: {}", field, e)),
+        }
+    }
+    match verify_key_rotation(&Ed25519Provider, &Ed25519Provider, record, old_key, new_key) {
+        Ok((_, pop_valid, auth_valid)) => {
+            if !pop_valid {
+                errors.push("proof of possession of the new key does not verify".into());
+            }
+            if !auth_valid {
+                errors.push("authorization by the old key does not verify".into());
+            }
+        }
+        Err(e) => errors.push(format!("rotation signatures: {}", e)),
+    }
+    if record.new_key.status != "active" {
+        errors.push(format!("new key status is {:?}, not \"active\"", record.new_key.status));
+    }
+    if let Some(expires_at) = &record.new_key.expires_at {
+        if parse_iso_epoch_secs(expires_at).is_none_or(|t| t <= now_epoch_secs()) {
+            errors.push(format!("new key expires_at {} is past or unparseable", expires_at));
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{canonicalize, hash_canonical, sign_canonical};
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+    use crate::v2::crypto_provider::CryptoProvider;
+    use crate::v2::proof_of_possession::create_key_rotation;
+
+    fn signed_with(public_key_b64: &str, secret_key_b64: &str) -> Value {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let mut signed = set.get("citizenship_bundle.signed").unwrap().value.clone();
+        let canon = canonicalize(&signed["bundle"]);
+        signed["signature"]["sig_b64"] = sign_canonical(&canon, secret_key_b64).unwrap().into();
+        signed["signature"]["bundle_hash"] = format!("sha256:{}", hash_canonical(&canon)).into();
+        signed["signature"]["signer"]["public_key_b64"] = public_key_b64.into();
+        signed
+    }
+
+    #[test]
+    fn test_rehearsal_reports_bundles_lost_after_retirement() {
+        let provider = Ed25519Provider;
+        let old = provider.generate_keypair().unwrap();
+        let new = provider.generate_keypair().unwrap();
+        let record = create_key_rotation(
+            &provider,
+            &provider,
+            &old.kid,
+            &old.secret_key_b64,
+            &new.kid,
+            &new.secret_key_b64,
+            &new.public_key_b64,
+            "ed25519",
+            "2026-06-01T00:00:00Z",
+            None,
+        )
+        .unwrap();
+        let bundles = [
+            signed_with(&old.public_key_b64, &old.secret_key_b64),
+            signed_with(&new.public_key_b64, &new.secret_key_b64),
+        ];
+
+        let report = validate_rollover_plan(&old.public_key_b64, &new.public_key_b64, &record, &bundles);
+        assert!(report.transition_is_safe(), "{:?}", report.record_errors);
+        assert_eq!(report.bundles[0].breaks_at(), Some(RolloverPhase::After));
+        // Not verifiable today, so not broken by the rotation.
+        assert_eq!(report.bundles[1].breaks_at(), None);
+        assert!(report.bundles[1].result(RolloverPhase::After).verified);
+        assert_eq!(report.breaking(RolloverPhase::After).count(), 1);
+
+        let swapped = validate_rollover_plan(&new.public_key_b64, &old.public_key_b64, &record, &bundles);
+        assert!(!swapped.transition_is_safe());
+    }
+}
@@ -0,0 +1,233 @@
+//! Passphrase-sealed key storage (feature = "keystore").
+//!
+//! Secret keys are encrypted with AES-256-GCM under a key derived from a
+//! passphrase with Argon2id. The sealed record is plain JSON so it can be
+//! persisted anywhere; the `wasm-keystore` feature adds IndexedDB helpers
+//! for browser agents on top of it (see `wasm::wasm_store_keypair`).
+//!
+//! The public key, `alg`, and KDF parameters are bound as AEAD associated
+//! data, so a sealed secret cannot be silently re-labelled with another
+//! public key or algorithm. Stored KDF parameters above
+//! [`KdfParams::MAX`] are refused before any work is done, so a tampered
+//! record cannot make opening it exhaust memory or CPU. Callers with less
+//! memory than that pass their own cap to [`open_sealed_key_with_max`];
+//! the IndexedDB helpers use [`KdfParams::WASM_MAX`].
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+/// Version tag written into every [`SealedKey`].
+pub const KEYSTORE_VERSION: &str = "dcp-keystore-v1";
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum KeystoreError {
+    #[error("Unsupported keystore record: {0}")]
+    Unsupported(String),
+    #[error("Key derivation failed: {0}")]
+    Kdf(String),
+    #[error("Wrong passphrase or corrupted record")]
+    Decrypt,
+    #[error("Encoding error: {0}")]
+    Encoding(String),
+}
+
+/// Argon2id cost parameters. Defaults follow the OWASP baseline
+/// (19 MiB, 2 iterations, 1 lane).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub m_cost_kib: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self { m_cost_kib: 19_456, t_cost: 2, p_cost: 1 }
+    }
+}
+
+impl KdfParams {
+    /// Highest costs accepted when sealing or opening: 1 GiB, 10
+    /// iterations, 16 lanes.
+    pub const MAX: KdfParams = KdfParams { m_cost_kib: 1 << 20, t_cost: 10, p_cost: 16 };
+
+    /// [`Self::MAX`] with memory capped at 256 MiB, for browser agents
+    /// whose linear memory cannot hold a 1 GiB Argon2 block table.
+    pub const WASM_MAX: KdfParams = KdfParams { m_cost_kib: 1 << 18, ..Self::MAX };
+
+    fn check(&self, max: &KdfParams) -> Result<(), KeystoreError> {
+        if self.m_cost_kib > max.m_cost_kib || self.t_cost > max.t_cost || self.p_cost > max.p_cost {
+            return Err(KeystoreError::Unsupported(format!("KDF parameters above limits: {:?}", self)));
+        }
+        Ok(())
+    }
+}
+
+/// Encrypted keypair record.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SealedKey {
+    pub version: String,
+    pub alg: String,
+    pub public_key_b64: String,
+    pub kdf: String,
+    pub kdf_params: KdfParams,
+    pub salt_b64: String,
+    pub nonce_b64: String,
+    pub ciphertext_b64: String,
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], params: &KdfParams) -> Result<Zeroizing<[u8; 32]>, KeystoreError> {
+    let p = Params::new(params.m_cost_kib, params.t_cost, params.p_cost, Some(32))
+        .map_err(|e| KeystoreError::Kdf(e.to_string()))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, p)
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+        .map_err(|e| KeystoreError::Kdf(e.to_string()))?;
+    Ok(key)
+}
+
+/// AEAD associated data: every record field that is not secret and not
+/// already an input to decryption.
+fn associated_data(public_key_b64: &str, alg: &str, kdf: &str, params: &KdfParams) -> Result<Vec<u8>, KeystoreError> {
+    serde_json::to_vec(&(public_key_b64, alg, kdf, params)).map_err(|e| KeystoreError::Encoding(e.to_string()))
+}
+
+/// Seal a base64 secret key under `passphrase`.
+pub fn seal_secret_key(
+    alg: &str,
+    public_key_b64: &str,
+    secret_key_b64: &str,
+    passphrase: &str,
+    params: KdfParams,
+) -> Result<SealedKey, KeystoreError> {
+    params.check(&KdfParams::MAX)?;
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 12];
+    let mut rng = rand::thread_rng();
+    rng.fill_bytes(&mut salt);
+    rng.fill_bytes(&mut nonce);
+
+    let key = derive_key(passphrase, &salt, &params)?;
+    let aad = associated_data(public_key_b64, alg, "argon2id", &params)?;
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key.as_ref()));
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg: secret_key_b64.as_bytes(), aad: &aad })
+        .map_err(|e| KeystoreError::Encoding(e.to_string()))?;
+
+    Ok(SealedKey {
+        version: KEYSTORE_VERSION.to_string(),
+        alg: alg.to_string(),
+        public_key_b64: public_key_b64.to_string(),
+        kdf: "argon2id".to_string(),
+        kdf_params: params,
+        salt_b64: BASE64.encode(salt),
+        nonce_b64: BASE64.encode(nonce),
+        ciphertext_b64: BASE64.encode(ciphertext),
+    })
+}
+
+/// Open a [`SealedKey`], returning the base64 secret key.
+pub fn open_sealed_key(sealed: &SealedKey, passphrase: &str) -> Result<Zeroizing<String>, KeystoreError> {
+    open_sealed_key_with_max(sealed, passphrase, KdfParams::MAX)
+}
+
+/// [`open_sealed_key`], refusing stored KDF parameters above `max`
+/// instead of [`KdfParams::MAX`].
+pub fn open_sealed_key_with_max(
+    sealed: &SealedKey,
+    passphrase: &str,
+    max: KdfParams,
+) -> Result<Zeroizing<String>, KeystoreError> {
+    if sealed.version != KEYSTORE_VERSION || sealed.kdf != "argon2id" {
+        return Err(KeystoreError::Unsupported(format!("{} / {}", sealed.version, sealed.kdf)));
+    }
+    sealed.kdf_params.check(&max)?;
+    let decode = |s: &str| BASE64.decode(s).map_err(|e| KeystoreError::Encoding(e.to_string()));
+    let salt = decode(&sealed.salt_b64)?;
+    let nonce = decode(&sealed.nonce_b64)?;
+    let ciphertext = decode(&sealed.ciphertext_b64)?;
+    if nonce.len() != 12 {
+        return Err(KeystoreError::Encoding("nonce must be 12 bytes".into()));
+    }
+
+    let key = derive_key(passphrase, &salt, &sealed.kdf_params)?;
+    let aad = associated_data(&sealed.public_key_b64, &sealed.alg, &sealed.kdf, &sealed.kdf_params)?;
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key.as_ref()));
+    let plaintext = Zeroizing::new(
+        cipher
+            .decrypt(Nonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: &aad })
+            .map_err(|_| KeystoreError::Decrypt)?,
+    );
+    let secret = std::str::from_utf8(&plaintext).map_err(|e| KeystoreError::Encoding(e.to_string()))?;
+    Ok(Zeroizing::new(secret.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAST: KdfParams = KdfParams { m_cost_kib: 64, t_cost: 1, p_cost: 1 };
+
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let (pk, sk) = crate::crypto::generate_keypair();
+        let sealed = seal_secret_key("ed25519", &pk, &sk, "correct horse", FAST).unwrap();
+        assert_eq!(sealed.version, KEYSTORE_VERSION);
+        let opened = open_sealed_key(&sealed, "correct horse").unwrap();
+        assert_eq!(opened.as_str(), sk);
+    }
+
+    #[test]
+    fn test_wrong_passphrase_rejected() {
+        let (pk, sk) = crate::crypto::generate_keypair();
+        let sealed = seal_secret_key("ed25519", &pk, &sk, "right", FAST).unwrap();
+        assert!(matches!(open_sealed_key(&sealed, "wrong"), Err(KeystoreError::Decrypt)));
+    }
+
+    #[test]
+    fn test_relabelled_public_key_rejected() {
+        let (pk, sk) = crate::crypto::generate_keypair();
+        let (other_pk, _) = crate::crypto::generate_keypair();
+        let mut sealed = seal_secret_key("ed25519", &pk, &sk, "pw", FAST).unwrap();
+        sealed.public_key_b64 = other_pk;
+        assert!(matches!(open_sealed_key(&sealed, "pw"), Err(KeystoreError::Decrypt)));
+    }
+
+    #[test]
+    fn test_relabelled_alg_and_oversized_kdf_params_rejected() {
+        let (pk, sk) = crate::crypto::generate_keypair();
+        let sealed = seal_secret_key("ed25519", &pk, &sk, "pw", FAST).unwrap();
+        let mut relabelled = sealed.clone();
+        relabelled.alg = "ml-dsa-65".into();
+        assert!(matches!(open_sealed_key(&relabelled, "pw"), Err(KeystoreError::Decrypt)));
+
+        let mut costly = sealed.clone();
+        costly.kdf_params.m_cost_kib = u32::MAX;
+        assert!(matches!(open_sealed_key(&costly, "pw"), Err(KeystoreError::Unsupported(_))));
+        costly.kdf_params = KdfParams { t_cost: 11, ..FAST };
+        assert!(matches!(open_sealed_key(&costly, "pw"), Err(KeystoreError::Unsupported(_))));
+        let sealed = seal_secret_key("ed25519", &pk, &sk, "pw", KdfParams { p_cost: 17, ..FAST });
+        assert!(matches!(sealed, Err(KeystoreError::Unsupported(_))));
+    }
+
+    #[test]
+    fn test_caller_kdf_cap_applies_when_opening() {
+        let (pk, sk) = crate::crypto::generate_keypair();
+        let sealed = seal_secret_key("ed25519", &pk, &sk, "pw", FAST).unwrap();
+        assert_eq!(open_sealed_key_with_max(&sealed, "pw", FAST).unwrap().as_str(), sk);
+        let low = KdfParams { m_cost_kib: 32, ..FAST };
+        assert!(matches!(open_sealed_key_with_max(&sealed, "pw", low), Err(KeystoreError::Unsupported(_))));
+
+        let mut costly = sealed;
+        costly.kdf_params.m_cost_kib = KdfParams::WASM_MAX.m_cost_kib + 1;
+        let opened = open_sealed_key_with_max(&costly, "pw", KdfParams::WASM_MAX);
+        assert!(matches!(opened, Err(KeystoreError::Unsupported(_))));
+    }
+}
@@ -0,0 +1,378 @@
+//! Append-only record of accepted bundles.
+//!
+//! A [`Ledger`] is a JSON Lines file a verifier appends to each time it
+//! accepts a bundle: the bundle hash, the signer, and the time. Every entry
+//! carries the hash of the one before it, so rewriting history breaks the
+//! chain. Every `checkpoint_every` entries (and on demand) the verifier
+//! signs the current head, fixing everything before it.
+//!
+//! [`Ledger::prove_accepted`] extracts an [`AcceptanceProof`]: the entry,
+//! the chain from it to a signed checkpoint, and that checkpoint. Anyone
+//! with the verifier's public key can check it with
+//! [`AcceptanceProof::verify`] without the rest of the file.
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use zeroize::Zeroizing;
+
+use crate::crypto::{hash_object, public_key_from_secret, sign_object, verify_object};
+use crate::types::{VerificationFailure, VerificationResult, VerifyError};
+use crate::v2::lifecycle::utc_now_iso_pub as utc_now_iso;
+
+/// `purpose` of every signed checkpoint payload.
+pub const CHECKPOINT_PURPOSE: &str = "DCP-AI.v1.LedgerCheckpoint";
+
+/// `prev_hash` of the first entry.
+pub const LEDGER_GENESIS: &str = "GENESIS";
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum LedgerError {
+    #[error("ledger I/O: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("ledger encoding: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("ledger line {line}: {reason}")]
+    Corrupt { line: usize, reason: String },
+    #[error("signed bundle has no {0}")]
+    MissingField(&'static str),
+    #[error("signing failed: {0}")]
+    Sign(String),
+}
+
+/// One accepted bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    /// 1-based position in the ledger.
+    pub seq: u64,
+    pub bundle_hash: String,
+    pub signer_id: String,
+    pub signer_public_key_b64: String,
+    pub accepted_at: String,
+    /// [`LedgerEntry::hash`] of the previous entry, or [`LEDGER_GENESIS`].
+    pub prev_hash: String,
+}
+
+impl LedgerEntry {
+    /// `sha256:<hex>` of the canonical entry; the next entry's `prev_hash`.
+    pub fn hash(&self) -> String {
+        format!("sha256:{}", hash_object(&serde_json::to_value(self).unwrap_or(Value::Null)))
+    }
+}
+
+/// The verifier's signature over the chain head after entry `seq`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerCheckpoint {
+    pub seq: u64,
+    pub head_hash: String,
+    pub created_at: String,
+    pub verifier_public_key_b64: String,
+    pub sig_b64: String,
+}
+
+impl LedgerCheckpoint {
+    /// The signed payload.
+    pub fn payload(&self) -> Value {
+        json!({
+            "purpose": CHECKPOINT_PURPOSE,
+            "seq": self.seq,
+            "head_hash": self.head_hash,
+            "created_at": self.created_at,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Line {
+    Entry(LedgerEntry),
+    Checkpoint(LedgerCheckpoint),
+}
+
+/// Everything needed to show a bundle was accepted, and when.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AcceptanceProof {
+    /// The entry for the bundle, followed by every later entry up to the
+    /// checkpoint.
+    pub entries: Vec<LedgerEntry>,
+    pub checkpoint: LedgerCheckpoint,
+}
+
+impl AcceptanceProof {
+    pub fn entry(&self) -> &LedgerEntry {
+        &self.entries[0]
+    }
+
+    /// Check the chain from the entry to the checkpoint and the
+    /// checkpoint's signature under `verifier_public_key_b64`.
+    pub fn verify(&self, verifier_public_key_b64: &str) -> VerificationResult {
+        let failure = |kind, detail: String| VerificationFailure::new(kind, detail, "");
+        let mut failures = Vec::new();
+        let Some(first) = self.entries.first() else {
+            return VerificationResult::fail(VerifyError::missing("ledger entry"), "");
+        };
+        for pair in self.entries.windows(2) {
+            if pair[1].prev_hash != pair[0].hash() || pair[1].seq != pair[0].seq + 1 {
+                failures.push(failure(
+                    VerifyError::LedgerChainBroken,
+                    format!("entry {} does not follow entry {}", pair[1].seq, pair[0].seq),
+                ));
+            }
+        }
+        let last = &self.entries[self.entries.len() - 1];
+        if last.seq != self.checkpoint.seq || last.hash() != self.checkpoint.head_hash {
+            failures.push(failure(
+                VerifyError::LedgerChainBroken,
+                format!("checkpoint at {} does not cover entry {}", self.checkpoint.seq, first.seq),
+            ));
+        }
+        if self.checkpoint.verifier_public_key_b64 != verifier_public_key_b64
+            || !matches!(
+                verify_object(&self.checkpoint.payload(), &self.checkpoint.sig_b64, verifier_public_key_b64),
+                Ok(true)
+            )
+        {
+            failures.push(failure(VerifyError::SignatureInvalid, format!("ledger checkpoint {}", self.checkpoint.seq)));
+        }
+        if failures.is_empty() {
+            VerificationResult::ok()
+        } else {
+            VerificationResult::fail_at(failures)
+        }
+    }
+}
+
+/// An open ledger file.
+pub struct Ledger {
+    path: PathBuf,
+    file: File,
+    secret_key_b64: Zeroizing<String>,
+    public_key_b64: String,
+    checkpoint_every: u64,
+    entries: Vec<LedgerEntry>,
+    checkpoints: Vec<LedgerCheckpoint>,
+    by_bundle_hash: HashMap<String, usize>,
+}
+
+impl std::fmt::Debug for Ledger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ledger")
+            .field("path", &self.path)
+            .field("entries", &self.entries.len())
+            .field("checkpoints", &self.checkpoints.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Ledger {
+    /// Open or create the ledger at `path`, signing checkpoints with
+    /// `verifier_secret_key_b64` every `checkpoint_every` entries (0
+    /// disables automatic checkpoints). An existing file is replayed and
+    /// its chain checked; a broken chain is [`LedgerError::Corrupt`].
+    pub fn open(
+        path: impl AsRef<Path>,
+        verifier_secret_key_b64: &str,
+        checkpoint_every: u64,
+    ) -> Result<Self, LedgerError> {
+        let path = path.as_ref().to_path_buf();
+        let public_key_b64 = public_key_from_secret(verifier_secret_key_b64).map_err(LedgerError::Sign)?;
+        let mut ledger = Self {
+            file: OpenOptions::new().create(true).append(true).read(true).open(&path)?,
+            path,
+            secret_key_b64: Zeroizing::new(verifier_secret_key_b64.to_string()),
+            public_key_b64,
+            checkpoint_every,
+            entries: Vec::new(),
+            checkpoints: Vec::new(),
+            by_bundle_hash: HashMap::new(),
+        };
+        ledger.replay()?;
+        Ok(ledger)
+    }
+
+    fn replay(&mut self) -> Result<(), LedgerError> {
+        let reader = BufReader::new(File::open(&self.path)?);
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let corrupt = |reason: String| LedgerError::Corrupt { line: i + 1, reason };
+            match serde_json::from_str::<Line>(&line).map_err(|e| corrupt(e.to_string()))? {
+                Line::Entry(entry) => {
+                    if entry.seq != self.next_seq() || entry.prev_hash != self.head_hash() {
+                        return Err(corrupt(format!("entry {} does not extend the chain", entry.seq)));
+                    }
+                    self.index(entry);
+                }
+                Line::Checkpoint(cp) => {
+                    if cp.seq != self.entries.len() as u64 || cp.head_hash != self.head_hash() {
+                        return Err(corrupt(format!("checkpoint {} does not match the chain head", cp.seq)));
+                    }
+                    self.checkpoints.push(cp);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn next_seq(&self) -> u64 {
+        self.entries.len() as u64 + 1
+    }
+
+    fn head_hash(&self) -> String {
+        self.entries.last().map(LedgerEntry::hash).unwrap_or_else(|| LEDGER_GENESIS.to_string())
+    }
+
+    fn index(&mut self, entry: LedgerEntry) {
+        self.by_bundle_hash.entry(entry.bundle_hash.clone()).or_insert(self.entries.len());
+        self.entries.push(entry);
+    }
+
+    fn append(&mut self, line: &Line) -> Result<(), LedgerError> {
+        let mut text = serde_json::to_string(line)?;
+        text.push('\n');
+        self.file.write_all(text.as_bytes())?;
+        self.file.sync_data()?;
+        Ok(())
+    }
+
+    /// Record an accepted signed bundle. Call it only after verification
+    /// passed.
+    pub fn record(&mut self, signed_bundle: &Value) -> Result<LedgerEntry, LedgerError> {
+        let signature = &signed_bundle["signature"];
+        let field = |name: &'static str, v: &Value| v.as_str().map(str::to_string).ok_or(LedgerError::MissingField(name));
+        let entry = LedgerEntry {
+            seq: self.next_seq(),
+            bundle_hash: field("signature.bundle_hash", &signature["bundle_hash"])?,
+            signer_id: field("signature.signer.id", &signature["signer"]["id"])?,
+            signer_public_key_b64: field("signature.signer.public_key_b64", &signature["signer"]["public_key_b64"])?,
+            accepted_at: utc_now_iso(),
+            prev_hash: self.head_hash(),
+        };
+        self.append(&Line::Entry(entry.clone()))?;
+        self.index(entry.clone());
+        if self.checkpoint_every > 0 && entry.seq.is_multiple_of(self.checkpoint_every) {
+            self.checkpoint()?;
+        }
+        Ok(entry)
+    }
+
+    /// Sign the current head now. Returns the existing checkpoint if the
+    /// head is already signed.
+    pub fn checkpoint(&mut self) -> Result<LedgerCheckpoint, LedgerError> {
+        let seq = self.entries.len() as u64;
+        if let Some(cp) = self.checkpoints.last().filter(|cp| cp.seq == seq) {
+            return Ok(cp.clone());
+        }
+        let mut cp = LedgerCheckpoint {
+            seq,
+            head_hash: self.head_hash(),
+            created_at: utc_now_iso(),
+            verifier_public_key_b64: self.public_key_b64.clone(),
+            sig_b64: String::new(),
+        };
+        cp.sig_b64 = sign_object(&cp.payload(), &self.secret_key_b64).map_err(LedgerError::Sign)?;
+        self.append(&Line::Checkpoint(cp.clone()))?;
+        self.checkpoints.push(cp.clone());
+        Ok(cp)
+    }
+
+    /// Prove `bundle_hash` was accepted, checkpointing first if no
+    /// checkpoint covers its entry yet. `None` if it was never recorded.
+    pub fn prove_accepted(&mut self, bundle_hash: &str) -> Result<Option<AcceptanceProof>, LedgerError> {
+        let Some(&at) = self.by_bundle_hash.get(bundle_hash) else {
+            return Ok(None);
+        };
+        let seq = self.entries[at].seq;
+        let checkpoint = match self.checkpoints.iter().find(|cp| cp.seq >= seq) {
+            Some(cp) => cp.clone(),
+            None => self.checkpoint()?,
+        };
+        Ok(Some(AcceptanceProof {
+            entries: self.entries[at..checkpoint.seq as usize].to_vec(),
+            checkpoint,
+        }))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn public_key_b64(&self) -> &str {
+        &self.public_key_b64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let nanos = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos();
+        std::env::temp_dir().join(format!("dcp-ledger-{}-{}-{}.jsonl", name, std::process::id(), nanos))
+    }
+
+    fn bundle(n: u32) -> Value {
+        json!({
+            "signature": {
+                "bundle_hash": format!("sha256:{:064x}", n),
+                "signer": { "id": "did:human:alice", "public_key_b64": "pk" },
+            }
+        })
+    }
+
+    #[test]
+    fn test_proof_survives_reopen_and_verifies() {
+        let path = temp_path("proof");
+        let (pk, sk) = generate_keypair();
+        {
+            let mut ledger = Ledger::open(&path, &sk, 2).unwrap();
+            for n in 1..=3 {
+                ledger.record(&bundle(n)).unwrap();
+            }
+        }
+        let mut ledger = Ledger::open(&path, &sk, 2).unwrap();
+        assert_eq!(ledger.len(), 3);
+
+        let proof = ledger.prove_accepted(&format!("sha256:{:064x}", 1)).unwrap().unwrap();
+        assert_eq!(proof.checkpoint.seq, 2);
+        assert!(proof.verify(&pk).verified);
+
+        // Entry 3 had no checkpoint yet; proving it signs one.
+        let proof = ledger.prove_accepted(&format!("sha256:{:064x}", 3)).unwrap().unwrap();
+        assert_eq!(proof.checkpoint.seq, 3);
+        assert!(proof.verify(&pk).verified);
+
+        let mut forged = proof.clone();
+        forged.entries[0].accepted_at = "2020-01-01T00:00:00Z".into();
+        assert!(!forged.verify(&pk).verified);
+        assert!(ledger.prove_accepted("sha256:unknown").unwrap().is_none());
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_rewritten_history_is_rejected_on_open() {
+        let path = temp_path("tamper");
+        let (_, sk) = generate_keypair();
+        {
+            let mut ledger = Ledger::open(&path, &sk, 0).unwrap();
+            ledger.record(&bundle(1)).unwrap();
+            ledger.record(&bundle(2)).unwrap();
+        }
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, text.replacen(&format!("{:064x}", 1), &format!("{:064x}", 9), 1)).unwrap();
+        assert!(matches!(Ledger::open(&path, &sk, 0), Err(LedgerError::Corrupt { line: 2, .. })));
+        std::fs::remove_file(&path).ok();
+    }
+}
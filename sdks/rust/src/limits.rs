@@ -0,0 +1,182 @@
+//! Compile-time input limits for memory-constrained verifiers.
+//!
+//! RTOS and embedded deployments size their heaps up front, so a bundle
+//! has to be rejected before verification if it could not fit. A
+//! [`Limits`] profile fixes the bounds as const generics; checking a
+//! bundle against it walks the parsed value once, before any hashing.
+//!
+//! ```
+//! use dcp_ai::limits::{EmbeddedLimits, Limits};
+//!
+//! type Tiny = Limits<2, 64, 8>;
+//! let sb = serde_json::json!({"bundle": {"audit_entries": [1, 2, 3]}});
+//! assert!(Tiny::check(&sb).is_err());
+//! assert_eq!(EmbeddedLimits::MAX_ENTRIES, 256);
+//! ```
+//!
+//! The crate itself still links `std`; these limits bound the input, so
+//! a `no_std` port can size its buffers from the same constants.
+
+use serde_json::Value;
+
+use crate::types::{VerificationFailure, VerificationResult, VerifyError};
+use crate::verify::{verify_signed_bundle_with_options, VerifyOptions};
+
+/// Bounds a bundle must satisfy: at most `MAX_ENTRIES` elements in any
+/// array (audit entries included), at most `MAX_STRING` bytes in any
+/// string or object key, and at most `MAX_DEPTH` levels of nesting.
+/// All three must be non-zero; a zero limit fails to compile on first use.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Limits<const MAX_ENTRIES: usize, const MAX_STRING: usize, const MAX_DEPTH: usize>;
+
+/// A profile for verifiers with a few hundred KiB of heap.
+pub type EmbeddedLimits = Limits<256, 4096, 16>;
+
+/// The same bounds chosen at run time, e.g. by a
+/// [`crate::verify::Preset`]. Unlike [`Limits`], zero is not rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputLimits {
+    pub max_entries: usize,
+    pub max_string: usize,
+    pub max_depth: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum LimitExceeded {
+    #[error("{pointer} has {got} elements, limit {max}")]
+    TooManyEntries { pointer: String, max: usize, got: usize },
+    #[error("{pointer} has a {got}-byte string, limit {max}")]
+    StringTooLong { pointer: String, max: usize, got: usize },
+    #[error("{pointer} is nested deeper than {max} levels")]
+    TooDeep { pointer: String, max: usize },
+}
+
+impl LimitExceeded {
+    /// A failed result (`LIMIT EXCEEDED: ...`) pointing at the offending value.
+    pub fn into_result(self) -> VerificationResult {
+        VerificationResult::fail_at(vec![VerificationFailure::new(
+            VerifyError::LimitExceeded,
+            self.to_string(),
+            self.pointer().to_string(),
+        )])
+    }
+
+    /// JSON pointer of the offending value.
+    pub fn pointer(&self) -> &str {
+        match self {
+            LimitExceeded::TooManyEntries { pointer, .. }
+            | LimitExceeded::StringTooLong { pointer, .. }
+            | LimitExceeded::TooDeep { pointer, .. } => pointer,
+        }
+    }
+}
+
+impl<const MAX_ENTRIES: usize, const MAX_STRING: usize, const MAX_DEPTH: usize>
+    Limits<MAX_ENTRIES, MAX_STRING, MAX_DEPTH>
+{
+    pub const MAX_ENTRIES: usize = MAX_ENTRIES;
+    pub const MAX_STRING: usize = MAX_STRING;
+    pub const MAX_DEPTH: usize = MAX_DEPTH;
+
+    const NON_ZERO: () = assert!(MAX_ENTRIES > 0 && MAX_STRING > 0 && MAX_DEPTH > 0, "limits must be non-zero");
+
+    /// First limit `signed_bundle` exceeds, in document order.
+    pub fn check(signed_bundle: &Value) -> Result<(), LimitExceeded> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::NON_ZERO;
+        InputLimits::from_profile::<MAX_ENTRIES, MAX_STRING, MAX_DEPTH>().check(signed_bundle)
+    }
+
+    /// [`Self::check`], then full verification under `opts`. A bundle
+    /// over the limits fails with `LIMIT EXCEEDED` without being verified.
+    pub fn verify(signed_bundle: &Value, public_key_b64: Option<&str>, opts: &VerifyOptions) -> VerificationResult {
+        match Self::check(signed_bundle) {
+            Ok(()) => verify_signed_bundle_with_options(signed_bundle, public_key_b64, opts),
+            Err(e) => e.into_result(),
+        }
+    }
+}
+
+impl InputLimits {
+    /// The bounds of a [`Limits`] profile.
+    pub const fn from_profile<const MAX_ENTRIES: usize, const MAX_STRING: usize, const MAX_DEPTH: usize>() -> Self {
+        Self { max_entries: MAX_ENTRIES, max_string: MAX_STRING, max_depth: MAX_DEPTH }
+    }
+
+    /// First limit `signed_bundle` exceeds, in document order.
+    pub fn check(&self, signed_bundle: &Value) -> Result<(), LimitExceeded> {
+        let mut pointer = String::new();
+        self.walk(signed_bundle, 0, &mut pointer)
+    }
+
+    fn walk(&self, value: &Value, depth: usize, pointer: &mut String) -> Result<(), LimitExceeded> {
+        if depth > self.max_depth {
+            return Err(LimitExceeded::TooDeep { pointer: pointer.clone(), max: self.max_depth });
+        }
+        let count = match value {
+            Value::Array(items) => items.len(),
+            Value::Object(map) => map.len(),
+            _ => 0,
+        };
+        if count > self.max_entries {
+            return Err(LimitExceeded::TooManyEntries { pointer: pointer.clone(), max: self.max_entries, got: count });
+        }
+        let too_long = |pointer: &str, got: usize| LimitExceeded::StringTooLong {
+            pointer: pointer.to_string(),
+            max: self.max_string,
+            got,
+        };
+        let len = pointer.len();
+        match value {
+            Value::String(s) if s.len() > self.max_string => Err(too_long(pointer, s.len())),
+            Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    pointer.push('/');
+                    pointer.push_str(&i.to_string());
+                    self.walk(item, depth + 1, pointer)?;
+                    pointer.truncate(len);
+                }
+                Ok(())
+            }
+            Value::Object(map) => {
+                for (key, item) in map {
+                    pointer.push('/');
+                    pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                    if key.len() > self.max_string {
+                        return Err(too_long(pointer, key.len()));
+                    }
+                    self.walk(item, depth + 1, pointer)?;
+                    pointer.truncate(len);
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_reports_first_exceeded_limit_with_pointer() {
+        type L = Limits<3, 8, 4>;
+        assert_eq!(L::check(&json!({"a": [1, 2, 3], "b": "12345678"})), Ok(()));
+        assert_eq!(
+            L::check(&json!({"a": ["ok", "123456789"]})),
+            Err(LimitExceeded::StringTooLong { pointer: "/a/1".into(), max: 8, got: 9 })
+        );
+        assert_eq!(
+            L::check(&json!({"a": [1, 2, 3, 4]})),
+            Err(LimitExceeded::TooManyEntries { pointer: "/a".into(), max: 3, got: 4 })
+        );
+        assert!(matches!(L::check(&json!([[[[[1]]]]])), Err(LimitExceeded::TooDeep { .. })));
+
+        let sb = json!({"bundle": {"audit_entries": [1, 2, 3, 4]}});
+        let result = Limits::<3, 16, 4>::verify(&sb, None, &VerifyOptions::default());
+        assert!(result.errors.unwrap()[0].starts_with("LIMIT EXCEEDED: /bundle/audit_entries has 4 elements"));
+    }
+}
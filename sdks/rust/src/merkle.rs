@@ -0,0 +1,285 @@
+//! Merkle multiproofs over audit entries.
+//!
+//! The tree is the one behind `signature.merkle_root`: SHA-256 over
+//! hex-decoded child pairs, with odd layers balanced by duplicating the
+//! last node (see [`crate::crypto::merkle_root_from_hex_leaves`]).
+//!
+//! A [`MultiProof`] proves several leaves at once. It carries only the
+//! sibling hashes the verifier cannot derive from the proven leaves
+//! themselves, so proving every DENY entry in a bundle costs one proof,
+//! not one per entry.
+//!
+//! Leaves are in chain order unless `signature.leaf_order` declares a
+//! [`LeafOrder`], which makes the root independent of how the producer
+//! ordered entries for display. Multiproofs index leaves in that order.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::hash::{digest_pair, HashAlgorithm};
+use crate::retention::entry_hash;
+
+/// Order of the leaves under `merkle_root`, as declared in
+/// `signature.leaf_order`. Absent means [`LeafOrder::Chain`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum LeafOrder {
+    /// The order of `audit_entries`.
+    #[default]
+    Chain,
+    /// Ascending `audit_id`.
+    AuditId,
+    /// Ascending `timestamp` (ISO-8601 UTC, compared as strings).
+    Timestamp,
+}
+
+impl LeafOrder {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LeafOrder::Chain => "chain",
+            LeafOrder::AuditId => "audit_id",
+            LeafOrder::Timestamp => "timestamp",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "chain" => Some(LeafOrder::Chain),
+            "audit_id" => Some(LeafOrder::AuditId),
+            "timestamp" => Some(LeafOrder::Timestamp),
+            _ => None,
+        }
+    }
+
+    /// `entry_hashes` (one per entry of `entries`) rearranged into this
+    /// order. Entries without the sort key come first; ties keep chain
+    /// order.
+    pub fn arrange(&self, entries: &[Value], entry_hashes: &[String]) -> Vec<String> {
+        self.positions(entries, entry_hashes.len()).into_iter().map(|i| entry_hashes[i].clone()).collect()
+    }
+
+    /// Chain positions of the first `len` entries, in this order.
+    fn positions(&self, entries: &[Value], len: usize) -> Vec<usize> {
+        let mut positions: Vec<usize> = (0..len).collect();
+        let key = match self {
+            LeafOrder::Chain => return positions,
+            LeafOrder::AuditId => "audit_id",
+            LeafOrder::Timestamp => "timestamp",
+        };
+        positions.sort_by_key(|&i| entries.get(i).and_then(|e| e.get(key)).and_then(Value::as_str).unwrap_or(""));
+        positions
+    }
+}
+
+/// Proof that the leaves at `indices` belong to a tree of `leaf_count`
+/// leaves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiProof {
+    pub leaf_count: usize,
+    /// Proven leaf positions, ascending and unique.
+    pub indices: Vec<usize>,
+    /// Sibling hashes (hex), layer by layer from the leaves up, left to
+    /// right within a layer.
+    pub hashes: Vec<String>,
+}
+
+fn hash_pair(left: &[u8], right: &[u8]) -> Vec<u8> {
+    digest_pair(HashAlgorithm::Sha256, left, right)
+}
+
+/// Build a multiproof for `indices` over hex leaf hashes. Returns `None`
+/// if there are no leaves or indices, an index is out of range, or a leaf
+/// is not valid hex.
+pub fn multiproof(leaves: &[String], indices: &[usize]) -> Option<MultiProof> {
+    if leaves.is_empty() || indices.is_empty() || indices.iter().any(|&i| i >= leaves.len()) {
+        return None;
+    }
+    let mut layer: Vec<Vec<u8>> = leaves.iter().map(hex::decode).collect::<Result<_, _>>().ok()?;
+    let mut known: BTreeSet<usize> = indices.iter().copied().collect();
+    let proven: Vec<usize> = known.iter().copied().collect();
+    let mut hashes = Vec::new();
+
+    while layer.len() > 1 {
+        if layer.len() % 2 == 1 {
+            let last = layer.len() - 1;
+            layer.push(layer[last].clone());
+            if known.contains(&last) {
+                known.insert(last + 1);
+            }
+        }
+        for &i in &known {
+            if !known.contains(&(i ^ 1)) {
+                hashes.push(hex::encode(&layer[i ^ 1]));
+            }
+        }
+        known = known.iter().map(|i| i / 2).collect();
+        layer = layer.chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+    }
+
+    Some(MultiProof { leaf_count: leaves.len(), indices: proven, hashes })
+}
+
+/// Check that `leaf_hashes` (hex, one per `proof.indices` entry, same
+/// order) hash up to `root_hex` under `proof`. `root_hex` may carry a
+/// `sha256:` prefix.
+pub fn verify_multiproof(root_hex: &str, proof: &MultiProof, leaf_hashes: &[String]) -> bool {
+    let root_hex = root_hex.strip_prefix("sha256:").unwrap_or(root_hex);
+    if proof.indices.is_empty()
+        || proof.indices.len() != leaf_hashes.len()
+        || proof.indices.windows(2).any(|w| w[0] >= w[1])
+        || proof.indices.iter().any(|&i| i >= proof.leaf_count)
+    {
+        return false;
+    }
+    let mut known = BTreeMap::new();
+    for (&i, leaf) in proof.indices.iter().zip(leaf_hashes) {
+        match hex::decode(leaf) {
+            Ok(bytes) => known.insert(i, bytes),
+            Err(_) => return false,
+        };
+    }
+    let mut siblings = proof.hashes.iter();
+    let mut width = proof.leaf_count;
+
+    while width > 1 {
+        if width % 2 == 1 {
+            if let Some(last) = known.get(&(width - 1)).cloned() {
+                known.insert(width, last);
+            }
+            width = match width.checked_add(1) {
+                Some(w) => w,
+                None => return false,
+            };
+        }
+        let mut next = BTreeMap::new();
+        for (&i, node) in &known {
+            if i % 2 == 1 && known.contains_key(&(i - 1)) {
+                continue;
+            }
+            let sibling = match known.get(&(i ^ 1)) {
+                Some(s) => s.clone(),
+                None => match siblings.next().map(hex::decode) {
+                    Some(Ok(s)) => s,
+                    _ => return false,
+                },
+            };
+            let parent = if i % 2 == 0 { hash_pair(node, &sibling) } else { hash_pair(&sibling, node) };
+            next.insert(i / 2, parent);
+        }
+        known = next;
+        width /= 2;
+    }
+
+    siblings.next().is_none() && known.get(&0).map(hex::encode).as_deref() == Some(root_hex)
+}
+
+/// Multiproof for the audit entries of a bundle matching `select`, e.g.
+/// every entry whose `policy_decision` is a denial. Leaves are built as
+/// [`crate::bundle::sign_bundle`] builds them: each entry's
+/// [`entry_hash`], arranged in the `leaf_order` the bundle was signed
+/// with.
+pub fn multiproof_for_entries(
+    bundle: &Value,
+    leaf_order: LeafOrder,
+    select: impl Fn(&Value) -> bool,
+) -> Option<MultiProof> {
+    let entries = bundle.get("audit_entries")?.as_array()?;
+    let positions = leaf_order.positions(entries, entries.len());
+    let leaves: Vec<String> = positions.iter().map(|&i| entry_hash(&entries[i])).collect();
+    let indices: Vec<usize> =
+        positions.iter().enumerate().filter(|(_, &i)| select(&entries[i])).map(|(at, _)| at).collect();
+    multiproof(&leaves, &indices)
+}
+
+/// Verify disclosed audit entries (in `proof.indices` order) against a
+/// bundle's `merkle_root`.
+pub fn verify_entries_multiproof(merkle_root: &str, proof: &MultiProof, entries: &[Value]) -> bool {
+    let leaves: Vec<String> = entries.iter().map(entry_hash).collect();
+    verify_multiproof(merkle_root, proof, &leaves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::merkle_root_from_hex_leaves;
+
+    fn leaves(n: usize) -> Vec<String> {
+        (0..n).map(|i| crate::hash::digest_hex(HashAlgorithm::Sha256, i.to_string().as_bytes())).collect()
+    }
+
+    #[test]
+    fn test_every_subset_verifies() {
+        for n in 1..=9 {
+            let leaves = leaves(n);
+            let root = merkle_root_from_hex_leaves(&leaves).unwrap();
+            for mask in 1u32..(1 << n) {
+                let indices: Vec<usize> = (0..n).filter(|i| mask & (1 << i) != 0).collect();
+                let proof = multiproof(&leaves, &indices).unwrap();
+                let disclosed: Vec<String> = indices.iter().map(|&i| leaves[i].clone()).collect();
+                assert!(verify_multiproof(&root, &proof, &disclosed), "n={} indices={:?}", n, indices);
+            }
+        }
+    }
+
+    #[test]
+    fn test_multiproof_is_smaller_than_single_proofs() {
+        let leaves = leaves(16);
+        let proof = multiproof(&leaves, &[0, 1, 2, 3]).unwrap();
+        assert_eq!(proof.hashes.len(), 2);
+    }
+
+    #[test]
+    fn test_wrong_leaf_rejected() {
+        let leaves = leaves(7);
+        let root = merkle_root_from_hex_leaves(&leaves).unwrap();
+        let proof = multiproof(&leaves, &[2, 5]).unwrap();
+        assert!(!verify_multiproof(&root, &proof, &[leaves[2].clone(), leaves[4].clone()]));
+        assert!(!verify_multiproof(&root, &proof, &[leaves[2].clone()]));
+        assert!(multiproof(&leaves, &[7]).is_none());
+    }
+
+    #[test]
+    fn test_hostile_leaf_count_rejected() {
+        let leaves = leaves(1);
+        let proof = MultiProof { leaf_count: usize::MAX, indices: vec![usize::MAX - 1], hashes: Vec::new() };
+        assert!(!verify_multiproof(&leaves[0], &proof, &leaves));
+    }
+
+    #[test]
+    fn test_entry_multiproof_matches_signed_root() {
+        use std::time::Duration;
+
+        use crate::bundle::{sign_bundle, SignOptions};
+        use crate::crypto::generate_keypair;
+        use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+        use crate::retention::{apply_at, RetentionPolicy};
+        use crate::types::CitizenshipBundle;
+
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let mut bundle: CitizenshipBundle =
+            serde_json::from_value(set.get("citizenship_bundle").unwrap().value.clone()).unwrap();
+        // Reverse the audit_id order so it differs from chain order.
+        let n = bundle.audit_entries.len();
+        for (i, entry) in bundle.audit_entries.iter_mut().enumerate() {
+            entry.audit_id = format!("audit{:03}", n - i);
+        }
+        let (_, sk) = generate_keypair();
+        let mut sign = SignOptions::new(&sk);
+        sign.leaf_order = Some(LeafOrder::AuditId);
+        let signed = sign_bundle(bundle.clone(), &sign).unwrap();
+
+        // Tombstoned entries still prove against the signed root.
+        let policy = RetentionPolicy::new("gdpr-30d", Duration::ZERO);
+        apply_at(&mut bundle, &policy, 4_102_444_800).unwrap();
+        let bundle = serde_json::to_value(&bundle).unwrap();
+        let first = |e: &Value| e["audit_id"] == format!("audit{:03}", n);
+        let proof = multiproof_for_entries(&bundle, LeafOrder::AuditId, first).unwrap();
+        assert_eq!(proof.indices, [n - 1]);
+        let root = signed.signature.merkle_root.unwrap();
+        assert!(verify_entries_multiproof(&root, &proof, &[bundle["audit_entries"][0].clone()]));
+        assert!(!verify_entries_multiproof(&root, &proof, &[bundle["audit_entries"][1].clone()]));
+    }
+}
@@ -0,0 +1,230 @@
+//! Third-party notarization of signed bundles.
+//!
+//! A notary is an organization outside the agent's chain of custody that
+//! verifies a bundle and vouches for the result. [`Notary::notarize`]
+//! runs [`verify_with_report`] and issues a [`NotarizationRecord`]
+//! committing to the bundle hash, the report, and the time, signed with
+//! the notary's key. The record is attached next to `signature` under
+//! `notarizations`, outside the producer's signature, so any number of
+//! notaries can add theirs after the fact.
+//!
+//! Downstream, [`verify_notarizations`] re-checks every attached record
+//! against the bundle and the notary keys the verifier trusts.
+//!
+//! ```ignore
+//! let notary = Notary::new("did:web:notary.example", &notary_sk)?;
+//! let (record, report) = notary.notarize(&signed_bundle)?;
+//! attach(&mut signed_bundle, &record)?;
+//! assert!(verify_notarizations(&signed_bundle, &[notary_pk]).verified);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use zeroize::Zeroizing;
+
+use crate::crypto::{canonicalize, hash_object, public_key_from_secret};
+use crate::hash::{digest_hex, HashAlgorithm};
+use crate::issue::{record_hash, sign_record, verify_record_signature};
+use crate::signer::{SignerError, SignerIdentity};
+use crate::types::{VerificationFailure, VerificationReport, VerificationResult, VerifyError};
+use crate::v2::lifecycle::utc_now_iso_pub as utc_now_iso;
+use crate::verify::{verify_with_report, VerifyOptions};
+
+/// Top-level signed bundle field holding notarization records.
+pub const NOTARIZATIONS_FIELD: &str = "notarizations";
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum NotaryError {
+    #[error("invalid notary identity: {0}")]
+    Identity(#[from] SignerError),
+    #[error("invalid notary key: {0}")]
+    InvalidKey(String),
+    #[error("signed bundle has no bundle")]
+    NoBundle,
+    #[error("malformed notarizations: {0}")]
+    Malformed(String),
+    #[error("signing failed: {0}")]
+    Sign(String),
+}
+
+/// A notary's signed statement that it verified a bundle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotarizationRecord {
+    pub dcp_version: String,
+    pub notarization_id: String,
+    /// Id of the notary, valid for signer type `notary`.
+    pub notary_id: String,
+    pub notary_public_key_b64: String,
+    /// `sha256:<hex>` of the canonical bundle, as in `signature.bundle_hash`.
+    pub bundle_hash: String,
+    /// Whether the bundle passed the notary's verification.
+    pub verified: bool,
+    /// [`record_hash`] of the notary's [`VerificationReport`].
+    pub report_hash: String,
+    pub timestamp: String,
+    /// Ed25519 signature of the notary over the record without `signature`.
+    pub signature: String,
+}
+
+impl NotarizationRecord {
+    /// Whether `report` is the report this record commits to.
+    pub fn covers_report(&self, report: &VerificationReport) -> bool {
+        record_hash(report).is_ok_and(|h| h == self.report_hash)
+    }
+}
+
+/// A notary's identity and key, and the options it verifies with.
+pub struct Notary {
+    identity: SignerIdentity,
+    secret_key_b64: Zeroizing<String>,
+    opts: VerifyOptions,
+}
+
+impl std::fmt::Debug for Notary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Notary").field("identity", &self.identity).finish_non_exhaustive()
+    }
+}
+
+impl Notary {
+    pub fn new(notary_id: &str, secret_key_b64: &str) -> Result<Self, NotaryError> {
+        let public_key_b64 = public_key_from_secret(secret_key_b64).map_err(NotaryError::InvalidKey)?;
+        Ok(Self {
+            identity: SignerIdentity::new("notary", notary_id, &public_key_b64)?,
+            secret_key_b64: Zeroizing::new(secret_key_b64.to_string()),
+            opts: VerifyOptions::default(),
+        })
+    }
+
+    /// Verify with `opts` instead of the defaults.
+    pub fn with_options(mut self, opts: VerifyOptions) -> Self {
+        self.opts = opts;
+        self
+    }
+
+    pub fn identity(&self) -> &SignerIdentity {
+        &self.identity
+    }
+
+    /// Verify `signed_bundle` and sign a record of the outcome. A bundle
+    /// that fails verification is notarized too, with `verified: false`.
+    pub fn notarize(&self, signed_bundle: &Value) -> Result<(NotarizationRecord, VerificationReport), NotaryError> {
+        let bundle = signed_bundle.get("bundle").ok_or(NotaryError::NoBundle)?;
+        let report = verify_with_report(signed_bundle, None, &self.opts);
+        let mut record = NotarizationRecord {
+            dcp_version: "1.0".into(),
+            notarization_id: format!("notarization-{}", hex::encode(rand::random::<[u8; 8]>())),
+            notary_id: self.identity.id.clone(),
+            notary_public_key_b64: self.identity.public_key.as_str().to_string(),
+            bundle_hash: format!("sha256:{}", hash_object(bundle)),
+            verified: report.verified(),
+            report_hash: record_hash(&report).map_err(NotaryError::Sign)?,
+            timestamp: utc_now_iso(),
+            signature: String::new(),
+        };
+        record.signature = sign_record(&record, &self.secret_key_b64).map_err(NotaryError::Sign)?.into();
+        Ok((record, report))
+    }
+}
+
+/// Append `record` to the signed bundle's `notarizations`.
+pub fn attach(signed_bundle: &mut Value, record: &NotarizationRecord) -> Result<(), NotaryError> {
+    let record = serde_json::to_value(record).map_err(|e| NotaryError::Malformed(e.to_string()))?;
+    let obj = signed_bundle.as_object_mut().ok_or(NotaryError::NoBundle)?;
+    match obj.entry(NOTARIZATIONS_FIELD).or_insert_with(|| Value::Array(Vec::new())) {
+        Value::Array(records) => records.push(record),
+        _ => return Err(NotaryError::Malformed(format!("{} is not an array", NOTARIZATIONS_FIELD))),
+    }
+    Ok(())
+}
+
+/// The records attached to `signed_bundle`; empty if there are none.
+pub fn attached(signed_bundle: &Value) -> Result<Vec<NotarizationRecord>, NotaryError> {
+    match signed_bundle.get(NOTARIZATIONS_FIELD) {
+        None => Ok(Vec::new()),
+        Some(v) => serde_json::from_value(v.clone()).map_err(|e| NotaryError::Malformed(e.to_string())),
+    }
+}
+
+/// Check one record against `signed_bundle`: signed by a notary key in
+/// `trusted_notary_keys`, a valid notary id, a `bundle_hash` matching the
+/// bundle, and a positive verdict.
+pub fn verify_notarization<K: AsRef<str>>(
+    signed_bundle: &Value,
+    record: &NotarizationRecord,
+    trusted_notary_keys: &[K],
+) -> VerificationResult {
+    let id = &record.notarization_id;
+    let mut errors = Vec::new();
+    match SignerIdentity::new("notary", &record.notary_id, &record.notary_public_key_b64) {
+        Ok(notary) if notary.key_in(trusted_notary_keys) => {}
+        Ok(_) => errors.push(format!("notarization {}: notary {} is not trusted", id, record.notary_id)),
+        Err(e) => errors.push(format!("notarization {}: {}", id, e)),
+    }
+    if !verify_record_signature(record, &record.notary_public_key_b64) {
+        errors.push(format!("notarization {}: signature invalid", id));
+    }
+    let bundle_hash = HashAlgorithm::split_prefixed(&record.bundle_hash).zip(signed_bundle.get("bundle"));
+    match bundle_hash {
+        Some(((alg, got), bundle)) if digest_hex(alg, canonicalize(bundle).as_bytes()) == got => {}
+        _ => errors.push(format!("notarization {}: bundle_hash does not match the bundle", id)),
+    }
+    if !record.verified {
+        errors.push(format!("notarization {}: notary {} found the bundle invalid", id, record.notary_id));
+    }
+    if errors.is_empty() {
+        VerificationResult::ok()
+    } else {
+        VerificationResult::fail_at(
+            errors.into_iter().map(|e| VerificationFailure::new(VerifyError::NotarizationInvalid, e, "")).collect(),
+        )
+    }
+}
+
+/// Check every record attached to `signed_bundle` with
+/// [`verify_notarization`]. Fails if there are none.
+pub fn verify_notarizations<K: AsRef<str>>(signed_bundle: &Value, trusted_notary_keys: &[K]) -> VerificationResult {
+    let records = match attached(signed_bundle) {
+        Ok(records) if records.is_empty() => return VerificationResult::fail(VerifyError::missing("notarizations"), ""),
+        Ok(records) => records,
+        Err(e) => return VerificationResult::fail(VerifyError::NotarizationInvalid, e.to_string()),
+    };
+    let failures: Vec<VerificationFailure> = records
+        .iter()
+        .flat_map(|r| verify_notarization(signed_bundle, r, trusted_notary_keys).failures.unwrap_or_default())
+        .collect();
+    if failures.is_empty() {
+        VerificationResult::ok()
+    } else {
+        VerificationResult::fail_at(failures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+    use crate::verify::verify_signed_bundle;
+
+    #[test]
+    fn test_notarization_round_trip() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let mut signed = set.get("citizenship_bundle.signed").unwrap().value.clone();
+        let (pk, sk) = generate_keypair();
+        let notary = Notary::new("did:web:notary.example", &sk).unwrap();
+
+        let (record, report) = notary.notarize(&signed).unwrap();
+        assert!(record.verified && record.covers_report(&report));
+        attach(&mut signed, &record).unwrap();
+        assert!(verify_signed_bundle(&signed, None).verified);
+        assert!(verify_notarizations(&signed, &[&pk]).verified);
+        assert!(!verify_notarizations(&signed, &[generate_keypair().0]).verified);
+
+        signed["bundle"]["intent"]["action_type"] = "send_payment".into();
+        let errors = verify_notarizations(&signed, &[&pk]).errors.unwrap();
+        assert!(errors[0].contains("bundle_hash"), "{:?}", errors);
+        assert!(Notary::new("notary one", &sk).is_err());
+    }
+}
@@ -0,0 +1,428 @@
+//! Webhook alerts for failed verifications (feature = "notify").
+//!
+//! A [`WebhookNotifier`] attached to a [`crate::verify::Verifier`] POSTs a
+//! JSON alert to every configured target when a verification fails at or
+//! above the notifier's severity threshold. Each request carries
+//!
+//! - `X-DCP-Timestamp`: unix seconds at send time
+//! - `X-DCP-Signature`: `sha256=<hex HMAC-SHA256(secret, timestamp + "." + body)>`
+//!
+//! so receivers can authenticate the alert and reject replays. Delivery
+//! failures never affect the verification result.
+//!
+//! Background delivery runs on one worker thread per notifier, fed by a
+//! bounded queue. When the queue is full further alerts are dropped and
+//! counted ([`WebhookNotifier::dropped`]) rather than blocking the
+//! verifier or spawning more threads.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::Sha256;
+
+use crate::types::{Impact, VerificationResult, VerifyError};
+use crate::v2::lifecycle::utc_now_iso_pub as utc_now_iso;
+
+/// Alert severity, ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    /// Severity of one verification failure.
+    ///
+    /// Integrity failures (signature, bundle hash, merkle root) are
+    /// critical; broken chains and rejected algorithms are high; missing
+    /// fields and unreachable agreements are medium; malformed or
+    /// oversized input is low.
+    pub fn of_error(error: &VerifyError) -> Self {
+        use VerifyError::*;
+        match error {
+            SignatureInvalid | Revoked | KeyNotBound | BundleHashMismatch | MerkleRootMismatch => Severity::Critical,
+            SignerInvalid
+            | KeyNotAttested
+            | SignatureTooOld
+            | BindingExpired
+            | TimestampOrder
+            | IntentHashMismatch { .. }
+            | ChainBreak { .. }
+            | SequenceOutOfOrder { .. }
+            | IntentNotYetValid { .. }
+            | IntentExpired { .. }
+            | DuplicateAuditId { .. }
+            | IntentIdMismatch { .. }
+            | AmendmentInvalid { .. }
+            | WeakAlgorithm
+            | UnsupportedCanonicalization
+            | UnsupportedLeafOrder
+            | UnsupportedSignatureAlg
+            | UnsupportedHashAlg
+            | ReferenceMismatch
+            | DecisionNotAudited
+            | TargetNotHashed
+            | PolicySnapshotUnapproved
+            | UnauditedAction
+            | ConsentMissing
+            | AgreementMismatch
+            | ChallengeMismatch
+            | ChallengeExpired
+            | HandshakeMismatch
+            | HandshakeExpired
+            | CapabilityMissing
+            | UpgradeInvalid
+            | RenewalInvalid
+            | IssuanceInvalid
+            | OverrideInvalid
+            | HandoffInvalid
+            | NotarizationInvalid
+            | CeremonyInvalid
+            | LedgerChainBroken
+            | AttestationMissing
+            | AttestationInvalid
+            | Internal => Severity::High,
+            Missing { .. } | AgreementUnavailable => Severity::Medium,
+            LimitExceeded | ArtifactUnparseable | DecompressionFailed | InvalidValue { .. } | NotVerified => {
+                Severity::Low
+            }
+        }
+    }
+
+    /// Highest severity among a result's errors, or `None` if it verified.
+    pub fn of(result: &VerificationResult) -> Option<Self> {
+        if result.verified {
+            return None;
+        }
+        let max = result.error_kinds.iter().flatten().map(Severity::of_error).max();
+        Some(max.unwrap_or(Severity::Low))
+    }
+}
+
+/// One webhook destination.
+#[derive(Debug, Clone)]
+pub struct WebhookTarget {
+    pub url: String,
+    /// Shared HMAC secret for this target.
+    pub secret: Vec<u8>,
+}
+
+impl WebhookTarget {
+    pub fn new(url: impl Into<String>, secret: impl Into<Vec<u8>>) -> Self {
+        Self { url: url.into(), secret: secret.into() }
+    }
+}
+
+/// JSON body POSTed to each target.
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct FailureAlert {
+    pub event: &'static str,
+    pub severity: Severity,
+    pub occurred_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer_id: Option<String>,
+    /// The intent's `estimated_impact`, rendered for humans.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_impact: Option<String>,
+    pub errors: Vec<String>,
+}
+
+impl FailureAlert {
+    fn new(signed_bundle: &Value, result: &VerificationResult, severity: Severity) -> Self {
+        let sig = signed_bundle.get("signature");
+        let field = |name: &str| sig.and_then(|s| s.get(name)).and_then(Value::as_str).map(str::to_string);
+        Self {
+            event: "dcp.verification_failed",
+            severity,
+            occurred_at: utc_now_iso(),
+            bundle_hash: field("bundle_hash"),
+            signer_id: sig
+                .and_then(|s| s.get("signer"))
+                .and_then(|s| s.get("id"))
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            estimated_impact: signed_bundle
+                .pointer("/bundle/intent/estimated_impact")
+                .and_then(|v| serde_json::from_value::<Impact>(v.clone()).ok())
+                .map(|i| i.to_string()),
+            errors: result.errors.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// Delivers signed alert bodies. The default [`HttpTransport`] POSTs over
+/// HTTP(S); substitute your own to route through an existing client.
+pub trait WebhookTransport: Send + Sync {
+    fn post(&self, url: &str, headers: &[(&str, String)], body: &str) -> Result<(), String>;
+}
+
+/// Blocking HTTP(S) transport.
+#[derive(Debug, Clone)]
+pub struct HttpTransport {
+    agent: ureq::Agent,
+}
+
+impl HttpTransport {
+    pub fn new(timeout: Duration) -> Self {
+        Self { agent: ureq::AgentBuilder::new().timeout(timeout).build() }
+    }
+}
+
+impl Default for HttpTransport {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(5))
+    }
+}
+
+impl WebhookTransport for HttpTransport {
+    fn post(&self, url: &str, headers: &[(&str, String)], body: &str) -> Result<(), String> {
+        let mut req = self.agent.post(url).set("Content-Type", "application/json");
+        for (name, value) in headers {
+            req = req.set(name, value);
+        }
+        req.send_string(body).map(|_| ()).map_err(|e| e.to_string())
+    }
+}
+
+/// `sha256=<hex>` signature header value for `body` sent at `timestamp`.
+pub fn sign_payload(secret: &[u8], timestamp: u64, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Alerts queued for the background worker before new ones are dropped.
+pub const DEFAULT_QUEUE_CAPACITY: usize = 64;
+
+/// Fans failure alerts out to webhook targets. Clones share one
+/// background worker and queue.
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    targets: Vec<WebhookTarget>,
+    threshold: Severity,
+    transport: Arc<dyn WebhookTransport>,
+    background: bool,
+    queue_capacity: usize,
+    worker: Arc<OnceLock<SyncSender<String>>>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl WebhookNotifier {
+    /// Notifier alerting on failures at or above `threshold`, delivered in
+    /// the background over [`HttpTransport`].
+    pub fn new(threshold: Severity) -> Self {
+        Self {
+            targets: Vec::new(),
+            threshold,
+            transport: Arc::new(HttpTransport::default()),
+            background: true,
+            queue_capacity: DEFAULT_QUEUE_CAPACITY,
+            worker: Arc::new(OnceLock::new()),
+            dropped: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn target(mut self, target: WebhookTarget) -> Self {
+        self.targets.push(target);
+        self.worker = Arc::new(OnceLock::new());
+        self
+    }
+
+    pub fn transport(mut self, transport: impl WebhookTransport + 'static) -> Self {
+        self.transport = Arc::new(transport);
+        self.worker = Arc::new(OnceLock::new());
+        self
+    }
+
+    /// Alerts the background worker may have pending before new ones are
+    /// dropped. Defaults to [`DEFAULT_QUEUE_CAPACITY`].
+    pub fn queue_capacity(mut self, capacity: usize) -> Self {
+        self.queue_capacity = capacity;
+        self.worker = Arc::new(OnceLock::new());
+        self
+    }
+
+    /// Deliver on the calling thread instead of a background thread.
+    pub fn blocking(mut self) -> Self {
+        self.background = false;
+        self
+    }
+
+    pub fn threshold(&self) -> Severity {
+        self.threshold
+    }
+
+    /// Alerts dropped because the background queue was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Alert on `result` if it failed at or above the threshold. Returns
+    /// the alert that was sent, if any. In background mode the alert is
+    /// queued for the worker, and delivery errors and alerts that do not
+    /// fit in the queue are dropped; in blocking mode the first delivery
+    /// error is returned.
+    pub fn notify(&self, signed_bundle: &Value, result: &VerificationResult) -> Result<Option<FailureAlert>, String> {
+        let severity = match Severity::of(result) {
+            Some(s) if s >= self.threshold => s,
+            _ => return Ok(None),
+        };
+        let alert = FailureAlert::new(signed_bundle, result, severity);
+        let body = serde_json::to_string(&alert).map_err(|e| e.to_string())?;
+
+        if self.background {
+            if self.sender().try_send(body).is_err() {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        } else {
+            for target in &self.targets {
+                deliver(self.transport.as_ref(), target, &body)?;
+            }
+        }
+        Ok(Some(alert))
+    }
+
+    /// The background worker's queue, starting the worker on first use. The
+    /// worker exits once every clone sharing it has been dropped.
+    fn sender(&self) -> &SyncSender<String> {
+        self.worker.get_or_init(|| {
+            let (tx, rx) = mpsc::sync_channel::<String>(self.queue_capacity);
+            let targets = self.targets.clone();
+            let transport = self.transport.clone();
+            std::thread::Builder::new()
+                .name("dcp-webhook".into())
+                .spawn(move || {
+                    for body in rx {
+                        for target in &targets {
+                            let _ = deliver(transport.as_ref(), target, &body);
+                        }
+                    }
+                })
+                .expect("spawn webhook worker");
+            tx
+        })
+    }
+}
+
+fn deliver(transport: &dyn WebhookTransport, target: &WebhookTarget, body: &str) -> Result<(), String> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let headers = [
+        ("X-DCP-Timestamp", timestamp.to_string()),
+        ("X-DCP-Signature", sign_payload(&target.secret, timestamp, body)),
+    ];
+    transport.post(&target.url, &headers, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    type Sent = (String, Vec<(String, String)>, String);
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<Sent>>);
+
+    impl WebhookTransport for Arc<Recorder> {
+        fn post(&self, url: &str, headers: &[(&str, String)], body: &str) -> Result<(), String> {
+            let headers = headers.iter().map(|(k, v)| (k.to_string(), v.clone())).collect();
+            self.0.lock().unwrap().push((url.to_string(), headers, body.to_string()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_severity_of_error() {
+        assert_eq!(Severity::of_error(&VerifyError::SignatureInvalid), Severity::Critical);
+        assert_eq!(Severity::of_error(&VerifyError::WeakAlgorithm), Severity::High);
+        assert_eq!(Severity::of_error(&VerifyError::UnsupportedLeafOrder), Severity::High);
+        assert_eq!(Severity::of_error(&VerifyError::missing("bundle")), Severity::Medium);
+        assert_eq!(Severity::of(&VerificationResult::ok()), None);
+    }
+
+    #[test]
+    fn test_threshold_and_signature() {
+        let rec = Arc::new(Recorder::default());
+        let notifier = WebhookNotifier::new(Severity::High)
+            .target(WebhookTarget::new("https://hooks.example/dcp", b"s3cret".to_vec()))
+            .transport(rec.clone())
+            .blocking();
+
+        let minor = VerificationResult::fail(VerifyError::missing("bundle"), "");
+        assert!(notifier.notify(&Value::Null, &minor).unwrap().is_none());
+
+        let major = VerificationResult::fail(VerifyError::SignatureInvalid, "");
+        let alert = notifier.notify(&Value::Null, &major).unwrap().unwrap();
+        assert_eq!(alert.severity, Severity::Critical);
+
+        let sent = rec.0.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        let (url, headers, body) = &sent[0];
+        assert_eq!(url, "https://hooks.example/dcp");
+        let ts: u64 = headers[0].1.parse().unwrap();
+        assert_eq!(headers[1].1, sign_payload(b"s3cret", ts, body));
+    }
+
+    /// Reports each post as it starts, then blocks it until released.
+    struct Gate {
+        entered: Mutex<mpsc::Sender<()>>,
+        release: Mutex<mpsc::Receiver<()>>,
+        sent: Arc<Recorder>,
+    }
+
+    impl WebhookTransport for Arc<Gate> {
+        fn post(&self, url: &str, headers: &[(&str, String)], body: &str) -> Result<(), String> {
+            self.entered.lock().unwrap().send(()).unwrap();
+            self.release.lock().unwrap().recv().unwrap();
+            self.sent.post(url, headers, body)
+        }
+    }
+
+    #[test]
+    fn test_background_queue_is_bounded() {
+        let (entered_tx, entered) = mpsc::channel();
+        let (release, release_rx) = mpsc::channel();
+        let rec = Arc::new(Recorder::default());
+        let gate = Gate { entered: Mutex::new(entered_tx), release: Mutex::new(release_rx), sent: rec.clone() };
+        let notifier = WebhookNotifier::new(Severity::Low)
+            .target(WebhookTarget::new("https://hooks.example/dcp", b"k".to_vec()))
+            .transport(Arc::new(gate))
+            .queue_capacity(1);
+
+        let failed = VerificationResult::fail(VerifyError::SignatureInvalid, "");
+        notifier.notify(&Value::Null, &failed).unwrap();
+        entered.recv().unwrap();
+        // The worker is busy with the first alert: one more fits in the
+        // queue and the rest are dropped instead of spawning threads.
+        for _ in 0..4 {
+            notifier.notify(&Value::Null, &failed).unwrap();
+        }
+        assert_eq!(notifier.dropped(), 3);
+
+        release.send(()).unwrap();
+        entered.recv().unwrap();
+        release.send(()).unwrap();
+        drop(notifier);
+        for _ in 0..100 {
+            if rec.0.lock().unwrap().len() == 2 {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(rec.0.lock().unwrap().len(), 2);
+    }
+}
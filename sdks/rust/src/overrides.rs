@@ -0,0 +1,159 @@
+//! Signed human override records.
+//!
+//! `override_rights: true` on the Responsible Principal Record says the
+//! human may override the agent. An [`OverrideRecord`] is the artifact of
+//! one such override: the human's signature over the intent, the action
+//! taken, and the reason. It enters the audit chain as an ordinary audit
+//! entry with `policy_decision = "human_override"` whose
+//! `evidence.result_ref` is the record's `dcp:record:sha256:` URI, so the
+//! chain commits to it without changing the entry schema. Entries written
+//! before URIs carry the bare `sha256:` hash and still verify.
+
+use serde::{Deserialize, Serialize};
+
+use crate::bundle::AuditEntryFields;
+use crate::issue::{record_hash, sign_record, verify_record_signature};
+use crate::types::{
+    AuditEvidence, CitizenshipBundle, SignatureB64, VerificationFailure, VerificationResult, VerifyError,
+};
+use crate::uri::DcpUri;
+
+/// `policy_decision` of audit entries that record an override.
+pub const OVERRIDE_POLICY_DECISION: &str = "human_override";
+
+/// `evidence.tool` of audit entries that record an override.
+pub const OVERRIDE_TOOL: &str = "dcp_override";
+
+/// A human's signed override of an agent intent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OverrideRecord {
+    pub dcp_version: String,
+    pub override_id: String,
+    pub intent_id: String,
+    pub human_id: String,
+    pub agent_id: String,
+    /// What the human did, e.g. `halt`, `deny`, `approve`, `modify`.
+    pub action_taken: String,
+    pub reason: String,
+    pub timestamp: String,
+    /// Scheme of `signature`, as on the Responsible Principal Record.
+    /// Absent means Ed25519.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_alg: Option<String>,
+    /// Signature of the human over the record without `signature`.
+    pub signature: SignatureB64,
+}
+
+pub struct OverrideParams<'a> {
+    pub override_id: &'a str,
+    pub intent_id: &'a str,
+    pub human_id: &'a str,
+    pub agent_id: &'a str,
+    pub action_taken: &'a str,
+    pub reason: &'a str,
+    pub timestamp: &'a str,
+}
+
+/// Create an override record signed with the human's key.
+pub fn create_override(params: OverrideParams<'_>, human_secret_key_b64: &str) -> Result<OverrideRecord, String> {
+    let mut record = OverrideRecord {
+        dcp_version: "1.0".into(),
+        override_id: params.override_id.into(),
+        intent_id: params.intent_id.into(),
+        human_id: params.human_id.into(),
+        agent_id: params.agent_id.into(),
+        action_taken: params.action_taken.into(),
+        reason: params.reason.into(),
+        timestamp: params.timestamp.into(),
+        signature_alg: None,
+        signature: SignatureB64::default(),
+    };
+    record.signature = sign_record(&record, human_secret_key_b64)?;
+    Ok(record)
+}
+
+impl OverrideRecord {
+    /// Audit entry fields recording this override in the chain.
+    pub fn audit_fields(&self, audit_id: &str) -> Result<AuditEntryFields, String> {
+        Ok(AuditEntryFields {
+            audit_id: audit_id.to_string(),
+            timestamp: self.timestamp.clone(),
+            agent_id: self.agent_id.clone(),
+            human_id: self.human_id.clone(),
+            policy_decision: OVERRIDE_POLICY_DECISION.to_string(),
+            outcome: format!("override:{}", self.action_taken),
+            evidence: AuditEvidence {
+                tool: Some(OVERRIDE_TOOL.to_string()),
+                result_ref: Some(DcpUri::record(&record_hash(self)?).map_err(|e| e.to_string())?.to_string()),
+                tombstone: None,
+            },
+        })
+    }
+}
+
+/// Check overrides against a bundle.
+///
+/// Requires `override_rights` on the RPR; `human_public_key_b64` must be
+/// the key that signed the RPR; every record must be signed by that key
+/// and name the bound human and the bundle's agent; and every
+/// `human_override` audit entry must reference one of `overrides` by hash
+/// and agree with it on human, agent, and intent.
+pub fn verify_overrides(
+    bundle: &CitizenshipBundle,
+    overrides: &[OverrideRecord],
+    human_public_key_b64: &str,
+) -> VerificationResult {
+    let rpr = &bundle.responsible_principal_record;
+    let mut errors = Vec::new();
+    if !verify_record_signature(rpr, human_public_key_b64) {
+        errors.push(format!("override key did not sign the record of {}", rpr.human_id));
+    }
+
+    let mut hashes = Vec::with_capacity(overrides.len());
+    for o in overrides {
+        if !verify_record_signature(o, human_public_key_b64) {
+            errors.push(format!("override {}: signature is not from the bound human's key", o.override_id));
+        }
+        if o.human_id != rpr.human_id {
+            errors.push(format!("override {}: human_id {} is not the bound human {}", o.override_id, o.human_id, rpr.human_id));
+        }
+        if o.agent_id != bundle.agent_passport.agent_id {
+            errors.push(format!("override {}: agent_id {} is not the bundle's agent", o.override_id, o.agent_id));
+        }
+        hashes.push(record_hash(o).ok());
+    }
+
+    for (i, entry) in bundle.audit_entries.iter().enumerate() {
+        if entry.policy_decision != OVERRIDE_POLICY_DECISION {
+            continue;
+        }
+        if !rpr.override_rights {
+            errors.push(format!("entry {}: override recorded but RPR grants no override_rights", i));
+        }
+        let reference = entry.evidence.result_ref.as_deref();
+        let referenced_hash = match entry.evidence.result_uri() {
+            Some(Ok(DcpUri::Record(h))) => Some(h.to_string()),
+            _ => reference.map(str::to_string),
+        };
+        let found = overrides
+            .iter()
+            .zip(&hashes)
+            .find(|(_, h)| h.is_some() && **h == referenced_hash);
+        match found {
+            Some((o, _)) => {
+                if o.human_id != entry.human_id || o.agent_id != entry.agent_id || o.intent_id != entry.intent_id {
+                    errors.push(format!("entry {}: does not match override {}", i, o.override_id));
+                }
+            }
+            None => errors.push(format!("entry {}: override record {:?} not provided", i, reference)),
+        }
+    }
+
+    if errors.is_empty() {
+        VerificationResult::ok()
+    } else {
+        VerificationResult::fail_at(
+            errors.into_iter().map(|e| VerificationFailure::new(VerifyError::OverrideInvalid, e, "")).collect(),
+        )
+    }
+}
@@ -0,0 +1,276 @@
+//! Capability upgrades for agent passports (v1.1).
+//!
+//! An agent that needs more than its passport grants does not get a new
+//! passport out of band. It signs a [`CapabilityUpgradeRequest`] naming
+//! its current passport; the responsible human signs a
+//! [`CapabilityUpgradeApproval`] granting all or part of it; the new
+//! passport carries a [`PassportUpgrade`] linking both. [`verify_upgrade`]
+//! checks one step and [`verify_lineage`] a whole history, so every
+//! capability an agent holds can be traced to an approval.
+//!
+//! ```ignore
+//! let request = request_upgrade(&passport, "req-1", vec!["payments".into()], None, &agent_sk)?;
+//! let approval = approve_upgrade(&request, &passport, None, &human_sk)?;
+//! let upgraded = issue_upgraded_passport(&passport, &approval, &human_sk)?;
+//! assert!(verify_upgrade(&passport, &request, &approval, &upgraded, &human_pk).verified);
+//! ```
+
+use std::collections::BTreeSet;
+
+use crate::issue::{record_hash, sign_record, verify_record_signature};
+use crate::types::{
+    AgentPassport, CapabilityUpgradeApproval, CapabilityUpgradeRequest, PassportUpgrade, SignatureB64,
+    VerificationFailure, VerificationResult, VerifyError,
+};
+use crate::v2::lifecycle::utc_now_iso_pub as utc_now_iso;
+
+/// How the capabilities of two passports differ, each list sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CapabilityDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub unchanged: Vec<String>,
+}
+
+impl CapabilityDiff {
+    /// Whether `new` holds anything `old` did not.
+    pub fn is_escalation(&self) -> bool {
+        !self.added.is_empty()
+    }
+}
+
+fn capability_set(passport: &AgentPassport) -> BTreeSet<&str> {
+    passport.capabilities.iter().flatten().map(String::as_str).collect()
+}
+
+/// Capabilities added, removed, and kept going from `old` to `new`. A
+/// passport without `capabilities` has none.
+pub fn diff_capabilities(old: &AgentPassport, new: &AgentPassport) -> CapabilityDiff {
+    let (before, after) = (capability_set(old), capability_set(new));
+    CapabilityDiff {
+        added: after.difference(&before).map(|c| c.to_string()).collect(),
+        removed: before.difference(&after).map(|c| c.to_string()).collect(),
+        unchanged: before.intersection(&after).map(|c| c.to_string()).collect(),
+    }
+}
+
+/// Request `requested` on top of `passport`'s capabilities, signed with
+/// the passport's agent key.
+pub fn request_upgrade(
+    passport: &AgentPassport,
+    request_id: &str,
+    requested: Vec<String>,
+    justification: Option<String>,
+    agent_secret_key_b64: &str,
+) -> Result<CapabilityUpgradeRequest, String> {
+    if requested.is_empty() {
+        return Err("nothing requested".into());
+    }
+    let mut request = CapabilityUpgradeRequest {
+        dcp_version: "1.0".into(),
+        request_id: request_id.to_string(),
+        agent_id: passport.agent_id.clone(),
+        passport_hash: record_hash(passport)?,
+        requested,
+        justification,
+        requested_at: utc_now_iso(),
+        signature: SignatureB64::default(),
+    };
+    request.signature = sign_record(&request, agent_secret_key_b64)?;
+    Ok(request)
+}
+
+/// Approve `request` as `passport`'s principal: all of it, or the
+/// `grant` subset.
+pub fn approve_upgrade(
+    request: &CapabilityUpgradeRequest,
+    passport: &AgentPassport,
+    grant: Option<Vec<String>>,
+    human_secret_key_b64: &str,
+) -> Result<CapabilityUpgradeApproval, String> {
+    let approved = grant.unwrap_or_else(|| request.requested.clone());
+    if let Some(extra) = approved.iter().find(|c| !request.requested.contains(c)) {
+        return Err(format!("{} was not requested", extra));
+    }
+    let mut approval = CapabilityUpgradeApproval {
+        dcp_version: "1.0".into(),
+        request_id: request.request_id.clone(),
+        request_hash: record_hash(request)?,
+        human_id: passport.principal_human_id().to_string(),
+        approved,
+        approved_at: utc_now_iso(),
+        signature: SignatureB64::default(),
+    };
+    approval.signature = sign_record(&approval, human_secret_key_b64)?;
+    Ok(approval)
+}
+
+/// The passport replacing `old` with `approval`'s capabilities added,
+/// linked to both and signed with the principal's key.
+pub fn issue_upgraded_passport(
+    old: &AgentPassport,
+    approval: &CapabilityUpgradeApproval,
+    human_secret_key_b64: &str,
+) -> Result<AgentPassport, String> {
+    let mut capabilities = old.capabilities.clone().unwrap_or_default();
+    for c in &approval.approved {
+        if !capabilities.contains(c) {
+            capabilities.push(c.clone());
+        }
+    }
+    let mut upgraded = old.clone();
+    upgraded.capabilities = Some(capabilities);
+    upgraded.created_at = utc_now_iso();
+    upgraded.upgrade = Some(PassportUpgrade {
+        prev_passport_hash: record_hash(old)?,
+        approval_hash: record_hash(approval)?,
+    });
+    upgraded.signature = sign_record(&upgraded, human_secret_key_b64)?;
+    Ok(upgraded)
+}
+
+/// Check that `new` is `old` upgraded through `request` and `approval`:
+/// the request is `old`'s agent asking from `old`, the approval answers
+/// it from `old`'s principal under `human_public_key_b64` without
+/// granting more than was asked, and `new` adds nothing beyond the
+/// approval. Failures are `UPGRADE INVALID` with pointers under
+/// `/request`, `/approval`, and `/passport`.
+pub fn verify_upgrade(
+    old: &AgentPassport,
+    request: &CapabilityUpgradeRequest,
+    approval: &CapabilityUpgradeApproval,
+    new: &AgentPassport,
+    human_public_key_b64: &str,
+) -> VerificationResult {
+    match check_upgrade(old, request, approval, new, human_public_key_b64) {
+        Ok(()) => VerificationResult::ok(),
+        Err(failure) => VerificationResult::fail_at(vec![failure]),
+    }
+}
+
+fn check_upgrade(
+    old: &AgentPassport,
+    request: &CapabilityUpgradeRequest,
+    approval: &CapabilityUpgradeApproval,
+    new: &AgentPassport,
+    human_public_key_b64: &str,
+) -> Result<(), VerificationFailure> {
+    let fail = |what: &str, pointer: &str| VerificationFailure::new(VerifyError::UpgradeInvalid, what, pointer);
+    let hash = |r: Result<String, String>| r.map_err(|e| fail(&e, ""));
+
+    let old_hash = hash(record_hash(old))?;
+    if request.agent_id != old.agent_id {
+        return Err(fail("request is for another agent", "/request/agent_id")
+            .expected(old.agent_id.as_str())
+            .actual(request.agent_id.as_str()));
+    }
+    if request.passport_hash != old_hash {
+        return Err(fail("request does not start from this passport", "/request/passport_hash")
+            .expected(old_hash)
+            .actual(request.passport_hash.as_str()));
+    }
+    if !verify_record_signature(request, old.public_key.as_str()) {
+        return Err(fail("request is not signed by the agent", "/request/signature"));
+    }
+
+    let request_hash = hash(record_hash(request))?;
+    if approval.request_hash != request_hash || approval.request_id != request.request_id {
+        return Err(fail("approval answers another request", "/approval/request_hash")
+            .expected(request_hash)
+            .actual(approval.request_hash.as_str()));
+    }
+    if approval.human_id != old.principal_human_id() {
+        return Err(fail("approval is not from the agent's principal", "/approval/human_id")
+            .expected(old.principal_human_id())
+            .actual(approval.human_id.as_str()));
+    }
+    if let Some(i) = approval.approved.iter().position(|c| !request.requested.contains(c)) {
+        return Err(fail("approval grants a capability that was not requested", &format!("/approval/approved/{}", i))
+            .actual(approval.approved[i].as_str()));
+    }
+    if !verify_record_signature(approval, human_public_key_b64) {
+        return Err(fail("approval is not signed by the principal", "/approval/signature"));
+    }
+
+    let link = PassportUpgrade { prev_passport_hash: old_hash, approval_hash: hash(record_hash(approval))? };
+    if new.upgrade.as_ref() != Some(&link) {
+        return Err(fail("passport does not link the previous passport and approval", "/passport/upgrade"));
+    }
+    if new.agent_id != old.agent_id || new.public_key != old.public_key {
+        return Err(fail("passport is for another agent or key", "/passport/agent_id"));
+    }
+    if let Some(c) = diff_capabilities(old, new).added.into_iter().find(|c| !approval.approved.contains(c)) {
+        return Err(fail("passport adds a capability that was not approved", "/passport/capabilities").actual(c));
+    }
+    if !verify_record_signature(new, human_public_key_b64) {
+        return Err(fail("passport is not signed by the principal", "/passport/signature"));
+    }
+    Ok(())
+}
+
+/// [`verify_upgrade`] along a history: `passports[i + 1]` is
+/// `passports[i]` upgraded through `upgrades[i]`. Pointers are prefixed
+/// with `/upgrades/<i>`.
+pub fn verify_lineage(
+    passports: &[AgentPassport],
+    upgrades: &[(CapabilityUpgradeRequest, CapabilityUpgradeApproval)],
+    human_public_key_b64: &str,
+) -> VerificationResult {
+    if passports.len() != upgrades.len() + 1 {
+        return VerificationResult::fail(
+            VerifyError::UpgradeInvalid,
+            format!(
+                "{} passports need {} upgrades, got {}",
+                passports.len(),
+                passports.len().saturating_sub(1),
+                upgrades.len()
+            ),
+        );
+    }
+    for (i, (pair, (request, approval))) in passports.windows(2).zip(upgrades).enumerate() {
+        if let Err(mut failure) = check_upgrade(&pair[0], request, approval, &pair[1], human_public_key_b64) {
+            failure.pointer = format!("/upgrades/{}{}", i, failure.pointer);
+            return VerificationResult::fail_at(vec![failure]);
+        }
+    }
+    VerificationResult::ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+
+    #[test]
+    fn test_upgrade_lineage() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let mut passport: AgentPassport =
+            serde_json::from_value(set.get("agent_passport").unwrap().value.clone()).unwrap();
+        let (agent_pk, agent_sk) = generate_keypair();
+        let (human_pk, human_sk) = generate_keypair();
+        passport.public_key = agent_pk.parse().unwrap();
+        passport.signature = sign_record(&passport, &human_sk).unwrap();
+
+        let wanted = vec!["payments".to_string(), "calendar".to_string()];
+        let request = request_upgrade(&passport, "req-1", wanted, None, &agent_sk).unwrap();
+        assert!(approve_upgrade(&request, &passport, Some(vec!["admin".into()]), &human_sk).is_err());
+        let approval = approve_upgrade(&request, &passport, Some(vec!["calendar".into()]), &human_sk).unwrap();
+        let upgraded = issue_upgraded_passport(&passport, &approval, &human_sk).unwrap();
+
+        let diff = diff_capabilities(&passport, &upgraded);
+        assert_eq!(diff.added, vec!["calendar"]);
+        assert!(diff.is_escalation() && diff.removed.is_empty());
+        let upgrades = [(request.clone(), approval.clone())];
+        assert!(verify_lineage(&[passport.clone(), upgraded.clone()], &upgrades, &human_pk).verified);
+
+        // Scope creep: the issuer slips in a capability nobody approved.
+        let mut crept = upgraded;
+        crept.capabilities.as_mut().unwrap().push("payments".into());
+        crept.signature = sign_record(&crept, &human_sk).unwrap();
+        let result = verify_lineage(&[passport, crept], &upgrades, &human_pk);
+        let failure = &result.failures.unwrap()[0];
+        assert!(failure.message.starts_with("UPGRADE INVALID: passport adds"), "{}", failure.message);
+        assert_eq!(failure.pointer, "/upgrades/0/passport/capabilities");
+    }
+}
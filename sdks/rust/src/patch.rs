@@ -0,0 +1,208 @@
+//! RFC 6902 JSON Patch.
+//!
+//! [`apply`] is all-or-nothing: the document is only changed if every
+//! operation succeeds. Used by [`crate::bundle::apply_patch`] for
+//! controlled corrections to a signed bundle.
+//!
+//! ```
+//! let mut doc = serde_json::json!({"contact": "alice@exmaple.com"});
+//! let patch = serde_json::json!([{"op": "replace", "path": "/contact", "value": "alice@example.com"}]);
+//! dcp_ai::patch::apply(&mut doc, &patch).unwrap();
+//! assert_eq!(doc["contact"], "alice@example.com");
+//! ```
+
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum PatchError {
+    #[error("patch must be an array of operations")]
+    NotAnArray,
+    #[error("operation {index}: {reason}")]
+    InvalidOperation { index: usize, reason: String },
+    #[error("operation {index}: {path} does not exist")]
+    PathNotFound { index: usize, path: String },
+    #[error("operation {index}: test of {path} failed")]
+    TestFailed { index: usize, path: String },
+}
+
+impl PatchError {
+    /// Index of the failing operation, when there is one.
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            PatchError::NotAnArray => None,
+            PatchError::InvalidOperation { index, .. }
+            | PatchError::PathNotFound { index, .. }
+            | PatchError::TestFailed { index, .. } => Some(*index),
+        }
+    }
+}
+
+/// Apply `patch` (an array of RFC 6902 operations) to `doc`.
+pub fn apply(doc: &mut Value, patch: &Value) -> Result<(), PatchError> {
+    let ops = patch.as_array().ok_or(PatchError::NotAnArray)?;
+    let mut patched = doc.clone();
+    for (index, op) in ops.iter().enumerate() {
+        apply_one(&mut patched, op, index)?;
+    }
+    *doc = patched;
+    Ok(())
+}
+
+/// The JSON Pointer paths `patch` changes (`path` of every operation but
+/// `test`, and `from` of `move`), for callers that restrict what may be
+/// patched.
+pub fn touched_paths(patch: &Value) -> Vec<&str> {
+    let mut paths = Vec::new();
+    for op in patch.as_array().into_iter().flatten() {
+        let member = |name| op.get(name).and_then(Value::as_str);
+        match member("op") {
+            Some("test") => continue,
+            Some("move") => paths.extend(member("from")),
+            _ => {}
+        }
+        paths.extend(member("path"));
+    }
+    paths
+}
+
+fn apply_one(doc: &mut Value, op: &Value, index: usize) -> Result<(), PatchError> {
+    let invalid = |reason: &str| PatchError::InvalidOperation { index, reason: reason.to_string() };
+    let not_found = |path: &str| PatchError::PathNotFound { index, path: path.to_string() };
+    let string_member = |name: &'static str| {
+        op.get(name).and_then(Value::as_str).ok_or_else(|| invalid(&format!("missing \"{}\"", name)))
+    };
+    let value_member = || op.get("value").cloned().ok_or_else(|| invalid("missing \"value\""));
+
+    let path = string_member("path")?;
+    let tokens = parse_pointer(path).ok_or_else(|| invalid(&format!("malformed pointer {:?}", path)))?;
+    match string_member("op")? {
+        "add" => add(doc, &tokens, value_member()?).ok_or_else(|| not_found(path)),
+        "remove" => remove(doc, &tokens).map(drop).ok_or_else(|| not_found(path)),
+        "replace" => {
+            let target = doc.pointer_mut(path).ok_or_else(|| not_found(path))?;
+            *target = value_member()?;
+            Ok(())
+        }
+        "move" => {
+            let from = string_member("from")?;
+            let from_tokens = parse_pointer(from).ok_or_else(|| invalid(&format!("malformed pointer {:?}", from)))?;
+            if tokens.len() > from_tokens.len() && tokens.starts_with(&from_tokens) {
+                return Err(invalid("cannot move a value into one of its children"));
+            }
+            let value = remove(doc, &from_tokens).ok_or_else(|| not_found(from))?;
+            add(doc, &tokens, value).ok_or_else(|| not_found(path))
+        }
+        "copy" => {
+            let from = string_member("from")?;
+            let value = doc.pointer(from).cloned().ok_or_else(|| not_found(from))?;
+            add(doc, &tokens, value).ok_or_else(|| not_found(path))
+        }
+        "test" => match doc.pointer(path) {
+            Some(actual) if *actual == value_member()? => Ok(()),
+            Some(_) => Err(PatchError::TestFailed { index, path: path.to_string() }),
+            None => Err(not_found(path)),
+        },
+        other => Err(invalid(&format!("unknown op {:?}", other))),
+    }
+}
+
+/// Reference tokens of `pointer`, unescaped; `None` unless it is `""` or
+/// starts with `/`.
+fn parse_pointer(pointer: &str) -> Option<Vec<String>> {
+    if pointer.is_empty() {
+        return Some(Vec::new());
+    }
+    let rest = pointer.strip_prefix('/')?;
+    Some(rest.split('/').map(|t| t.replace("~1", "/").replace("~0", "~")).collect())
+}
+
+/// Index of an existing array element: digits, no leading zeros.
+fn array_index(token: &str, len: usize) -> Option<usize> {
+    if token.is_empty() || (token.len() > 1 && token.starts_with('0')) || !token.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    token.parse().ok().filter(|i| *i < len)
+}
+
+fn parent_mut<'a>(doc: &'a mut Value, tokens: &[String]) -> Option<&'a mut Value> {
+    tokens.iter().try_fold(doc, |node, token| match node {
+        Value::Object(map) => map.get_mut(token),
+        Value::Array(items) => {
+            let i = array_index(token, items.len())?;
+            items.get_mut(i)
+        }
+        _ => None,
+    })
+}
+
+fn add(doc: &mut Value, tokens: &[String], value: Value) -> Option<()> {
+    let Some((last, parents)) = tokens.split_last() else {
+        *doc = value;
+        return Some(());
+    };
+    match parent_mut(doc, parents)? {
+        Value::Object(map) => {
+            map.insert(last.clone(), value);
+        }
+        Value::Array(items) if last == "-" => items.push(value),
+        Value::Array(items) => {
+            // Inserting at `len` appends.
+            let i = array_index(last, items.len() + 1)?;
+            items.insert(i, value);
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+fn remove(doc: &mut Value, tokens: &[String]) -> Option<Value> {
+    let (last, parents) = tokens.split_last()?;
+    match parent_mut(doc, parents)? {
+        Value::Object(map) => map.remove(last),
+        Value::Array(items) => {
+            let i = array_index(last, items.len())?;
+            Some(items.remove(i))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_operations() {
+        let mut doc = json!({"a": {"b": [1, 2]}, "c/d": "x"});
+        let patch = json!([
+            {"op": "add", "path": "/a/b/1", "value": 9},
+            {"op": "add", "path": "/a/b/-", "value": 3},
+            {"op": "remove", "path": "/c~1d"},
+            {"op": "copy", "from": "/a/b", "path": "/e"},
+            {"op": "move", "from": "/e/0", "path": "/f"},
+            {"op": "replace", "path": "/a/b/0", "value": 0},
+            {"op": "test", "path": "/f", "value": 1},
+        ]);
+        apply(&mut doc, &patch).unwrap();
+        assert_eq!(doc, json!({"a": {"b": [0, 9, 2, 3]}, "e": [9, 2, 3], "f": 1}));
+        assert_eq!(touched_paths(&patch).len(), 7);
+    }
+
+    #[test]
+    fn test_failed_operation_leaves_document_unchanged() {
+        let mut doc = json!({"a": [1]});
+        let patch = json!([
+            {"op": "replace", "path": "/a/0", "value": 2},
+            {"op": "test", "path": "/a/0", "value": 1},
+        ]);
+        assert_eq!(apply(&mut doc, &patch), Err(PatchError::TestFailed { index: 1, path: "/a/0".into() }));
+        assert_eq!(doc, json!({"a": [1]}));
+
+        let err = apply(&mut doc, &json!([{"op": "remove", "path": "/a/01"}])).unwrap_err();
+        assert_eq!(err.index(), Some(0));
+        let err = apply(&mut doc, &json!([{"op": "move", "from": "/a", "path": "/a/0"}])).unwrap_err();
+        assert!(matches!(err, PatchError::InvalidOperation { .. }));
+    }
+}
@@ -0,0 +1,158 @@
+//! OpenPGP signatures from human principals (feature = "pgp").
+//!
+//! Some organizations only recognize OpenPGP signatures from the humans
+//! they bind. A Responsible Principal Record or override record with
+//! `signature_alg: "openpgp"` carries a detached OpenPGP signature,
+//! base64 of the binary packet, over the same payload an Ed25519
+//! signature would cover: the canonical record without `signature`
+//! ([`signing_payload`]). The key passed where an Ed25519 key would go is
+//! the human's certificate, base64 of its binary (not armored) form.
+//!
+//! [`crate::issue::verify_record_signature`] dispatches on
+//! `signature_alg`, so everything that checks those records (issuance
+//! and renewal chains, challenges, [`crate::overrides::verify_overrides`])
+//! accepts either. Agent signatures stay Ed25519.
+//!
+//! ```text
+//! dcp payload rpr.json > payload.json   # signing_payload, with signature_alg set
+//! gpg --detach-sign --output payload.sig payload.json
+//! base64 -w0 payload.sig                # -> rpr.signature
+//! ```
+
+use std::io::Write;
+
+use sequoia_openpgp as openpgp;
+
+use openpgp::cert::Cert;
+use openpgp::parse::stream::{DetachedVerifierBuilder, MessageLayer, MessageStructure, VerificationHelper};
+use openpgp::parse::Parse;
+use openpgp::policy::StandardPolicy;
+use openpgp::KeyHandle;
+use serde::Serialize;
+
+use crate::b64;
+use crate::crypto::canonicalize;
+use crate::issue::record_payload;
+
+/// `signature_alg` of records signed with OpenPGP.
+pub const OPENPGP_ALG: &str = "openpgp";
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum PgpError {
+    #[error("invalid base64: {0}")]
+    Encoding(String),
+    #[error("invalid OpenPGP certificate: {0}")]
+    Certificate(String),
+    #[error("OpenPGP signature does not verify: {0}")]
+    Signature(String),
+}
+
+/// The bytes a human signs for `record`: its canonical form without
+/// `signature`. Set `signature_alg` to [`OPENPGP_ALG`] first; it is part
+/// of the payload.
+pub fn signing_payload<T: Serialize>(record: &T) -> Result<String, String> {
+    Ok(canonicalize(&record_payload(record)?))
+}
+
+/// Verify a detached OpenPGP signature (base64 binary packet) over
+/// `payload` with a certificate (base64 binary), under sequoia's standard
+/// policy as of now.
+pub fn verify_detached(payload: &[u8], signature_b64: &str, certificate_b64: &str) -> Result<(), PgpError> {
+    let signature = b64::decode(signature_b64).map_err(|e| PgpError::Encoding(e.to_string()))?;
+    let certificate = b64::decode(certificate_b64).map_err(|e| PgpError::Encoding(e.to_string()))?;
+    let cert = Cert::from_bytes(&certificate).map_err(|e| PgpError::Certificate(e.to_string()))?;
+    let policy = StandardPolicy::new();
+    let mut verifier = DetachedVerifierBuilder::from_bytes(&signature)
+        .and_then(|b| b.with_policy(&policy, None, Helper { cert }))
+        .map_err(|e| PgpError::Signature(e.to_string()))?;
+    verifier.verify_bytes(payload).map_err(|e| PgpError::Signature(e.to_string()))
+}
+
+/// [`verify_detached`] over `canon`, as a yes/no for record checks.
+pub(crate) fn verify_canonical(canon: &str, signature_b64: &str, certificate_b64: &str) -> bool {
+    verify_detached(canon.as_bytes(), signature_b64, certificate_b64).is_ok()
+}
+
+/// Offers the one certificate the caller named and accepts the message
+/// if any signature in it verifies under that certificate.
+struct Helper {
+    cert: Cert,
+}
+
+impl VerificationHelper for Helper {
+    fn get_certs(&mut self, _ids: &[KeyHandle]) -> openpgp::Result<Vec<Cert>> {
+        Ok(vec![self.cert.clone()])
+    }
+
+    fn check(&mut self, structure: MessageStructure) -> openpgp::Result<()> {
+        for layer in structure.into_iter() {
+            if let MessageLayer::SignatureGroup { results } = layer {
+                if results.iter().any(Result::is_ok) {
+                    return Ok(());
+                }
+            }
+        }
+        Err(openpgp::Error::BadSignature("no valid signature from the certificate".into()).into())
+    }
+}
+
+/// Write a detached signature over `payload` with the first signing-capable
+/// secret key of `cert`. For tests and tooling; humans normally sign with
+/// their own OpenPGP implementation.
+pub fn sign_detached(payload: &[u8], cert: &Cert) -> Result<String, PgpError> {
+    use openpgp::serialize::stream::{Message, Signer};
+
+    let policy = StandardPolicy::new();
+    let key = cert
+        .keys()
+        .unencrypted_secret()
+        .with_policy(&policy, None)
+        .supported()
+        .alive()
+        .revoked(false)
+        .for_signing()
+        .next()
+        .ok_or_else(|| PgpError::Certificate("no usable signing key".into()))?;
+    let keypair = key.key().clone().into_keypair().map_err(|e| PgpError::Certificate(e.to_string()))?;
+    let mut sink = Vec::new();
+    let signing = |sink: &mut Vec<u8>| -> openpgp::Result<()> {
+        let mut signer = Signer::new(Message::new(sink), keypair).detached().build()?;
+        signer.write_all(payload)?;
+        signer.finalize()
+    };
+    signing(&mut sink).map_err(|e| PgpError::Signature(e.to_string()))?;
+    Ok(b64::encode(&sink))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openpgp::cert::CertBuilder;
+    use openpgp::serialize::SerializeInto;
+
+    use crate::issue::verify_record_signature;
+    use crate::overrides::{create_override, OverrideParams};
+
+    #[test]
+    fn test_openpgp_signed_override_verifies() {
+        let (cert, _) = CertBuilder::new().add_userid("alice@example.com").add_signing_subkey().generate().unwrap();
+        let cert_b64 = b64::encode(&cert.to_vec().unwrap());
+        let params = OverrideParams {
+            override_id: "ovr-1",
+            intent_id: "intent001",
+            human_id: "did:human:alice",
+            agent_id: "did:agent:a",
+            action_taken: "halt",
+            reason: "wrong recipient",
+            timestamp: "2026-01-01T01:05:00Z",
+        };
+        let mut record = create_override(params, &crate::crypto::generate_keypair().1).unwrap();
+        record.signature_alg = Some(OPENPGP_ALG.into());
+        record.signature = sign_detached(signing_payload(&record).unwrap().as_bytes(), &cert).unwrap();
+        assert!(verify_record_signature(&record, &cert_b64));
+
+        record.reason = "changed after signing".into();
+        assert!(!verify_record_signature(&record, &cert_b64));
+    }
+}
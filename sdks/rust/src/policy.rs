@@ -0,0 +1,75 @@
+//! Policy snapshots (v1.1).
+//!
+//! A [`PolicySet`] is the ruleset a policy engine evaluated. Its
+//! [`snapshot_hash`] goes into the decision's `policy_snapshot_hash`, so
+//! each decision names exactly which ruleset version produced it, and a
+//! verifier with [`crate::verify::VerifyOptions::approved_policy_snapshots`]
+//! rejects decisions made under any other (`POLICY SNAPSHOT UNAPPROVED`).
+//!
+//! ```
+//! use dcp_ai::policy::{snapshot_hash, PolicyRule, PolicySet};
+//!
+//! let rules = PolicySet {
+//!     policy_id: "outbound-email".into(),
+//!     version: "2026-10-01".into(),
+//!     rules: vec![PolicyRule { rule_id: "r1".into(), effect: "escalate".into(), when: Default::default() }],
+//! };
+//! assert!(snapshot_hash(&rules).unwrap().starts_with("sha256:"));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::dataclasses::intent_categories;
+use crate::issue::record_hash;
+use crate::types::{Intent, PolicyDecision};
+
+/// `when` key of a rule that applies only to intents engaging one of the
+/// listed [`crate::dataclasses::RegulatoryCategory`] names, e.g.
+/// `{"regulatory_categories": ["hipaa:phi"]}`.
+pub const WHEN_REGULATORY_CATEGORIES: &str = "regulatory_categories";
+
+/// One rule. Rules are evaluated in order, so reordering them changes
+/// the snapshot hash.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyRule {
+    pub rule_id: String,
+    /// `approve`, `escalate`, or `block`.
+    pub effect: String,
+    /// Engine-specific match conditions.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub when: Map<String, Value>,
+}
+
+impl PolicyRule {
+    /// Whether `intent` engages a category under
+    /// [`WHEN_REGULATORY_CATEGORIES`]. A rule without that condition
+    /// matches every intent; other `when` keys are left to the engine.
+    pub fn matches_regulatory_categories(&self, intent: &Intent) -> bool {
+        let Some(wanted) = self.when.get(WHEN_REGULATORY_CATEGORIES) else { return true };
+        let engaged = intent_categories(intent);
+        let wanted = wanted.as_array().map(Vec::as_slice).unwrap_or_default();
+        wanted.iter().filter_map(Value::as_str).any(|name| engaged.iter().any(|c| c.as_str() == name))
+    }
+}
+
+/// A versioned ruleset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicySet {
+    pub policy_id: String,
+    pub version: String,
+    pub rules: Vec<PolicyRule>,
+}
+
+/// `sha256:<hex>` of the canonical JSON of `policy`.
+pub fn snapshot_hash(policy: &PolicySet) -> Result<String, String> {
+    record_hash(policy)
+}
+
+impl PolicyDecision {
+    /// Record `policy` as the ruleset this decision was made under.
+    pub fn with_policy_snapshot(mut self, policy: &PolicySet) -> Result<Self, String> {
+        self.policy_snapshot_hash = Some(snapshot_hash(policy)?);
+        Ok(self)
+    }
+}
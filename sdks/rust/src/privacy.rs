@@ -0,0 +1,181 @@
+//! Hard privacy mode: hash-only intent targets.
+//!
+//! Some deployments cannot put recipient emails, domains, or URLs into
+//! audit artifacts. [`IntentTarget::hashed`] replaces each of `to`,
+//! `domain`, and `url` with a salted SHA-256 commitment and records the
+//! salt's id in `salt_id`; `channel` stays plaintext so policy can still
+//! reason about it. The bundle signs and verifies as usual.
+//!
+//! The salt is the secret. Without it a hashed email is not brute-forceable
+//! from a dictionary; with it, a relying party holding the plaintext can
+//! confirm a match via [`IntentTarget::matches_plaintext`]. Share the salt
+//! (base64, via [`TargetSalt::to_b64`]) only with parties entitled to that
+//! confirmation.
+//!
+//! Each value is hashed as
+//! `SHA-256("DCP-AI.v1.TargetHash" || 0x00 || salt || field || 0x00 || value)`
+//! and written as `sha256:<hex>`. The field name is bound in, so a hashed
+//! `to` never matches a plaintext `domain`.
+
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::b64::{self, B64Error};
+use crate::types::IntentTarget;
+
+const TARGET_HASH_DOMAIN: &[u8] = b"DCP-AI.v1.TargetHash\0";
+const SALT_ID_DOMAIN: &[u8] = b"DCP-AI.v1.TargetSalt\0";
+
+/// A 256-bit target-hashing salt.
+#[derive(Clone)]
+pub struct TargetSalt {
+    id: String,
+    bytes: Zeroizing<[u8; 32]>,
+}
+
+impl std::fmt::Debug for TargetSalt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TargetSalt").field("id", &self.id).finish_non_exhaustive()
+    }
+}
+
+impl TargetSalt {
+    /// A fresh random salt.
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        Self::from_bytes(bytes)
+    }
+
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        let mut h = Sha256::new();
+        h.update(SALT_ID_DOMAIN);
+        h.update(bytes);
+        let id = format!("salt:{}", &hex::encode(h.finalize())[..16]);
+        Self { id, bytes: Zeroizing::new(bytes) }
+    }
+
+    /// Decode a salt exported with [`Self::to_b64`].
+    pub fn from_b64(input: &str) -> Result<Self, B64Error> {
+        b64::decode_exact::<32>(input).map(Self::from_bytes)
+    }
+
+    pub fn to_b64(&self) -> Zeroizing<String> {
+        Zeroizing::new(b64::encode(*self.bytes))
+    }
+
+    /// Public identifier written to `IntentTarget::salt_id`. Derived from
+    /// the salt, so it reveals nothing about it.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// `sha256:<hex>` commitment to `value` in target field `field`.
+    pub fn hash(&self, field: &str, value: &str) -> String {
+        let mut h = Sha256::new();
+        h.update(TARGET_HASH_DOMAIN);
+        h.update(*self.bytes);
+        h.update(field.as_bytes());
+        h.update([0u8]);
+        h.update(value.as_bytes());
+        format!("sha256:{}", hex::encode(h.finalize()))
+    }
+}
+
+/// Whether `value` has the `sha256:<64 hex>` shape of a hashed target field.
+pub fn is_hashed_value(value: &str) -> bool {
+    value
+        .strip_prefix("sha256:")
+        .is_some_and(|h| h.len() == 64 && h.bytes().all(|c| c.is_ascii_hexdigit()))
+}
+
+impl IntentTarget {
+    /// This target with `to`, `domain`, and `url` replaced by salted
+    /// hashes. Already-hashed targets are returned unchanged.
+    pub fn hashed(&self, salt: &TargetSalt) -> IntentTarget {
+        if self.is_hashed() {
+            return self.clone();
+        }
+        let hash = |field: &str, v: &Option<String>| v.as_deref().map(|v| salt.hash(field, v));
+        IntentTarget {
+            channel: self.channel.clone(),
+            to: hash("to", &self.to),
+            domain: hash("domain", &self.domain),
+            url: hash("url", &self.url),
+            salt_id: Some(salt.id().to_string()),
+        }
+    }
+
+    pub fn is_hashed(&self) -> bool {
+        self.salt_id.is_some()
+    }
+
+    /// Fields that carry plaintext where a hash is expected: any present
+    /// `to`, `domain`, or `url` not in `sha256:<hex>` form.
+    pub fn plaintext_fields(&self) -> Vec<&'static str> {
+        [("to", &self.to), ("domain", &self.domain), ("url", &self.url)]
+            .into_iter()
+            .filter(|(_, v)| v.as_deref().is_some_and(|v| !is_hashed_value(v)))
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Whether this hashed target commits to `plaintext` under `salt`:
+    /// same salt id, same channel, and every field present in exactly one
+    /// of the two is a mismatch.
+    pub fn matches_plaintext(&self, plaintext: &IntentTarget, salt: &TargetSalt) -> bool {
+        if self.salt_id.as_deref() != Some(salt.id()) || self.channel != plaintext.channel {
+            return false;
+        }
+        let field_matches = |field: &str, hashed: &Option<String>, plain: &Option<String>| match (hashed, plain) {
+            (None, None) => true,
+            (Some(h), Some(p)) => *h == salt.hash(field, p),
+            _ => false,
+        };
+        field_matches("to", &self.to, &plaintext.to)
+            && field_matches("domain", &self.domain, &plaintext.domain)
+            && field_matches("url", &self.url, &plaintext.url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain() -> IntentTarget {
+        IntentTarget {
+            channel: "email".into(),
+            to: Some("bob@example.com".into()),
+            domain: None,
+            url: None,
+            salt_id: None,
+        }
+    }
+
+    #[test]
+    fn test_hashed_target_matches_plaintext_only_with_salt() {
+        let salt = TargetSalt::generate();
+        let hashed = plain().hashed(&salt);
+        assert!(hashed.is_hashed());
+        assert!(hashed.plaintext_fields().is_empty());
+        assert_ne!(hashed.to, plain().to);
+        assert!(hashed.matches_plaintext(&plain(), &salt));
+
+        let other = TargetSalt::generate();
+        assert!(!hashed.matches_plaintext(&plain(), &other));
+
+        let mut wrong = plain();
+        wrong.to = Some("mallory@example.com".into());
+        assert!(!hashed.matches_plaintext(&wrong, &salt));
+    }
+
+    #[test]
+    fn test_salt_round_trips_through_b64() {
+        let salt = TargetSalt::from_bytes([7u8; 32]);
+        let restored = TargetSalt::from_b64(&salt.to_b64()).unwrap();
+        assert_eq!(restored.id(), salt.id());
+        assert_eq!(restored.hash("to", "x"), salt.hash("to", "x"));
+        assert_ne!(salt.hash("to", "x"), salt.hash("domain", "x"));
+    }
+}
@@ -0,0 +1,147 @@
+//! Who/what/when provenance snippets for signed bundles.
+//!
+//! Downstream services log and display the same handful of fields from a
+//! bundle. [`SignedBundle::provenance`] extracts them in one place so every
+//! consumer renders identical snippets. Call it only on bundles that passed
+//! verification; the top-level values are taken as-is from the signed
+//! artifacts. `signature.signer` and `signature.created_at` are not covered
+//! by the signature, so anyone relaying the bundle can change them; they
+//! appear only under [`Provenance::unverified`].
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{IntentTarget, SignedBundle};
+
+/// Signer summary within a [`Provenance`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ProvenanceSigner {
+    #[serde(rename = "type")]
+    pub signer_type: String,
+    pub id: String,
+}
+
+/// Claims from the signature block, which the signature does not cover.
+/// Display them as claims, never as facts about the bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct UnverifiedProvenance {
+    /// `signature.signer`.
+    pub signer: ProvenanceSigner,
+    /// `signature.created_at`.
+    pub signature_created_at: String,
+}
+
+/// Consistent provenance snippet for a signed bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Provenance {
+    /// Agent that acted (`agent_passport.agent_id`).
+    pub agent: String,
+    /// Responsible human (`responsible_principal_record.human_id`).
+    pub human: String,
+    /// Latest audit entry `timestamp`; `None` without audit entries.
+    pub last_entry_at: Option<String>,
+    /// One-line `action_type -> target` description of the intent.
+    pub intent_summary: String,
+    pub jurisdiction: String,
+    /// Policy decision with its risk explanation when one is attached,
+    /// e.g. `approve, risk 0.35 (m1): pii +0.25, amount +0.10`.
+    pub decision_summary: String,
+    pub unverified: UnverifiedProvenance,
+}
+
+impl SignedBundle {
+    /// Extract the who/what/when of this bundle.
+    pub fn provenance(&self) -> Provenance {
+        let b = &self.bundle;
+        Provenance {
+            agent: b.agent_passport.agent_id.clone(),
+            human: b.responsible_principal_record.human_id.clone(),
+            // ISO-8601 UTC timestamps compare lexicographically.
+            last_entry_at: b.audit_entries.iter().map(|e| &e.timestamp).max().cloned(),
+            intent_summary: summarize_intent(&b.intent.action_type, &b.intent.target),
+            jurisdiction: b.responsible_principal_record.jurisdiction.clone(),
+            decision_summary: match &b.policy_decision.risk {
+                Some(risk) => format!("{}, {}", b.policy_decision.decision, risk),
+                None => b.policy_decision.decision.clone(),
+            },
+            unverified: UnverifiedProvenance {
+                signer: ProvenanceSigner {
+                    signer_type: self.signature.signer.signer_type.clone(),
+                    id: self.signature.signer.id.clone(),
+                },
+                signature_created_at: self.signature.created_at.clone(),
+            },
+        }
+    }
+}
+
+fn summarize_intent(action_type: &str, target: &IntentTarget) -> String {
+    let dest = target
+        .to
+        .as_deref()
+        .or(target.domain.as_deref())
+        .or(target.url.as_deref());
+    match dest {
+        Some(d) => format!("{} via {} -> {}", action_type, target.channel, d),
+        None => format!("{} via {}", action_type, target.channel),
+    }
+}
+
+impl fmt::Display for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "agent={} human={} ({}) last_entry_at={} intent=\"{}\" decision=\"{}\" unverified_signer={}:{}",
+            self.agent,
+            self.human,
+            self.jurisdiction,
+            self.last_entry_at.as_deref().unwrap_or("-"),
+            self.intent_summary,
+            self.decision_summary,
+            self.unverified.signer.signer_type,
+            self.unverified.signer.id
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+
+    #[test]
+    fn test_summary_prefers_recipient() {
+        let target = IntentTarget {
+            channel: "email".into(),
+            to: Some("bob@example.com".into()),
+            domain: Some("example.com".into()),
+            url: None,
+            salt_id: None,
+        };
+        assert_eq!(summarize_intent("send_email", &target), "send_email via email -> bob@example.com");
+    }
+
+    #[test]
+    fn test_unsigned_signature_fields_stay_unverified() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let mut sb: SignedBundle = serde_json::from_value(set.get("citizenship_bundle.signed").unwrap().value.clone()).unwrap();
+        sb.signature.signer.id = "did:human:mallory".into();
+        sb.signature.created_at = "2099-01-01T00:00:00Z".into();
+
+        let p = sb.provenance();
+        assert_eq!(p.last_entry_at.as_deref(), Some(sb.bundle.audit_entries[1].timestamp.as_str()));
+        assert_eq!(p.unverified.signer.id, "did:human:mallory");
+        assert_eq!(p.unverified.signature_created_at, "2099-01-01T00:00:00Z");
+        assert!(p.to_string().ends_with("unverified_signer=human:did:human:mallory"), "{}", p);
+    }
+
+    #[test]
+    fn test_summary_without_destination() {
+        let target = IntentTarget { channel: "api".into(), to: None, domain: None, url: None, salt_id: None };
+        assert_eq!(summarize_intent("api_call", &target), "api_call via api");
+    }
+}
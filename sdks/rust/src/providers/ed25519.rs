@@ -1,7 +1,8 @@
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD as BASE64;
-use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey, Signature};
+use ed25519_dalek::{Signer, SigningKey, VerifyingKey, Signature};
 
+use crate::b64;
 use crate::v2::crypto_provider::{CryptoError, CryptoProvider, GeneratedKeypair, derive_kid};
 
 pub struct Ed25519Provider;
@@ -37,29 +38,23 @@ impl CryptoProvider for Ed25519Provider {
     }
 
     fn sign(&self, message: &[u8], secret_key_b64: &str) -> Result<Vec<u8>, CryptoError> {
-        let sk_bytes = BASE64.decode(secret_key_b64)
-            .map_err(|e| CryptoError::KeyError(format!("base64 decode: {}", e)))?;
-        let key_bytes: [u8; 32] = sk_bytes[..32]
-            .try_into()
-            .map_err(|_| CryptoError::KeyError("invalid secret key length".into()))?;
+        let key_bytes = b64::decode_ed25519_secret(secret_key_b64)
+            .map_err(|e| CryptoError::KeyError(format!("secret key: {}", e)))?;
         let signing_key = SigningKey::from_bytes(&key_bytes);
         let sig = signing_key.sign(message);
         Ok(sig.to_bytes().to_vec())
     }
 
     fn verify(&self, message: &[u8], signature: &[u8], public_key_b64: &str) -> Result<bool, CryptoError> {
-        let pk_bytes = BASE64.decode(public_key_b64)
-            .map_err(|e| CryptoError::KeyError(format!("base64 decode: {}", e)))?;
-        let pk_array: [u8; 32] = pk_bytes
-            .try_into()
-            .map_err(|_| CryptoError::KeyError("invalid public key length (expected 32 bytes)".into()))?;
+        let pk_array = b64::decode_exact::<32>(public_key_b64)
+            .map_err(|e| CryptoError::KeyError(format!("public key: {}", e)))?;
         let sig_array: [u8; 64] = signature
             .try_into()
             .map_err(|_| CryptoError::SignatureError("invalid signature length (expected 64 bytes)".into()))?;
         let verifying_key = VerifyingKey::from_bytes(&pk_array)
             .map_err(|e| CryptoError::KeyError(e.to_string()))?;
         let sig = Signature::from_bytes(&sig_array);
-        Ok(verifying_key.verify(message, &sig).is_ok())
+        Ok(verifying_key.verify_strict(message, &sig).is_ok())
     }
 }
 
@@ -4,6 +4,7 @@ use fips204::ml_dsa_65;
 use fips204::traits::{KeyGen, SerDes, Signer, Verifier};
 use zeroize::Zeroize;
 
+use crate::b64;
 use crate::v2::crypto_provider::{CryptoError, CryptoProvider, GeneratedKeypair, derive_kid};
 
 pub struct MlDsa65Provider;
@@ -38,8 +39,8 @@ impl CryptoProvider for MlDsa65Provider {
 
         let result = GeneratedKeypair {
             kid,
-            public_key_b64: BASE64.encode(&pk_bytes),
-            secret_key_b64: BASE64.encode(&sk_bytes),
+            public_key_b64: BASE64.encode(pk_bytes.as_slice()),
+            secret_key_b64: BASE64.encode(sk_bytes.as_slice()),
         };
 
         sk_bytes.zeroize();
@@ -47,7 +48,7 @@ impl CryptoProvider for MlDsa65Provider {
     }
 
     fn sign(&self, message: &[u8], secret_key_b64: &str) -> Result<Vec<u8>, CryptoError> {
-        let mut sk_bytes = BASE64.decode(secret_key_b64)
+        let mut sk_bytes = b64::decode(secret_key_b64)
             .map_err(|e| CryptoError::KeyError(format!("base64 decode: {}", e)))?;
         let sk_arr: [u8; SK_LEN] = sk_bytes.as_slice().try_into()
             .map_err(|_| CryptoError::KeyError(format!("invalid ML-DSA-65 secret key length: expected {}", SK_LEN)))?;
@@ -61,7 +62,7 @@ impl CryptoProvider for MlDsa65Provider {
     }
 
     fn verify(&self, message: &[u8], signature: &[u8], public_key_b64: &str) -> Result<bool, CryptoError> {
-        let pk_bytes = BASE64.decode(public_key_b64)
+        let pk_bytes = b64::decode(public_key_b64)
             .map_err(|e| CryptoError::KeyError(format!("base64 decode: {}", e)))?;
         let pk_arr: [u8; PK_LEN] = pk_bytes.as_slice().try_into()
             .map_err(|_| CryptoError::KeyError(format!("invalid ML-DSA-65 public key length: expected {}", PK_LEN)))?;
@@ -4,6 +4,7 @@ use fips203::ml_kem_768;
 use fips203::traits::{Decaps, Encaps, SerDes, KeyGen};
 use zeroize::Zeroize;
 
+use crate::b64;
 use crate::v2::crypto_provider::{CryptoError, GeneratedKeypair, KemProvider, derive_kid};
 
 pub struct MlKem768Provider;
@@ -38,8 +39,8 @@ impl KemProvider for MlKem768Provider {
 
         let result = GeneratedKeypair {
             kid,
-            public_key_b64: BASE64.encode(&ek_bytes),
-            secret_key_b64: BASE64.encode(&dk_bytes),
+            public_key_b64: BASE64.encode(ek_bytes.as_slice()),
+            secret_key_b64: BASE64.encode(dk_bytes.as_slice()),
         };
 
         dk_bytes.zeroize();
@@ -47,7 +48,7 @@ impl KemProvider for MlKem768Provider {
     }
 
     fn encapsulate(&self, public_key_b64: &str) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
-        let ek_bytes = BASE64.decode(public_key_b64)
+        let ek_bytes = b64::decode(public_key_b64)
             .map_err(|e| CryptoError::KeyError(format!("base64 decode: {}", e)))?;
         let ek = ml_kem_768::EncapsKey::try_from_bytes(ek_bytes.as_slice().try_into()
             .map_err(|_| CryptoError::KeyError("invalid encapsulation key length".to_string()))?)
@@ -60,7 +61,7 @@ impl KemProvider for MlKem768Provider {
     }
 
     fn decapsulate(&self, ciphertext: &[u8], secret_key_b64: &str) -> Result<Vec<u8>, CryptoError> {
-        let mut dk_bytes = BASE64.decode(secret_key_b64)
+        let mut dk_bytes = b64::decode(secret_key_b64)
             .map_err(|e| CryptoError::KeyError(format!("base64 decode: {}", e)))?;
         let dk = ml_kem_768::DecapsKey::try_from_bytes(dk_bytes.as_slice().try_into()
             .map_err(|_| CryptoError::KeyError("invalid decapsulation key length".to_string()))?)
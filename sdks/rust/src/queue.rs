@@ -0,0 +1,301 @@
+//! Crash-safe verification job queue (feature = "queue").
+//!
+//! A [`VerificationQueue`] keeps its jobs in a JSON Lines journal: a line
+//! when a job is enqueued, one when its result is known, and one when the
+//! result has been delivered. Each line is synced before the call that
+//! wrote it returns, so a verifier service that crashes loses nothing it
+//! accepted: on [`VerificationQueue::open`] the journal is replayed,
+//! unfinished jobs are verified again, and results that were computed but
+//! not delivered are delivered from the journal without re-verifying.
+//!
+//! Results leave through a [`ResultSink`]. A job's delivery is journaled
+//! only after the sink accepts it, so a crash between the two delivers it
+//! again on restart. Sinks that commit the job id together with the
+//! result (a database row keyed by it, say) answer
+//! [`ResultSink::has_emitted`] and make delivery exactly-once; others get
+//! at-least-once delivery, keyed by job id for deduplication.
+//!
+//! ```ignore
+//! let mut queue = VerificationQueue::open("verify-queue.jsonl", VerifyOptions::default())?;
+//! let job_id = queue.enqueue(&signed_bundle, None)?;
+//! queue.process(&mut |job_id: &str, result: &VerificationResult| store(job_id, result))?;
+//! ```
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::types::VerificationResult;
+use crate::v2::lifecycle::utc_now_iso_pub as utc_now_iso;
+use crate::verify::{verify_untrusted, VerifyOptions};
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum QueueError {
+    #[error("queue I/O: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("queue encoding: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("queue journal line {line}: {reason}")]
+    Corrupt { line: usize, reason: String },
+    /// The sink refused the result; the job stays queued with its result.
+    #[error("sink refused job {job_id}: {reason}")]
+    Sink { job_id: String, reason: String },
+}
+
+/// Where [`VerificationQueue::process`] delivers results.
+pub trait ResultSink {
+    /// Deliver the result of `job_id`. An error leaves the job queued, to
+    /// be delivered again by a later [`VerificationQueue::process`].
+    fn emit(&mut self, job_id: &str, result: &VerificationResult) -> Result<(), String>;
+
+    /// Whether `job_id` was already delivered. Asked before every
+    /// delivery, so a sink that records job ids atomically with results
+    /// never sees a job twice, even across a crash. Default `false`.
+    fn has_emitted(&self, _job_id: &str) -> bool {
+        false
+    }
+}
+
+impl<F: FnMut(&str, &VerificationResult) -> Result<(), String>> ResultSink for F {
+    fn emit(&mut self, job_id: &str, result: &VerificationResult) -> Result<(), String> {
+        self(job_id, result)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Line {
+    Enqueued {
+        job_id: String,
+        signed_bundle: Value,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        public_key_b64: Option<String>,
+        enqueued_at: String,
+    },
+    Completed {
+        job_id: String,
+        result: VerificationResult,
+        completed_at: String,
+    },
+    Emitted {
+        job_id: String,
+    },
+}
+
+/// A job not yet delivered.
+#[derive(Debug, Clone)]
+struct Job {
+    job_id: String,
+    signed_bundle: Value,
+    public_key_b64: Option<String>,
+    /// Known once verified; journaled, so never verified twice.
+    result: Option<VerificationResult>,
+}
+
+/// An open queue journal.
+pub struct VerificationQueue {
+    path: PathBuf,
+    file: File,
+    opts: VerifyOptions,
+    /// Undelivered jobs, in enqueue order.
+    jobs: Vec<Job>,
+}
+
+impl std::fmt::Debug for VerificationQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VerificationQueue")
+            .field("path", &self.path)
+            .field("pending", &self.jobs.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl VerificationQueue {
+    /// Open or create the journal at `path`, verifying jobs with `opts`.
+    /// Jobs left undelivered by a previous run are queued again. A torn
+    /// last line, from a crash in the middle of a write that never
+    /// returned, is dropped.
+    pub fn open(path: impl AsRef<Path>, opts: VerifyOptions) -> Result<Self, QueueError> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().create(true).read(true).append(true).open(&path)?;
+        let mut text = std::fs::read_to_string(&path)?;
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.truncate(text.rfind('\n').map_or(0, |i| i + 1));
+            file.set_len(text.len() as u64)?;
+        }
+        let mut queue = Self { path, file, opts, jobs: Vec::new() };
+        for (i, line) in text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let corrupt = |reason: String| QueueError::Corrupt { line: i + 1, reason };
+            queue.apply(serde_json::from_str(line).map_err(|e| corrupt(e.to_string()))?).map_err(corrupt)?;
+        }
+        Ok(queue)
+    }
+
+    fn apply(&mut self, line: Line) -> Result<(), String> {
+        match line {
+            Line::Enqueued { job_id, signed_bundle, public_key_b64, .. } => {
+                self.jobs.push(Job { job_id, signed_bundle, public_key_b64, result: None })
+            }
+            Line::Completed { job_id, result, .. } => self.job_mut(&job_id)?.result = Some(result),
+            Line::Emitted { job_id } => {
+                self.job_mut(&job_id)?;
+                self.jobs.retain(|j| j.job_id != job_id);
+            }
+        }
+        Ok(())
+    }
+
+    fn job_mut(&mut self, job_id: &str) -> Result<&mut Job, String> {
+        self.jobs.iter_mut().find(|j| j.job_id == job_id).ok_or_else(|| format!("unknown job {}", job_id))
+    }
+
+    fn append(&mut self, line: &Line) -> Result<(), QueueError> {
+        let mut text = serde_json::to_string(line)?;
+        text.push('\n');
+        self.file.write_all(text.as_bytes())?;
+        self.file.sync_data()?;
+        Ok(())
+    }
+
+    /// Queue `signed_bundle` for verification against `public_key_b64`
+    /// (as in [`crate::verify::verify_signed_bundle`]). Durable once this
+    /// returns. Returns the job id results are delivered under, random so
+    /// that it is never reused, even after [`Self::compact`].
+    pub fn enqueue(&mut self, signed_bundle: &Value, public_key_b64: Option<&str>) -> Result<String, QueueError> {
+        let job = Job {
+            job_id: format!("job-{}", hex::encode(rand::random::<[u8; 8]>())),
+            signed_bundle: signed_bundle.clone(),
+            public_key_b64: public_key_b64.map(str::to_string),
+            result: None,
+        };
+        self.append(&Line::Enqueued {
+            job_id: job.job_id.clone(),
+            signed_bundle: job.signed_bundle.clone(),
+            public_key_b64: job.public_key_b64.clone(),
+            enqueued_at: utc_now_iso(),
+        })?;
+        let job_id = job.job_id.clone();
+        self.jobs.push(job);
+        Ok(job_id)
+    }
+
+    /// Verify every queued job in order and deliver its result to `sink`.
+    /// Returns how many were delivered. Stops at the first result the
+    /// sink refuses, leaving it and later jobs queued.
+    pub fn process(&mut self, sink: &mut impl ResultSink) -> Result<usize, QueueError> {
+        let mut delivered = 0;
+        while let Some(job) = self.jobs.first().cloned() {
+            let result = match job.result {
+                Some(result) => result,
+                None => {
+                    let result = verify_untrusted(&job.signed_bundle, job.public_key_b64.as_deref(), &self.opts)
+                        .unwrap_or_else(|e| e.into_result());
+                    let completed_at = utc_now_iso();
+                    self.append(&Line::Completed { job_id: job.job_id.clone(), result: result.clone(), completed_at })?;
+                    self.jobs[0].result = Some(result.clone());
+                    result
+                }
+            };
+            if !sink.has_emitted(&job.job_id) {
+                let refused = |reason| QueueError::Sink { job_id: job.job_id.clone(), reason };
+                sink.emit(&job.job_id, &result).map_err(refused)?;
+                delivered += 1;
+            }
+            self.append(&Line::Emitted { job_id: job.job_id })?;
+            self.jobs.remove(0);
+        }
+        Ok(delivered)
+    }
+
+    /// Rewrite the journal with only the undelivered jobs, so it does not
+    /// grow without bound.
+    pub fn compact(&mut self) -> Result<(), QueueError> {
+        let tmp = self.path.with_extension("compacting");
+        {
+            let mut out = File::create(&tmp)?;
+            for job in &self.jobs {
+                let mut lines = vec![Line::Enqueued {
+                    job_id: job.job_id.clone(),
+                    signed_bundle: job.signed_bundle.clone(),
+                    public_key_b64: job.public_key_b64.clone(),
+                    enqueued_at: utc_now_iso(),
+                }];
+                if let Some(result) = &job.result {
+                    lines.push(Line::Completed {
+                        job_id: job.job_id.clone(),
+                        result: result.clone(),
+                        completed_at: utc_now_iso(),
+                    });
+                }
+                for line in lines {
+                    writeln!(out, "{}", serde_json::to_string(&line)?)?;
+                }
+            }
+            out.sync_all()?;
+        }
+        std::fs::rename(&tmp, &self.path)?;
+        self.file = OpenOptions::new().append(true).open(&self.path)?;
+        Ok(())
+    }
+
+    /// Jobs not yet delivered.
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+
+    fn temp_path(name: &str) -> PathBuf {
+        let nanos = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos();
+        std::env::temp_dir().join(format!("dcp-queue-{}-{}-{}.jsonl", name, std::process::id(), nanos))
+    }
+
+    #[test]
+    fn test_resumes_after_crash_without_redelivery() {
+        let path = temp_path("resume");
+        let signed = generate_fixtures(&GOLDEN_SEED).unwrap().get("citizenship_bundle.signed").unwrap().value.clone();
+        let mut delivered: Vec<(String, bool)> = Vec::new();
+        let (first, second) = {
+            let mut queue = VerificationQueue::open(&path, VerifyOptions::default()).unwrap();
+            let first = queue.enqueue(&signed, None).unwrap();
+            let second = queue.enqueue(&serde_json::json!({"bundle": {}}), None).unwrap();
+            let mut refuse_second = |job_id: &str, r: &VerificationResult| {
+                if job_id == second {
+                    return Err("sink down".to_string());
+                }
+                delivered.push((job_id.to_string(), r.verified));
+                Ok(())
+            };
+            assert!(matches!(queue.process(&mut refuse_second), Err(QueueError::Sink { .. })));
+            (first, second)
+        };
+        // A write torn by the crash.
+        OpenOptions::new().append(true).open(&path).unwrap().write_all(b"{\"kind\":\"enq").unwrap();
+
+        let mut queue = VerificationQueue::open(&path, VerifyOptions::default()).unwrap();
+        assert_eq!(queue.len(), 1);
+        let mut sink = |job_id: &str, r: &VerificationResult| {
+            delivered.push((job_id.to_string(), r.verified));
+            Ok(())
+        };
+        assert_eq!(queue.process(&mut sink).unwrap(), 1);
+        assert_eq!(delivered, vec![(first, true), (second, false)]);
+
+        queue.enqueue(&signed, None).unwrap();
+        queue.compact().unwrap();
+        assert_eq!(VerificationQueue::open(&path, VerifyOptions::default()).unwrap().len(), 1);
+        std::fs::remove_file(&path).ok();
+    }
+}
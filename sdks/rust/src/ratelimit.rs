@@ -0,0 +1,276 @@
+//! Per-key signing counters and caps.
+//!
+//! [`RateLimitedSigner`] wraps any [`CryptoProvider`] and counts signatures
+//! per key in fixed UTC minute and day windows. Past a cap it refuses to
+//! sign with [`CryptoError::RateLimited`] unless a [`LimitOverride`] hook
+//! approves the extra signature, so a deployment can enforce its
+//! `risk_tier` signing policy in code rather than in runbooks.
+//!
+//! Keys are tracked by [`key_id`], a one-way fingerprint of the secret key;
+//! the wrapper never stores key material.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use sha2::{Digest, Sha256};
+
+use crate::bundle::{self, SignOptions};
+use crate::types::{CitizenshipBundle, SignedBundle};
+use crate::v2::crypto_provider::{CryptoError, CryptoProvider, GeneratedKeypair};
+
+const KEY_ID_DOMAIN: &[u8] = b"DCP-AI.v1.SignerUsage\0";
+
+/// Signature caps per key. `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SigningLimits {
+    pub per_minute: Option<u32>,
+    pub per_day: Option<u32>,
+}
+
+impl SigningLimits {
+    pub fn new(per_minute: Option<u32>, per_day: Option<u32>) -> Self {
+        Self { per_minute, per_day }
+    }
+
+    /// Defaults for an agent passport `risk_tier`: `low` 600/min and
+    /// 100 000/day, `medium` 60/min and 5 000/day, `high` 10/min and
+    /// 500/day. Unknown tiers get the `high` caps.
+    pub fn for_risk_tier(tier: &str) -> Self {
+        match tier {
+            "low" => Self::new(Some(600), Some(100_000)),
+            "medium" => Self::new(Some(60), Some(5_000)),
+            _ => Self::new(Some(10), Some(500)),
+        }
+    }
+}
+
+/// Which cap a signature would exceed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitWindow {
+    Minute,
+    Day,
+}
+
+/// Decides whether a signature over a cap may go ahead anyway, e.g. after
+/// a human approval or for an incident-response key.
+pub trait LimitOverride: Send + Sync {
+    /// `used` signatures already made in `window` against a cap of `cap`.
+    fn allow(&self, key_id: &str, window: LimitWindow, used: u32, cap: u32) -> bool;
+}
+
+impl<F> LimitOverride for F
+where
+    F: Fn(&str, LimitWindow, u32, u32) -> bool + Send + Sync,
+{
+    fn allow(&self, key_id: &str, window: LimitWindow, used: u32, cap: u32) -> bool {
+        self(key_id, window, used, cap)
+    }
+}
+
+/// Signatures made with one key in the current windows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeyUsage {
+    pub this_minute: u32,
+    pub today: u32,
+    /// Signatures let through by a [`LimitOverride`] since the wrapper
+    /// was created.
+    pub overridden: u32,
+    minute: u64,
+    day: u64,
+}
+
+impl KeyUsage {
+    fn roll(&mut self, now_secs: u64) {
+        if self.minute != now_secs / 60 {
+            self.minute = now_secs / 60;
+            self.this_minute = 0;
+        }
+        if self.day != now_secs / 86_400 {
+            self.day = now_secs / 86_400;
+            self.today = 0;
+        }
+    }
+}
+
+/// Public fingerprint a [`RateLimitedSigner`] tracks `secret_key_b64` under.
+pub fn key_id(secret_key_b64: &str) -> String {
+    let mut h = Sha256::new();
+    h.update(KEY_ID_DOMAIN);
+    h.update(secret_key_b64.as_bytes());
+    hex::encode(&h.finalize()[..8])
+}
+
+type Clock = Arc<dyn Fn() -> u64 + Send + Sync>;
+
+/// A [`CryptoProvider`] that refuses to sign past its [`SigningLimits`].
+pub struct RateLimitedSigner<P> {
+    inner: P,
+    limits: SigningLimits,
+    usage: Mutex<HashMap<String, KeyUsage>>,
+    overrides: Option<Arc<dyn LimitOverride>>,
+    clock: Clock,
+}
+
+impl<P> std::fmt::Debug for RateLimitedSigner<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimitedSigner").field("limits", &self.limits).finish_non_exhaustive()
+    }
+}
+
+impl<P: CryptoProvider> RateLimitedSigner<P> {
+    pub fn new(inner: P, limits: SigningLimits) -> Self {
+        Self {
+            inner,
+            limits,
+            usage: Mutex::new(HashMap::new()),
+            overrides: None,
+            clock: Arc::new(|| SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()),
+        }
+    }
+
+    /// Consult `hook` before refusing a signature over a cap.
+    pub fn with_override(mut self, hook: impl LimitOverride + 'static) -> Self {
+        self.overrides = Some(Arc::new(hook));
+        self
+    }
+
+    /// Replace the wall clock (seconds since the Unix epoch); for tests.
+    pub fn with_clock(mut self, clock: impl Fn() -> u64 + Send + Sync + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    pub fn limits(&self) -> SigningLimits {
+        self.limits
+    }
+
+    /// Current usage for `secret_key_b64`.
+    pub fn usage(&self, secret_key_b64: &str) -> KeyUsage {
+        let now = (self.clock)();
+        let mut usage = self.lock().get(&key_id(secret_key_b64)).copied().unwrap_or_default();
+        usage.roll(now);
+        usage
+    }
+
+    /// Count one signature for `secret_key_b64`, or refuse it. Called by
+    /// every signing method; call it directly to meter signatures made
+    /// outside this wrapper.
+    pub fn acquire(&self, secret_key_b64: &str) -> Result<(), CryptoError> {
+        let id = key_id(secret_key_b64);
+        let now = (self.clock)();
+        let mut table = self.lock();
+        let usage = table.entry(id.clone()).or_default();
+        usage.roll(now);
+
+        let over = [
+            (LimitWindow::Minute, usage.this_minute, self.limits.per_minute),
+            (LimitWindow::Day, usage.today, self.limits.per_day),
+        ]
+        .into_iter()
+        .find_map(|(window, used, cap)| cap.filter(|cap| used >= *cap).map(|cap| (window, used, cap)));
+
+        if let Some((window, used, cap)) = over {
+            let allowed = self.overrides.as_ref().is_some_and(|o| o.allow(&id, window, used, cap));
+            if !allowed {
+                return Err(CryptoError::RateLimited(format!(
+                    "key {} made {} signatures this {}, cap {}",
+                    id,
+                    used,
+                    match window {
+                        LimitWindow::Minute => "minute",
+                        LimitWindow::Day => "day",
+                    },
+                    cap
+                )));
+            }
+            usage.overridden += 1;
+        }
+        usage.this_minute += 1;
+        usage.today += 1;
+        Ok(())
+    }
+
+    /// [`bundle::sign_bundle`] under this wrapper's limits.
+    pub fn sign_bundle(&self, bundle: CitizenshipBundle, opts: &SignOptions<'_>) -> Result<SignedBundle, String> {
+        self.acquire(opts.secret_key_b64).map_err(|e| e.to_string())?;
+        bundle::sign_bundle(bundle, opts)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, KeyUsage>> {
+        self.usage.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<P: CryptoProvider> CryptoProvider for RateLimitedSigner<P> {
+    fn alg(&self) -> &str {
+        self.inner.alg()
+    }
+
+    fn key_size(&self) -> usize {
+        self.inner.key_size()
+    }
+
+    fn sig_size(&self) -> usize {
+        self.inner.sig_size()
+    }
+
+    fn is_constant_time(&self) -> bool {
+        self.inner.is_constant_time()
+    }
+
+    fn generate_keypair(&self) -> Result<GeneratedKeypair, CryptoError> {
+        self.inner.generate_keypair()
+    }
+
+    fn sign(&self, message: &[u8], secret_key_b64: &str) -> Result<Vec<u8>, CryptoError> {
+        self.acquire(secret_key_b64)?;
+        self.inner.sign(message, secret_key_b64)
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8], public_key_b64: &str) -> Result<bool, CryptoError> {
+        self.inner.verify(message, signature, public_key_b64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::ed25519::Ed25519Provider;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    fn signer(limits: SigningLimits) -> (RateLimitedSigner<Ed25519Provider>, Arc<AtomicU64>) {
+        let now = Arc::new(AtomicU64::new(1_767_225_600));
+        let clock = now.clone();
+        let signer = RateLimitedSigner::new(Ed25519Provider, limits).with_clock(move || clock.load(Ordering::SeqCst));
+        (signer, now)
+    }
+
+    #[test]
+    fn test_minute_cap_resets_with_the_window() {
+        let (signer, now) = signer(SigningLimits::new(Some(2), Some(3)));
+        let kp = signer.generate_keypair().unwrap();
+        signer.sign(b"a", &kp.secret_key_b64).unwrap();
+        signer.sign(b"b", &kp.secret_key_b64).unwrap();
+        assert!(matches!(signer.sign(b"c", &kp.secret_key_b64), Err(CryptoError::RateLimited(_))));
+
+        now.fetch_add(60, Ordering::SeqCst);
+        signer.sign(b"c", &kp.secret_key_b64).unwrap();
+        assert!(matches!(signer.sign(b"d", &kp.secret_key_b64), Err(CryptoError::RateLimited(_))));
+        assert_eq!(signer.usage(&kp.secret_key_b64).today, 3);
+
+        let other = signer.generate_keypair().unwrap();
+        signer.sign(b"e", &other.secret_key_b64).unwrap();
+    }
+
+    #[test]
+    fn test_override_hook_can_allow_excess() {
+        let (signer, _) = signer(SigningLimits::new(Some(1), None));
+        let signer = signer.with_override(|_: &str, window: LimitWindow, _: u32, _: u32| window == LimitWindow::Minute);
+        let kp = signer.generate_keypair().unwrap();
+        signer.sign(b"a", &kp.secret_key_b64).unwrap();
+        signer.sign(b"b", &kp.secret_key_b64).unwrap();
+        assert_eq!(signer.usage(&kp.secret_key_b64).overridden, 1);
+    }
+}
@@ -0,0 +1,302 @@
+//! Key escrow and threshold recovery (Shamir secret sharing over GF(256)).
+//!
+//! Organizations whose `liability_mode` mandates multi-custodian control can
+//! split an agent signing key into `n` shares, any `k` of which recover it.
+//! Fewer than `k` shares reveal nothing about the secret.
+//!
+//! Share encoding (`dcp-sss-v1`) is a single ASCII line:
+//!
+//! ```text
+//! dcp-sss-v1:<threshold>:<index>:<base64(y-bytes)>
+//! ```
+//!
+//! `threshold` and `index` are decimal in `1..=255`; `index` is the
+//! x-coordinate of the share and is never `0`. The y-bytes have the same
+//! length as the secret. Arithmetic uses the AES field polynomial
+//! `x^8 + x^4 + x^3 + x + 1` (0x11B).
+//!
+//! ```
+//! use dcp_ai::recovery::{split_secret, recover_secret};
+//!
+//! let shares = split_secret(b"agent secret key", 5, 3).unwrap();
+//! let recovered = recover_secret(&shares[1..4]).unwrap();
+//! assert_eq!(recovered.as_slice(), b"agent secret key");
+//! ```
+
+use std::collections::HashSet;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use rand::RngCore;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// Prefix identifying the share encoding version.
+pub const SHARE_ENCODING_PREFIX: &str = "dcp-sss-v1";
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RecoveryError {
+    #[error("Invalid threshold: k={k}, n={n} (require 1 <= k <= n <= 255)")]
+    InvalidThreshold { k: u8, n: u8 },
+    #[error("Secret must not be empty")]
+    EmptySecret,
+    #[error("Not enough shares: need {needed}, got {got}")]
+    NotEnoughShares { needed: u8, got: usize },
+    #[error("Shares are inconsistent: {0}")]
+    InconsistentShares(String),
+    #[error("Malformed share encoding: {0}")]
+    MalformedShare(String),
+}
+
+/// One share of a split secret.
+#[derive(Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct Share {
+    pub threshold: u8,
+    pub index: u8,
+    pub data: Vec<u8>,
+}
+
+impl std::fmt::Debug for Share {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Share")
+            .field("threshold", &self.threshold)
+            .field("index", &self.index)
+            .field("data", &"<redacted>")
+            .finish()
+    }
+}
+
+impl Share {
+    /// Encode as `dcp-sss-v1:<threshold>:<index>:<base64>`.
+    pub fn encode(&self) -> String {
+        format!(
+            "{}:{}:{}:{}",
+            SHARE_ENCODING_PREFIX,
+            self.threshold,
+            self.index,
+            BASE64.encode(&self.data)
+        )
+    }
+
+    /// Parse a share produced by [`Share::encode`].
+    pub fn decode(s: &str) -> Result<Self, RecoveryError> {
+        let parts: Vec<&str> = s.trim().split(':').collect();
+        if parts.len() != 4 || parts[0] != SHARE_ENCODING_PREFIX {
+            return Err(RecoveryError::MalformedShare(format!(
+                "expected {}:<threshold>:<index>:<data>",
+                SHARE_ENCODING_PREFIX
+            )));
+        }
+        let threshold: u8 = parts[1]
+            .parse()
+            .map_err(|_| RecoveryError::MalformedShare(format!("bad threshold: {}", parts[1])))?;
+        let index: u8 = parts[2]
+            .parse()
+            .map_err(|_| RecoveryError::MalformedShare(format!("bad index: {}", parts[2])))?;
+        if threshold == 0 || index == 0 {
+            return Err(RecoveryError::MalformedShare("threshold and index must be non-zero".into()));
+        }
+        let data = BASE64
+            .decode(parts[3])
+            .map_err(|e| RecoveryError::MalformedShare(e.to_string()))?;
+        if data.is_empty() {
+            return Err(RecoveryError::MalformedShare("empty share data".into()));
+        }
+        Ok(Share { threshold, index, data })
+    }
+}
+
+/// Split `secret` into `n` shares, any `k` of which recover it.
+pub fn split_secret(secret: &[u8], n: u8, k: u8) -> Result<Vec<Share>, RecoveryError> {
+    if k == 0 || n == 0 || k > n {
+        return Err(RecoveryError::InvalidThreshold { k, n });
+    }
+    if secret.is_empty() {
+        return Err(RecoveryError::EmptySecret);
+    }
+
+    let mut shares: Vec<Share> = (1..=n)
+        .map(|index| Share { threshold: k, index, data: Vec::with_capacity(secret.len()) })
+        .collect();
+
+    let mut rng = rand::thread_rng();
+    // coeffs[0] is the secret byte; coeffs[1..k] are random per byte.
+    let mut coeffs = Zeroizing::new(vec![0u8; k as usize]);
+    for &byte in secret {
+        coeffs[0] = byte;
+        rng.fill_bytes(&mut coeffs[1..]);
+        for share in shares.iter_mut() {
+            share.data.push(eval_poly(&coeffs, share.index));
+        }
+    }
+    Ok(shares)
+}
+
+/// Recover the secret from at least `threshold` distinct shares.
+///
+/// Extra shares beyond the threshold are ignored. Shares must agree on
+/// threshold and length and carry distinct indices. The secret is wiped
+/// from memory when the returned buffer is dropped.
+pub fn recover_secret(shares: &[Share]) -> Result<Zeroizing<Vec<u8>>, RecoveryError> {
+    let first = shares.first().ok_or(RecoveryError::NotEnoughShares { needed: 1, got: 0 })?;
+    let k = first.threshold;
+    let len = first.data.len();
+
+    let mut seen = HashSet::new();
+    for s in shares {
+        if s.threshold != k {
+            return Err(RecoveryError::InconsistentShares("threshold differs between shares".into()));
+        }
+        if s.data.len() != len {
+            return Err(RecoveryError::InconsistentShares("share lengths differ".into()));
+        }
+        if s.index == 0 || !seen.insert(s.index) {
+            return Err(RecoveryError::InconsistentShares(format!("duplicate or zero index {}", s.index)));
+        }
+    }
+    if shares.len() < k as usize {
+        return Err(RecoveryError::NotEnoughShares { needed: k, got: shares.len() });
+    }
+
+    let used = &shares[..k as usize];
+    let mut secret = Zeroizing::new(vec![0u8; len]);
+    for (i, out) in secret.iter_mut().enumerate() {
+        *out = interpolate_at_zero(used.iter().map(|s| (s.index, s.data[i])));
+    }
+    Ok(secret)
+}
+
+/// Convenience wrapper: recover from `dcp-sss-v1` encoded strings.
+pub fn recover_secret_encoded(encoded: &[&str]) -> Result<Zeroizing<Vec<u8>>, RecoveryError> {
+    let shares = encoded
+        .iter()
+        .map(|s| Share::decode(s))
+        .collect::<Result<Vec<_>, _>>()?;
+    recover_secret(&shares)
+}
+
+// ── GF(256) arithmetic ──
+
+/// Constant time: always eight rounds, and the conditional XORs are
+/// masks (`0x00` or `0xFF`) rather than branches on share or secret bytes.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut p = 0u8;
+    for _ in 0..8 {
+        p ^= a & (b & 1).wrapping_neg();
+        let carry = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (0x1B & carry);
+        b >>= 1;
+    }
+    p
+}
+
+fn gf_inv(a: u8) -> u8 {
+    // a^254 == a^-1 in GF(256); a is never 0 for distinct non-zero indices.
+    // The exponent is public, so branching on its bits leaks nothing.
+    let mut result = 1u8;
+    let mut base = a;
+    let mut exp = 254u8;
+    while exp != 0 {
+        if exp & 1 != 0 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+fn eval_poly(coeffs: &[u8], x: u8) -> u8 {
+    // Horner's rule, highest degree first.
+    coeffs.iter().rev().fold(0u8, |acc, &c| gf_mul(acc, x) ^ c)
+}
+
+fn interpolate_at_zero<I: Iterator<Item = (u8, u8)> + Clone>(points: I) -> u8 {
+    let mut acc = 0u8;
+    for (xi, yi) in points.clone() {
+        let mut num = 1u8;
+        let mut den = 1u8;
+        for (xj, _) in points.clone() {
+            if xj == xi {
+                continue;
+            }
+            // Lagrange basis at 0: prod xj / (xj - xi); subtraction is XOR.
+            num = gf_mul(num, xj);
+            den = gf_mul(den, xj ^ xi);
+        }
+        acc ^= gf_mul(yi, gf_mul(num, gf_inv(den)));
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gf_mul_matches_reference() {
+        fn reference(mut a: u8, mut b: u8) -> u8 {
+            let mut p = 0u8;
+            while b != 0 {
+                if b & 1 != 0 {
+                    p ^= a;
+                }
+                let carry = a & 0x80;
+                a <<= 1;
+                if carry != 0 {
+                    a ^= 0x1B;
+                }
+                b >>= 1;
+            }
+            p
+        }
+        for a in 0..=255u8 {
+            for b in 0..=255u8 {
+                assert_eq!(gf_mul(a, b), reference(a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_gf_inverse() {
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, gf_inv(a)), 1);
+        }
+    }
+
+    #[test]
+    fn test_any_k_shares_recover() {
+        let secret = b"0123456789abcdef0123456789abcdef";
+        let shares = split_secret(secret, 5, 3).unwrap();
+        assert_eq!(shares.len(), 5);
+        for combo in [[0, 1, 2], [0, 2, 4], [1, 3, 4], [2, 3, 4]] {
+            let subset: Vec<Share> = combo.iter().map(|&i| shares[i].clone()).collect();
+            assert_eq!(recover_secret(&subset).unwrap().as_slice(), secret);
+        }
+    }
+
+    #[test]
+    fn test_too_few_shares_rejected() {
+        let shares = split_secret(b"secret", 4, 3).unwrap();
+        let err = recover_secret(&shares[..2]).unwrap_err();
+        assert_eq!(err, RecoveryError::NotEnoughShares { needed: 3, got: 2 });
+    }
+
+    #[test]
+    fn test_encoding_roundtrip() {
+        let shares = split_secret(b"secret", 3, 2).unwrap();
+        let encoded: Vec<String> = shares.iter().map(Share::encode).collect();
+        assert!(encoded[0].starts_with("dcp-sss-v1:2:1:"));
+        let refs: Vec<&str> = encoded.iter().map(String::as_str).collect();
+        assert_eq!(recover_secret_encoded(&refs[1..]).unwrap().as_slice(), b"secret");
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(matches!(split_secret(b"s", 2, 3), Err(RecoveryError::InvalidThreshold { .. })));
+        assert!(matches!(split_secret(b"s", 2, 0), Err(RecoveryError::InvalidThreshold { .. })));
+        assert_eq!(split_secret(b"", 2, 2).unwrap_err(), RecoveryError::EmptySecret);
+        assert!(Share::decode("dcp-sss-v1:2:0:AAAA").is_err());
+        assert!(Share::decode("other:2:1:AAAA").is_err());
+    }
+}
@@ -0,0 +1,309 @@
+//! Live revocation for long-running verifiers (feature = "registry").
+//!
+//! A [`RevocationWatcher`] is a tokio task that periodically pulls a
+//! revocation snapshot (CRL, registry export, gateway feed) from a
+//! [`RevocationSource`] and publishes it through a shared
+//! [`RevocationState`]. Verifiers hold a cloned `RevocationState` and
+//! call [`RevocationState::check`] after signature verification; the
+//! watcher swaps the snapshot underneath them.
+//!
+//! ```ignore
+//! let watcher = RevocationWatcher::spawn(my_source, Duration::from_secs(60));
+//! let state = watcher.state();
+//! let mut events = watcher.subscribe();
+//! tokio::spawn(async move {
+//!     while let Ok(RevocationEvent::AgentRevoked(id)) = events.recv().await {
+//!         eprintln!("agent {id} revoked");
+//!     }
+//! });
+//! ```
+
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::types::{VerificationFailure, VerificationResult, VerifyError};
+use crate::uri::DcpUri;
+use crate::v2::lifecycle::utc_now_iso_pub as utc_now_iso;
+
+/// Buffered events per subscriber before the slowest one starts lagging.
+const EVENT_CAPACITY: usize = 256;
+
+/// Revoked identifiers as of one fetch.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RevocationSnapshot {
+    pub revoked_agents: HashSet<String>,
+    pub revoked_humans: HashSet<String>,
+}
+
+/// Where revocation snapshots come from. Called on a blocking thread, so
+/// implementations may do synchronous I/O.
+pub trait RevocationSource: Send + Sync + 'static {
+    fn fetch(&self) -> Result<RevocationSnapshot, String>;
+}
+
+impl<F> RevocationSource for F
+where
+    F: Fn() -> Result<RevocationSnapshot, String> + Send + Sync + 'static,
+{
+    fn fetch(&self) -> Result<RevocationSnapshot, String> {
+        self()
+    }
+}
+
+/// Emitted by the watcher when a refresh changes what is revoked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RevocationEvent {
+    AgentRevoked(String),
+    HumanRevoked(String),
+    /// A fetch failed; the previous snapshot stays in force.
+    RefreshFailed(String),
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    snapshot: RevocationSnapshot,
+    refreshed_at: Option<String>,
+}
+
+/// Shared, cheaply cloneable view of the current revocation snapshot.
+#[derive(Debug, Clone, Default)]
+pub struct RevocationState {
+    inner: Arc<RwLock<Inner>>,
+}
+
+impl RevocationState {
+    pub fn is_agent_revoked(&self, agent_id: &str) -> bool {
+        self.read(|i| i.snapshot.revoked_agents.contains(agent_id))
+    }
+
+    pub fn is_human_revoked(&self, human_id: &str) -> bool {
+        self.read(|i| i.snapshot.revoked_humans.contains(human_id))
+    }
+
+    /// Registry lookup by `dcp:` URI. Sources may list agents and humans
+    /// by bare identifier or by URI; both match. Other URI kinds are never
+    /// revoked here.
+    pub fn is_revoked(&self, uri: &DcpUri) -> bool {
+        let full = uri.to_string();
+        self.read(|i| match uri {
+            DcpUri::Agent(fp) => i.snapshot.revoked_agents.contains(fp) || i.snapshot.revoked_agents.contains(&full),
+            DcpUri::Human(id) => i.snapshot.revoked_humans.contains(id) || i.snapshot.revoked_humans.contains(&full),
+            _ => false,
+        })
+    }
+
+    /// When the snapshot was last replaced, or `None` before the first
+    /// successful fetch.
+    pub fn refreshed_at(&self) -> Option<String> {
+        self.read(|i| i.refreshed_at.clone())
+    }
+
+    /// Fail if the bundle's agent or responsible human is revoked. Does
+    /// not verify signatures; run it after verification.
+    pub fn check(&self, signed_bundle: &Value) -> VerificationResult {
+        let bundle = &signed_bundle["bundle"];
+        let agent = bundle["agent_passport"]["agent_id"].as_str().unwrap_or("");
+        let human = bundle["responsible_principal_record"]["human_id"].as_str().unwrap_or("");
+        let mut failures = Vec::new();
+        let agent_key = bundle["agent_passport"]["public_key"].as_str().and_then(|pk| DcpUri::agent_for_key(pk).ok());
+        if self.is_agent_revoked(agent) || agent_key.is_some_and(|uri| self.is_revoked(&uri)) {
+            failures.push(VerificationFailure::new(
+                VerifyError::Revoked,
+                format!("agent {}", agent),
+                "/bundle/agent_passport/agent_id",
+            ));
+        }
+        if self.is_human_revoked(human) {
+            failures.push(VerificationFailure::new(
+                VerifyError::Revoked,
+                format!("human {}", human),
+                "/bundle/responsible_principal_record/human_id",
+            ));
+        }
+        if failures.is_empty() {
+            VerificationResult::ok()
+        } else {
+            VerificationResult::fail_at(failures)
+        }
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&Inner) -> T) -> T {
+        let guard = self.inner.read().unwrap_or_else(|e| e.into_inner());
+        f(&guard)
+    }
+
+    /// Install `next` and return the events it implies.
+    fn replace(&self, next: RevocationSnapshot) -> Vec<RevocationEvent> {
+        let mut guard = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        let mut events: Vec<RevocationEvent> = next
+            .revoked_agents
+            .difference(&guard.snapshot.revoked_agents)
+            .map(|id| RevocationEvent::AgentRevoked(id.clone()))
+            .collect();
+        events.extend(
+            next.revoked_humans
+                .difference(&guard.snapshot.revoked_humans)
+                .map(|id| RevocationEvent::HumanRevoked(id.clone())),
+        );
+        guard.snapshot = next;
+        guard.refreshed_at = Some(utc_now_iso());
+        events
+    }
+}
+
+/// Background task keeping a [`RevocationState`] current. Dropping the
+/// watcher stops the task.
+pub struct RevocationWatcher {
+    source: Arc<dyn RevocationSource>,
+    state: RevocationState,
+    events: broadcast::Sender<RevocationEvent>,
+    task: JoinHandle<()>,
+}
+
+impl RevocationWatcher {
+    /// Start refreshing from `source` every `period`, beginning
+    /// immediately. Must be called inside a tokio runtime.
+    pub fn spawn(source: impl RevocationSource, period: Duration) -> Self {
+        let source: Arc<dyn RevocationSource> = Arc::new(source);
+        let state = RevocationState::default();
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+
+        let task = {
+            let (source, state, events) = (source.clone(), state.clone(), events.clone());
+            tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(period);
+                ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                loop {
+                    ticker.tick().await;
+                    refresh(&source, &state, &events).await;
+                }
+            })
+        };
+        Self { source, state, events, task }
+    }
+
+    /// Handle for verifiers.
+    pub fn state(&self) -> RevocationState {
+        self.state.clone()
+    }
+
+    /// Receive change events from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<RevocationEvent> {
+        self.events.subscribe()
+    }
+
+    /// Refresh outside the schedule and return the events it produced.
+    pub async fn refresh_now(&self) -> Vec<RevocationEvent> {
+        refresh(&self.source, &self.state, &self.events).await
+    }
+
+    pub fn stop(&self) {
+        self.task.abort();
+    }
+}
+
+impl Drop for RevocationWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn refresh(
+    source: &Arc<dyn RevocationSource>,
+    state: &RevocationState,
+    events: &broadcast::Sender<RevocationEvent>,
+) -> Vec<RevocationEvent> {
+    let src = source.clone();
+    let fetched = match tokio::task::spawn_blocking(move || src.fetch()).await {
+        Ok(r) => r,
+        Err(e) => Err(format!("revocation fetch task failed: {}", e)),
+    };
+    let produced = match fetched {
+        Ok(snapshot) => state.replace(snapshot),
+        Err(e) => vec![RevocationEvent::RefreshFailed(e)],
+    };
+    for event in &produced {
+        // No subscribers is fine.
+        let _ = events.send(event.clone());
+    }
+    produced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn snapshot(agents: &[&str]) -> RevocationSnapshot {
+        RevocationSnapshot {
+            revoked_agents: agents.iter().map(|a| a.to_string()).collect(),
+            revoked_humans: HashSet::new(),
+        }
+    }
+
+    /// Wait for the watcher's immediate first refresh, so later
+    /// `refresh_now` calls don't race it.
+    async fn first_refresh(watcher: &RevocationWatcher) {
+        while watcher.state().refreshed_at().is_none() {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refresh_emits_newly_revoked_only() {
+        let feed = Arc::new(Mutex::new(snapshot(&["did:agent:a"])));
+        let src = feed.clone();
+        let watcher = RevocationWatcher::spawn(move || Ok(src.lock().unwrap().clone()), Duration::from_secs(3600));
+        let mut rx = watcher.subscribe();
+
+        first_refresh(&watcher).await;
+        assert!(watcher.state().is_agent_revoked("did:agent:a"));
+
+        *feed.lock().unwrap() = snapshot(&["did:agent:a", "did:agent:b"]);
+        let events = watcher.refresh_now().await;
+        assert_eq!(events, [RevocationEvent::AgentRevoked("did:agent:b".into())]);
+
+        let mut seen = Vec::new();
+        while let Ok(e) = rx.try_recv() {
+            seen.push(e);
+        }
+        assert_eq!(seen.last(), Some(&RevocationEvent::AgentRevoked("did:agent:b".into())));
+    }
+
+    #[tokio::test]
+    async fn test_failed_fetch_keeps_previous_snapshot() {
+        let ok = Arc::new(Mutex::new(true));
+        let flag = ok.clone();
+        let watcher = RevocationWatcher::spawn(
+            move || if *flag.lock().unwrap() { Ok(snapshot(&["did:agent:a"])) } else { Err("down".into()) },
+            Duration::from_secs(3600),
+        );
+        first_refresh(&watcher).await;
+        *ok.lock().unwrap() = false;
+        let events = watcher.refresh_now().await;
+        assert_eq!(events, [RevocationEvent::RefreshFailed("down".into())]);
+        assert!(watcher.state().is_agent_revoked("did:agent:a"));
+
+        let sb = serde_json::json!({"bundle": {"agent_passport": {"agent_id": "did:agent:a"}}});
+        let result = watcher.state().check(&sb);
+        assert!(result.errors.unwrap()[0].starts_with("REVOKED: agent"));
+    }
+
+    #[tokio::test]
+    async fn test_lookup_by_uri() {
+        let fp = "0123456789abcdef0123456789abcdef";
+        let revoked = move || Ok(snapshot(&[&format!("dcp:agent:{}", fp)]));
+        let watcher = RevocationWatcher::spawn(revoked, Duration::from_secs(3600));
+        first_refresh(&watcher).await;
+        let state = watcher.state();
+        assert!(state.is_revoked(&DcpUri::Agent(fp.into())));
+        assert!(!state.is_revoked(&DcpUri::Human(fp.into())));
+    }
+}
@@ -0,0 +1,111 @@
+//! Human-readable rendering of signed data.
+//!
+//! Reviewers want to read what was signed, not a one-line canonical dump,
+//! but an ordinary pretty-printer may reorder keys or reformat numbers
+//! and so show something other than the signed bytes. [`pretty_canonical`]
+//! only adds whitespace to the canonical form: removing it again with
+//! [`strip_whitespace`] gives back [`canonicalize`]'s output byte for
+//! byte, so the rendering hashes to the signed `bundle_hash`.
+//!
+//! ```
+//! use dcp_ai::crypto::canonicalize;
+//! use dcp_ai::render::{pretty_canonical, strip_whitespace};
+//!
+//! let obj = serde_json::json!({"b": [1, 2.5], "a": "x y"});
+//! assert_eq!(pretty_canonical(&obj), "{\n  \"a\": \"x y\",\n  \"b\": [\n    1,\n    2.5\n  ]\n}");
+//! assert_eq!(strip_whitespace(&pretty_canonical(&obj)), canonicalize(&obj));
+//! ```
+
+use serde_json::Value;
+
+use crate::crypto::canonicalize;
+
+/// Indentation per nesting level.
+pub const INDENT: &str = "  ";
+
+/// `obj` in canonical form, one member or element per line. Scalars are
+/// written by [`canonicalize`] itself, and members in its key order.
+pub fn pretty_canonical(obj: &Value) -> String {
+    let mut out = String::new();
+    write_pretty(obj, 0, &mut out);
+    out
+}
+
+fn write_pretty(value: &Value, depth: usize, out: &mut String) {
+    let newline = |depth: usize, out: &mut String| {
+        out.push('\n');
+        (0..depth).for_each(|_| out.push_str(INDENT));
+    };
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            let mut members: Vec<(&String, &Value)> = map.iter().collect();
+            members.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (k, v)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                newline(depth + 1, out);
+                out.push_str(&canonicalize(&Value::String(k.clone())));
+                out.push_str(": ");
+                write_pretty(v, depth + 1, out);
+            }
+            newline(depth, out);
+            out.push('}');
+        }
+        Value::Array(items) if !items.is_empty() => {
+            out.push('[');
+            for (i, v) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                newline(depth + 1, out);
+                write_pretty(v, depth + 1, out);
+            }
+            newline(depth, out);
+            out.push(']');
+        }
+        _ => out.push_str(&canonicalize(value)),
+    }
+}
+
+/// `json` without whitespace outside string literals: the inverse of
+/// [`pretty_canonical`].
+pub fn strip_whitespace(json: &str) -> String {
+    let mut out = String::with_capacity(json.len());
+    let (mut in_string, mut escaped) = (false, false);
+    for c in json.chars() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if matches!(c, ' ' | '\n' | '\r' | '\t') {
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+
+    #[test]
+    fn test_stripping_reproduces_canonical_form() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let tricky = serde_json::json!({
+            "z": {}, "é": [], "a b": "line\nbreak \"quoted\" \\ tab\t",
+            "n": [0, -1, 1.5, 1e300, 12345678901234567890u64], "nested": [[{"k": null}], true],
+        });
+        for value in [&set.get("citizenship_bundle.signed").unwrap().value, &tricky] {
+            assert_eq!(strip_whitespace(&pretty_canonical(value)), canonicalize(value));
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! Evidence retention: deleting audit evidence without breaking the chain.
+//!
+//! Evidence references (`evidence.result_ref`, and the tool that produced
+//! them) can point at personal data that must be erased after a retention
+//! period. [`apply`] replaces the evidence of every entry past its
+//! deadline with an [`EvidenceTombstone`] holding the entry's original
+//! hash. Verifiers and [`crate::bundle::sign_bundle`] use that hash for
+//! the entry in the `prev_hash` chain and the merkle tree ([`entry_hash`]),
+//! so the chain still links and `merkle_root` is unchanged.
+//!
+//! The bundle must be signed again after [`apply`]: `bundle_hash` covers
+//! the deleted evidence. Verification accepts tombstoned entries and
+//! reports each as a warning. The tombstone stands in for the entry's
+//! original bytes, so its remaining fields are covered only by the new
+//! signature; the unchanged `merkle_root` still matches the one the
+//! original signer committed to.
+//!
+//! ```ignore
+//! let policy = RetentionPolicy::new("gdpr-30d", Duration::from_secs(30 * 86_400)).tool("crm", Duration::ZERO);
+//! let deleted = retention::apply(&mut signed.bundle, &policy)?;
+//! let signed = sign_bundle(signed.bundle, &SignOptions::new(&retention_key))?;
+//! ```
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::challenge::iso_from_secs;
+use crate::crypto::hash_object;
+use crate::types::{AuditEvidence, CitizenshipBundle, EvidenceTombstone};
+use crate::v2::advisory::{now_epoch_secs, parse_iso_epoch_secs};
+
+/// How long evidence is kept, counted from the entry's `timestamp`.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RetentionPolicy {
+    /// Recorded in every tombstone this policy produces.
+    pub policy_id: String,
+    /// Period for evidence with no per-tool period.
+    pub retain_for: Duration,
+    /// Periods by `evidence.tool`.
+    pub per_tool: HashMap<String, Duration>,
+    /// Whether a tombstoned entry keeps `evidence.tool`. Default `true`;
+    /// `false` strips it with the reference.
+    pub keep_tool: bool,
+}
+
+impl RetentionPolicy {
+    pub fn new(policy_id: impl Into<String>, retain_for: Duration) -> Self {
+        Self { policy_id: policy_id.into(), retain_for, per_tool: HashMap::new(), keep_tool: true }
+    }
+
+    /// Keep evidence produced by `tool` for `retain_for` instead.
+    pub fn tool(mut self, tool: impl Into<String>, retain_for: Duration) -> Self {
+        self.per_tool.insert(tool.into(), retain_for);
+        self
+    }
+
+    /// Strip `evidence.tool` along with the reference.
+    pub fn strip_tool(mut self) -> Self {
+        self.keep_tool = false;
+        self
+    }
+
+    fn period(&self, evidence: &AuditEvidence) -> Duration {
+        evidence.tool.as_ref().and_then(|t| self.per_tool.get(t)).copied().unwrap_or(self.retain_for)
+    }
+}
+
+/// [`apply_at`] as of now.
+pub fn apply(bundle: &mut CitizenshipBundle, policy: &RetentionPolicy) -> Result<Vec<String>, String> {
+    apply_at(bundle, policy, now_epoch_secs())
+}
+
+/// Tombstone the evidence of every entry whose retention deadline
+/// (`timestamp` + period) is at or before `now_secs`. Returns the
+/// `audit_id`s tombstoned. Entries already tombstoned or with no evidence
+/// are left alone; an entry whose timestamp does not parse is an error,
+/// since its deadline is unknown.
+pub fn apply_at(
+    bundle: &mut CitizenshipBundle,
+    policy: &RetentionPolicy,
+    now_secs: i64,
+) -> Result<Vec<String>, String> {
+    let mut deleted = Vec::new();
+    for entry in &mut bundle.audit_entries {
+        let evidence = &entry.evidence;
+        if evidence.tombstone.is_some() || (evidence.tool.is_none() && evidence.result_ref.is_none()) {
+            continue;
+        }
+        let at = parse_iso_epoch_secs(&entry.timestamp)
+            .ok_or_else(|| format!("audit entry {}: unparseable timestamp {}", entry.audit_id, entry.timestamp))?;
+        if at.saturating_add(policy.period(evidence).as_secs() as i64) > now_secs {
+            continue;
+        }
+        let tombstone = EvidenceTombstone {
+            entry_hash: hash_object(&serde_json::to_value(&*entry).map_err(|e| e.to_string())?),
+            evidence_hash: hash_object(&serde_json::to_value(evidence).map_err(|e| e.to_string())?),
+            deleted_at: iso_from_secs(now_secs),
+            policy_id: policy.policy_id.clone(),
+        };
+        entry.evidence = AuditEvidence {
+            tool: entry.evidence.tool.take().filter(|_| policy.keep_tool),
+            result_ref: None,
+            tombstone: Some(tombstone),
+        };
+        deleted.push(entry.audit_id.clone());
+    }
+    Ok(deleted)
+}
+
+/// The hash an audit entry contributes to the `prev_hash` chain and the
+/// merkle tree: the tombstoned original's, or its own.
+pub fn entry_hash(entry: &Value) -> String {
+    match tombstoned_hash(entry) {
+        Some(h) => h.to_string(),
+        None => hash_object(entry),
+    }
+}
+
+/// The original entry hash recorded by a tombstone, if `entry` has one
+/// and it is a SHA-256 hex digest. The tombstone is bundle content, so
+/// anything else is ignored and the entry stands for itself.
+pub fn tombstoned_hash(entry: &Value) -> Option<&str> {
+    let hash = entry.pointer("/evidence/tombstone/entry_hash").and_then(Value::as_str)?;
+    let is_digest = hash.len() == 64 && hash.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'));
+    is_digest.then_some(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::{sign_bundle, SignOptions};
+    use crate::crypto::generate_keypair;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+    use crate::verify::verify_signed_bundle;
+
+    #[test]
+    fn test_tombstones_keep_chain_and_merkle_root() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let mut bundle: CitizenshipBundle =
+            serde_json::from_value(set.get("citizenship_bundle").unwrap().value.clone()).unwrap();
+        let (_, sk) = generate_keypair();
+        let original = sign_bundle(bundle.clone(), &SignOptions::new(&sk)).unwrap();
+
+        let timestamp = parse_iso_epoch_secs(&bundle.audit_entries[0].timestamp).unwrap();
+        let policy = RetentionPolicy::new("gdpr-30d", Duration::from_secs(30 * 86_400));
+        assert!(apply_at(&mut bundle, &policy, timestamp + 86_400).unwrap().is_empty());
+        let deleted = apply_at(&mut bundle, &policy, timestamp + 31 * 86_400).unwrap();
+        assert_eq!(deleted.len(), bundle.audit_entries.len());
+        assert!(bundle.audit_entries.iter().all(|e| e.evidence.result_ref.is_none()));
+
+        let resigned = sign_bundle(bundle, &SignOptions::new(&sk)).unwrap();
+        assert_eq!(resigned.signature.merkle_root, original.signature.merkle_root);
+        let result = verify_signed_bundle(&serde_json::to_value(&resigned).unwrap(), None);
+        assert!(result.verified, "{:?}", result.errors);
+        assert_eq!(result.warnings.len(), deleted.len());
+    }
+
+    #[test]
+    fn test_malformed_tombstone_hash_fails_without_panicking() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let mut bundle: CitizenshipBundle =
+            serde_json::from_value(set.get("citizenship_bundle").unwrap().value.clone()).unwrap();
+        assert!(bundle.audit_entries.len() >= 2);
+        let timestamp = parse_iso_epoch_secs(&bundle.audit_entries[0].timestamp).unwrap();
+        let policy = RetentionPolicy::new("gdpr-30d", Duration::from_secs(30 * 86_400));
+        apply_at(&mut bundle, &policy, timestamp + 31 * 86_400).unwrap();
+        bundle.audit_entries[0].evidence.tombstone.as_mut().unwrap().entry_hash = "zz".into();
+
+        let (_, sk) = generate_keypair();
+        let signed = serde_json::to_value(sign_bundle(bundle, &SignOptions::new(&sk)).unwrap()).unwrap();
+        assert_eq!(tombstoned_hash(&signed["bundle"]["audit_entries"][0]), None);
+        assert!(!verify_signed_bundle(&signed, None).verified);
+    }
+}
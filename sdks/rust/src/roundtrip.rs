@@ -0,0 +1,125 @@
+//! Serde round-trip equivalence guard.
+//!
+//! A DCP artifact is signed over its canonical JSON. If deserializing it
+//! into a Rust type drops something — an unknown field, an explicit
+//! `null` the type skips, a renamed key — re-serializing it yields
+//! different bytes, and the bundle later fails verification for no
+//! obvious reason. [`check`] catches that at the parse boundary by
+//! comparing canonical hashes before and after, and names the JSON
+//! Pointers that differ.
+//!
+//! [`from_value`] and [`from_str`] are drop-in replacements for the
+//! `serde_json` functions. With the `strict-roundtrip` feature in a debug
+//! build they run [`check`] and panic on a mismatch; otherwise they cost
+//! nothing extra.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::crypto::hash_object;
+use crate::types::json_pointer_escape;
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum RoundTripError {
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// Re-serialization changed the canonical form.
+    #[error("{type_name} does not round-trip: canonical hash {expected} became {actual}; differs at {}", .paths.join(", "))]
+    Mismatch {
+        type_name: &'static str,
+        expected: String,
+        actual: String,
+        /// JSON Pointers where the re-serialized value differs.
+        paths: Vec<String>,
+    },
+}
+
+/// Deserialize `original` as `T` and confirm serializing it back gives
+/// the same canonical hash.
+pub fn check<T: DeserializeOwned + Serialize>(original: &Value) -> Result<T, RoundTripError> {
+    let typed: T = serde_json::from_value(original.clone())?;
+    let reserialized = serde_json::to_value(&typed)?;
+    let (expected, actual) = (hash_object(original), hash_object(&reserialized));
+    if expected != actual {
+        let mut paths = Vec::new();
+        diff("", original, &reserialized, &mut paths);
+        return Err(RoundTripError::Mismatch { type_name: std::any::type_name::<T>(), expected, actual, paths });
+    }
+    Ok(typed)
+}
+
+/// `serde_json::from_value` with the round-trip guard under
+/// `strict-roundtrip` in debug builds.
+pub fn from_value<T: DeserializeOwned + Serialize>(value: Value) -> Result<T, serde_json::Error> {
+    #[cfg(all(feature = "strict-roundtrip", debug_assertions))]
+    {
+        match check(&value) {
+            Ok(typed) => Ok(typed),
+            Err(RoundTripError::Json(e)) => Err(e),
+            Err(mismatch) => panic!("{}", mismatch),
+        }
+    }
+    #[cfg(not(all(feature = "strict-roundtrip", debug_assertions)))]
+    {
+        serde_json::from_value(value)
+    }
+}
+
+/// `serde_json::from_str` with the round-trip guard under
+/// `strict-roundtrip` in debug builds.
+pub fn from_str<T: DeserializeOwned + Serialize>(s: &str) -> Result<T, serde_json::Error> {
+    from_value(serde_json::from_str(s)?)
+}
+
+fn diff(pointer: &str, a: &Value, b: &Value, out: &mut Vec<String>) {
+    match (a, b) {
+        (Value::Object(ma), Value::Object(mb)) => {
+            for (k, va) in ma {
+                let p = format!("{}/{}", pointer, json_pointer_escape(k));
+                match mb.get(k) {
+                    Some(vb) => diff(&p, va, vb, out),
+                    None => out.push(p),
+                }
+            }
+            for k in mb.keys().filter(|k| !ma.contains_key(*k)) {
+                out.push(format!("{}/{}", pointer, json_pointer_escape(k)));
+            }
+        }
+        (Value::Array(xa), Value::Array(xb)) if xa.len() == xb.len() => {
+            for (i, (va, vb)) in xa.iter().zip(xb).enumerate() {
+                diff(&format!("{}/{}", pointer, i), va, vb, out);
+            }
+        }
+        _ if a != b => out.push(if pointer.is_empty() { "/".to_string() } else { pointer.to_string() }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+    use crate::types::{ResponsiblePrincipalRecord, SignedBundle};
+
+    #[test]
+    fn test_fixture_bundle_round_trips() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let value = set.get("citizenship_bundle.signed").unwrap().value.clone();
+        assert!(check::<SignedBundle>(&value).is_ok());
+    }
+
+    #[test]
+    fn test_lossy_fields_are_located() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let mut rpr = set.get("citizenship_bundle.signed").unwrap().value["bundle"]["responsible_principal_record"].clone();
+        rpr["contact"] = Value::Null;
+        rpr["x-extension"] = "kept by other SDKs".into();
+
+        match check::<ResponsiblePrincipalRecord>(&rpr) {
+            Err(RoundTripError::Mismatch { paths, .. }) => assert_eq!(paths, ["/contact", "/x-extension"]),
+            other => panic!("expected mismatch, got {:?}", other.map(|_| ())),
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Validated signer identities.
+//!
+//! [`Signer`] is the wire form of `signature.signer`: three strings. A
+//! [`SignerIdentity`] is one that has been checked: a known
+//! [`SignerType`], an id that follows that type's rules, and an Ed25519
+//! public key that is on the curve and not of small order.
+//!
+//! | Type           | Id rules (besides 1-256 characters, no whitespace)           |
+//! |----------------|--------------------------------------------------------------|
+//! | `human`        | a `did:` id, a `dcp:human:` URI, or a plain id               |
+//! | `agent`        | as `human`; a `dcp:agent:` URI must be the key's fingerprint |
+//! | `organization` | a `did:` id or a plain id                                    |
+//! | `notary`       | a `did:` id or a plain id                                    |
+//!
+//! ```
+//! use dcp_ai::signer::{SignerIdentity, SignerType};
+//!
+//! let pk = dcp_ai::crypto::generate_keypair().0;
+//! let signer = SignerIdentity::new("human", "did:human:alice", &pk).unwrap();
+//! assert_eq!(signer.signer_type, SignerType::Human);
+//! assert!(SignerIdentity::new("robot", "did:human:alice", &pk).is_err());
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use crate::b64::{self, B64Error};
+use crate::types::{PublicKeyB64, Signer};
+use crate::uri::DcpUri;
+
+/// Longest accepted signer id, in characters.
+pub const MAX_ID_LEN: usize = 256;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum SignerError {
+    #[error("unknown signer type {0:?}")]
+    UnknownType(String),
+    #[error("invalid {signer_type} signer id {id:?}: {reason}")]
+    InvalidId { signer_type: SignerType, id: String, reason: &'static str },
+    #[error("invalid signer key: {0}")]
+    InvalidKey(#[from] B64Error),
+    #[error("signer key is not a valid Ed25519 point")]
+    NotOnCurve,
+    #[error("signer key has small order")]
+    WeakKey,
+    #[error("signer id {id} is not the fingerprint of the signer key ({expected})")]
+    KeyMismatch { id: String, expected: String },
+}
+
+/// `signature.signer.type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SignerType {
+    Human,
+    Agent,
+    Organization,
+    Notary,
+}
+
+impl SignerType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SignerType::Human => "human",
+            SignerType::Agent => "agent",
+            SignerType::Organization => "organization",
+            SignerType::Notary => "notary",
+        }
+    }
+}
+
+impl fmt::Display for SignerType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SignerType {
+    type Err = SignerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(SignerType::Human),
+            "agent" => Ok(SignerType::Agent),
+            "organization" => Ok(SignerType::Organization),
+            "notary" => Ok(SignerType::Notary),
+            _ => Err(SignerError::UnknownType(s.to_string())),
+        }
+    }
+}
+
+/// A [`Signer`] whose type, id, and key have been validated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignerIdentity {
+    pub signer_type: SignerType,
+    pub id: String,
+    pub public_key: PublicKeyB64,
+}
+
+impl SignerIdentity {
+    pub fn new(signer_type: &str, id: &str, public_key_b64: &str) -> Result<Self, SignerError> {
+        let signer_type: SignerType = signer_type.parse()?;
+        let public_key = PublicKeyB64::parse(public_key_b64)?;
+        let key = ed25519_dalek::VerifyingKey::from_bytes(&b64::decode_exact::<32>(public_key_b64)?)
+            .map_err(|_| SignerError::NotOnCurve)?;
+        if key.is_weak() {
+            return Err(SignerError::WeakKey);
+        }
+        check_id(signer_type, id, public_key_b64)?;
+        Ok(Self { signer_type, id: id.to_string(), public_key })
+    }
+
+    /// Validate a `signature.signer` JSON object. Missing fields are
+    /// treated as empty and fail validation.
+    pub fn from_value(signer: &Value) -> Result<Self, SignerError> {
+        let field = |name: &str| signer.get(name).and_then(Value::as_str).unwrap_or("");
+        Self::new(field("type"), field("id"), field("public_key_b64"))
+    }
+
+    /// Whether `other` names the same principal: the same type and id.
+    /// Keys are not compared, so a principal that rotated its key is
+    /// still the same principal.
+    pub fn is_same_principal(&self, other: &SignerIdentity) -> bool {
+        self.signer_type == other.signer_type && self.id == other.id
+    }
+
+    /// Whether this signer's key is one of `keys`, e.g.
+    /// [`crate::verify::VerifyOptions::trusted_signer_keys`].
+    pub fn key_in<K: AsRef<str>>(&self, keys: &[K]) -> bool {
+        keys.iter().any(|k| k.as_ref() == self.public_key.as_str())
+    }
+}
+
+impl TryFrom<&Signer> for SignerIdentity {
+    type Error = SignerError;
+
+    fn try_from(signer: &Signer) -> Result<Self, Self::Error> {
+        Self::new(&signer.signer_type, &signer.id, signer.public_key_b64.as_str())
+    }
+}
+
+impl fmt::Display for SignerIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.signer_type, self.id)
+    }
+}
+
+/// [`SignerIdentity::is_same_principal`] on unvalidated signers: `false`
+/// if either is invalid.
+pub fn is_same_principal(a: &Signer, b: &Signer) -> bool {
+    match (SignerIdentity::try_from(a), SignerIdentity::try_from(b)) {
+        (Ok(a), Ok(b)) => a.is_same_principal(&b),
+        _ => false,
+    }
+}
+
+fn check_id(signer_type: SignerType, id: &str, public_key_b64: &str) -> Result<(), SignerError> {
+    let invalid = |reason| Err(SignerError::InvalidId { signer_type, id: id.to_string(), reason });
+    if id.is_empty() {
+        return invalid("empty");
+    }
+    if id.chars().count() > MAX_ID_LEN {
+        return invalid("longer than 256 characters");
+    }
+    if id.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return invalid("contains whitespace or control characters");
+    }
+    if let Some(rest) = id.strip_prefix("did:") {
+        let valid = rest.split_once(':').is_some_and(|(method, specific)| {
+            !method.is_empty()
+                && method.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+                && !specific.is_empty()
+        });
+        return if valid { Ok(()) } else { invalid("not a did:<method>:<id>") };
+    }
+    if !id.starts_with(crate::uri::SCHEME) {
+        return Ok(());
+    }
+    match (signer_type, id.parse::<DcpUri>()) {
+        (SignerType::Human, Ok(DcpUri::Human(_))) => Ok(()),
+        (SignerType::Agent, Ok(uri @ DcpUri::Agent(_))) => {
+            let expected = DcpUri::agent_for_key(public_key_b64)?;
+            if uri == expected {
+                Ok(())
+            } else {
+                Err(SignerError::KeyMismatch { id: id.to_string(), expected: expected.to_string() })
+            }
+        }
+        (_, Ok(_)) => invalid("dcp: URI of another kind"),
+        (_, Err(_)) => invalid("malformed dcp: URI"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+
+    #[test]
+    fn test_signer_identity_rules() {
+        let (pk, _) = generate_keypair();
+        let agent_uri = DcpUri::agent_for_key(&pk).unwrap().to_string();
+        assert!(SignerIdentity::new("agent", &agent_uri, &pk).is_ok());
+        let (other_pk, _) = generate_keypair();
+        assert!(matches!(
+            SignerIdentity::new("agent", &agent_uri, &other_pk),
+            Err(SignerError::KeyMismatch { .. })
+        ));
+        assert!(SignerIdentity::new("human", &agent_uri, &pk).is_err());
+        assert!(SignerIdentity::new("human", "did::alice", &pk).is_err());
+        assert!(SignerIdentity::new("human", "alice smith", &pk).is_err());
+        assert!(SignerIdentity::new("organization", "acme-corp", &pk).is_ok());
+
+        // The identity point is of small order.
+        let mut identity_point = [0u8; 32];
+        identity_point[0] = 1;
+        let weak = crate::b64::encode(identity_point);
+        assert_eq!(SignerIdentity::new("human", "alice", &weak), Err(SignerError::WeakKey));
+
+        let alice = SignerIdentity::new("human", "did:human:alice", &pk).unwrap();
+        let rotated = SignerIdentity::new("human", "did:human:alice", &other_pk).unwrap();
+        assert!(alice.is_same_principal(&rotated));
+        assert!(!alice.is_same_principal(&SignerIdentity::new("agent", "did:human:alice", &pk).unwrap()));
+        assert!(alice.key_in(&[pk.as_str()]) && !rotated.key_in(&[pk.as_str()]));
+    }
+}
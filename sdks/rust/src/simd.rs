@@ -0,0 +1,146 @@
+//! simd-json interchange (feature = "simd").
+//!
+//! High-throughput verifiers parse with simd-json. The verifier itself
+//! operates on `serde_json::Value`; this module converts between the two
+//! trees directly (no intermediate string) and offers a parse path that
+//! lets simd-json deserialize straight into `serde_json::Value`.
+//!
+//! ```ignore
+//! let mut bytes = std::fs::read("bundle.signed.json")?;
+//! let sb = dcp_ai::simd::parse_signed_bundle(&mut bytes)?;
+//! let result = dcp_ai::verify_signed_bundle(&sb, None);
+//! ```
+
+use serde_json::{Map, Number, Value};
+use simd_json::{OwnedValue, StaticNode};
+
+use crate::types::{VerificationResult, VerifyError};
+use crate::verify::{verify_signed_bundle_with_options, VerifyOptions};
+
+/// Parse JSON bytes with simd-json directly into a `serde_json::Value`.
+/// simd-json parses in place, so `bytes` is clobbered.
+pub fn parse_signed_bundle(bytes: &mut [u8]) -> Result<Value, String> {
+    simd_json::serde::from_slice::<Value>(bytes).map_err(|e| e.to_string())
+}
+
+/// Convert a simd-json tree into a `serde_json::Value`.
+///
+/// Non-finite floats (which simd-json can carry but JSON cannot) are
+/// rejected with the JSON pointer of the offending node; mapping them to
+/// `null` would change what the signature covers.
+pub fn owned_to_serde(v: &OwnedValue) -> Result<Value, String> {
+    owned_to_serde_at(v, &mut String::new())
+}
+
+fn owned_to_serde_at(v: &OwnedValue, pointer: &mut String) -> Result<Value, String> {
+    Ok(match v {
+        OwnedValue::Static(StaticNode::Null) => Value::Null,
+        OwnedValue::Static(StaticNode::Bool(b)) => Value::Bool(*b),
+        OwnedValue::Static(StaticNode::I64(i)) => Value::Number((*i).into()),
+        OwnedValue::Static(StaticNode::U64(u)) => Value::Number((*u).into()),
+        OwnedValue::Static(StaticNode::F64(f)) => match Number::from_f64(*f) {
+            Some(n) => Value::Number(n),
+            None => return Err(format!("non-finite number {} at {:?}", f, pointer)),
+        },
+        OwnedValue::String(s) => Value::String(s.to_string()),
+        OwnedValue::Array(arr) => {
+            let mut out = Vec::with_capacity(arr.len());
+            for (i, item) in arr.iter().enumerate() {
+                let len = pointer.len();
+                pointer.push_str(&format!("/{}", i));
+                out.push(owned_to_serde_at(item, pointer)?);
+                pointer.truncate(len);
+            }
+            Value::Array(out)
+        }
+        OwnedValue::Object(obj) => {
+            let mut map = Map::with_capacity(obj.len());
+            for (k, v) in obj.iter() {
+                let len = pointer.len();
+                pointer.push('/');
+                pointer.push_str(&k.replace('~', "~0").replace('/', "~1"));
+                map.insert(k.to_string(), owned_to_serde_at(v, pointer)?);
+                pointer.truncate(len);
+            }
+            Value::Object(map)
+        }
+    })
+}
+
+/// Convert a `serde_json::Value` into a simd-json tree.
+pub fn serde_to_owned(v: &Value) -> OwnedValue {
+    match v {
+        Value::Null => OwnedValue::Static(StaticNode::Null),
+        Value::Bool(b) => OwnedValue::Static(StaticNode::Bool(*b)),
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                OwnedValue::Static(StaticNode::I64(i))
+            } else if let Some(u) = n.as_u64() {
+                OwnedValue::Static(StaticNode::U64(u))
+            } else {
+                OwnedValue::Static(StaticNode::F64(n.as_f64().unwrap_or(0.0)))
+            }
+        }
+        Value::String(s) => OwnedValue::from(s.as_str()),
+        Value::Array(arr) => OwnedValue::from(arr.iter().map(serde_to_owned).collect::<Vec<_>>()),
+        Value::Object(map) => {
+            let mut obj = simd_json::owned::Object::with_capacity(map.len());
+            for (k, v) in map {
+                obj.insert(k.clone(), serde_to_owned(v));
+            }
+            OwnedValue::from(obj)
+        }
+    }
+}
+
+/// Verify a signed bundle that was parsed into a simd-json tree.
+pub fn verify_signed_bundle_owned(
+    signed_bundle: &OwnedValue,
+    public_key_b64: Option<&str>,
+    opts: &VerifyOptions,
+) -> VerificationResult {
+    match owned_to_serde(signed_bundle) {
+        Ok(value) => verify_signed_bundle_with_options(&value, public_key_b64, opts),
+        Err(e) => VerificationResult::fail(VerifyError::InvalidValue { entry: None }, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_roundtrip_preserves_canonical_hash() {
+        let v = json!({
+            "a": [1, -2, 18446744073709551615u64, true, null],
+            "b": {"nested": "str", "f": 1.5}
+        });
+        let back = owned_to_serde(&serde_to_owned(&v)).unwrap();
+        assert_eq!(crate::crypto::hash_object(&v), crate::crypto::hash_object(&back));
+    }
+
+    #[test]
+    fn test_parse_direct_into_serde_value() {
+        let mut bytes = br#"{"bundle":{"x":1},"signature":{}}"#.to_vec();
+        let v = parse_signed_bundle(&mut bytes).unwrap();
+        assert_eq!(v["bundle"]["x"], 1);
+    }
+
+    #[test]
+    fn test_non_finite_float_is_rejected_not_nulled() {
+        let mut tree = serde_to_owned(&json!({"bundle": {"a/b": [0, 1.0]}, "signature": {}}));
+        let nan = OwnedValue::Static(StaticNode::F64(f64::NAN));
+        if let OwnedValue::Object(root) = &mut tree {
+            if let Some(OwnedValue::Object(bundle)) = root.get_mut("bundle") {
+                bundle.insert("a/b".into(), OwnedValue::from(vec![OwnedValue::from(0), nan]));
+            }
+        }
+        let err = owned_to_serde(&tree).unwrap_err();
+        assert!(err.contains("/bundle/a~1b/1"), "{}", err);
+
+        let result = verify_signed_bundle_owned(&tree, None, &VerifyOptions::default());
+        assert!(!result.verified);
+        assert!(result.errors.unwrap()[0].starts_with("INVALID VALUE: non-finite number"));
+    }
+}
@@ -0,0 +1,192 @@
+//! DCP v1 enumerations, as listed in `schemas/v1`.
+//!
+//! The wire types keep these fields as strings so v1.1 producers and
+//! older bundles still parse; this module is the one place that says
+//! which values the spec allows. Each field has a string list (e.g.
+//! [`ACTION_TYPES`]) and an enum that parses from and prints to the wire
+//! value. [`validate_bundle`] checks a whole bundle.
+//!
+//! ```
+//! use dcp_ai::spec::{ActionType, DATA_CLASSES};
+//!
+//! assert_eq!("send_email".parse::<ActionType>().unwrap(), ActionType::SendEmail);
+//! assert!("send_fax".parse::<ActionType>().is_err());
+//! assert!(DATA_CLASSES.contains(&"pii"));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{CitizenshipBundle, VerificationFailure, VerificationResult, VerifyError};
+
+/// A value outside the spec's list for `field`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("INVALID VALUE: {field} {value:?} is not one of {expected:?}")]
+pub struct SpecError {
+    pub field: &'static str,
+    pub value: String,
+    pub expected: &'static [&'static str],
+}
+
+macro_rules! spec_enum {
+    (
+        $(#[$doc:meta])*
+        $name:ident, $list:ident, $field:literal { $($variant:ident => $wire:literal,)+ }
+    ) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        #[non_exhaustive]
+        pub enum $name {
+            $(#[serde(rename = $wire)] $variant,)+
+        }
+
+        #[doc = concat!("Valid `", $field, "` values.")]
+        pub const $list: &[&str] = &[$($wire),+];
+
+        impl $name {
+            pub const ALL: &'static [$name] = &[$($name::$variant),+];
+
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $wire,)+
+                }
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = SpecError;
+
+            fn from_str(s: &str) -> Result<Self, SpecError> {
+                match s {
+                    $($wire => Ok($name::$variant),)+
+                    _ => Err(SpecError { field: $field, value: s.to_string(), expected: $list }),
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+}
+
+spec_enum! {
+    /// `Intent::action_type`.
+    ActionType, ACTION_TYPES, "action_type" {
+        Browse => "browse",
+        ApiCall => "api_call",
+        SendEmail => "send_email",
+        CreateCalendarEvent => "create_calendar_event",
+        InitiatePayment => "initiate_payment",
+        UpdateCrm => "update_crm",
+        WriteFile => "write_file",
+        ExecuteCode => "execute_code",
+    }
+}
+
+spec_enum! {
+    /// An element of `Intent::data_classes`.
+    DataClass, DATA_CLASSES, "data_classes" {
+        NoData => "none",
+        ContactInfo => "contact_info",
+        Pii => "pii",
+        Credentials => "credentials",
+        FinancialData => "financial_data",
+        HealthData => "health_data",
+        ChildrenData => "children_data",
+        CompanyConfidential => "company_confidential",
+    }
+}
+
+spec_enum! {
+    /// `ResponsiblePrincipalRecord::entity_type`.
+    EntityType, ENTITY_TYPES, "entity_type" {
+        NaturalPerson => "natural_person",
+        Organization => "organization",
+    }
+}
+
+spec_enum! {
+    /// `ResponsiblePrincipalRecord::liability_mode`.
+    LiabilityMode, LIABILITY_MODES, "liability_mode" {
+        OwnerResponsible => "owner_responsible",
+    }
+}
+
+spec_enum! {
+    /// `PolicyDecision::decision`.
+    Decision, DECISIONS, "decision" {
+        Approve => "approve",
+        Escalate => "escalate",
+        Block => "block",
+    }
+}
+
+spec_enum! {
+    /// `AgentPassport::status`.
+    AgentStatus, AGENT_STATUSES, "status" {
+        Active => "active",
+        Revoked => "revoked",
+        Suspended => "suspended",
+    }
+}
+
+/// Check every enumerated field of `bundle` against the spec lists.
+/// Failures carry `INVALID VALUE` messages and pointers into the signed
+/// bundle.
+pub fn validate_bundle(bundle: &CitizenshipBundle) -> VerificationResult {
+    fn check<T: std::str::FromStr<Err = SpecError>>(value: &str, pointer: String, out: &mut Vec<VerificationFailure>) {
+        if let Err(e) = value.parse::<T>() {
+            let detail = format!("{} {:?} is not one of {:?}", e.field, e.value, e.expected);
+            let kind = VerifyError::InvalidValue { entry: None };
+            out.push(VerificationFailure::new(kind, detail, pointer).actual(value));
+        }
+    }
+
+    let mut failures = Vec::new();
+    let rpr = &bundle.responsible_principal_record;
+    let rpr_ptr = "/bundle/responsible_principal_record";
+    check::<EntityType>(&rpr.entity_type, format!("{}/entity_type", rpr_ptr), &mut failures);
+    check::<LiabilityMode>(&rpr.liability_mode, format!("{}/liability_mode", rpr_ptr), &mut failures);
+    check::<AgentStatus>(&bundle.agent_passport.status, "/bundle/agent_passport/status".into(), &mut failures);
+    check::<ActionType>(&bundle.intent.action_type, "/bundle/intent/action_type".into(), &mut failures);
+    for (i, class) in bundle.intent.data_classes.iter().enumerate() {
+        check::<DataClass>(class, format!("/bundle/intent/data_classes/{}", i), &mut failures);
+    }
+    check::<Decision>(&bundle.policy_decision.decision, "/bundle/policy_decision/decision".into(), &mut failures);
+
+    if failures.is_empty() {
+        VerificationResult::ok()
+    } else {
+        VerificationResult::fail_at(failures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+
+    #[test]
+    fn test_round_trips_and_validation() {
+        for action in ActionType::ALL {
+            assert_eq!(action.as_str().parse::<ActionType>().unwrap(), *action);
+            assert_eq!(serde_json::to_value(action).unwrap(), action.as_str());
+        }
+        assert_eq!(DataClass::ALL.len(), DATA_CLASSES.len());
+
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let mut bundle: CitizenshipBundle =
+            serde_json::from_value(set.get("citizenship_bundle").unwrap().value.clone()).unwrap();
+        assert!(validate_bundle(&bundle).verified);
+
+        bundle.intent.data_classes.push("gossip".into());
+        bundle.policy_decision.decision = "maybe".into();
+        let result = validate_bundle(&bundle);
+        let failures = result.failures.unwrap();
+        assert_eq!(failures.len(), 2);
+        assert!(failures[0].message.starts_with("INVALID VALUE: data_classes \"gossip\""));
+        assert_eq!(failures[1].pointer, "/bundle/policy_decision/decision");
+    }
+}
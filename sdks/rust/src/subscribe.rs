@@ -0,0 +1,326 @@
+//! Verified bundles from a live DCP event stream (feature = "subscribe").
+//!
+//! [`subscribe`] connects to a Server-Sent Events endpoint (`http://`,
+//! `https://`) or a WebSocket (`ws://`, `wss://`), parses each message
+//! as a signed bundle, and verifies several at once on tokio's blocking
+//! threads. Results come out in arrival order as [`VerifiedBundle`]s, so
+//! a monitor sees every bundle its agent fleet emits, with its verdict,
+//! as it is produced.
+//!
+//! A message that is not JSON yields a [`SubscribeError::Message`] and
+//! the subscription continues; a transport error yields one error and
+//! ends it. Reconnecting is left to the caller.
+//!
+//! ```ignore
+//! let mut bundles = std::pin::pin!(subscribe("wss://dcp.example/events", SubscribeOptions::default()).await?);
+//! while let Some(item) = bundles.next().await {
+//!     match item {
+//!         Ok(b) if b.verified() => index(b.signed_bundle),
+//!         Ok(b) => alert(b.report.result.errors),
+//!         Err(e) => log(e),
+//!     }
+//! }
+//! ```
+
+use std::sync::Arc;
+
+use futures_util::StreamExt;
+use serde_json::Value;
+use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::types::VerificationReport;
+use crate::verify::{report_contained, VerifyOptions};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum SubscribeError {
+    #[error("Connect failed: {0}")]
+    Connect(String),
+    #[error("Event stream responded with HTTP {0}")]
+    Http(u16),
+    #[error("Malformed message: {0}")]
+    Message(String),
+    #[error("Message of {0} bytes exceeds the limit")]
+    TooLarge(usize),
+    #[error("Transport error: {0}")]
+    Transport(String),
+}
+
+/// How [`subscribe`] reaches the stream, chosen from the URL scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Transport {
+    Sse,
+    WebSocket,
+}
+
+impl Transport {
+    pub fn for_url(url: &str) -> Self {
+        if url.starts_with("ws://") || url.starts_with("wss://") {
+            Transport::WebSocket
+        } else {
+            Transport::Sse
+        }
+    }
+}
+
+/// Options for [`subscribe`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct SubscribeOptions {
+    pub verify: VerifyOptions,
+    /// Bundles verified at once. Default: one per available CPU.
+    pub in_flight: usize,
+    /// Extra request headers, e.g. `Authorization`.
+    pub headers: Vec<(String, String)>,
+    /// Largest message accepted. Default 16 MiB.
+    pub max_message_bytes: usize,
+}
+
+impl Default for SubscribeOptions {
+    fn default() -> Self {
+        Self {
+            verify: VerifyOptions::default(),
+            in_flight: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            headers: Vec::new(),
+            max_message_bytes: 16 << 20,
+        }
+    }
+}
+
+impl SubscribeOptions {
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// A bundle received from the stream with its [`crate::verify::verify_with_report`]
+/// report. Failed bundles are yielded too; check [`Self::verified`].
+#[derive(Debug, Clone)]
+pub struct VerifiedBundle {
+    pub signed_bundle: Value,
+    pub report: VerificationReport,
+}
+
+impl VerifiedBundle {
+    pub fn verified(&self) -> bool {
+        self.report.verified()
+    }
+}
+
+type Incoming = Result<Value, SubscribeError>;
+
+/// Connect to the event stream at `url` and verify every bundle it
+/// sends. Fails if the connection cannot be made; errors after that are
+/// stream items. Must be called inside a tokio runtime.
+pub async fn subscribe(
+    url: &str,
+    opts: SubscribeOptions,
+) -> Result<impl Stream<Item = Result<VerifiedBundle, SubscribeError>>, SubscribeError> {
+    let in_flight = opts.in_flight.max(1);
+    let (tx, rx) = mpsc::channel(in_flight);
+    let connect = |e: &dyn std::fmt::Display| SubscribeError::Connect(e.to_string());
+    match Transport::for_url(url) {
+        Transport::WebSocket => {
+            let mut request = url.into_client_request().map_err(|e| connect(&e))?;
+            for (name, value) in &opts.headers {
+                let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| connect(&e))?;
+                let value = HeaderValue::from_str(value).map_err(|e| connect(&e))?;
+                request.headers_mut().insert(name, value);
+            }
+            let (socket, _) = tokio_tungstenite::connect_async(request).await.map_err(|e| connect(&e))?;
+            tokio::spawn(read_websocket(socket, tx, opts.max_message_bytes));
+        }
+        Transport::Sse => {
+            let mut request = reqwest::Client::new().get(url).header(reqwest::header::ACCEPT, "text/event-stream");
+            for (name, value) in &opts.headers {
+                request = request.header(name.as_str(), value.as_str());
+            }
+            let response = request.send().await.map_err(|e| connect(&e))?;
+            if !response.status().is_success() {
+                return Err(SubscribeError::Http(response.status().as_u16()));
+            }
+            tokio::spawn(read_sse(response, tx, opts.max_message_bytes));
+        }
+    }
+    Ok(verify_incoming(rx, opts.verify, in_flight))
+}
+
+fn parse_message(data: &[u8], max_message_bytes: usize) -> Incoming {
+    if data.len() > max_message_bytes {
+        return Err(SubscribeError::TooLarge(data.len()));
+    }
+    serde_json::from_slice(data).map_err(|e| SubscribeError::Message(e.to_string()))
+}
+
+async fn read_websocket<S>(mut socket: tokio_tungstenite::WebSocketStream<S>, tx: Sender<Incoming>, max: usize)
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    while let Some(message) = socket.next().await {
+        let incoming = match message {
+            Ok(Message::Text(text)) => parse_message(text.as_bytes(), max),
+            Ok(Message::Binary(bytes)) => parse_message(&bytes, max),
+            Ok(Message::Close(_)) => return,
+            Ok(_) => continue,
+            Err(e) => {
+                let _ = tx.send(Err(SubscribeError::Transport(e.to_string()))).await;
+                return;
+            }
+        };
+        if tx.send(incoming).await.is_err() {
+            return;
+        }
+    }
+}
+
+async fn read_sse(response: reqwest::Response, tx: Sender<Incoming>, max: usize) {
+    let mut body = response.bytes_stream();
+    let mut parser = SseParser::default();
+    while let Some(chunk) = body.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                let _ = tx.send(Err(SubscribeError::Transport(e.to_string()))).await;
+                return;
+            }
+        };
+        for data in parser.feed(&chunk) {
+            if tx.send(parse_message(data.as_bytes(), max)).await.is_err() {
+                return;
+            }
+        }
+        if parser.pending() > max {
+            let _ = tx.send(Err(SubscribeError::TooLarge(parser.pending()))).await;
+            return;
+        }
+    }
+}
+
+/// Incremental `text/event-stream` parser. Yields the `data` of each
+/// `message` or `bundle` event; other event types (heartbeats) and
+/// comments are skipped.
+#[derive(Debug, Default)]
+struct SseParser {
+    line: Vec<u8>,
+    event: String,
+    data: String,
+}
+
+impl SseParser {
+    fn feed(&mut self, bytes: &[u8]) -> Vec<String> {
+        let mut events = Vec::new();
+        for &b in bytes {
+            if b != b'\n' {
+                self.line.push(b);
+                continue;
+            }
+            let raw = std::mem::take(&mut self.line);
+            let line = String::from_utf8_lossy(raw.strip_suffix(b"\r").unwrap_or(&raw)).into_owned();
+            if line.is_empty() {
+                let data = std::mem::take(&mut self.data);
+                let event = std::mem::take(&mut self.event);
+                if !data.is_empty() && matches!(event.as_str(), "" | "message" | "bundle") {
+                    events.push(data.strip_suffix('\n').unwrap_or(&data).to_string());
+                }
+                continue;
+            }
+            let (field, value) = line.split_once(':').unwrap_or((line.as_str(), ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "data" => {
+                    self.data.push_str(value);
+                    self.data.push('\n');
+                }
+                "event" => self.event = value.to_string(),
+                _ => {}
+            }
+        }
+        events
+    }
+
+    /// Bytes held for an event not yet terminated.
+    fn pending(&self) -> usize {
+        self.line.len() + self.data.len()
+    }
+}
+
+fn verify_incoming(
+    mut rx: Receiver<Incoming>,
+    opts: VerifyOptions,
+    in_flight: usize,
+) -> impl Stream<Item = Result<VerifiedBundle, SubscribeError>> {
+    let opts = Arc::new(opts);
+    let (jobs_tx, mut jobs_rx) = mpsc::channel(in_flight);
+    let (out_tx, out_rx) = mpsc::channel(in_flight);
+
+    tokio::spawn(async move {
+        while let Some(incoming) = rx.recv().await {
+            let job = incoming.map(|signed_bundle| {
+                let opts = opts.clone();
+                tokio::task::spawn_blocking(move || {
+                    let report = report_contained(&signed_bundle, &opts);
+                    VerifiedBundle { signed_bundle, report }
+                })
+            });
+            if jobs_tx.send(job).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    tokio::spawn(async move {
+        while let Some(job) = jobs_rx.recv().await {
+            let item = match job {
+                // Panics are contained inside the job, so it only fails
+                // if the runtime is shutting down.
+                Ok(handle) => match handle.await {
+                    Ok(verified) => Ok(verified),
+                    Err(_) => break,
+                },
+                Err(e) => Err(e),
+            };
+            if out_tx.send(item).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    ReceiverStream::new(out_rx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+
+    #[test]
+    fn test_sse_parser_splits_events_across_chunks() {
+        let mut parser = SseParser::default();
+        assert!(parser.feed(b": keep-alive\n\nevent: ping\ndata: {}\n\ndata: {\"a\"").is_empty());
+        assert_eq!(parser.feed(b":1}\r\n\r\nevent: bundle\ndata: [1,\ndata: 2]\n\n"), vec!["{\"a\":1}", "[1,\n2]"]);
+        assert_eq!(parser.pending(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_incoming_verified_in_order_with_errors() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let signed = set.get("citizenship_bundle.signed").unwrap().value.clone();
+        let (tx, rx) = mpsc::channel(4);
+        tokio::spawn(async move {
+            tx.send(Ok(signed)).await.unwrap();
+            tx.send(parse_message(b"not json", 1024)).await.unwrap();
+            tx.send(Ok(serde_json::json!({}))).await.unwrap();
+        });
+        let items: Vec<_> = verify_incoming(rx, VerifyOptions::default(), 2).collect().await;
+        assert!(items[0].as_ref().unwrap().verified());
+        assert!(matches!(items[1], Err(SubscribeError::Message(_))));
+        assert!(!items[2].as_ref().unwrap().verified());
+    }
+}
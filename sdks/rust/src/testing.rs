@@ -0,0 +1,132 @@
+//! Assertions for test suites that check verification behavior.
+//!
+//! ```
+//! use dcp_ai::testing::{assert_fails_with, assert_verified, ErrorCode};
+//! use dcp_ai::{VerificationResult, VerifyError};
+//!
+//! assert_verified!(VerificationResult::ok());
+//! assert_fails_with!(VerificationResult::fail(VerifyError::MerkleRootMismatch, ""), ErrorCode::MerkleRootMismatch);
+//! ```
+//!
+//! On failure both print every failure with its location and an
+//! expected/actual diff, instead of `assertion failed: result.verified`.
+
+use std::fmt::Write;
+
+pub use crate::v2::error_codes::DcpErrorCode as ErrorCode;
+pub use crate::{assert_fails_with, assert_verified};
+
+use crate::types::{VerificationReport, VerificationResult};
+
+/// Results the assertion macros accept.
+pub trait AsVerificationResult {
+    fn as_verification_result(&self) -> &VerificationResult;
+}
+
+impl AsVerificationResult for VerificationResult {
+    fn as_verification_result(&self) -> &VerificationResult {
+        self
+    }
+}
+
+impl AsVerificationResult for VerificationReport {
+    fn as_verification_result(&self) -> &VerificationResult {
+        &self.result
+    }
+}
+
+impl<T: AsVerificationResult + ?Sized> AsVerificationResult for &T {
+    fn as_verification_result(&self) -> &VerificationResult {
+        (**self).as_verification_result()
+    }
+}
+
+/// Assert that a [`VerificationResult`] or [`VerificationReport`] verified.
+#[macro_export]
+macro_rules! assert_verified {
+    ($result:expr $(,)?) => {
+        if let Err(report) = $crate::testing::check_verified(&$result) {
+            panic!("{}", report);
+        }
+    };
+}
+
+/// Assert that a result failed, with some failure mapping to `code`
+/// ([`ErrorCode::from_verify_error`]).
+#[macro_export]
+macro_rules! assert_fails_with {
+    ($result:expr, $code:expr $(,)?) => {
+        if let Err(report) = $crate::testing::check_fails_with(&$result, $code) {
+            panic!("{}", report);
+        }
+    };
+}
+
+#[doc(hidden)]
+pub fn check_verified(result: &impl AsVerificationResult) -> Result<(), String> {
+    let result = result.as_verification_result();
+    if result.verified {
+        return Ok(());
+    }
+    let mut report = String::from("expected the bundle to verify, but it failed:\n");
+    write_failures(&mut report, result);
+    Err(report)
+}
+
+#[doc(hidden)]
+pub fn check_fails_with(result: &impl AsVerificationResult, code: ErrorCode) -> Result<(), String> {
+    let result = result.as_verification_result();
+    if result.verified {
+        return Err(format!("expected failure {} ({}), but the bundle verified", code, code.description().0));
+    }
+    let matched = result.error_kinds.iter().flatten().any(|k| ErrorCode::from_verify_error(k) == Some(code));
+    if matched {
+        return Ok(());
+    }
+    let mut report = format!("expected failure {} ({}), got:\n", code, code.description().0);
+    write_failures(&mut report, result);
+    Err(report)
+}
+
+fn write_failures(out: &mut String, result: &VerificationResult) {
+    let failures = result.failures.as_deref().unwrap_or_default();
+    if failures.is_empty() {
+        out.push_str("  (no failure recorded)\n");
+    }
+    for f in failures {
+        let code = ErrorCode::from_verify_error(&f.kind).map(|c| format!(" [{}]", c)).unwrap_or_default();
+        let _ = writeln!(out, "  {}{}", f.message, code);
+        if !f.pointer.is_empty() {
+            let _ = writeln!(out, "    at {}", f.pointer);
+        }
+        if let Some(expected) = &f.expected {
+            let _ = writeln!(out, "    - expected: {}", expected);
+        }
+        if let Some(actual) = &f.actual {
+            let _ = writeln!(out, "    + actual:   {}", actual);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{VerificationFailure, VerifyError};
+
+    #[test]
+    fn test_reports_show_codes_and_diffs() {
+        let result = VerificationResult::fail_at(vec![VerificationFailure::new(
+            VerifyError::MerkleRootMismatch,
+            "",
+            "/signature/merkle_root",
+        )
+        .expected("aa")
+        .actual("bb")]);
+        assert!(check_fails_with(&result, ErrorCode::MerkleRootMismatch).is_ok());
+
+        let report = check_fails_with(&result, ErrorCode::ClassicalSigInvalid).unwrap_err();
+        assert!(report.contains("MERKLE ROOT MISMATCH [DCP-E202]"), "{}", report);
+        assert!(report.contains("- expected: aa") && report.contains("+ actual:   bb"), "{}", report);
+        assert!(check_verified(&result).unwrap_err().contains("at /signature/merkle_root"));
+    }
+}
@@ -0,0 +1,209 @@
+//! W3C Trace Context ids on audit entries.
+//!
+//! An [`AuditEntry`] may carry the `trace_id` and `span_id` of the
+//! distributed-trace span that performed its action, in the lowercase hex
+//! form of the W3C `traceparent` header (32 and 16 digits, not all zero).
+//! The ids are covered by the entry hash like any other field, so a
+//! verified audit chain can be joined to the traces of the same actions.
+//! Verification fails with `INVALID VALUE` on a malformed id.
+//!
+//! With the `trace-context` feature, [`current`] reads the ids of the
+//! active `tracing` span (through `tracing-opentelemetry`) and
+//! [`AuditEntry::stamp_current_span`] copies them onto an entry.
+//!
+//! ```
+//! use dcp_ai::trace_context::TraceContext;
+//!
+//! let ctx: TraceContext = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".parse().unwrap();
+//! assert_eq!(ctx.span_id, "00f067aa0ba902b7");
+//! assert!("00-00000000000000000000000000000000-00f067aa0ba902b7-01".parse::<TraceContext>().is_err());
+//! ```
+
+use serde_json::Value;
+
+use crate::types::{AuditEntry, VerificationFailure, VerifyError};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum TraceContextError {
+    #[error("traceparent must be version-traceid-spanid-flags")]
+    MalformedTraceparent,
+    #[error("trace_id must be 32 lowercase hex digits, not all zero: {0:?}")]
+    InvalidTraceId(String),
+    #[error("span_id must be 16 lowercase hex digits, not all zero: {0:?}")]
+    InvalidSpanId(String),
+    #[error("span_id requires a trace_id")]
+    SpanWithoutTrace,
+}
+
+/// The ids of one span of a distributed trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: String,
+    pub span_id: String,
+}
+
+impl TraceContext {
+    /// Validated ids.
+    pub fn new(trace_id: impl Into<String>, span_id: impl Into<String>) -> Result<Self, TraceContextError> {
+        let ctx = Self { trace_id: trace_id.into(), span_id: span_id.into() };
+        validate(Some(&ctx.trace_id), Some(&ctx.span_id))?;
+        Ok(ctx)
+    }
+
+    /// This context as a `traceparent` header value, sampled flag set.
+    pub fn to_traceparent(&self) -> String {
+        format!("00-{}-{}-01", self.trace_id, self.span_id)
+    }
+}
+
+impl std::str::FromStr for TraceContext {
+    type Err = TraceContextError;
+
+    /// Parse a `traceparent` header value. Versions other than `00` are
+    /// accepted as long as they start with the `00` fields, as the spec
+    /// asks of parsers.
+    fn from_str(s: &str) -> Result<Self, TraceContextError> {
+        let mut parts = s.trim().split('-');
+        let (Some(version), Some(trace_id), Some(span_id), Some(flags)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(TraceContextError::MalformedTraceparent);
+        };
+        let extra = parts.next().is_some();
+        if !is_hex(version, 2) || version == "ff" || !is_hex(flags, 2) || (version == "00" && extra) {
+            return Err(TraceContextError::MalformedTraceparent);
+        }
+        Self::new(trace_id, span_id)
+    }
+}
+
+impl std::fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_traceparent())
+    }
+}
+
+fn is_hex(s: &str, len: usize) -> bool {
+    s.len() == len && s.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+fn is_id(s: &str, len: usize) -> bool {
+    is_hex(s, len) && s.bytes().any(|b| b != b'0')
+}
+
+/// Check a `trace_id` / `span_id` pair as carried by an audit entry: each
+/// is optional, but a span needs its trace.
+pub fn validate(trace_id: Option<&str>, span_id: Option<&str>) -> Result<(), TraceContextError> {
+    if let Some(t) = trace_id.filter(|t| !is_id(t, 32)) {
+        return Err(TraceContextError::InvalidTraceId(t.to_string()));
+    }
+    match span_id {
+        Some(s) if !is_id(s, 16) => Err(TraceContextError::InvalidSpanId(s.to_string())),
+        Some(_) if trace_id.is_none() => Err(TraceContextError::SpanWithoutTrace),
+        _ => Ok(()),
+    }
+}
+
+/// Check the trace ids of serialized audit entries. `Err` when no entry
+/// carries any.
+pub(crate) fn check_entries(entries: &[Value]) -> Result<Option<VerificationFailure>, &'static str> {
+    if entries.iter().all(|e| e.get("trace_id").is_none() && e.get("span_id").is_none()) {
+        return Err("no audit entry carries trace context");
+    }
+    for (i, entry) in entries.iter().enumerate() {
+        let text = |name| match entry.get(name) {
+            None | Some(Value::Null) => Ok(None),
+            Some(Value::String(s)) => Ok(Some(s.as_str())),
+            Some(other) => Err(other.to_string()),
+        };
+        let (name, actual, message) = match (text("trace_id"), text("span_id")) {
+            (Err(actual), _) => ("trace_id", actual, "trace_id must be a string".to_string()),
+            (_, Err(actual)) => ("span_id", actual, "span_id must be a string".to_string()),
+            (Ok(trace_id), Ok(span_id)) => match validate(trace_id, span_id) {
+                Ok(()) => continue,
+                Err(e @ TraceContextError::InvalidTraceId(_)) => {
+                    ("trace_id", trace_id.unwrap_or_default().to_string(), e.to_string())
+                }
+                Err(e) => ("span_id", span_id.unwrap_or_default().to_string(), e.to_string()),
+            },
+        };
+        return Ok(Some(
+            VerificationFailure::new(
+                VerifyError::InvalidValue { entry: Some(i) },
+                message,
+                format!("/bundle/audit_entries/{}/{}", i, name),
+            )
+            .actual(actual),
+        ));
+    }
+    Ok(None)
+}
+
+/// The ids of the active `tracing` span, when it belongs to a sampled or
+/// recorded OpenTelemetry trace (`tracing-opentelemetry` layer installed).
+#[cfg(feature = "trace-context")]
+pub fn current() -> Option<TraceContext> {
+    use opentelemetry::trace::TraceContextExt;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let context = tracing::Span::current().context();
+    let span = context.span();
+    let span_context = span.span_context();
+    if !span_context.is_valid() {
+        return None;
+    }
+    Some(TraceContext { trace_id: span_context.trace_id().to_string(), span_id: span_context.span_id().to_string() })
+}
+
+impl AuditEntry {
+    /// Record `ctx` as the span that performed this entry's action.
+    pub fn with_trace_context(mut self, ctx: &TraceContext) -> Self {
+        self.trace_id = Some(ctx.trace_id.clone());
+        self.span_id = Some(ctx.span_id.clone());
+        self
+    }
+
+    /// This entry's trace ids, if it carries a valid pair.
+    pub fn trace_context(&self) -> Option<TraceContext> {
+        TraceContext::new(self.trace_id.clone()?, self.span_id.clone()?).ok()
+    }
+
+    /// Stamp the ids of the active `tracing` span; returns whether there
+    /// was one. Must run before the entry is chained and signed.
+    #[cfg(feature = "trace-context")]
+    pub fn stamp_current_span(&mut self) -> bool {
+        match current() {
+            Some(ctx) => {
+                self.trace_id = Some(ctx.trace_id);
+                self.span_id = Some(ctx.span_id);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_traceparent_and_entry_check() {
+        let ctx: TraceContext = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".parse().unwrap();
+        assert_eq!(ctx.to_string(), "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01");
+        assert!("00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01".parse::<TraceContext>().is_err());
+        assert!("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-x".parse::<TraceContext>().is_err());
+        assert_eq!(validate(None, Some("00f067aa0ba902b7")), Err(TraceContextError::SpanWithoutTrace));
+
+        let good = json!({"trace_id": ctx.trace_id, "span_id": ctx.span_id});
+        assert_eq!(check_entries(&[json!({})]), Err("no audit entry carries trace context"));
+        assert_eq!(check_entries(&[json!({}), good.clone()]), Ok(None));
+        let failure = check_entries(&[good, json!({"trace_id": ctx.trace_id, "span_id": "0000000000000000"})])
+            .unwrap()
+            .unwrap();
+        assert_eq!(failure.pointer, "/bundle/audit_entries/1/span_id");
+        assert!(failure.message.starts_with("INVALID VALUE (entry 1)"));
+    }
+}
@@ -0,0 +1,162 @@
+//! Signed verification transcripts for dispute resolution.
+//!
+//! [`crate::verify::verify_with_transcript`] records the options it ran
+//! with, every input the verifier hashed (by JSON Pointer, canonical
+//! length, and SHA-256), every value it recomputed, and the outcome of
+//! every comparison. The `merkle_root` step records the leaves the root
+//! was computed over, after leaf ordering and volatile fields were
+//! applied. The transcript is deterministic for a given bundle, key and
+//! options, so any party can [`Transcript::replay`] it; a verifier that
+//! signs its transcript commits to exactly what it checked.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::crypto::{hash_canonical, hash_object, public_key_from_secret, sign_object, verify_object};
+use crate::types::VerificationResult;
+use crate::verify::{verify_with_transcript, VerifyOptions};
+
+/// Transcript format identifier.
+pub const TRANSCRIPT_VERSION: &str = "dcp-transcript/1";
+
+/// One comparison made during verification.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct TranscriptStep {
+    /// Check name, indexed for per-entry checks (e.g. `prev_hash[3]`).
+    pub check: String,
+    /// JSON Pointer (RFC 6901) into the signed bundle of the hashed input.
+    pub pointer: String,
+    /// Length in bytes of the canonical form of the input.
+    pub input_len: usize,
+    /// SHA-256 hex of the canonical form of the input. For `merkle_root`
+    /// the input is the array of [`Self::leaves`].
+    pub input_sha256: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claimed: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recomputed: Option<String>,
+    pub matched: bool,
+    /// `merkle_root` only: the leaf hashes, in the order they were combined.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leaves: Option<Vec<String>>,
+}
+
+/// Replayable record of one verification run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Transcript {
+    pub transcript_version: String,
+    pub sdk_version: String,
+    /// SHA-256 of the canonical signed bundle that was verified.
+    pub signed_bundle_hash: String,
+    /// Public key the signature was checked against.
+    pub public_key_b64: String,
+    /// The [`VerifyOptions`] verification ran with, serialized.
+    pub options: Value,
+    pub steps: Vec<TranscriptStep>,
+    pub verified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<String>>,
+}
+
+/// A transcript signed by the verifier that produced it. The signature
+/// covers the transcript together with `verifier_id` and
+/// `verifier_public_key_b64`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedTranscript {
+    pub transcript: Transcript,
+    pub verifier_id: String,
+    pub verifier_public_key_b64: String,
+    pub sig_b64: String,
+}
+
+impl Transcript {
+    pub(crate) fn begin(signed_bundle: &Value, opts: &VerifyOptions) -> Self {
+        Self {
+            transcript_version: TRANSCRIPT_VERSION.to_string(),
+            sdk_version: env!("CARGO_PKG_VERSION").to_string(),
+            signed_bundle_hash: hash_object(signed_bundle),
+            public_key_b64: String::new(),
+            // Every option is a string, number, bool, or list of those.
+            options: serde_json::to_value(opts).expect("VerifyOptions serializes to JSON"),
+            steps: Vec::new(),
+            verified: false,
+            errors: None,
+        }
+    }
+
+    pub(crate) fn record(
+        &mut self,
+        check: &str,
+        pointer: &str,
+        input_canon: &str,
+        claimed: Option<&str>,
+        recomputed: Option<&str>,
+        matched: bool,
+    ) {
+        self.steps.push(TranscriptStep {
+            check: check.to_string(),
+            pointer: pointer.to_string(),
+            input_len: input_canon.len(),
+            input_sha256: hash_canonical(input_canon),
+            claimed: claimed.map(str::to_string),
+            recomputed: recomputed.map(str::to_string),
+            matched,
+            leaves: None,
+        });
+    }
+
+    pub(crate) fn record_leaves(&mut self, leaves: &[String]) {
+        if let Some(step) = self.steps.last_mut() {
+            step.leaves = Some(leaves.to_vec());
+        }
+    }
+
+    pub(crate) fn finish(&mut self, result: &VerificationResult) {
+        self.verified = result.verified;
+        self.errors = result.errors.clone();
+    }
+
+    /// Re-run verification of `signed_bundle` against the recorded public key
+    /// and check it reproduces this transcript exactly. `opts` must match
+    /// the recorded [`Self::options`].
+    pub fn replay(&self, signed_bundle: &Value, opts: &VerifyOptions) -> bool {
+        if hash_object(signed_bundle) != self.signed_bundle_hash {
+            return false;
+        }
+        let key = (!self.public_key_b64.is_empty()).then_some(self.public_key_b64.as_str());
+        let (_, replayed) = verify_with_transcript(signed_bundle, key, opts);
+        replayed == *self
+    }
+
+    /// Sign this transcript as `verifier_id` with the verifier's Ed25519
+    /// key. The embedded public key is derived from the secret key.
+    pub fn sign(self, verifier_id: &str, verifier_secret_key_b64: &str) -> Result<SignedTranscript, String> {
+        let verifier_public_key_b64 = public_key_from_secret(verifier_secret_key_b64)?;
+        let payload = signed_payload(&self, verifier_id, &verifier_public_key_b64)?;
+        let sig_b64 = sign_object(&payload, verifier_secret_key_b64)?;
+        Ok(SignedTranscript { transcript: self, verifier_id: verifier_id.to_string(), verifier_public_key_b64, sig_b64 })
+    }
+}
+
+impl SignedTranscript {
+    /// Check the signature was made by `expected_verifier_public_key_b64`
+    /// over the transcript, `verifier_id`, and the embedded key. A
+    /// transcript carrying any other key is rejected without checking.
+    pub fn verify_signature(&self, expected_verifier_public_key_b64: &str) -> Result<bool, String> {
+        if self.verifier_public_key_b64 != expected_verifier_public_key_b64 {
+            return Ok(false);
+        }
+        let payload = signed_payload(&self.transcript, &self.verifier_id, &self.verifier_public_key_b64)?;
+        verify_object(&payload, &self.sig_b64, expected_verifier_public_key_b64)
+    }
+}
+
+fn signed_payload(transcript: &Transcript, verifier_id: &str, verifier_public_key_b64: &str) -> Result<Value, String> {
+    Ok(serde_json::json!({
+        "transcript": serde_json::to_value(transcript).map_err(|e| e.to_string())?,
+        "verifier_id": verifier_id,
+        "verifier_public_key_b64": verifier_public_key_b64,
+    }))
+}
@@ -2,8 +2,116 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::b64::{self, B64Error};
+use crate::uri::{DcpUri, UriError};
+
+/// Base64 Ed25519 public key. Deserializing or [`parse`](Self::parse)-ing
+/// checks it is standard padded base64 of exactly 32 bytes, so a key
+/// cannot be confused with a signature or left truncated.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[serde(try_from = "String", into = "String")]
+pub struct PublicKeyB64(String);
+
+impl PublicKeyB64 {
+    pub fn parse(s: &str) -> Result<Self, B64Error> {
+        b64::decode_exact::<32>(s)?;
+        Ok(Self(s.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Verify `signature` over the canonical form of `obj` with this key.
+    pub fn verify(&self, obj: &serde_json::Value, signature: &SignatureB64) -> Result<bool, String> {
+        crate::crypto::verify_object(obj, signature.as_str(), self.as_str())
+    }
+}
+
+/// Base64 signature. Deserializing or [`parse`](Self::parse)-ing checks it
+/// is standard padded base64; the empty string is the unsigned
+/// placeholder used while a record is being built.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[serde(try_from = "String", into = "String")]
+pub struct SignatureB64(String);
+
+impl SignatureB64 {
+    pub fn parse(s: &str) -> Result<Self, B64Error> {
+        b64::decode(s)?;
+        Ok(Self(s.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Wrap output of this crate's own signers, which is always valid.
+    pub(crate) fn from_signer(s: String) -> Self {
+        Self(s)
+    }
+}
+
+macro_rules! b64_newtype_impls {
+    ($t:ty) => {
+        impl TryFrom<String> for $t {
+            type Error = B64Error;
+            fn try_from(s: String) -> Result<Self, B64Error> {
+                Self::parse(&s)
+            }
+        }
+
+        impl std::str::FromStr for $t {
+            type Err = B64Error;
+            fn from_str(s: &str) -> Result<Self, B64Error> {
+                Self::parse(s)
+            }
+        }
+
+        impl From<$t> for String {
+            fn from(v: $t) -> String {
+                v.0
+            }
+        }
+
+        impl AsRef<str> for $t {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl std::fmt::Display for $t {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl PartialEq<str> for $t {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $t {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+
+b64_newtype_impls!(PublicKeyB64);
+b64_newtype_impls!(SignatureB64);
+
 /// DCP-01: Responsible Principal Record.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[non_exhaustive]
 pub struct ResponsiblePrincipalRecord {
     pub dcp_version: String,
     pub human_id: String,
@@ -16,15 +124,101 @@ pub struct ResponsiblePrincipalRecord {
     pub expires_at: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub contact: Option<String>,
-    pub signature: String,
+    /// v1.1: `sha256:` hash of the record this one renews.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_record_hash: Option<String>,
+    /// v1.1: `human_id` of the organization that issued this record.
+    /// Absent on self-issued (root) records.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer_binding_reference: Option<String>,
+    /// v1.1: legal documents the binding rests on; checked against the
+    /// documents themselves by [`crate::agreements::verify_agreements`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub agreements: Vec<Agreement>,
+    /// v1.1: scheme of `signature`. Absent means Ed25519; `openpgp`
+    /// (feature = "pgp") is a detached OpenPGP signature, for principals
+    /// whose organization requires one. Covered by the signature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_alg: Option<String>,
+    pub signature: SignatureB64,
+}
+
+impl ResponsiblePrincipalRecord {
+    /// An unsigned v1.0 record with no expiry, contact or agreements.
+    /// Sign it with [`crate::issue::sign_record`].
+    pub fn new(
+        human_id: impl Into<String>,
+        legal_name: impl Into<String>,
+        entity_type: impl Into<String>,
+        jurisdiction: impl Into<String>,
+        liability_mode: impl Into<String>,
+        override_rights: bool,
+        issued_at: impl Into<String>,
+    ) -> Self {
+        Self {
+            dcp_version: "1.0".into(),
+            human_id: human_id.into(),
+            legal_name: legal_name.into(),
+            entity_type: entity_type.into(),
+            jurisdiction: jurisdiction.into(),
+            liability_mode: liability_mode.into(),
+            override_rights,
+            issued_at: issued_at.into(),
+            expires_at: None,
+            contact: None,
+            prev_record_hash: None,
+            issuer_binding_reference: None,
+            agreements: Vec::new(),
+            signature_alg: None,
+            signature: SignatureB64::default(),
+        }
+    }
+
+    pub fn with_expires_at(mut self, expires_at: impl Into<String>) -> Self {
+        self.expires_at = Some(expires_at.into());
+        self
+    }
+
+    pub fn with_contact(mut self, contact: impl Into<String>) -> Self {
+        self.contact = Some(contact.into());
+        self
+    }
+
+    /// Attach `agreement`. The record must be signed again afterwards.
+    pub fn with_agreement(mut self, agreement: Agreement) -> Self {
+        self.agreements.push(agreement);
+        self
+    }
+}
+
+/// A legal document (terms of liability, an insurance certificate)
+/// anchored to a Responsible Principal Record by its hash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct Agreement {
+    /// e.g. `terms_of_liability`, `insurance_certificate`.
+    pub doc_type: String,
+    /// Where the document can be fetched.
+    pub uri: String,
+    /// Lowercase hex SHA-256 of the document bytes.
+    pub sha256: String,
+}
+
+impl Agreement {
+    /// An agreement for `document`, hashing its bytes.
+    pub fn for_document(doc_type: impl Into<String>, uri: impl Into<String>, document: &[u8]) -> Self {
+        Self { doc_type: doc_type.into(), uri: uri.into(), sha256: crate::v2::dual_hash::sha256_hex(document) }
+    }
 }
 
 /// DCP-01: Agent Passport.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[non_exhaustive]
 pub struct AgentPassport {
     pub dcp_version: String,
     pub agent_id: String,
-    pub public_key: String,
+    pub public_key: PublicKeyB64,
     pub principal_binding_reference: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub capabilities: Option<Vec<String>>,
@@ -32,11 +226,129 @@ pub struct AgentPassport {
     pub risk_tier: Option<String>,
     pub created_at: String,
     pub status: String,
-    pub signature: String,
+    /// Hardware key attestation (v1.1); checked by `attestation::AttestationVerifier`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attestation: Option<KeyAttestation>,
+    /// v1.1: set on a passport issued by a capability upgrade
+    /// ([`crate::passport`]), linking it to the passport it replaces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upgrade: Option<PassportUpgrade>,
+    pub signature: SignatureB64,
+}
+
+impl AgentPassport {
+    /// An unsigned, `active` v1.0 passport with no capabilities or risk
+    /// tier. Sign it with [`crate::issue::sign_record`].
+    pub fn new(
+        agent_id: impl Into<String>,
+        public_key: PublicKeyB64,
+        principal_binding_reference: impl Into<String>,
+        created_at: impl Into<String>,
+    ) -> Self {
+        Self {
+            dcp_version: "1.0".into(),
+            agent_id: agent_id.into(),
+            public_key,
+            principal_binding_reference: principal_binding_reference.into(),
+            capabilities: None,
+            risk_tier: None,
+            created_at: created_at.into(),
+            status: "active".into(),
+            attestation: None,
+            upgrade: None,
+            signature: SignatureB64::default(),
+        }
+    }
+
+    pub fn with_capabilities(mut self, capabilities: Vec<String>) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+
+    pub fn with_risk_tier(mut self, risk_tier: impl Into<String>) -> Self {
+        self.risk_tier = Some(risk_tier.into());
+        self
+    }
+
+    /// The bound human's `human_id`. `principal_binding_reference` may hold
+    /// it bare or as a `dcp:human:` URI.
+    pub fn principal_human_id(&self) -> &str {
+        let r = self.principal_binding_reference.as_str();
+        r.strip_prefix("dcp:human:").unwrap_or(r)
+    }
+
+    /// `principal_binding_reference` as a `dcp:human:` URI.
+    pub fn principal_uri(&self) -> Result<DcpUri, UriError> {
+        format!("dcp:human:{}", self.principal_human_id()).parse()
+    }
+
+    /// `dcp:agent:` fingerprint URI of the passport key.
+    pub fn agent_uri(&self) -> Result<DcpUri, B64Error> {
+        DcpUri::agent_for_key(self.public_key.as_str())
+    }
+}
+
+/// Link from an upgraded passport to its predecessor and the approval
+/// that granted the new capabilities. Hashes are `sha256:<hex>` record
+/// hashes ([`crate::issue::record_hash`]).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct PassportUpgrade {
+    pub prev_passport_hash: String,
+    pub approval_hash: String,
+}
+
+/// v1.1: an agent's signed request for capabilities beyond its passport.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct CapabilityUpgradeRequest {
+    pub dcp_version: String,
+    pub request_id: String,
+    pub agent_id: String,
+    /// Record hash of the passport the request starts from.
+    pub passport_hash: String,
+    /// Capabilities wanted in addition to the passport's.
+    pub requested: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub justification: Option<String>,
+    pub requested_at: String,
+    /// Agent key of the current passport.
+    pub signature: SignatureB64,
+}
+
+/// v1.1: the responsible human's signed answer to a
+/// [`CapabilityUpgradeRequest`], granting all or part of it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct CapabilityUpgradeApproval {
+    pub dcp_version: String,
+    pub request_id: String,
+    /// Record hash of the request.
+    pub request_hash: String,
+    pub human_id: String,
+    /// Granted capabilities, a subset of `requested`.
+    pub approved: Vec<String>,
+    pub approved_at: String,
+    pub signature: SignatureB64,
+}
+
+/// Platform statement that an agent key was generated in, and cannot
+/// leave, a TPM or secure enclave.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct KeyAttestation {
+    /// Statement format, e.g. `tpm2` or `apple-sep`.
+    pub format: String,
+    /// Format-specific attestation statement, base64.
+    pub statement_b64: String,
+    /// DER certificates, leaf first, base64. The last one chains to a
+    /// platform root the verifier trusts.
+    pub cert_chain_b64: Vec<String>,
 }
 
 /// Intent target.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
 pub struct IntentTarget {
     pub channel: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -45,10 +357,146 @@ pub struct IntentTarget {
     pub domain: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
+    /// Set when `to`, `domain`, and `url` are salted hashes rather than
+    /// plaintext; names the salt. See [`crate::privacy`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salt_id: Option<String>,
+}
+
+/// `Intent::estimated_impact`: free text (`"low"`, `"medium"`, `"high"`)
+/// in v1.0 intents, or a structured [`ImpactAssessment`] in v1.1. Text
+/// re-serializes as the same string, so existing intent hashes hold.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[serde(untagged)]
+pub enum Impact {
+    Structured(ImpactAssessment),
+    Text(String),
+}
+
+/// v1.1 structured impact estimate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct ImpactAssessment {
+    pub categories: Vec<ImpactCategory>,
+    pub magnitude: ImpactMagnitude,
+    /// People or organizations affected besides the principal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "codegen", ts(type = "number | null"))]
+    pub affected_parties: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ImpactCategory {
+    Financial,
+    Privacy,
+    Safety,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum ImpactMagnitude {
+    Low,
+    Medium,
+    High,
+}
+
+impl ImpactMagnitude {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImpactMagnitude::Low => "low",
+            ImpactMagnitude::Medium => "medium",
+            ImpactMagnitude::High => "high",
+        }
+    }
+}
+
+impl ImpactCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImpactCategory::Financial => "financial",
+            ImpactCategory::Privacy => "privacy",
+            ImpactCategory::Safety => "safety",
+        }
+    }
+}
+
+impl Impact {
+    /// Magnitude of a structured impact, or of text that is exactly
+    /// `low`, `medium`, or `high` (any case).
+    pub fn magnitude(&self) -> Option<ImpactMagnitude> {
+        match self {
+            Impact::Structured(a) => Some(a.magnitude),
+            Impact::Text(t) => match t.to_ascii_lowercase().as_str() {
+                "low" => Some(ImpactMagnitude::Low),
+                "medium" => Some(ImpactMagnitude::Medium),
+                "high" => Some(ImpactMagnitude::High),
+                _ => None,
+            },
+        }
+    }
+
+    /// Categories of a structured impact; empty for text.
+    pub fn categories(&self) -> &[ImpactCategory] {
+        match self {
+            Impact::Structured(a) => &a.categories,
+            Impact::Text(_) => &[],
+        }
+    }
+
+    pub fn affected_parties(&self) -> Option<u64> {
+        match self {
+            Impact::Structured(a) => a.affected_parties,
+            Impact::Text(_) => None,
+        }
+    }
+}
+
+impl From<&str> for Impact {
+    fn from(text: &str) -> Self {
+        Impact::Text(text.to_string())
+    }
+}
+
+impl From<String> for Impact {
+    fn from(text: String) -> Self {
+        Impact::Text(text)
+    }
+}
+
+impl From<ImpactAssessment> for Impact {
+    fn from(assessment: ImpactAssessment) -> Self {
+        Impact::Structured(assessment)
+    }
+}
+
+/// Text as written; structured as e.g. `high (financial, privacy; 12 parties)`.
+impl std::fmt::Display for Impact {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Impact::Text(t) => f.write_str(t),
+            Impact::Structured(a) => {
+                f.write_str(a.magnitude.as_str())?;
+                let categories: Vec<&str> = a.categories.iter().map(ImpactCategory::as_str).collect();
+                match (categories.is_empty(), a.affected_parties) {
+                    (true, None) => Ok(()),
+                    (false, None) => write!(f, " ({})", categories.join(", ")),
+                    (true, Some(n)) => write!(f, " ({} parties)", n),
+                    (false, Some(n)) => write!(f, " ({}; {} parties)", categories.join(", "), n),
+                }
+            }
+        }
+    }
 }
 
 /// DCP-02: Intent Declaration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[non_exhaustive]
 pub struct Intent {
     pub dcp_version: String,
     pub intent_id: String,
@@ -58,30 +506,283 @@ pub struct Intent {
     pub action_type: String,
     pub target: IntentTarget,
     pub data_classes: Vec<String>,
-    pub estimated_impact: String,
+    pub estimated_impact: Impact,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub requires_consent: Option<bool>,
+    /// v1.1: earliest time the intent may be acted on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_before: Option<String>,
+    /// v1.1: latest time the intent may be acted on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<String>,
+}
+
+impl Intent {
+    /// A v1.0 intent with no data classes, consent flag or validity
+    /// window.
+    pub fn new(
+        intent_id: impl Into<String>,
+        agent_id: impl Into<String>,
+        human_id: impl Into<String>,
+        timestamp: impl Into<String>,
+        action_type: impl Into<String>,
+        target: IntentTarget,
+        estimated_impact: impl Into<Impact>,
+    ) -> Self {
+        Self {
+            dcp_version: "1.0".into(),
+            intent_id: intent_id.into(),
+            agent_id: agent_id.into(),
+            human_id: human_id.into(),
+            timestamp: timestamp.into(),
+            action_type: action_type.into(),
+            target,
+            data_classes: Vec::new(),
+            estimated_impact: estimated_impact.into(),
+            requires_consent: None,
+            not_before: None,
+            valid_until: None,
+        }
+    }
+
+    pub fn with_data_classes(mut self, data_classes: Vec<String>) -> Self {
+        self.data_classes = data_classes;
+        self
+    }
+
+    pub fn with_requires_consent(mut self, requires_consent: bool) -> Self {
+        self.requires_consent = Some(requires_consent);
+        self
+    }
+
+    /// Set the v1.1 validity window. Either bound may be open.
+    pub fn with_validity_window(mut self, not_before: Option<String>, valid_until: Option<String>) -> Self {
+        self.not_before = not_before;
+        self.valid_until = valid_until;
+        self
+    }
+
+    /// Whether `timestamp` falls inside the validity window, inclusive.
+    /// ISO-8601 UTC timestamps are compared lexicographically.
+    pub fn is_valid_at(&self, timestamp: &str) -> bool {
+        let started = match &self.not_before {
+            Some(nb) => timestamp >= nb.as_str(),
+            None => true,
+        };
+        let unexpired = match &self.valid_until {
+            Some(vu) => timestamp <= vu.as_str(),
+            None => true,
+        };
+        started && unexpired
+    }
 }
 
 /// DCP-02: Policy Decision.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[non_exhaustive]
 pub struct PolicyDecision {
     pub dcp_version: String,
     pub intent_id: String,
     pub decision: String,
     pub risk_score: f64,
     pub reasons: Vec<String>,
+    /// Structured risk behind `risk_score`. When present, `risk.score`
+    /// equals `risk_score`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk: Option<RiskAssessment>,
+    /// v1.1: [`crate::policy::snapshot_hash`] of the ruleset that made
+    /// this decision.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_snapshot_hash: Option<String>,
+}
+
+impl PolicyDecision {
+    /// A v1.0 decision on `intent_id`, without a structured risk
+    /// assessment or policy snapshot.
+    pub fn new(
+        intent_id: impl Into<String>,
+        decision: impl Into<String>,
+        risk_score: f64,
+        reasons: Vec<String>,
+    ) -> Self {
+        Self {
+            dcp_version: "1.0".into(),
+            intent_id: intent_id.into(),
+            decision: decision.into(),
+            risk_score,
+            reasons,
+            risk: None,
+            policy_snapshot_hash: None,
+        }
+    }
+
+    /// Attach a structured risk assessment and set `risk_score` from it.
+    pub fn with_risk(mut self, risk: RiskAssessment) -> Self {
+        self.risk_score = risk.score;
+        self.risk = Some(risk);
+        self
+    }
+}
+
+/// One input to a [`RiskAssessment`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct RiskFactor {
+    pub name: String,
+    /// Weight the model gives this factor.
+    pub weight: f64,
+    /// `weight × value`: this factor's share of the score.
+    pub contribution: f64,
+}
+
+/// Explainable risk score: which model produced it and how each factor
+/// contributed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct RiskAssessment {
+    /// Sum of factor contributions, clamped to `[0, 1]`.
+    pub score: f64,
+    pub model_id: String,
+    pub factors: Vec<RiskFactor>,
+}
+
+impl RiskAssessment {
+    pub fn builder(model_id: impl Into<String>) -> RiskAssessmentBuilder {
+        RiskAssessmentBuilder { model_id: model_id.into(), factors: Vec::new() }
+    }
+
+    /// Factors ordered by contribution, largest first.
+    pub fn top_factors(&self) -> Vec<&RiskFactor> {
+        let mut factors: Vec<&RiskFactor> = self.factors.iter().collect();
+        factors.sort_by(|a, b| b.contribution.total_cmp(&a.contribution));
+        factors
+    }
+}
+
+impl std::fmt::Display for RiskAssessment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "risk {:.2} ({})", self.score, self.model_id)?;
+        for (i, factor) in self.top_factors().into_iter().enumerate() {
+            let sep = if i == 0 { ": " } else { ", " };
+            write!(f, "{}{} {:+.2}", sep, factor.name, factor.contribution)?;
+        }
+        Ok(())
+    }
+}
+
+/// Builder for [`RiskAssessment`].
+#[derive(Debug, Clone)]
+pub struct RiskAssessmentBuilder {
+    model_id: String,
+    factors: Vec<RiskFactor>,
+}
+
+impl RiskAssessmentBuilder {
+    /// Add a factor observed at `value` (typically `0..=1`); its
+    /// contribution is `weight × value`.
+    pub fn factor(mut self, name: impl Into<String>, weight: f64, value: f64) -> Self {
+        self.factors.push(RiskFactor { name: name.into(), weight, contribution: weight * value });
+        self
+    }
+
+    pub fn build(self) -> RiskAssessment {
+        let score = self.factors.iter().map(|f| f.contribution).sum::<f64>().clamp(0.0, 1.0);
+        RiskAssessment { score, model_id: self.model_id, factors: self.factors }
+    }
 }
 
 /// Evidence attached to an audit entry.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
 pub struct AuditEvidence {
     pub tool: Option<String>,
     pub result_ref: Option<String>,
+    /// v1.1: set when the evidence was deleted under a retention policy;
+    /// see [`crate::retention`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tombstone: Option<EvidenceTombstone>,
+}
+
+/// Record of evidence deleted by [`crate::retention::apply`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct EvidenceTombstone {
+    /// Hash of the audit entry before deletion. Stands in for the entry's
+    /// own hash in the `prev_hash` chain and the merkle tree.
+    pub entry_hash: String,
+    /// Hash of the deleted `evidence` object.
+    pub evidence_hash: String,
+    pub deleted_at: String,
+    /// Identifier of the retention policy that required the deletion.
+    pub policy_id: String,
+}
+
+impl AuditEvidence {
+    /// `result_ref` parsed as a `dcp:` URI. `None` when absent; an error
+    /// for legacy opaque references.
+    pub fn result_uri(&self) -> Option<Result<DcpUri, UriError>> {
+        self.result_ref.as_deref().map(str::parse)
+    }
+}
+
+/// Audit outcome taxonomy.
+///
+/// `AuditEntry::outcome` stays a free-form string on the wire; this enum
+/// names the values verifiers reason about. Overrides recorded by
+/// [`crate::overrides`] (`override:<action>`) classify as `Overridden`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Outcome {
+    Success,
+    Failure,
+    Partial,
+    Aborted,
+    Overridden,
+}
+
+impl Outcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Success => "success",
+            Outcome::Failure => "failure",
+            Outcome::Partial => "partial",
+            Outcome::Aborted => "aborted",
+            Outcome::Overridden => "overridden",
+        }
+    }
+
+    /// Classify an `outcome` string; `None` if it is outside the taxonomy.
+    pub fn classify(outcome: &str) -> Option<Self> {
+        match outcome {
+            "success" => Some(Outcome::Success),
+            "failure" => Some(Outcome::Failure),
+            "partial" => Some(Outcome::Partial),
+            "aborted" => Some(Outcome::Aborted),
+            "overridden" => Some(Outcome::Overridden),
+            o if o.starts_with("override:") => Some(Outcome::Overridden),
+            _ => None,
+        }
+    }
+
+    /// Whether the action is finished. Only `Partial` is not.
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, Outcome::Partial)
+    }
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// DCP-03: Audit Entry.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[non_exhaustive]
 pub struct AuditEntry {
     pub dcp_version: String,
     pub audit_id: String,
@@ -94,59 +795,626 @@ pub struct AuditEntry {
     pub policy_decision: String,
     pub outcome: String,
     pub evidence: AuditEvidence,
+    /// v1.1: position in the producer's sequence, strictly increasing along
+    /// the chain. Orders entries that share a timestamp.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "codegen", ts(type = "number | null"))]
+    pub seq: Option<u64>,
+    /// W3C Trace Context id of the trace that performed the action
+    /// (see [`crate::trace_context`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    /// W3C Trace Context id of the span within `trace_id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_id: Option<String>,
+}
+
+impl AuditEntry {
+    /// A v1.0 entry from `fields`, bound to `intent_id` by `intent_hash`
+    /// and chained to the previous entry by `prev_hash` (`GENESIS` for the
+    /// first). [`crate::bundle::BundleBuilder::create_audit_entry`]
+    /// computes both hashes.
+    pub fn new(
+        fields: crate::bundle::AuditEntryFields,
+        intent_id: impl Into<String>,
+        intent_hash: impl Into<String>,
+        prev_hash: impl Into<String>,
+    ) -> Self {
+        Self {
+            dcp_version: "1.0".into(),
+            audit_id: fields.audit_id,
+            prev_hash: prev_hash.into(),
+            timestamp: fields.timestamp,
+            agent_id: fields.agent_id,
+            human_id: fields.human_id,
+            intent_id: intent_id.into(),
+            intent_hash: intent_hash.into(),
+            policy_decision: fields.policy_decision,
+            outcome: fields.outcome,
+            evidence: fields.evidence,
+            seq: None,
+            trace_id: None,
+            span_id: None,
+        }
+    }
+
+    /// This entry's outcome in the [`Outcome`] taxonomy.
+    pub fn outcome_kind(&self) -> Option<Outcome> {
+        Outcome::classify(&self.outcome)
+    }
+}
+
+/// v1.1: a signed revision of an intent after it was declared.
+///
+/// `changes` replaces top-level intent fields; the hashes are the plain
+/// hex `intent_hash` form used by audit entries. Signed by the agent key
+/// in the passport, over the amendment without `signature`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct IntentAmendment {
+    pub dcp_version: String,
+    pub amendment_id: String,
+    pub intent_id: String,
+    /// Hash of the intent before this amendment.
+    pub prev_intent_hash: String,
+    pub changes: serde_json::Map<String, serde_json::Value>,
+    /// Hash of the intent after applying `changes`.
+    pub new_intent_hash: String,
+    pub amended_at: String,
+    pub signature: SignatureB64,
 }
 
 /// Citizenship Bundle — contains all DCP artifacts.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
 pub struct CitizenshipBundle {
     pub responsible_principal_record: ResponsiblePrincipalRecord,
     pub agent_passport: AgentPassport,
     pub intent: Intent,
     pub policy_decision: PolicyDecision,
     pub audit_entries: Vec<AuditEntry>,
+    /// v1.1: revisions of `intent`, oldest first. See [`crate::amendment`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub intent_amendments: Vec<IntentAmendment>,
+    /// v1.1: corrections applied after signing, oldest first. See
+    /// [`crate::bundle::apply_patch`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patches: Vec<BundlePatch>,
+}
+
+/// One correction to a signed bundle: the RFC 6902 operations, applied to
+/// the bundle that `prev_bundle_hash` identifies.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct BundlePatch {
+    /// `bundle_hash` of the signed bundle before this patch.
+    pub prev_bundle_hash: String,
+    pub operations: serde_json::Value,
+    pub applied_at: String,
+    /// Signer id of the bundle produced by this patch.
+    pub applied_by: String,
 }
 
 /// Signer information.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
 pub struct Signer {
     #[serde(rename = "type")]
     pub signer_type: String,
     pub id: String,
-    pub public_key_b64: String,
+    pub public_key_b64: PublicKeyB64,
 }
 
 /// Bundle signature block.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[non_exhaustive]
 pub struct BundleSignature {
     pub alg: String,
     pub created_at: String,
     pub signer: Signer,
     pub bundle_hash: String,
     pub merkle_root: Option<String>,
-    pub sig_b64: String,
+    pub sig_b64: SignatureB64,
+    /// Canonicalization the signature was computed over (e.g. `dcp-c14n/1`).
+    /// Absent on bundles signed before the field existed; verifiers treat
+    /// that as `dcp-c14n/1`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canon: Option<String>,
+    /// v1.1: order of the leaves under `merkle_root`. Absent means chain
+    /// order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leaf_order: Option<crate::merkle::LeafOrder>,
+}
+
+impl BundleSignature {
+    /// A signature block without a Merkle root, declared canonicalization
+    /// or leaf order. [`crate::bundle::sign_bundle`] fills all three.
+    pub fn new(
+        alg: impl Into<String>,
+        created_at: impl Into<String>,
+        signer: Signer,
+        bundle_hash: impl Into<String>,
+        sig_b64: SignatureB64,
+    ) -> Self {
+        Self {
+            alg: alg.into(),
+            created_at: created_at.into(),
+            signer,
+            bundle_hash: bundle_hash.into(),
+            merkle_root: None,
+            sig_b64,
+            canon: None,
+            leaf_order: None,
+        }
+    }
 }
 
 /// Signed Bundle — bundle + cryptographic signature.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
 pub struct SignedBundle {
     pub bundle: CitizenshipBundle,
     pub signature: BundleSignature,
 }
 
+/// Signature over an artifact's SHA-256 digest rather than its bytes, for
+/// artifacts hashed outside the SDK ([`crate::crypto::sign_digest`]).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct DetachedSignature {
+    /// `sha256:<hex>` of the artifact.
+    pub hash_ref: String,
+    pub alg: String,
+    pub signer: Signer,
+    pub sig_b64: SignatureB64,
+}
+
+impl DetachedSignature {
+    /// Sign `digest` as `signer`, whose `public_key_b64` must match
+    /// `secret_key_b64`.
+    pub fn sign(digest: &[u8; 32], signer: Signer, secret_key_b64: &str) -> Result<Self, String> {
+        let public_key = crate::crypto::public_key_from_secret(secret_key_b64)?;
+        if signer.public_key_b64 != public_key.as_str() {
+            return Err("signer public key does not match the secret key".into());
+        }
+        Ok(Self {
+            hash_ref: format!("sha256:{}", hex::encode(digest)),
+            alg: "ed25519".into(),
+            signer,
+            sig_b64: SignatureB64::from_signer(crate::crypto::sign_digest(digest, secret_key_b64)?),
+        })
+    }
+
+    /// The digest named by `hash_ref`.
+    pub fn digest(&self) -> Result<[u8; 32], String> {
+        let hex_digest = self
+            .hash_ref
+            .strip_prefix("sha256:")
+            .ok_or_else(|| format!("hash_ref {:?} is not sha256:<hex>", self.hash_ref))?;
+        let mut digest = [0u8; 32];
+        hex::decode_to_slice(hex_digest, &mut digest).map_err(|e| format!("hash_ref: {}", e))?;
+        Ok(digest)
+    }
+
+    /// Whether this signs `digest` under `public_key_b64`, or the
+    /// embedded signer key when `None`. A `hash_ref` naming another
+    /// digest is `Ok(false)`.
+    pub fn verify(&self, digest: &[u8; 32], public_key_b64: Option<&str>) -> Result<bool, String> {
+        if self.alg != "ed25519" {
+            return Err(format!("unsupported alg {:?}", self.alg));
+        }
+        if self.digest()? != *digest {
+            return Ok(false);
+        }
+        let key = public_key_b64.unwrap_or(self.signer.public_key_b64.as_str());
+        crate::crypto::verify_digest(digest, self.sig_b64.as_str(), key)
+    }
+}
+
+/// Where and why a verification check failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[non_exhaustive]
+pub struct VerificationFailure {
+    #[serde(flatten)]
+    pub kind: VerifyError,
+    /// [`VerifyError::message`] of `kind` with the failure's detail.
+    pub message: String,
+    /// JSON Pointer (RFC 6901) into the signed bundle, e.g.
+    /// `/bundle/audit_entries/7/prev_hash`.
+    pub pointer: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<String>,
+}
+
+impl VerificationFailure {
+    /// A `kind` failure at `pointer`; `detail` (may be empty) follows the
+    /// kind's tag in the message.
+    pub fn new(kind: VerifyError, detail: impl AsRef<str>, pointer: impl Into<String>) -> Self {
+        let message = kind.message(detail.as_ref());
+        Self { kind, message, pointer: pointer.into(), expected: None, actual: None }
+    }
+
+    pub fn expected(mut self, expected: impl Into<String>) -> Self {
+        self.expected = Some(expected.into());
+        self
+    }
+
+    pub fn actual(mut self, actual: impl Into<String>) -> Self {
+        self.actual = Some(actual.into());
+        self
+    }
+}
+
+/// What a verification failure is. Every failure is built from one of
+/// these, and its message, alert severity and canonical error code are
+/// derived from it, so callers can branch without matching strings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum VerifyError {
+    #[error("missing {field}")]
+    Missing { field: String },
+    #[error("signature invalid")]
+    SignatureInvalid,
+    #[error("signer invalid")]
+    SignerInvalid,
+    #[error("signing key not bound to the agent")]
+    KeyNotBound,
+    #[error("signing key not hardware-attested")]
+    KeyNotAttested,
+    #[error("signature too old")]
+    SignatureTooOld,
+    #[error("principal binding expired")]
+    BindingExpired,
+    #[error("timestamps out of order")]
+    TimestampOrder,
+    #[error("bundle hash mismatch")]
+    BundleHashMismatch,
+    #[error("merkle root mismatch")]
+    MerkleRootMismatch,
+    #[error("intent hash mismatch")]
+    IntentHashMismatch { entry: Option<usize> },
+    #[error("audit chain broken at entry {entry}")]
+    ChainBreak { entry: usize },
+    #[error("audit entry {entry} out of sequence")]
+    SequenceOutOfOrder { entry: usize },
+    #[error("intent not yet valid at entry {entry}")]
+    IntentNotYetValid { entry: usize },
+    #[error("intent expired at entry {entry}")]
+    IntentExpired { entry: usize },
+    #[error("duplicate audit id at entry {entry}")]
+    DuplicateAuditId { entry: usize },
+    #[error("audit entry {entry} is for another intent")]
+    IntentIdMismatch { entry: usize },
+    #[error("invalid amendment")]
+    AmendmentInvalid { entry: Option<usize> },
+    #[error("weak algorithm rejected")]
+    WeakAlgorithm,
+    #[error("unsupported canonicalization")]
+    UnsupportedCanonicalization,
+    #[error("unsupported leaf order")]
+    UnsupportedLeafOrder,
+    #[error("unsupported signature algorithm")]
+    UnsupportedSignatureAlg,
+    #[error("unsupported hash algorithm")]
+    UnsupportedHashAlg,
+    #[error("policy decision is for another intent")]
+    ReferenceMismatch,
+    #[error("approved decision not audited")]
+    DecisionNotAudited,
+    #[error("intent target not hashed")]
+    TargetNotHashed,
+    #[error("policy snapshot not approved")]
+    PolicySnapshotUnapproved,
+    #[error("action not audited")]
+    UnauditedAction,
+    #[error("consent missing")]
+    ConsentMissing,
+    #[error("agreement mismatch")]
+    AgreementMismatch,
+    #[error("agreement unavailable")]
+    AgreementUnavailable,
+    #[error("revoked")]
+    Revoked,
+    #[error("challenge mismatch")]
+    ChallengeMismatch,
+    #[error("challenge expired")]
+    ChallengeExpired,
+    #[error("handshake mismatch")]
+    HandshakeMismatch,
+    #[error("handshake expired")]
+    HandshakeExpired,
+    #[error("capability missing")]
+    CapabilityMissing,
+    #[error("invalid capability upgrade")]
+    UpgradeInvalid,
+    #[error("invalid renewal chain")]
+    RenewalInvalid,
+    #[error("invalid issuance chain")]
+    IssuanceInvalid,
+    #[error("invalid override")]
+    OverrideInvalid,
+    #[error("invalid handoff")]
+    HandoffInvalid,
+    #[error("invalid notarization")]
+    NotarizationInvalid,
+    #[error("invalid key ceremony")]
+    CeremonyInvalid,
+    #[error("ledger chain broken")]
+    LedgerChainBroken,
+    #[error("attestation missing")]
+    AttestationMissing,
+    #[error("attestation invalid")]
+    AttestationInvalid,
+    #[error("limit exceeded")]
+    LimitExceeded,
+    #[error("artifact unparseable")]
+    ArtifactUnparseable,
+    #[error("decompression failed")]
+    DecompressionFailed,
+    #[error("invalid value")]
+    InvalidValue { entry: Option<usize> },
+    #[error("not verified")]
+    NotVerified,
+    #[error("verifier internal error")]
+    Internal,
+}
+
+impl VerifyError {
+    /// [`Self::Missing`] for `field`.
+    pub fn missing(field: impl Into<String>) -> Self {
+        Self::Missing { field: field.into() }
+    }
+
+    /// Failure message: the kind's tag, the audit entry if any, then
+    /// `detail` (`prev_hash chain (entry 2): expected ..., got ...`).
+    pub fn message(&self, detail: &str) -> String {
+        let mut message = match self {
+            Self::Missing { field } => format!("Missing {}", field),
+            _ => self.tag().to_string(),
+        };
+        if let Some(entry) = self.entry() {
+            message.push_str(&format!(" (entry {})", entry));
+        }
+        if !detail.is_empty() {
+            message.push_str(": ");
+            message.push_str(detail);
+        }
+        message
+    }
+
+    /// The audit entry the failure is about, if any.
+    pub fn entry(&self) -> Option<usize> {
+        match self {
+            Self::ChainBreak { entry }
+            | Self::SequenceOutOfOrder { entry }
+            | Self::IntentNotYetValid { entry }
+            | Self::IntentExpired { entry }
+            | Self::DuplicateAuditId { entry }
+            | Self::IntentIdMismatch { entry } => Some(*entry),
+            Self::IntentHashMismatch { entry } | Self::AmendmentInvalid { entry } | Self::InvalidValue { entry } => {
+                *entry
+            }
+            _ => None,
+        }
+    }
+
+    fn tag(&self) -> &'static str {
+        match self {
+            Self::Missing { .. } => "Missing",
+            Self::SignatureInvalid => "SIGNATURE INVALID",
+            Self::SignerInvalid => "SIGNER INVALID",
+            Self::KeyNotBound => "KEY NOT BOUND",
+            Self::KeyNotAttested => "KEY NOT ATTESTED",
+            Self::SignatureTooOld => "SIGNATURE TOO OLD",
+            Self::BindingExpired => "BINDING EXPIRED",
+            Self::TimestampOrder => "TIMESTAMP ORDER",
+            Self::BundleHashMismatch => "BUNDLE HASH MISMATCH",
+            Self::MerkleRootMismatch => "MERKLE ROOT MISMATCH",
+            Self::IntentHashMismatch { .. } => "intent_hash",
+            Self::ChainBreak { .. } => "prev_hash chain",
+            Self::SequenceOutOfOrder { .. } => "SEQUENCE OUT OF ORDER",
+            Self::IntentNotYetValid { .. } => "INTENT NOT YET VALID",
+            Self::IntentExpired { .. } => "INTENT EXPIRED",
+            Self::DuplicateAuditId { .. } => "DUPLICATE AUDIT ID",
+            Self::IntentIdMismatch { .. } => "INTENT ID MISMATCH",
+            Self::AmendmentInvalid { .. } => "AMENDMENT INVALID",
+            Self::WeakAlgorithm => "WEAK ALGORITHM REJECTED",
+            Self::UnsupportedCanonicalization => "UNSUPPORTED CANONICALIZATION",
+            Self::UnsupportedLeafOrder => "UNSUPPORTED LEAF ORDER",
+            Self::UnsupportedSignatureAlg => "UNSUPPORTED SIGNATURE ALG",
+            Self::UnsupportedHashAlg => "UNSUPPORTED HASH ALG",
+            Self::ReferenceMismatch => "REFERENCE MISMATCH",
+            Self::DecisionNotAudited => "DECISION NOT AUDITED",
+            Self::TargetNotHashed => "TARGET NOT HASHED",
+            Self::PolicySnapshotUnapproved => "POLICY SNAPSHOT UNAPPROVED",
+            Self::UnauditedAction => "UNAUDITED ACTION",
+            Self::ConsentMissing => "CONSENT MISSING",
+            Self::AgreementMismatch => "AGREEMENT MISMATCH",
+            Self::AgreementUnavailable => "AGREEMENT UNAVAILABLE",
+            Self::Revoked => "REVOKED",
+            Self::ChallengeMismatch => "CHALLENGE MISMATCH",
+            Self::ChallengeExpired => "CHALLENGE EXPIRED",
+            Self::HandshakeMismatch => "HANDSHAKE MISMATCH",
+            Self::HandshakeExpired => "HANDSHAKE EXPIRED",
+            Self::CapabilityMissing => "CAPABILITY MISSING",
+            Self::UpgradeInvalid => "UPGRADE INVALID",
+            Self::RenewalInvalid => "RENEWAL INVALID",
+            Self::IssuanceInvalid => "ISSUANCE INVALID",
+            Self::OverrideInvalid => "OVERRIDE INVALID",
+            Self::HandoffInvalid => "HANDOFF INVALID",
+            Self::NotarizationInvalid => "NOTARIZATION INVALID",
+            Self::CeremonyInvalid => "CEREMONY INVALID",
+            Self::LedgerChainBroken => "LEDGER CHAIN BROKEN",
+            Self::AttestationMissing => "ATTESTATION MISSING",
+            Self::AttestationInvalid => "ATTESTATION INVALID",
+            Self::LimitExceeded => "LIMIT EXCEEDED",
+            Self::ArtifactUnparseable => "ARTIFACT UNPARSEABLE",
+            Self::DecompressionFailed => "DECOMPRESSION FAILED",
+            Self::InvalidValue { .. } => "INVALID VALUE",
+            Self::NotVerified => "NOT VERIFIED",
+            Self::Internal => "VERIFIER INTERNAL ERROR",
+        }
+    }
+}
+
 /// Verification result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[non_exhaustive]
 pub struct VerificationResult {
     pub verified: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub errors: Option<Vec<String>>,
+    /// Structured counterpart of `errors`, with locations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failures: Option<Vec<VerificationFailure>>,
+    /// [`VerifyError`] of each of `errors`, in the same order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_kinds: Option<Vec<VerifyError>>,
+    /// The candidate key that verified the signature, when verifying
+    /// against [`crate::verify::VerifyOptions::candidate_keys`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matched_key: Option<String>,
+    /// Conditions that did not fail verification but need attention,
+    /// e.g. a binding inside [`crate::verify::VerifyOptions::expiry_grace`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
 impl VerificationResult {
     pub fn ok() -> Self {
-        Self { verified: true, errors: None }
+        Self {
+            verified: true,
+            errors: None,
+            failures: None,
+            error_kinds: None,
+            matched_key: None,
+            warnings: Vec::new(),
+        }
     }
 
-    pub fn fail(errors: Vec<String>) -> Self {
-        Self { verified: false, errors: Some(errors) }
+    /// Fail with one failure that has no location in a bundle.
+    pub fn fail(kind: VerifyError, detail: impl AsRef<str>) -> Self {
+        Self::fail_at(vec![VerificationFailure::new(kind, detail, "")])
+    }
+
+    /// Fail with located failures; `errors` and `error_kinds` carry their
+    /// messages and kinds.
+    pub fn fail_at(failures: Vec<VerificationFailure>) -> Self {
+        Self {
+            verified: false,
+            errors: Some(failures.iter().map(|f| f.message.clone()).collect()),
+            error_kinds: Some(failures.iter().map(|f| f.kind.clone()).collect()),
+            failures: Some(failures),
+            matched_key: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn with_matched_key(mut self, key: Option<&str>) -> Self {
+        self.matched_key = key.map(str::to_string);
+        self
+    }
+
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+}
+
+/// Outcome of one named verification check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum CheckStatus {
+    Passed,
+    Failed,
+    /// Not run: the data it needs is absent, the options disable it, or an
+    /// earlier check failed first. See [`CheckResult::skipped_reason`].
+    Skipped,
+}
+
+/// One entry of [`VerificationReport::checks`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[non_exhaustive]
+pub struct CheckResult {
+    /// One of [`crate::verify::CHECK_NAMES`], e.g. `signature`,
+    /// `merkle_root`, or `policy_snapshot`.
+    pub name: String,
+    pub status: CheckStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped_reason: Option<String>,
+    /// Why a failed check failed: the failure it raised, with its pointer
+    /// and expected and actual values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure: Option<VerificationFailure>,
+}
+
+impl CheckResult {
+    pub fn new(name: impl Into<String>, status: CheckStatus) -> Self {
+        Self { name: name.into(), status, skipped_reason: None, failure: None }
+    }
+
+    pub fn skipped(name: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self { name: name.into(), status: CheckStatus::Skipped, skipped_reason: Some(reason.into()), failure: None }
+    }
+
+    pub fn failed(name: impl Into<String>, failure: VerificationFailure) -> Self {
+        Self { name: name.into(), status: CheckStatus::Failed, skipped_reason: None, failure: Some(failure) }
+    }
+}
+
+/// A [`VerificationResult`] plus the status of every check, so a
+/// `verified: true` says which checks actually ran.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "codegen", derive(schemars::JsonSchema, ts_rs::TS))]
+#[non_exhaustive]
+pub struct VerificationReport {
+    #[serde(flatten)]
+    pub result: VerificationResult,
+    pub checks: Vec<CheckResult>,
+}
+
+impl VerificationReport {
+    pub fn verified(&self) -> bool {
+        self.result.verified
+    }
+
+    pub fn check(&self, name: &str) -> Option<&CheckResult> {
+        self.checks.iter().find(|c| c.name == name)
+    }
+
+    /// Checks that did not run.
+    pub fn skipped(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks.iter().filter(|c| c.status == CheckStatus::Skipped)
+    }
+
+    /// Checks that ran and failed.
+    pub fn failed(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks.iter().filter(|c| c.status == CheckStatus::Failed)
+    }
+}
+
+/// Escape one reference token for a JSON Pointer (RFC 6901 §3).
+pub fn json_pointer_escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decision_summary_explains_risk() {
+        let risk = RiskAssessment::builder("m1").factor("amount", 0.2, 0.5).factor("pii", 0.5, 0.5).build();
+        assert_eq!(risk.to_string(), "risk 0.35 (m1): pii +0.25, amount +0.10");
     }
 }
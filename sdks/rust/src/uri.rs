@@ -0,0 +1,219 @@
+//! `dcp:` URIs for references between DCP artifacts.
+//!
+//! References such as `evidence.result_ref` and
+//! `principal_binding_reference` used to be opaque strings each consumer
+//! had to guess at. A [`DcpUri`] names what is referenced and how:
+//!
+//! | URI                       | Refers to                                    |
+//! |---------------------------|----------------------------------------------|
+//! | `dcp:agent:<fingerprint>` | an agent key, by [`derive_kid`] fingerprint  |
+//! | `dcp:human:<human_id>`    | a responsible principal                      |
+//! | `dcp:intent:<intent_id>`  | an intent                                    |
+//! | `dcp:audit:<audit_id>`    | an audit entry                               |
+//! | `dcp:bundle:<alg>:<hex>`  | a citizenship bundle, by content hash        |
+//! | `dcp:record:<alg>:<hex>`  | a signed record (e.g. an override), by hash  |
+//!
+//! ```
+//! use dcp_ai::uri::DcpUri;
+//!
+//! let uri: DcpUri = "dcp:intent:intent-fixture-001".parse().unwrap();
+//! assert_eq!(uri, DcpUri::Intent("intent-fixture-001".into()));
+//! assert_eq!(uri.to_string(), "dcp:intent:intent-fixture-001");
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::b64::{self, B64Error};
+use crate::v2::crypto_provider::derive_kid;
+
+/// URI scheme prefix, colon included.
+pub const SCHEME: &str = "dcp:";
+
+/// Hash algorithms a content-addressed URI may name.
+const HASH_ALGS: [&str; 2] = ["sha256", "sha3-256"];
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum UriError {
+    #[error("not a dcp: URI")]
+    NotDcp,
+    #[error("unknown dcp: URI kind {0:?}")]
+    UnknownKind(String),
+    #[error("malformed dcp:{kind} URI: {reason}")]
+    Malformed { kind: &'static str, reason: String },
+}
+
+/// A content hash inside a `dcp:bundle:` or `dcp:record:` URI.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContentHash {
+    /// `sha256` or `sha3-256`.
+    pub alg: String,
+    /// 64 lowercase hex digits.
+    pub hex: String,
+}
+
+impl ContentHash {
+    /// Parse the `<alg>:<hex>` form the rest of the SDK uses for hashes.
+    pub fn parse(s: &str) -> Option<Self> {
+        let (alg, hex) = s.split_once(':')?;
+        let valid = HASH_ALGS.contains(&alg)
+            && hex.len() == 64
+            && hex.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+        valid.then(|| Self { alg: alg.to_string(), hex: hex.to_string() })
+    }
+}
+
+impl fmt::Display for ContentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.alg, self.hex)
+    }
+}
+
+/// A parsed `dcp:` URI.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DcpUri {
+    /// Agent key fingerprint: 32 lowercase hex digits.
+    Agent(String),
+    Human(String),
+    Intent(String),
+    Audit(String),
+    Bundle(ContentHash),
+    Record(ContentHash),
+}
+
+impl DcpUri {
+    /// `dcp:agent:` URI of an Ed25519 public key.
+    pub fn agent_for_key(public_key_b64: &str) -> Result<Self, B64Error> {
+        let pk = b64::decode_exact::<32>(public_key_b64)?;
+        Ok(DcpUri::Agent(derive_kid("ed25519", &pk)))
+    }
+
+    /// `dcp:bundle:` URI for a `<alg>:<hex>` bundle hash.
+    pub fn bundle(hash: &str) -> Result<Self, UriError> {
+        ContentHash::parse(hash).map(DcpUri::Bundle).ok_or_else(|| bad_hash("bundle", hash))
+    }
+
+    /// `dcp:record:` URI for a `<alg>:<hex>` record hash.
+    pub fn record(hash: &str) -> Result<Self, UriError> {
+        ContentHash::parse(hash).map(DcpUri::Record).ok_or_else(|| bad_hash("record", hash))
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DcpUri::Agent(_) => "agent",
+            DcpUri::Human(_) => "human",
+            DcpUri::Intent(_) => "intent",
+            DcpUri::Audit(_) => "audit",
+            DcpUri::Bundle(_) => "bundle",
+            DcpUri::Record(_) => "record",
+        }
+    }
+
+    /// Identifier or `<alg>:<hex>` hash after the kind.
+    pub fn value(&self) -> String {
+        match self {
+            DcpUri::Agent(v) | DcpUri::Human(v) | DcpUri::Intent(v) | DcpUri::Audit(v) => v.clone(),
+            DcpUri::Bundle(h) | DcpUri::Record(h) => h.to_string(),
+        }
+    }
+
+    /// The content hash of a `bundle` or `record` URI.
+    pub fn content_hash(&self) -> Option<&ContentHash> {
+        match self {
+            DcpUri::Bundle(h) | DcpUri::Record(h) => Some(h),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DcpUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}:{}", SCHEME, self.kind(), self.value())
+    }
+}
+
+impl FromStr for DcpUri {
+    type Err = UriError;
+
+    fn from_str(s: &str) -> Result<Self, UriError> {
+        let rest = s.strip_prefix(SCHEME).ok_or(UriError::NotDcp)?;
+        let (kind, value) = rest.split_once(':').ok_or_else(|| UriError::UnknownKind(rest.to_string()))?;
+        match kind {
+            "agent" => {
+                if value.len() == 32 && value.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b)) {
+                    Ok(DcpUri::Agent(value.to_string()))
+                } else {
+                    let reason = "fingerprint must be 32 lowercase hex digits".into();
+                    Err(UriError::Malformed { kind: "agent", reason })
+                }
+            }
+            "human" => id("human", value).map(DcpUri::Human),
+            "intent" => id("intent", value).map(DcpUri::Intent),
+            "audit" => id("audit", value).map(DcpUri::Audit),
+            "bundle" => DcpUri::bundle(value),
+            "record" => DcpUri::record(value),
+            other => Err(UriError::UnknownKind(other.to_string())),
+        }
+    }
+}
+
+impl TryFrom<&str> for DcpUri {
+    type Error = UriError;
+
+    fn try_from(s: &str) -> Result<Self, UriError> {
+        s.parse()
+    }
+}
+
+impl From<DcpUri> for String {
+    fn from(uri: DcpUri) -> String {
+        uri.to_string()
+    }
+}
+
+/// Identifiers are non-empty and limited to RFC 3986 unreserved
+/// characters plus `:`, so `did:` identifiers embed unescaped.
+fn id(kind: &'static str, value: &str) -> Result<String, UriError> {
+    if value.is_empty() {
+        return Err(UriError::Malformed { kind, reason: "empty identifier".into() });
+    }
+    match value.chars().find(|c| !(c.is_ascii_alphanumeric() || "-._~:".contains(*c))) {
+        Some(c) => Err(UriError::Malformed { kind, reason: format!("invalid character {:?}", c) }),
+        None => Ok(value.to_string()),
+    }
+}
+
+fn bad_hash(kind: &'static str, hash: &str) -> UriError {
+    UriError::Malformed { kind, reason: format!("expected <sha256|sha3-256>:<64 hex digits>, got {:?}", hash) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+
+    #[test]
+    fn test_round_trip_and_rejections() {
+        let hash = format!("sha256:{}", "ab".repeat(32));
+        for s in [
+            "dcp:human:did:human:fixture".to_string(),
+            "dcp:audit:audit-00001".to_string(),
+            format!("dcp:bundle:{}", hash),
+            format!("dcp:record:{}", hash),
+        ] {
+            assert_eq!(s.parse::<DcpUri>().unwrap().to_string(), s);
+        }
+
+        let (pk, _) = generate_keypair();
+        let agent = DcpUri::agent_for_key(&pk).unwrap();
+        assert_eq!(agent.to_string().parse::<DcpUri>(), Ok(agent));
+
+        assert_eq!("sha256:abc".parse::<DcpUri>(), Err(UriError::NotDcp));
+        assert_eq!("dcp:widget:1".parse::<DcpUri>(), Err(UriError::UnknownKind("widget".into())));
+        assert!(matches!("dcp:agent:did:agent:x".parse::<DcpUri>(), Err(UriError::Malformed { kind: "agent", .. })));
+        assert!(matches!("dcp:bundle:md5:00".parse::<DcpUri>(), Err(UriError::Malformed { kind: "bundle", .. })));
+        assert!(matches!("dcp:intent:a b".parse::<DcpUri>(), Err(UriError::Malformed { kind: "intent", .. })));
+    }
+}
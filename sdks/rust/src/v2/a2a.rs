@@ -12,6 +12,7 @@ use rand::RngCore;
 use serde_json::{json, Value};
 use sha2::{Digest, Sha256};
 
+use crate::spec::AgentStatus;
 use crate::v2::lifecycle::utc_now_iso_pub as utc_now_iso;
 
 // ── Discovery ──
@@ -73,7 +74,7 @@ pub fn validate_directory_entry(entry: &Value) -> Vec<String> {
         errors.push("Missing a2a_endpoint".into());
     }
     let status = entry.get("status").and_then(Value::as_str).unwrap_or("");
-    if !matches!(status, "active" | "suspended" | "revoked") {
+    if status.parse::<AgentStatus>().is_err() {
         errors.push("Invalid status".into());
     }
     errors
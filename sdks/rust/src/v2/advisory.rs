@@ -6,11 +6,14 @@
 //! to a concrete struct schema.
 
 use std::collections::BTreeSet;
+use std::time::Duration;
 
 use serde_json::{json, Value};
 
 /// Lightweight timestamp comparison — both sides are UTC ISO-8601 with Z.
-fn parse_iso_epoch_secs(ts: &str) -> Option<i64> {
+/// `None` unless the year is 0000-9999 and every other field is in range,
+/// so the result never overflows arithmetic on it.
+pub(crate) fn parse_iso_epoch_secs(ts: &str) -> Option<i64> {
     // Accept both "YYYY-MM-DDTHH:MM:SS.sssZ" and "YYYY-MM-DDTHH:MM:SSZ".
     let (date, time) = ts.split_once('T')?;
     let time_part = time.trim_end_matches('Z');
@@ -23,6 +26,22 @@ fn parse_iso_epoch_secs(ts: &str) -> Option<i64> {
     let hour: i64 = tp.next()?.parse().ok()?;
     let minute: i64 = tp.next()?.parse().ok()?;
     let second: i64 = tp.next()?.parse().ok()?;
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let month_days = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    if !(0..=9999).contains(&year)
+        || !(1..=12).contains(&month)
+        || !(1..=month_days).contains(&day)
+        || !(0..24).contains(&hour)
+        || !(0..60).contains(&minute)
+        || !(0..60).contains(&second)
+    {
+        return None;
+    }
 
     // Days from civil (year, month, day) — Howard Hinnant.
     let m = if month <= 2 { month + 9 } else { month - 3 };
@@ -35,7 +54,12 @@ fn parse_iso_epoch_secs(ts: &str) -> Option<i64> {
     Some(days * 86_400 + hour * 3600 + minute * 60 + second)
 }
 
-fn now_epoch_secs() -> i64 {
+/// `d` in whole seconds, saturating at `i64::MAX`.
+pub(crate) fn duration_secs(d: Duration) -> i64 {
+    i64::try_from(d.as_secs()).unwrap_or(i64::MAX)
+}
+
+pub(crate) fn now_epoch_secs() -> i64 {
     use std::time::{SystemTime, UNIX_EPOCH};
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -201,3 +225,30 @@ pub fn build_algorithm_advisory(
         "issuer": issuer,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_iso_epoch_secs_known() {
+        assert_eq!(parse_iso_epoch_secs("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(parse_iso_epoch_secs("2024-02-29T23:59:59.999Z"), Some(1_709_251_199));
+    }
+
+    #[test]
+    fn test_parse_iso_epoch_secs_rejects_out_of_range() {
+        for ts in [
+            "99999999999999999-01-01T00:00:00Z",
+            "2026-13-45T99:99:99Z",
+            "2026-02-29T00:00:00Z",
+            "2026-04-31T00:00:00Z",
+            "2026-01-01T24:00:00Z",
+            "2026-01-01T00:60:00Z",
+            "2026-01-01T00:00:60Z",
+            "-0001-01-01T00:00:00Z",
+        ] {
+            assert_eq!(parse_iso_epoch_secs(ts), None, "{}", ts);
+        }
+    }
+}
@@ -32,7 +32,7 @@ pub fn evaluate_significance(ctx: &SignificanceContext) -> u32 {
     ];
     let mut total = 0.0;
     for (v, w) in weights {
-        let clamped = v.max(0.0).min(1.0);
+        let clamped = v.clamp(0.0, 1.0);
         total += clamped * w;
     }
     (total * 1000.0).round() as u32
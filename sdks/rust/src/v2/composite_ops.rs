@@ -1,6 +1,7 @@
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD as BASE64;
 
+use crate::b64;
 use crate::v2::composite_sig::{CompositeSignature, SignatureEntry};
 use crate::v2::crypto_provider::{CryptoError, CryptoProvider};
 use crate::v2::domain_separation::domain_separated_message;
@@ -35,7 +36,7 @@ pub fn composite_sign(
     pq_key: &CompositeKeyInfo,
 ) -> Result<CompositeSignature, CryptoError> {
     let dsm = domain_separated_message(context, canonical_payload)
-        .map_err(|e| CryptoError::SignatureError(e))?;
+        .map_err(CryptoError::SignatureError)?;
 
     let classical_sig = classical_provider.sign(&dsm, &classical_key.secret_key_b64)?;
 
@@ -67,7 +68,7 @@ pub fn classical_only_sign(
     key: &CompositeKeyInfo,
 ) -> Result<CompositeSignature, CryptoError> {
     let dsm = domain_separated_message(context, canonical_payload)
-        .map_err(|e| CryptoError::SignatureError(e))?;
+        .map_err(CryptoError::SignatureError)?;
 
     let sig = classical_provider.sign(&dsm, &key.secret_key_b64)?;
 
@@ -93,7 +94,7 @@ pub fn composite_verify(
     pq_pubkey_b64: Option<&str>,
 ) -> Result<CompositeVerifyResult, CryptoError> {
     let dsm = domain_separated_message(context, canonical_payload)
-        .map_err(|e| CryptoError::SignatureError(e))?;
+        .map_err(CryptoError::SignatureError)?;
 
     if composite_sig.binding == "classical_only" {
         if composite_sig.pq.is_some() {
@@ -103,7 +104,7 @@ pub fn composite_verify(
                 pq_valid: false,
             });
         }
-        let classical_sig_bytes = BASE64.decode(&composite_sig.classical.sig_b64)
+        let classical_sig_bytes = b64::decode(&composite_sig.classical.sig_b64)
             .map_err(|e| CryptoError::SignatureError(format!("base64 decode: {}", e)))?;
         let classical_valid = classical_provider.verify(
             &dsm,
@@ -135,9 +136,9 @@ pub fn composite_verify(
         CryptoError::KeyError("PQ public key required for pq_over_classical verification".into())
     })?;
 
-    let classical_sig_bytes = BASE64.decode(&composite_sig.classical.sig_b64)
+    let classical_sig_bytes = b64::decode(&composite_sig.classical.sig_b64)
         .map_err(|e| CryptoError::SignatureError(format!("base64 decode: {}", e)))?;
-    let pq_sig_bytes = BASE64.decode(&pq_entry.sig_b64)
+    let pq_sig_bytes = b64::decode(&pq_entry.sig_b64)
         .map_err(|e| CryptoError::SignatureError(format!("base64 decode: {}", e)))?;
 
     let mut composite_message = Vec::with_capacity(dsm.len() + classical_sig_bytes.len());
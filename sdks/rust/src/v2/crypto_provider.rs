@@ -1,6 +1,7 @@
 use sha2::{Sha256, Digest};
 
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum CryptoError {
     #[error("Key error: {0}")]
     KeyError(String),
@@ -8,6 +9,9 @@ pub enum CryptoError {
     SignatureError(String),
     #[error("Unknown algorithm: {0}")]
     UnknownAlgorithm(String),
+    /// Refused by [`crate::ratelimit::RateLimitedSigner`].
+    #[error("Rate limit exceeded: {0}")]
+    RateLimited(String),
 }
 
 #[derive(Debug, Clone)]
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
-use sha2::{Sha256, Digest};
-use sha3::Sha3_256;
+
+use crate::hash::{digest_hex, digest_pair, HashAlgorithm};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DualHash {
@@ -9,15 +9,11 @@ pub struct DualHash {
 }
 
 pub fn sha256_hex(data: &[u8]) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(data);
-    hex::encode(hasher.finalize())
+    digest_hex(HashAlgorithm::Sha256, data)
 }
 
 pub fn sha3_256_hex(data: &[u8]) -> String {
-    let mut hasher = Sha3_256::new();
-    hasher.update(data);
-    hex::encode(hasher.finalize())
+    digest_hex(HashAlgorithm::Sha3_256, data)
 }
 
 pub fn dual_hash(data: &[u8]) -> DualHash {
@@ -32,9 +28,9 @@ pub fn dual_hash_canonical(canonical_json: &str) -> DualHash {
 }
 
 /// Reduce a layer of hex-encoded leaf hashes to a single Merkle root using
-/// the given hasher. Odd layers are balanced by duplicating the last leaf,
+/// the given algorithm. Odd layers are balanced by duplicating the last leaf,
 /// matching the Python/TS/WASM behaviour.
-fn merkle_root_hex<D: Digest>(leaves: &[String]) -> Option<String> {
+fn merkle_root_hex(algorithm: HashAlgorithm, leaves: &[String]) -> Option<String> {
     if leaves.is_empty() {
         return None;
     }
@@ -48,10 +44,7 @@ fn merkle_root_hex<D: Digest>(leaves: &[String]) -> Option<String> {
         }
         let mut next: Vec<Vec<u8>> = Vec::with_capacity(layer.len() / 2);
         for pair in layer.chunks(2) {
-            let mut hasher = D::new();
-            hasher.update(&pair[0]);
-            hasher.update(&pair[1]);
-            next.push(hasher.finalize().to_vec());
+            next.push(digest_pair(algorithm, &pair[0], &pair[1]));
         }
         layer = next;
     }
@@ -70,8 +63,8 @@ pub fn dual_merkle_root(leaves: &[DualHash]) -> Option<DualHash> {
     }
     let sha256_leaves: Vec<String> = leaves.iter().map(|l| l.sha256.clone()).collect();
     let sha3_leaves: Vec<String> = leaves.iter().map(|l| l.sha3_256.clone()).collect();
-    let sha256 = merkle_root_hex::<Sha256>(&sha256_leaves)?;
-    let sha3_256 = merkle_root_hex::<Sha3_256>(&sha3_leaves)?;
+    let sha256 = merkle_root_hex(HashAlgorithm::Sha256, &sha256_leaves)?;
+    let sha3_256 = merkle_root_hex(HashAlgorithm::Sha3_256, &sha3_leaves)?;
     Some(DualHash { sha256, sha3_256 })
 }
 
@@ -107,7 +100,7 @@ mod tests {
     #[test]
     fn test_dual_merkle_root_single_leaf() {
         let leaf = dual_hash(b"leaf-0");
-        let root = dual_merkle_root(&[leaf.clone()]).unwrap();
+        let root = dual_merkle_root(std::slice::from_ref(&leaf)).unwrap();
         assert_eq!(root.sha256, leaf.sha256);
         assert_eq!(root.sha3_256, leaf.sha3_256);
     }
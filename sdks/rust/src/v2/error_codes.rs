@@ -2,9 +2,12 @@
 
 use std::fmt;
 
+use crate::types::VerifyError;
+
 /// DCP canonical error code. The display representation (e.g. `DCP-E100`)
 /// is the stable identifier shared across SDKs.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum DcpErrorCode {
     // Schema errors (E001-E099)
     BundleSchemaInvalid,
@@ -150,6 +153,61 @@ impl DcpErrorCode {
     pub fn retryable(&self) -> bool {
         self.description().1
     }
+
+    /// Canonical code for a v1 verifier failure, or `None` if it has no
+    /// canonical counterpart.
+    pub fn from_verify_error(error: &VerifyError) -> Option<Self> {
+        use DcpErrorCode::*;
+        Some(match error {
+            VerifyError::Missing { field } if field == "signature" || field == "sig_b64" => SignatureMissing,
+            VerifyError::Missing { .. } => BundleSchemaInvalid,
+            VerifyError::SignatureInvalid => ClassicalSigInvalid,
+            VerifyError::SignatureTooOld => SignatureExpired,
+            VerifyError::BindingExpired
+            | VerifyError::TimestampOrder
+            | VerifyError::AgreementMismatch
+            | VerifyError::AgreementUnavailable
+            | VerifyError::RenewalInvalid
+            | VerifyError::IssuanceInvalid => RprInvalid,
+            VerifyError::BundleHashMismatch => ManifestHashMismatch,
+            VerifyError::MerkleRootMismatch => MerkleRootMismatch,
+            VerifyError::IntentHashMismatch { .. } | VerifyError::AmendmentInvalid { .. } => IntentHashMismatch,
+            VerifyError::ChainBreak { .. } | VerifyError::LedgerChainBroken => HashChainBroken,
+            VerifyError::WeakAlgorithm => TierInsufficient,
+            VerifyError::UnsupportedCanonicalization | VerifyError::UnsupportedLeafOrder => VersionUnsupported,
+            VerifyError::UnsupportedSignatureAlg | VerifyError::UnsupportedHashAlg => AlgorithmUnavailable,
+            VerifyError::Revoked => AgentRevoked,
+            VerifyError::KeyNotBound | VerifyError::SignerInvalid => KidMismatch,
+            VerifyError::UpgradeInvalid | VerifyError::CapabilityMissing | VerifyError::HandoffInvalid => {
+                CapabilityDenied
+            }
+            VerifyError::HandshakeMismatch | VerifyError::ChallengeMismatch => SessionNonceInvalid,
+            VerifyError::HandshakeExpired | VerifyError::ChallengeExpired => SessionExpired,
+            VerifyError::IntentExpired { .. }
+            | VerifyError::IntentNotYetValid { .. }
+            | VerifyError::DecisionNotAudited
+            | VerifyError::TargetNotHashed
+            | VerifyError::PolicySnapshotUnapproved
+            | VerifyError::UnauditedAction
+            | VerifyError::ConsentMissing
+            | VerifyError::OverrideInvalid => PolicyViolation,
+            VerifyError::SequenceOutOfOrder { .. } => SequenceOutOfOrder,
+            VerifyError::ReferenceMismatch
+            | VerifyError::IntentIdMismatch { .. }
+            | VerifyError::DuplicateAuditId { .. }
+            | VerifyError::LimitExceeded
+            | VerifyError::ArtifactUnparseable
+            | VerifyError::DecompressionFailed
+            | VerifyError::InvalidValue { .. } => BundleSchemaInvalid,
+            VerifyError::Internal => InternalError,
+            VerifyError::KeyNotAttested
+            | VerifyError::AttestationMissing
+            | VerifyError::AttestationInvalid
+            | VerifyError::NotarizationInvalid
+            | VerifyError::CeremonyInvalid
+            | VerifyError::NotVerified => return None,
+        })
+    }
 }
 
 impl fmt::Display for DcpErrorCode {
@@ -47,7 +47,7 @@ pub fn compute_vitality_score(m: &VitalityMetricsFloat) -> u32 {
         + (1.0 - m.error_rate) * W_ERROR_RATE
         + m.human_satisfaction * W_HUMAN_SATISFACTION
         + m.policy_alignment * W_POLICY_ALIGNMENT;
-    let clamped = raw.max(0.0).min(1.0);
+    let clamped = raw.clamp(0.0, 1.0);
     (clamped * 1000.0).round() as u32
 }
 
@@ -71,7 +71,7 @@ pub(crate) fn utc_now_iso_pub() -> String {
 }
 
 /// Convert seconds since epoch to (year, month, day, hour, minute, second) UTC.
-fn civil_from_secs(secs: i64) -> (i64, u32, u32, u32, u32, u32) {
+pub(crate) fn civil_from_secs(secs: i64) -> (i64, u32, u32, u32, u32, u32) {
     // Howard Hinnant's civil_from_days algorithm.
     let days = secs.div_euclid(86_400);
     let tod = secs.rem_euclid(86_400) as u32;
@@ -82,7 +82,7 @@ fn civil_from_secs(secs: i64) -> (i64, u32, u32, u32, u32, u32) {
     let y = yoe as i64 + era * 400;
     let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
     let mp = (5 * doy + 2) / 153;
-    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let d = doy - (153 * mp + 2) / 5 + 1;
     let m = if mp < 10 { mp + 3 } else { mp - 9 };
     let y = if m <= 2 { y + 1 } else { y };
     let h = tod / 3600;
@@ -124,13 +124,13 @@ pub fn create_commissioning_certificate(
         "principal_binding_reference": params.principal_binding_reference,
     });
 
-    Ok(finalize_lifecycle_payload(
+    finalize_lifecycle_payload(
         classical_provider,
         pq_provider,
         classical_key,
         pq_key,
         payload,
-    )?)
+    )
 }
 
 /// Parameters for creating a vitality report.
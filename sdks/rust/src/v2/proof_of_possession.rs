@@ -2,6 +2,7 @@ use base64::Engine;
 use base64::engine::general_purpose::STANDARD as BASE64;
 use serde::{Deserialize, Serialize};
 
+use crate::b64;
 use crate::v2::composite_sig::SignatureEntry;
 use crate::v2::crypto_provider::{CryptoError, CryptoProvider};
 use crate::v2::domain_separation::{
@@ -47,9 +48,9 @@ pub fn generate_registration_pop(
 ) -> Result<SignatureEntry, CryptoError> {
     let canonical = canonicalize_v2(&serde_json::to_value(challenge)
         .map_err(|e| CryptoError::SignatureError(format!("serialize: {}", e)))?)
-        .map_err(|e| CryptoError::SignatureError(e))?;
+        .map_err(CryptoError::SignatureError)?;
     let dsm = domain_separated_message(CTX_PROOF_OF_POSSESSION, canonical.as_bytes())
-        .map_err(|e| CryptoError::SignatureError(e))?;
+        .map_err(CryptoError::SignatureError)?;
     let sig = provider.sign(&dsm, secret_key_b64)?;
 
     Ok(SignatureEntry {
@@ -68,10 +69,10 @@ pub fn verify_registration_pop(
 ) -> Result<bool, CryptoError> {
     let canonical = canonicalize_v2(&serde_json::to_value(challenge)
         .map_err(|e| CryptoError::SignatureError(format!("serialize: {}", e)))?)
-        .map_err(|e| CryptoError::SignatureError(e))?;
+        .map_err(CryptoError::SignatureError)?;
     let dsm = domain_separated_message(CTX_PROOF_OF_POSSESSION, canonical.as_bytes())
-        .map_err(|e| CryptoError::SignatureError(e))?;
-    let sig_bytes = BASE64.decode(&pop.sig_b64)
+        .map_err(CryptoError::SignatureError)?;
+    let sig_bytes = b64::decode(&pop.sig_b64)
         .map_err(|e| CryptoError::SignatureError(format!("base64 decode: {}", e)))?;
     provider.verify(&dsm, &sig_bytes, public_key_b64)
 }
@@ -85,6 +86,7 @@ struct RotationPayload {
 }
 
 /// Create a key rotation record with proof-of-possession.
+#[allow(clippy::too_many_arguments)]
 pub fn create_key_rotation(
     old_provider: &dyn CryptoProvider,
     new_provider: &dyn CryptoProvider,
@@ -104,9 +106,9 @@ pub fn create_key_rotation(
     };
     let canonical = canonicalize_v2(&serde_json::to_value(&payload)
         .map_err(|e| CryptoError::SignatureError(format!("serialize: {}", e)))?)
-        .map_err(|e| CryptoError::SignatureError(e))?;
+        .map_err(CryptoError::SignatureError)?;
     let dsm = domain_separated_message(CTX_KEY_ROTATION, canonical.as_bytes())
-        .map_err(|e| CryptoError::SignatureError(e))?;
+        .map_err(CryptoError::SignatureError)?;
 
     let pop_sig = new_provider.sign(&dsm, new_secret_key_b64)?;
     let auth_sig = old_provider.sign(&dsm, old_secret_key_b64)?;
@@ -152,13 +154,13 @@ pub fn verify_key_rotation(
     };
     let canonical = canonicalize_v2(&serde_json::to_value(&payload)
         .map_err(|e| CryptoError::SignatureError(format!("serialize: {}", e)))?)
-        .map_err(|e| CryptoError::SignatureError(e))?;
+        .map_err(CryptoError::SignatureError)?;
     let dsm = domain_separated_message(CTX_KEY_ROTATION, canonical.as_bytes())
-        .map_err(|e| CryptoError::SignatureError(e))?;
+        .map_err(CryptoError::SignatureError)?;
 
-    let pop_sig = BASE64.decode(&record.proof_of_possession.sig_b64)
+    let pop_sig = b64::decode(&record.proof_of_possession.sig_b64)
         .map_err(|e| CryptoError::SignatureError(format!("base64 decode: {}", e)))?;
-    let auth_sig = BASE64.decode(&record.authorization_sig.sig_b64)
+    let auth_sig = b64::decode(&record.authorization_sig.sig_b64)
         .map_err(|e| CryptoError::SignatureError(format!("base64 decode: {}", e)))?;
 
     let pop_valid = new_provider.verify(&dsm, &pop_sig, new_public_key_b64)?;
@@ -2,6 +2,8 @@
 
 use serde_json::Value;
 
+use crate::types::{Impact, ImpactCategory, ImpactMagnitude};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SecurityTier {
     Routine,
@@ -51,7 +53,7 @@ pub fn compute_security_tier(intent: &Value) -> SecurityTier {
     let has_sensitive = data_classes.iter().any(|d| SENSITIVE_DATA_CLASSES.contains(d));
     let is_payment = intent.get("action_type").and_then(Value::as_str) == Some("initiate_payment");
 
-    if score >= 800 || has_high_value {
+    let tier = if score >= 800 || has_high_value {
         SecurityTier::Maximum
     } else if score >= 500 || has_sensitive || is_payment {
         SecurityTier::Elevated
@@ -59,6 +61,22 @@ pub fn compute_security_tier(intent: &Value) -> SecurityTier {
         SecurityTier::Standard
     } else {
         SecurityTier::Routine
+    };
+    match intent.get("estimated_impact").and_then(|v| serde_json::from_value::<Impact>(v.clone()).ok()) {
+        Some(impact @ Impact::Structured(_)) => max_tier(tier, impact_tier(&impact)),
+        _ => tier,
+    }
+}
+
+/// Floor set by a structured `estimated_impact`. Free-text impacts do not
+/// move the tier, matching the other SDKs for v1.0 intents.
+fn impact_tier(impact: &Impact) -> SecurityTier {
+    let safety = impact.categories().contains(&ImpactCategory::Safety);
+    match impact.magnitude() {
+        Some(ImpactMagnitude::High) if safety => SecurityTier::Maximum,
+        Some(ImpactMagnitude::High) => SecurityTier::Elevated,
+        Some(ImpactMagnitude::Medium) => SecurityTier::Standard,
+        _ => SecurityTier::Routine,
     }
 }
 
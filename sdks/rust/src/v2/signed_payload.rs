@@ -49,7 +49,7 @@ mod tests {
         let payload = json!({"action": "test", "value": 42});
         let (_, hash) = prepare_payload(&payload).unwrap();
         let signed = SignedPayload {
-            payload: payload,
+            payload,
             payload_hash: hash,
             composite_sig: CompositeSignature::classical_only(SignatureEntry {
                 alg: "ed25519".into(),
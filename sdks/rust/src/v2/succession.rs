@@ -164,7 +164,7 @@ pub fn update_digital_testament(
     let prev_version = previous_obj
         .get("testament_version")
         .and_then(Value::as_u64)
-        .unwrap_or(0) as u64;
+        .unwrap_or(0);
 
     let payload = json!({
         "dcp_version": "2.0",
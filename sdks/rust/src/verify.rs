@@ -1,102 +1,1708 @@
 //! Full DCP signed bundle verification.
 
+use std::collections::HashMap;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
 use serde_json::Value;
-use crate::crypto::{canonicalize, hash_object, verify_object, merkle_root_from_hex_leaves};
-use crate::types::VerificationResult;
+use crate::amendment::{check_lineage_in, AMENDED_OUTCOME};
+use crate::bundle::{normalize_with, NormalizeOptions};
+use crate::challenge::iso_from_secs;
+use crate::crypto::{
+    canonicalize, merkle_root_from_hex_leaves, verify_canonical_multi_with, Canon, Canonicalizer, CANON_V1,
+};
+use crate::hash::{digest_hex, HashAlgorithm};
+use crate::limits::InputLimits;
+use crate::merkle::LeafOrder;
+use crate::observability::dcp_telemetry;
+use crate::signer::{SignerIdentity, SignerType};
+use crate::trace_context;
+use crate::transcript::Transcript;
+use crate::types::{
+    CheckResult, CheckStatus, Outcome, SignedBundle, VerificationFailure, VerificationReport, VerificationResult,
+    VerifyError,
+};
+use crate::v2::advisory::{duration_secs, now_epoch_secs, parse_iso_epoch_secs};
+use crate::volatile::VolatileFields;
+
+mod cross_check;
+pub use cross_check::{
+    cross_check, cross_check_with, CrossCheckOptions, CrossCheckReport, EventMatch, ExternalEvent,
+};
+mod best_effort;
+pub use best_effort::{verify_best_effort, ArtifactReport, BestEffortReport};
+mod tenant;
+pub use tenant::{LimitCheck, MultiTenantVerifier, TenantConfig, TenantError};
+#[cfg(feature = "pool")]
+mod pool;
+#[cfg(feature = "pool")]
+pub use pool::VerifierPool;
+#[cfg(feature = "compressed")]
+mod compressed;
+#[cfg(feature = "compressed")]
+pub use compressed::{verify_compressed, CompressedOptions, Encoding};
+#[cfg(feature = "stream")]
+mod stream;
+#[cfg(feature = "stream")]
+pub use stream::{stream_verifier, stream_verifier_with_concurrency};
+#[cfg(feature = "subscribe")]
+pub(crate) use stream::report_contained;
+#[cfg(feature = "mmap")]
+mod file;
+#[cfg(feature = "mmap")]
+pub use file::{verify_file, verify_file_with_progress, FileProgress};
+
+/// Digest algorithms the verifier can recompute `bundle_hash` under, as
+/// written in its `<alg>:<hex>` prefix.
+pub const KNOWN_BUNDLE_HASH_ALGS: &[&str] = &["sha256", "sha3-256"];
+
+/// Digest algorithms the verifier can recompute `merkle_root` under. Audit
+/// trees are built with sha256 only.
+pub const KNOWN_MERKLE_HASH_ALGS: &[&str] = &["sha256"];
+
+/// Signature algorithms ordered from weakest to strongest. Used to
+/// enforce [`VerifyOptions::minimum_signature_alg`].
+pub const SIGNATURE_ALG_STRENGTH: &[&str] = &["ed25519", "ml-dsa-65", "slh-dsa-192f"];
+
+/// Signature algorithms this verifier checks signatures under. The
+/// `signature.alg` label is not covered by the signature, so algorithm
+/// policy is enforced only for these; any other label fails
+/// `UNSUPPORTED SIGNATURE ALG` when a policy is set.
+pub const VERIFIED_SIGNATURE_ALGS: &[&str] = &["ed25519"];
+
+/// Checks reported by [`verify_with_report`], in the order they run.
+pub const CHECK_NAMES: &[&str] = &[
+    "signature_policy",
+    "signature",
+    "signer_binding",
+    "signature_age",
+    "binding_expiry",
+    "temporal_order",
+    "entry_freshness",
+    "bundle_hash",
+    "merkle_root",
+    "expiry",
+    "intent_hash",
+    "prev_hash",
+    "sequence",
+    "trace_context",
+    "references",
+    "identifiers",
+    "decision_coverage",
+    "hashed_targets",
+    "policy_snapshot",
+];
+
+/// Verifier policy. `Default` accepts every digest in
+/// [`KNOWN_BUNDLE_HASH_ALGS`] and [`KNOWN_MERKLE_HASH_ALGS`] and any
+/// signature algorithm, matching [`verify_signed_bundle`].
+///
+/// Operators phase out a digest by removing it from `accepted_hash_algs`
+/// or `accepted_merkle_algs`;
+/// bundles still using it then fail with `WEAK ALGORITHM REJECTED`.
+///
+/// The signature covers the canonical `bundle` only. Nothing in the
+/// `signature` block is signed: `created_at`, `alg`, `signer`,
+/// `bundle_hash`, and `merkle_root` can be rewritten without breaking it.
+/// Options that read them ([`Self::max_signature_age`],
+/// [`Self::max_entry_age`], [`Self::require_signer_binding`]) bound what
+/// a producer claims, not what it proved; the time checks that matter
+/// ([`Self::require_unexpired_binding`], [`Self::require_temporal_order`])
+/// use bundle timestamps or [`Self::now_secs`] instead.
+///
+/// Those temporal checks are off by default, so upgrading does not change
+/// the verdict on bundles that verified before; [`Preset::ApiGateway`] and
+/// [`Preset::Archival`] turn both on. Because `signature.created_at` is
+/// unsigned, "signature created before the passport" is checked as "audit
+/// entry logged before the passport was created" instead.
+#[derive(Debug, Clone, serde::Serialize)]
+#[non_exhaustive]
+pub struct VerifyOptions {
+    /// Digest prefixes accepted in `bundle_hash`.
+    pub accepted_hash_algs: Vec<String>,
+    /// Digest prefixes accepted in `merkle_root`. A prefix outside
+    /// [`KNOWN_MERKLE_HASH_ALGS`] fails `UNSUPPORTED HASH ALG` rather than
+    /// going unchecked.
+    pub accepted_merkle_algs: Vec<String>,
+    /// Weakest acceptable signature algorithm per
+    /// [`SIGNATURE_ALG_STRENGTH`], judged by the algorithm the signature
+    /// was verified under ([`VERIFIED_SIGNATURE_ALGS`]), not the label.
+    pub minimum_signature_alg: Option<String>,
+    /// Require an approved policy decision to be followed by an
+    /// `approved` audit entry for the intent with a terminal
+    /// [`crate::types::Outcome`].
+    pub require_decision_coverage: bool,
+    /// Hard privacy mode: the intent target's `to`, `domain`, and `url`
+    /// must be salted hashes ([`crate::privacy`]). Targets that declare a
+    /// `salt_id` are held to this regardless.
+    pub require_hashed_targets: bool,
+    /// Keys to try, in order, when no explicit public key is passed;
+    /// the signer's embedded key is then ignored. Lets a verifier hold
+    /// the old and new keys during a rotation. The key that verified is
+    /// reported in [`VerificationResult::matched_key`].
+    pub candidate_keys: Vec<String>,
+    /// Online use (API gateways): reject signatures whose
+    /// `signature.created_at` is older than this, with `SIGNATURE TOO OLD`.
+    /// `created_at` is not covered by the signature, so this bounds replay
+    /// of captured bundles rather than proving when signing happened.
+    /// Leave `None` for archival verification.
+    pub max_signature_age: Option<Duration>,
+    /// Strict mode: reject a bundle whose audit entries reuse an
+    /// `audit_id` (`DUPLICATE AUDIT ID`) or name an intent other than the
+    /// bundle's (`INTENT ID MISMATCH`), or whose policy decision is for
+    /// another intent (`REFERENCE MISMATCH`). Off by default, and those
+    /// checks are reported as skipped.
+    pub strict_identifiers: bool,
+    /// Migration shim for producers that skipped `None` fields when
+    /// signing but shipped them as `null` (or the reverse happened in
+    /// transit): a bundle that fails its signature or `bundle_hash` under
+    /// spec canonicalization and has `null` members is verified again with
+    /// them treated as absent
+    /// ([`crate::crypto::CanonicalizeOptions::treat_null_as_absent`]).
+    pub accept_null_as_absent: bool,
+    /// Reject a bundle whose Responsible Principal Record had expired
+    /// (`BINDING EXPIRED`). Expiry is judged at the latest audit entry
+    /// `timestamp`, which the signature covers, or now if no entry has
+    /// one; `signature.created_at` is not signed and is not used. Also on
+    /// under [`Self::require_temporal_order`]. Default `false`.
+    pub require_unexpired_binding: bool,
+    /// How long after the Responsible Principal Record's `expires_at` a
+    /// bundle may still be acted on under
+    /// [`Self::require_unexpired_binding`]: inside the window verification
+    /// passes with a `BINDING IN GRACE PERIOD` warning, past it the bundle
+    /// fails with `BINDING EXPIRED`. Default zero.
+    pub expiry_grace: Duration,
+    /// Strict Ed25519 verification ([`crate::crypto::verify_object`]):
+    /// reject signatures under small-order keys or with a small-order
+    /// `R`, which lenient verifiers accept for any message. Default `true`;
+    /// turn off only to reproduce a lenient verifier's verdict.
+    pub strict_signatures: bool,
+    /// Strict mode: the key that verified the signature must be the
+    /// agent passport's `public_key` (`KEY NOT BOUND` otherwise), so a
+    /// bundle signed by an unrelated key that merely self-verifies is
+    /// rejected. See [`Self::trusted_signer_keys`] for other signers.
+    /// `signature.signer` must also be a valid
+    /// [`crate::signer::SignerIdentity`] (`SIGNER INVALID` otherwise).
+    pub require_signer_binding: bool,
+    /// Keys of notaries, responsible humans, and other non-agent signers
+    /// accepted in place of the passport key under
+    /// [`Self::require_signer_binding`]. Only honored when
+    /// `signature.signer.type` is not `agent`.
+    pub trusted_signer_keys: Vec<String>,
+    /// Audit entry fields left out of entry hashes for the `prev_hash`
+    /// chain and `merkle_root` ([`crate::volatile`]). Default empty.
+    pub volatile_audit_fields: VolatileFields,
+    /// [`crate::policy::snapshot_hash`]es of approved rulesets. When
+    /// non-empty, the policy decision's `policy_snapshot_hash` must be one
+    /// of them (`POLICY SNAPSHOT UNAPPROVED` otherwise). Default empty.
+    pub approved_policy_snapshots: Vec<String>,
+    /// Freshness window for audit entries: an entry more than this older
+    /// than `signature.created_at` adds a `STALE AUDIT ENTRY` warning.
+    /// The producer-side counterpart is
+    /// [`crate::bundle::BundleBuilder::finalize_with_freshness`]. Default `None`.
+    pub max_entry_age: Option<Duration>,
+    /// Fail a bundle whose signature has no verifiable `bundle_hash`
+    /// (`Missing bundle_hash`), instead of skipping the check.
+    pub require_bundle_hash: bool,
+    /// Fail a bundle whose signature has no verifiable `merkle_root`
+    /// (`Missing merkle_root`), instead of skipping the check.
+    pub require_merkle_root: bool,
+    /// Fail an intent without a `not_before` / `valid_until` window, or
+    /// with no timestamped audit entry to check it against
+    /// (`Missing valid_until`), instead of skipping the check.
+    pub require_expiry_check: bool,
+    /// Signature algorithms accepted, judged like
+    /// [`Self::minimum_signature_alg`] by the algorithm the signature was
+    /// verified under. Empty accepts any. Default empty.
+    pub allowed_algs: Vec<String>,
+    /// How far the signer's and verifier's clocks may disagree: widens
+    /// the intent validity window, the binding expiry, and
+    /// [`Self::max_signature_age`] by this much. Default zero.
+    pub clock_skew: Duration,
+    /// Reject a bundle whose signed timestamps are out of order
+    /// (`TIMESTAMP ORDER`): a passport created before its Responsible
+    /// Principal Record was issued, an audit entry logged before the
+    /// passport was created, or an audit entry from more than
+    /// [`Self::clock_skew`] after [`Self::now_secs`]. Also rejects an
+    /// expired binding, as [`Self::require_unexpired_binding`] does.
+    /// `signature.created_at` is not signed and is not compared. Missing
+    /// or unparseable timestamps are not compared.
+    pub require_temporal_order: bool,
+    /// Unix seconds to verify as of, in place of the system clock, for
+    /// tests and for reproducing a past verdict. Default `None`.
+    pub now_secs: Option<i64>,
+}
+
+/// [`VerifyOptions`] under the name used for per-deployment strictness
+/// settings.
+pub type VerificationOptions = VerifyOptions;
+
+impl Default for VerifyOptions {
+    fn default() -> Self {
+        Self {
+            accepted_hash_algs: KNOWN_BUNDLE_HASH_ALGS.iter().map(|a| a.to_string()).collect(),
+            accepted_merkle_algs: KNOWN_MERKLE_HASH_ALGS.iter().map(|a| a.to_string()).collect(),
+            minimum_signature_alg: None,
+            require_decision_coverage: false,
+            require_hashed_targets: false,
+            candidate_keys: Vec::new(),
+            max_signature_age: None,
+            strict_identifiers: false,
+            accept_null_as_absent: false,
+            require_unexpired_binding: false,
+            expiry_grace: Duration::ZERO,
+            strict_signatures: true,
+            require_signer_binding: false,
+            trusted_signer_keys: Vec::new(),
+            volatile_audit_fields: VolatileFields::default(),
+            approved_policy_snapshots: Vec::new(),
+            max_entry_age: None,
+            require_bundle_hash: false,
+            require_merkle_root: false,
+            require_expiry_check: false,
+            allowed_algs: Vec::new(),
+            clock_skew: Duration::ZERO,
+            require_temporal_order: false,
+            now_secs: None,
+        }
+    }
+}
+
+impl VerifyOptions {
+    fn accepts_hash(&self, field: &str, alg: &str) -> bool {
+        let accepted = if field == "merkle_root" { &self.accepted_merkle_algs } else { &self.accepted_hash_algs };
+        accepted.iter().any(|a| a == alg)
+    }
+
+    fn now(&self) -> i64 {
+        self.now_secs.unwrap_or_else(now_epoch_secs)
+    }
+}
+
+/// The algorithm `signature` is verified under, or `UNSUPPORTED
+/// SIGNATURE ALG` if its `alg` is missing or one this verifier does not
+/// run.
+fn verified_signature_alg(signature: &Value) -> Result<&'static str, VerificationFailure> {
+    let alg = signature.get("alg").and_then(|v| v.as_str()).unwrap_or("");
+    VERIFIED_SIGNATURE_ALGS.iter().copied().find(|a| *a == alg).ok_or_else(|| {
+        let detail = format!("{:?} is not verified by this verifier", alg);
+        VerificationFailure::new(VerifyError::UnsupportedSignatureAlg, detail, "/signature/alg")
+            .expected(VERIFIED_SIGNATURE_ALGS.join(", ")).actual(alg)
+    })
+}
+
+fn signature_alg_rank(alg: &str) -> Option<usize> {
+    SIGNATURE_ALG_STRENGTH.iter().position(|a| *a == alg)
+}
 
 /// Verify a signed bundle from its JSON Value representation.
 /// Checks signature, bundle_hash, merkle_root, intent_hash chain, and prev_hash chain.
 pub fn verify_signed_bundle(signed_bundle: &Value, public_key_b64: Option<&str>) -> VerificationResult {
+    verify_signed_bundle_with_options(signed_bundle, public_key_b64, &VerifyOptions::default())
+}
+
+/// [`verify_signed_bundle`] with an explicit algorithm policy.
+pub fn verify_signed_bundle_with_options(
+    signed_bundle: &Value,
+    public_key_b64: Option<&str>,
+    opts: &VerifyOptions,
+) -> VerificationResult {
+    verify_impl(signed_bundle, public_key_b64, opts, Depth::Full, None, None, None)
+}
+
+/// [`verify_signed_bundle`] for a parsed [`SignedBundle`].
+///
+/// The checks run on the JSON form the signature covers, so the bundle
+/// is serialized once here rather than by the caller. A typed bundle
+/// keeps no members its types do not know: one parsed from JSON carrying
+/// extension fields fails `BUNDLE HASH MISMATCH`, and should be verified
+/// from the original [`Value`].
+pub fn verify_signed_bundle_typed(signed_bundle: &SignedBundle, public_key_b64: Option<&str>) -> VerificationResult {
+    verify_signed_bundle_typed_with_options(signed_bundle, public_key_b64, &VerifyOptions::default())
+}
+
+/// [`verify_signed_bundle_typed`] with an explicit algorithm policy.
+pub fn verify_signed_bundle_typed_with_options(
+    signed_bundle: &SignedBundle,
+    public_key_b64: Option<&str>,
+    opts: &VerifyOptions,
+) -> VerificationResult {
+    match serde_json::to_value(signed_bundle) {
+        Ok(value) => verify_signed_bundle_with_options(&value, public_key_b64, opts),
+        Err(e) => VerificationResult::fail_at(vec![VerificationFailure::new(
+            VerifyError::InvalidValue { entry: None },
+            format!("bundle does not serialize: {}", e),
+            "",
+        )]),
+    }
+}
+
+/// Verify and record every input, recomputed hash, and comparison into a
+/// replayable [`Transcript`]. Sign it with [`Transcript::sign`] to settle
+/// later disputes about whether the bundle verified.
+pub fn verify_with_transcript(
+    signed_bundle: &Value,
+    public_key_b64: Option<&str>,
+    opts: &VerifyOptions,
+) -> (VerificationResult, Transcript) {
+    let mut transcript = Transcript::begin(signed_bundle, opts);
+    let result = verify_impl(signed_bundle, public_key_b64, opts, Depth::Full, None, Some(&mut transcript), None);
+    transcript.finish(&result);
+    (result, transcript)
+}
+
+/// [`verify_signed_bundle_with_options`] plus the status of every check
+/// in [`CHECK_NAMES`]. Checks that had nothing to verify, are disabled by
+/// `opts`, or were never reached because an earlier one failed are
+/// reported as skipped with a reason; the check that failed carries its
+/// [`VerificationFailure`].
+pub fn verify_with_report(
+    signed_bundle: &Value,
+    public_key_b64: Option<&str>,
+    opts: &VerifyOptions,
+) -> VerificationReport {
+    let mut recorded = Vec::with_capacity(CHECK_NAMES.len());
+    let result = verify_impl(signed_bundle, public_key_b64, opts, Depth::Full, None, None, Some(&mut recorded));
+    // Verification stops at the first failure, so at most one check failed.
+    let mut failure = result.failures.iter().flatten().next().cloned();
+    let checks = CHECK_NAMES
+        .iter()
+        .map(|name| match recorded.iter().find(|c| c.name == *name) {
+            Some(c) if c.status == CheckStatus::Failed => match failure.take() {
+                Some(failure) => CheckResult::failed(*name, failure),
+                None => c.clone(),
+            },
+            Some(c) => c.clone(),
+            None => CheckResult::skipped(*name, "not reached: an earlier check failed"),
+        })
+        .collect();
+    VerificationReport { result, checks }
+}
+
+/// Reusable verifier: a [`VerifyOptions`] policy plus optional hooks
+/// that run after each verification.
+///
+/// Keeps a pool of canonicalization buffers, so gateways verifying many
+/// bundles stop allocating once the buffers have grown to bundle size.
+/// Clones share the pool. [`Verifier::builder`] starts from a [`Preset`].
+#[derive(Clone)]
+pub struct Verifier {
+    opts: VerifyOptions,
+    buffers: Arc<Mutex<Vec<Canonicalizer>>>,
+    pool_buffers: bool,
+    limits: Option<InputLimits>,
+    telemetry: bool,
+    #[cfg(feature = "notify")]
+    notifier: Option<crate::notify::WebhookNotifier>,
+}
+
+impl Default for Verifier {
+    fn default() -> Self {
+        Self::new(VerifyOptions::default())
+    }
+}
+
+impl Verifier {
+    pub fn new(opts: VerifyOptions) -> Self {
+        Self {
+            opts,
+            buffers: Arc::default(),
+            pool_buffers: true,
+            limits: None,
+            telemetry: false,
+            #[cfg(feature = "notify")]
+            notifier: None,
+        }
+    }
+
+    /// A [`VerifierBuilder`], by default configured like [`Verifier::new`]
+    /// with default options.
+    pub fn builder() -> VerifierBuilder {
+        VerifierBuilder::default()
+    }
+
+    pub fn options(&self) -> &VerifyOptions {
+        &self.opts
+    }
+
+    /// Input limits checked before verification, if any.
+    pub fn limits(&self) -> Option<&InputLimits> {
+        self.limits.as_ref()
+    }
+
+    /// Send webhook alerts for failures at or above the notifier's threshold.
+    #[cfg(feature = "notify")]
+    pub fn with_notifier(mut self, notifier: crate::notify::WebhookNotifier) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    /// Verify a signed bundle under this verifier's policy.
+    pub fn verify(&self, signed_bundle: &Value, public_key_b64: Option<&str>) -> VerificationResult {
+        let t0 = Instant::now();
+        let result = match self.limits.map(|l| l.check(signed_bundle)) {
+            Some(Err(e)) => e.into_result(),
+            _ if self.pool_buffers => {
+                let mut buffers = lock_pool(&self.buffers).pop().unwrap_or_default();
+                let result =
+                    verify_impl(signed_bundle, public_key_b64, &self.opts, Depth::Full, Some(&mut buffers), None, None);
+                if buffers.capacity() <= MAX_POOLED_BUFFER {
+                    lock_pool(&self.buffers).push(buffers);
+                }
+                result
+            }
+            _ => verify_impl(signed_bundle, public_key_b64, &self.opts, Depth::Full, None, None, None),
+        };
+        if self.telemetry {
+            dcp_telemetry().record_bundle_verify(t0.elapsed().as_secs_f64() * 1000.0, result.verified, "v1");
+        }
+        #[cfg(feature = "notify")]
+        if let Some(notifier) = &self.notifier {
+            let _ = notifier.notify(signed_bundle, &result);
+        }
+        result
+    }
+
+    /// [`Self::verify`] with panics contained; see [`verify_untrusted`].
+    pub fn verify_untrusted(
+        &self,
+        signed_bundle: &Value,
+        public_key_b64: Option<&str>,
+    ) -> Result<VerificationResult, VerifierInternalError> {
+        contain(|| self.verify(signed_bundle, public_key_b64))
+    }
+}
+
+/// Starting configurations for [`Verifier::builder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Preset {
+    /// Online verification of untrusted traffic: input limits sized for
+    /// request bodies, strict identifiers, signer binding, a five-minute
+    /// signature age window against replay, binding expiry and timestamp
+    /// order checks, pooled buffers, and telemetry.
+    ApiGateway,
+    /// Re-verifying stored bundles: no input limits or signature age,
+    /// binding expiry and timestamp order checks, the `null`-as-absent
+    /// migration shim on, no buffer pool, no telemetry.
+    Archival,
+    /// In-page (wasm) verification: tight input limits, strict
+    /// identifiers and signer binding, no buffer pool held between calls,
+    /// no telemetry.
+    Browser,
+}
+
+impl Preset {
+    /// Input limits for [`Preset::ApiGateway`].
+    pub const API_GATEWAY_LIMITS: InputLimits = InputLimits { max_entries: 4096, max_string: 64 << 10, max_depth: 32 };
+    /// Input limits for [`Preset::Browser`].
+    pub const BROWSER_LIMITS: InputLimits = InputLimits { max_entries: 1024, max_string: 16 << 10, max_depth: 24 };
+}
+
+/// Builder for [`Verifier`]. [`Self::preset`] sets every setting, so call
+/// it first and adjust afterwards.
+#[derive(Debug, Clone)]
+pub struct VerifierBuilder {
+    opts: VerifyOptions,
+    pool_buffers: bool,
+    limits: Option<InputLimits>,
+    telemetry: bool,
+}
+
+impl Default for VerifierBuilder {
+    fn default() -> Self {
+        Self { opts: VerifyOptions::default(), pool_buffers: true, limits: None, telemetry: false }
+    }
+}
+
+impl VerifierBuilder {
+    /// Replace the whole configuration with `preset`'s.
+    pub fn preset(self, preset: Preset) -> Self {
+        let mut opts = VerifyOptions::default();
+        match preset {
+            Preset::ApiGateway => {
+                opts.strict_identifiers = true;
+                opts.require_signer_binding = true;
+                opts.max_signature_age = Some(Duration::from_secs(300));
+                opts.require_unexpired_binding = true;
+                opts.require_temporal_order = true;
+                Self { opts, pool_buffers: true, limits: Some(Preset::API_GATEWAY_LIMITS), telemetry: true }
+            }
+            Preset::Archival => {
+                opts.accept_null_as_absent = true;
+                opts.require_unexpired_binding = true;
+                opts.require_temporal_order = true;
+                Self { opts, pool_buffers: false, limits: None, telemetry: false }
+            }
+            Preset::Browser => {
+                opts.strict_identifiers = true;
+                opts.require_signer_binding = true;
+                Self { opts, pool_buffers: false, limits: Some(Preset::BROWSER_LIMITS), telemetry: false }
+            }
+        }
+    }
+
+    /// Replace the verification policy.
+    pub fn options(mut self, opts: VerifyOptions) -> Self {
+        self.opts = opts;
+        self
+    }
+
+    /// Adjust the verification policy in place.
+    pub fn configure(mut self, f: impl FnOnce(&mut VerifyOptions)) -> Self {
+        f(&mut self.opts);
+        self
+    }
+
+    /// Reject bundles over `limits` with `LIMIT EXCEEDED` before verifying.
+    pub fn limits(mut self, limits: InputLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    pub fn no_limits(mut self) -> Self {
+        self.limits = None;
+        self
+    }
+
+    /// Keep canonicalization buffers between verifications.
+    pub fn buffer_pool(mut self, enabled: bool) -> Self {
+        self.pool_buffers = enabled;
+        self
+    }
+
+    /// Record each verification's latency and verdict with
+    /// [`crate::observability::DcpTelemetry::record_bundle_verify`]
+    /// (a no-op until telemetry is initialized).
+    pub fn telemetry(mut self, enabled: bool) -> Self {
+        self.telemetry = enabled;
+        self
+    }
+
+    pub fn build(self) -> Verifier {
+        Verifier {
+            pool_buffers: self.pool_buffers,
+            limits: self.limits,
+            telemetry: self.telemetry,
+            ..Verifier::new(self.opts)
+        }
+    }
+}
+
+/// Buffers that grew past this (one huge bundle) are dropped rather than
+/// pinned in the pool.
+const MAX_POOLED_BUFFER: usize = 16 << 20;
+
+fn lock_pool(pool: &Mutex<Vec<Canonicalizer>>) -> std::sync::MutexGuard<'_, Vec<Canonicalizer>> {
+    pool.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// A panic inside the verifier, caught by [`verify_untrusted`]. Always a
+/// verifier bug, never a verdict on the bundle.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("verifier internal error: {message}")]
+#[non_exhaustive]
+pub struct VerifierInternalError {
+    /// The panic message, when the payload was a string.
+    pub message: String,
+}
+
+impl VerifierInternalError {
+    /// A failed result (`VERIFIER INTERNAL ERROR: ...`) for callers that
+    /// only handle [`VerificationResult`]s.
+    pub fn into_result(self) -> VerificationResult {
+        VerificationResult::fail_at(vec![VerificationFailure::new(VerifyError::Internal, self.message, "")])
+    }
+}
+
+/// [`verify_signed_bundle_with_options`] for bundles from untrusted
+/// sources: a panic anywhere in verification is caught and returned as a
+/// [`VerifierInternalError`] instead of unwinding into the caller.
+///
+/// The panic hook still runs (and by default prints to stderr), and
+/// builds with `panic = "abort"` cannot catch anything; services that
+/// rely on this must keep the default `unwind` strategy.
+pub fn verify_untrusted(
+    signed_bundle: &Value,
+    public_key_b64: Option<&str>,
+    opts: &VerifyOptions,
+) -> Result<VerificationResult, VerifierInternalError> {
+    contain(|| verify_signed_bundle_with_options(signed_bundle, public_key_b64, opts))
+}
+
+fn contain<T>(verify: impl FnOnce() -> T) -> Result<T, VerifierInternalError> {
+    std::panic::catch_unwind(AssertUnwindSafe(verify)).map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "non-string panic payload".into());
+        VerifierInternalError { message }
+    })
+}
+
+/// Cheap pre-filter: checks only the signature and `bundle_hash`,
+/// skipping the merkle root and the audit chain walk.
+///
+/// A passing result means the bundle is intact as signed, not that its
+/// audit trail is consistent. Use it to shed obvious garbage at ingest,
+/// then run [`verify_signed_bundle`] before trusting the bundle.
+pub fn quick_verify(signed_bundle: &Value, public_key_b64: Option<&str>) -> VerificationResult {
+    verify_impl(signed_bundle, public_key_b64, &VerifyOptions::default(), Depth::Quick, None, None, None)
+}
+
+/// [`verify_signed_bundle_with_options`] that does not stop at the first
+/// failure: every check runs and every failure is returned, in check
+/// order, with its location. For debugging a broken bundle; a bundle is
+/// rejected all the same on its first failure.
+///
+/// Only a missing bundle, signature, or key, or an unsupported
+/// canonicalization, still stops verification, since nothing after it
+/// can be checked. Later failures may follow from earlier ones (a
+/// tampered entry breaks both `merkle_root` and the next `prev_hash`).
+pub fn verify_signed_bundle_exhaustive(
+    signed_bundle: &Value,
+    public_key_b64: Option<&str>,
+    opts: &VerifyOptions,
+) -> VerificationResult {
+    verify_impl(signed_bundle, public_key_b64, opts, Depth::Exhaustive, None, None, None)
+}
+
+/// How far [`verify_impl`] goes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Depth {
+    /// Signature and bundle_hash only.
+    Quick,
+    Full,
+    /// Every check, past failures, collecting all of them.
+    Exhaustive,
+}
+
+fn verify_impl(
+    signed_bundle: &Value,
+    public_key_b64: Option<&str>,
+    opts: &VerifyOptions,
+    depth: Depth,
+    mut buffers: Option<&mut Canonicalizer>,
+    mut transcript: Option<&mut Transcript>,
+    mut checks: Option<&mut Vec<CheckResult>>,
+) -> VerificationResult {
+    let result = verify_prepared(
+        signed_bundle,
+        public_key_b64,
+        opts,
+        depth,
+        Prepared { buffers: buffers.as_deref_mut(), ..Prepared::default() },
+        transcript.as_deref_mut(),
+        checks.as_deref_mut(),
+    );
+    let integrity_failed = result
+        .errors
+        .as_ref()
+        .and_then(|e| e.first())
+        .is_some_and(|e| e.starts_with("SIGNATURE INVALID") || e.starts_with("BUNDLE HASH MISMATCH"));
+    if !opts.accept_null_as_absent || !integrity_failed || !signed_bundle.get("bundle").is_some_and(has_null_member) {
+        return result;
+    }
+
+    // Dropping the members up front makes every later hash (intent,
+    // entries, merkle leaves) agree with the null-as-absent producer too.
+    let mut stripped = signed_bundle.clone();
+    if let Some(bundle) = stripped.get_mut("bundle") {
+        let drop_nulls = NormalizeOptions { drop_nulls: true, trim_ids: false, utc_timestamps: false };
+        normalize_with(bundle, &drop_nulls);
+    }
+    if let Some(c) = checks.as_deref_mut() {
+        c.clear();
+    }
+    if let Some(t) = transcript.as_deref_mut() {
+        t.steps.clear();
+    }
+    let prepared = Prepared { buffers, ..Prepared::default() };
+    verify_prepared(&stripped, public_key_b64, opts, depth, prepared, transcript, checks)
+}
+
+fn has_null_member(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => map.values().any(|v| v.is_null() || has_null_member(v)),
+        Value::Array(arr) => arr.iter().any(has_null_member),
+        _ => false,
+    }
+}
+
+/// Derived values a caller has already computed from the full bundle, so
+/// [`verify_prepared`] can run on a bundle whose audit entries were
+/// slimmed down (see `verify_file`), plus reusable buffers.
+#[derive(Default)]
+struct Prepared<'c> {
+    /// `dcp-c14n/1` canonical form of the full bundle.
+    canon: Option<String>,
+    /// `hash_object` of each full audit entry, in order.
+    entry_hashes: Option<Vec<String>>,
+    /// Canonicalization buffer to reuse; a fresh one otherwise.
+    buffers: Option<&'c mut Canonicalizer>,
+}
+
+fn verify_prepared(
+    signed_bundle: &Value,
+    public_key_b64: Option<&str>,
+    opts: &VerifyOptions,
+    depth: Depth,
+    prepared: Prepared<'_>,
+    mut transcript: Option<&mut Transcript>,
+    mut checks: Option<&mut Vec<CheckResult>>,
+) -> VerificationResult {
     let bundle = match signed_bundle.get("bundle") {
         Some(b) => b,
-        None => return VerificationResult::fail(vec!["Missing bundle".into()]),
+        None => {
+            let failure = VerificationFailure::new(VerifyError::missing("bundle"), "", "/bundle");
+            return VerificationResult::fail_at(vec![failure]);
+        }
     };
 
     let signature = match signed_bundle.get("signature") {
         Some(s) => s,
-        None => return VerificationResult::fail(vec!["Missing signature".into()]),
+        None => {
+            let failure = VerificationFailure::new(VerifyError::missing("signature"), "", "/signature");
+            return VerificationResult::fail_at(vec![failure]);
+        }
     };
 
     let sig_b64 = match signature.get("sig_b64").and_then(|v| v.as_str()) {
         Some(s) => s,
-        None => return VerificationResult::fail(vec!["Missing sig_b64".into()]),
+        None => {
+            let failure = VerificationFailure::new(VerifyError::missing("sig_b64"), "", "/signature/sig_b64");
+            return VerificationResult::fail_at(vec![failure]);
+        }
     };
 
-    let pub_key = public_key_b64
-        .or_else(|| {
-            signature.get("signer")
-                .and_then(|s| s.get("public_key_b64"))
-                .and_then(|v| v.as_str())
-        });
+    let embedded_key = signature.get("signer").and_then(|s| s.get("public_key_b64")).and_then(|v| v.as_str());
+    let candidates: Vec<&str> = match public_key_b64 {
+        Some(k) => vec![k],
+        None if !opts.candidate_keys.is_empty() => opts.candidate_keys.iter().map(String::as_str).collect(),
+        None => embedded_key.into_iter().collect(),
+    };
+    if candidates.is_empty() {
+        return VerificationResult::fail_at(vec![VerificationFailure::new(
+            VerifyError::missing("public key"),
+            "",
+            "/signature/signer/public_key_b64",
+        )]);
+    }
+    let multi_key = public_key_b64.is_none() && !opts.candidate_keys.is_empty();
+    if let Some(t) = transcript.as_deref_mut() {
+        t.public_key_b64 = candidates[0].to_string();
+    }
+
+    // A failed check ends verification, except in exhaustive mode, where
+    // it is collected and the checks go on.
+    let mut failures = Vec::new();
+    macro_rules! fail {
+        ($failure:expr) => {{
+            let failure = $failure;
+            if depth != Depth::Exhaustive {
+                return VerificationResult::fail_at(vec![failure]);
+            }
+            failures.push(failure);
+        }};
+    }
+
+    // 0) Algorithm policy. An unverifiable alg is reported once, however
+    // many policies need it.
+    if opts.minimum_signature_alg.is_some() || !opts.allowed_algs.is_empty() {
+        match verified_signature_alg(signature) {
+            Err(failure) => {
+                note(checks.as_deref_mut(), "signature_policy", CheckStatus::Failed);
+                fail!(failure)
+            }
+            Ok(alg) => {
+                if let Some(min) = &opts.minimum_signature_alg {
+                    let weak = match (signature_alg_rank(alg), signature_alg_rank(min)) {
+                        (Some(got), Some(needed)) => got < needed,
+                        _ => true,
+                    };
+                    if weak {
+                        note(checks.as_deref_mut(), "signature_policy", CheckStatus::Failed);
+                        fail!(VerificationFailure::new(
+                            VerifyError::WeakAlgorithm,
+                            format!("signature alg {:?} is below minimum {}", alg, min),
+                            "/signature/alg",
+                        )
+                        .expected(min.as_str())
+                        .actual(alg));
+                    }
+                }
+                if !opts.allowed_algs.is_empty() && !opts.allowed_algs.iter().any(|a| a == alg) {
+                    note(checks.as_deref_mut(), "signature_policy", CheckStatus::Failed);
+                    fail!(VerificationFailure::new(
+                        VerifyError::WeakAlgorithm,
+                        format!("signature alg {:?} is not allowed", alg),
+                        "/signature/alg",
+                    )
+                    .expected(opts.allowed_algs.join(", "))
+                    .actual(alg))
+                }
+            }
+        }
+    }
+    for field in ["bundle_hash", "merkle_root"] {
+        if let Some((alg, _)) = signature.get(field).and_then(|v| v.as_str()).and_then(|h| h.split_once(':')) {
+            if !opts.accepts_hash(field, alg) {
+                note(checks.as_deref_mut(), "signature_policy", CheckStatus::Failed);
+                fail!(VerificationFailure::new(
+                    VerifyError::WeakAlgorithm,
+                    format!("{} uses {}", field, alg),
+                    format!("/signature/{}", field),
+                )
+                .actual(alg));
+            }
+        }
+    }
 
-    let pub_key = match pub_key {
-        Some(k) => k,
-        None => return VerificationResult::fail(vec!["Missing public key".into()]),
+    // Canonicalize the bundle once, with the implementation it was signed
+    // under; the signature and bundle_hash checks both use the same bytes.
+    // Entry, intent and merkle leaf hashes use that implementation too.
+    let canon_id = signature.get("canon").and_then(|v| v.as_str()).unwrap_or(CANON_V1);
+    let mut fresh = Canonicalizer::new();
+    let buffers = prepared.buffers.unwrap_or(&mut fresh);
+    let Some(canon) = Canon::parse(canon_id) else {
+        note(checks.as_deref_mut(), "signature_policy", CheckStatus::Failed);
+        // Nothing after this point can run without canonical bytes.
+        failures.push(
+            VerificationFailure::new(VerifyError::UnsupportedCanonicalization, canon_id, "/signature/canon")
+                .actual(canon_id),
+        );
+        return VerificationResult::fail_at(failures);
     };
+    let bundle_canon = match prepared.canon.as_deref() {
+        Some(c) if canon == Canon::V1 => c,
+        _ => buffers.canonicalize_in(canon, bundle),
+    };
+    note(checks.as_deref_mut(), "signature_policy", CheckStatus::Passed);
 
     // 1) Signature verification
-    match verify_object(bundle, sig_b64, pub_key) {
-        Ok(true) => {}
-        _ => return VerificationResult::fail(vec!["SIGNATURE INVALID".into()]),
+    let matched = verify_canonical_multi_with(bundle_canon, sig_b64, &candidates, opts.strict_signatures);
+    let sig_ok = matched.is_ok();
+    let pub_key = candidates[*matched.as_ref().unwrap_or(&0)];
+    if let Some(t) = transcript.as_deref_mut() {
+        t.public_key_b64 = pub_key.to_string();
+        t.record("signature", "/bundle", bundle_canon, Some(sig_b64), None, sig_ok);
+    }
+    if let Err(reasons) = matched {
+        let failure = if multi_key {
+            VerificationFailure::new(
+                VerifyError::SignatureInvalid,
+                format!("none of {} candidate keys matched", candidates.len()),
+                "/signature/sig_b64",
+            )
+            .actual(reasons.join("; "))
+        } else {
+            VerificationFailure::new(VerifyError::SignatureInvalid, "", "/signature/sig_b64")
+        };
+        note(checks.as_deref_mut(), "signature", CheckStatus::Failed);
+        fail!(failure);
+    }
+    note(checks.as_deref_mut(), "signature", CheckStatus::Passed);
+    let matched_key = (multi_key && sig_ok).then_some(pub_key);
+
+    // 1a) Signer binding
+    if opts.require_signer_binding {
+        if let Some(failure) = check_signer_binding(bundle, signature, pub_key, &opts.trusted_signer_keys) {
+            note(checks.as_deref_mut(), "signer_binding", CheckStatus::Failed);
+            fail!(failure);
+        }
+        note(checks.as_deref_mut(), "signer_binding", CheckStatus::Passed);
+    } else {
+        note_outcome(checks.as_deref_mut(), "signer_binding", Err("disabled by options"));
+    }
+
+    // 1b) Signature age
+    match opts.max_signature_age {
+        Some(max) => {
+            if let Some(failure) = check_signature_age(signature, max.saturating_add(opts.clock_skew), opts.now()) {
+                note(checks.as_deref_mut(), "signature_age", CheckStatus::Failed);
+                fail!(failure);
+            }
+            note(checks.as_deref_mut(), "signature_age", CheckStatus::Passed);
+        }
+        None => note_outcome(checks.as_deref_mut(), "signature_age", Err("disabled by options")),
+    }
+
+    // 1c) Binding expiry, as of the last audit entry (or now, if none is
+    // timestamped)
+    let mut warnings = Vec::new();
+    match bundle.pointer("/responsible_principal_record/expires_at").and_then(|v| v.as_str()) {
+        _ if !(opts.require_unexpired_binding || opts.require_temporal_order) => {
+            note_outcome(checks.as_deref_mut(), "binding_expiry", Err("disabled by options"))
+        }
+        None => note_outcome(checks.as_deref_mut(), "binding_expiry", Err("record has no expires_at")),
+        Some(expires_at) => {
+            let at = latest_entry_secs(bundle).map(|latest| {
+                latest.unwrap_or_else(|| opts.now()).saturating_sub(duration_secs(opts.clock_skew))
+            });
+            match at.and_then(|at| check_binding_expiry(expires_at, at, opts.expiry_grace)) {
+                Err(failure) => {
+                    note(checks.as_deref_mut(), "binding_expiry", CheckStatus::Failed);
+                    fail!(failure);
+                }
+                Ok(warning) => {
+                    warnings.extend(warning);
+                    note(checks.as_deref_mut(), "binding_expiry", CheckStatus::Passed);
+                }
+            }
+        }
+    }
+
+    // 1d) Timestamp order
+    if opts.require_temporal_order {
+        if let Err(failure) = check_temporal_order(bundle, opts.clock_skew, opts.now()) {
+            note(checks.as_deref_mut(), "temporal_order", CheckStatus::Failed);
+            fail!(failure);
+        }
+        note(checks.as_deref_mut(), "temporal_order", CheckStatus::Passed);
+    } else {
+        note_outcome(checks.as_deref_mut(), "temporal_order", Err("disabled by options"));
+    }
+
+    // 1e) Entry freshness, as of signing
+    match opts.max_entry_age {
+        Some(max) => {
+            let signed_at = signature.get("created_at").and_then(|v| v.as_str()).and_then(parse_iso_epoch_secs);
+            warnings.extend(stale_entries(bundle, max, signed_at.unwrap_or_else(|| opts.now())));
+            note(checks.as_deref_mut(), "entry_freshness", CheckStatus::Passed);
+        }
+        None => note_outcome(checks.as_deref_mut(), "entry_freshness", Err("disabled by options")),
     }
 
     // 2) bundle_hash
+    let mut bundle_hash_status = Err("no bundle_hash in signature");
     if let Some(bh) = signature.get("bundle_hash").and_then(|v| v.as_str()) {
-        if bh.starts_with("sha256:") {
-            let expected = {
-                let canon = canonicalize(bundle);
-                use sha2::{Sha256, Digest};
-                let mut hasher = Sha256::new();
-                hasher.update(canon.as_bytes());
-                hex::encode(hasher.finalize())
-            };
-            let got = &bh["sha256:".len()..];
+        bundle_hash_status = Err("unrecognized bundle_hash algorithm");
+        let expected =
+            HashAlgorithm::split_prefixed(bh).map(|(alg, got)| (got, digest_hex(alg, bundle_canon.as_bytes())));
+        if let Some((got, expected)) = expected {
+            if let Some(t) = transcript.as_deref_mut() {
+                t.record("bundle_hash", "/bundle", bundle_canon, Some(bh), Some(expected.as_str()), got == expected);
+            }
             if got != expected {
-                return VerificationResult::fail(vec!["BUNDLE HASH MISMATCH".into()]);
+                note(checks.as_deref_mut(), "bundle_hash", CheckStatus::Failed);
+                fail!(VerificationFailure::new(VerifyError::BundleHashMismatch, "", "/signature/bundle_hash")
+                .expected(expected)
+                .actual(got));
             }
+            bundle_hash_status = Ok(());
         }
     }
+    if let (true, Err(reason)) = (opts.require_bundle_hash, bundle_hash_status) {
+        note(checks.as_deref_mut(), "bundle_hash", CheckStatus::Failed);
+        fail!(VerificationFailure::new(VerifyError::missing("bundle_hash"), reason, "/signature/bundle_hash"));
+    }
+    note_outcome(checks.as_deref_mut(), "bundle_hash", bundle_hash_status);
+
+    if depth == Depth::Quick {
+        return VerificationResult::ok().with_matched_key(matched_key).with_warnings(warnings);
+    }
+
+    // Each audit entry is hashed exactly once and reused as both its
+    // merkle leaf and the expected prev_hash of the following entry.
+    let entries: &[Value] = bundle
+        .get("audit_entries")
+        .and_then(|v| v.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+    // A tombstoned entry (see `crate::retention`) stands in with the hash
+    // of the entry it replaced.
+    let entry_hashes: Vec<String> = match prepared.entry_hashes {
+        Some(hashes) if canon == Canon::V1 => hashes,
+        _ => entries.iter().map(|e| opts.volatile_audit_fields.entry_hash_in(buffers, canon, e)).collect(),
+    };
+    for (i, tombstone) in entries.iter().enumerate().filter_map(|(i, e)| Some((i, e.pointer("/evidence/tombstone")?))) {
+        warnings.push(format!(
+            "audit entry {}: evidence deleted at {} under retention policy {}",
+            i,
+            tombstone.get("deleted_at").and_then(Value::as_str).unwrap_or("?"),
+            tombstone.get("policy_id").and_then(Value::as_str).unwrap_or("?"),
+        ));
+    }
 
     // 3) merkle_root
+    let merkle_failures = failures.len();
+    let mut merkle_status = Err("no merkle_root in signature");
     if let Some(mr) = signature.get("merkle_root").and_then(|v| v.as_str()) {
-        if mr.starts_with("sha256:") {
-            if let Some(entries) = bundle.get("audit_entries").and_then(|v| v.as_array()) {
-                let leaves: Vec<String> = entries.iter().map(|e| hash_object(e)).collect();
-                if let Some(expected) = merkle_root_from_hex_leaves(&leaves) {
-                    let got = &mr["sha256:".len()..];
-                    if got != expected {
-                        return VerificationResult::fail(vec!["MERKLE ROOT MISMATCH".into()]);
+        let got = mr.strip_prefix("sha256:");
+        if got.is_none() {
+            let alg = mr.split_once(':').map_or(mr, |(alg, _)| alg);
+            note(checks.as_deref_mut(), "merkle_root", CheckStatus::Failed);
+            fail!(VerificationFailure::new(
+                VerifyError::UnsupportedHashAlg,
+                format!("merkle_root uses {:?}; only sha256 merkle roots are verified", alg),
+                "/signature/merkle_root",
+            )
+            .expected("sha256")
+            .actual(alg));
+        }
+        if let Some(got) = got {
+            merkle_status = Err("no audit entries");
+            let declared = signature.get("leaf_order").and_then(|v| v.as_str());
+            let leaf_order = match declared.map(LeafOrder::parse) {
+                None => Some(LeafOrder::Chain),
+                Some(Some(order)) => Some(order),
+                Some(None) => {
+                    note(checks.as_deref_mut(), "merkle_root", CheckStatus::Failed);
+                    fail!(VerificationFailure::new(
+                        VerifyError::UnsupportedLeafOrder,
+                        declared.unwrap_or_default(),
+                        "/signature/leaf_order",
+                    )
+                    .expected("chain, audit_id, or timestamp")
+                    .actual(declared.unwrap_or_default()));
+                    None
+                }
+            };
+            if let Some(leaf_order) = leaf_order.filter(|_| bundle.get("audit_entries").is_some_and(Value::is_array)) {
+                let leaves = leaf_order.arrange(entries, &entry_hashes);
+                match merkle_root_from_hex_leaves(&leaves) {
+                    // A declared root over no leaves commits to nothing.
+                    None => {
+                        note(checks.as_deref_mut(), "merkle_root", CheckStatus::Failed);
+                        fail!(VerificationFailure::new(
+                            VerifyError::MerkleRootMismatch,
+                            "declared over no audit entries",
+                            "/signature/merkle_root",
+                        )
+                        .actual(got));
+                    }
+                    Some(expected) => {
+                        if let Some(t) = transcript.as_deref_mut() {
+                            let input = canonicalize(&Value::from(leaves.clone()));
+                            let (pointer, matched) = ("/bundle/audit_entries", got == expected);
+                            t.record("merkle_root", pointer, &input, Some(mr), Some(&expected), matched);
+                            t.record_leaves(&leaves);
+                        }
+                        if got != expected {
+                            note(checks.as_deref_mut(), "merkle_root", CheckStatus::Failed);
+                            let pointer = "/signature/merkle_root";
+                            fail!(VerificationFailure::new(VerifyError::MerkleRootMismatch, "", pointer)
+                                .expected(expected)
+                                .actual(got));
+                        }
+                        merkle_status = Ok(());
                     }
                 }
             }
         }
     }
+    // A root that was present but failed above is not also reported as
+    // missing in exhaustive mode.
+    if let (true, Err(reason)) = (opts.require_merkle_root && failures.len() == merkle_failures, merkle_status) {
+        note(checks.as_deref_mut(), "merkle_root", CheckStatus::Failed);
+        fail!(VerificationFailure::new(VerifyError::missing("merkle_root"), reason, "/signature/merkle_root"));
+    }
+    note_outcome(checks.as_deref_mut(), "merkle_root", merkle_status);
 
     // 4) intent_hash and prev_hash chain
+    let mut expiry_status = Err("bundle has no intent");
+    let mut intent_hash_status = Err("bundle has no intent");
+    let mut prev_hash_status = Err("no audit entry carries prev_hash");
     if let Some(intent) = bundle.get("intent") {
-        let expected_intent_hash = hash_object(intent);
-
-        if let Some(entries) = bundle.get("audit_entries").and_then(|v| v.as_array()) {
-            let mut prev_expected = "GENESIS".to_string();
-            for (i, entry) in entries.iter().enumerate() {
-                if let Some(ih) = entry.get("intent_hash").and_then(|v| v.as_str()) {
-                    if ih != expected_intent_hash {
-                        return VerificationResult::fail(vec![
-                            format!("intent_hash (entry {}): expected {}, got {}", i, expected_intent_hash, ih),
-                        ]);
+        // Before any amendment, entries may carry either accepted form.
+        let [mut expected_intent_hash, normalized] = buffers.accepted_intent_hashes_in(canon, intent);
+        let mut normalized_intent_hash = Some(normalized);
+        // Amended revisions, in the order the audit chain must announce them.
+        let lineage = match check_lineage_in(bundle, buffers, canon) {
+            Ok(lineage) => lineage,
+            Err(failure) => {
+                note(checks.as_deref_mut(), "intent_hash", CheckStatus::Failed);
+                fail!(failure);
+                Vec::new()
+            }
+        };
+        let mut amendments = lineage.into_iter().peekable();
+
+        let not_before = intent.get("not_before").and_then(|v| v.as_str());
+        let valid_until = intent.get("valid_until").and_then(|v| v.as_str());
+        expiry_status = Err(if not_before.is_none() && valid_until.is_none() {
+            "intent has no validity window"
+        } else {
+            "no timestamped audit entry for the intent"
+        });
+        intent_hash_status = Err("no audit entry carries intent_hash");
+
+        let mut prev_expected = "GENESIS";
+        for (i, (entry, entry_hash)) in entries.iter().zip(&entry_hashes).enumerate() {
+            // v1.1 intent validity window; a timestamp that does not parse fails.
+            if entry.get("intent_id") == intent.get("intent_id") {
+                if let Some(ts) = entry.get("timestamp").and_then(|v| v.as_str()) {
+                    let pointer = format!("/bundle/audit_entries/{}/timestamp", i);
+                    let early = not_before.and_then(|nb| match skewed_before(ts, nb, opts.clock_skew) {
+                        Some(false) => None,
+                        Some(true) => Some((nb, format!("{} is before not_before {}", ts, nb))),
+                        None => Some((nb, format!("{} or not_before {} is unparseable", ts, nb))),
+                    });
+                    if let Some((nb, detail)) = early {
+                        note(checks.as_deref_mut(), "expiry", CheckStatus::Failed);
+                        fail!(VerificationFailure::new(
+                            VerifyError::IntentNotYetValid { entry: i },
+                            detail,
+                            pointer.as_str(),
+                        )
+                        .expected(format!(">= {}", nb))
+                        .actual(ts));
                     }
+                    let late = valid_until.and_then(|vu| match skewed_before(vu, ts, opts.clock_skew) {
+                        Some(false) => None,
+                        Some(true) => Some((vu, format!("{} is after valid_until {}", ts, vu))),
+                        None => Some((vu, format!("{} or valid_until {} is unparseable", ts, vu))),
+                    });
+                    if let Some((vu, detail)) = late {
+                        note(checks.as_deref_mut(), "expiry", CheckStatus::Failed);
+                        fail!(VerificationFailure::new(VerifyError::IntentExpired { entry: i }, detail, pointer.as_str())
+                            .expected(format!("<= {}", vu))
+                            .actual(ts));
+                    }
+                    if not_before.is_some() || valid_until.is_some() {
+                        expiry_status = Ok(());
+                    }
+                }
+            }
+            if let Some(ih) = entry.get("intent_hash").and_then(|v| v.as_str()) {
+                let normalized = normalized_intent_hash.as_deref().filter(|h| *h == ih);
+                let matched = ih == expected_intent_hash || normalized.is_some();
+                if let Some(t) = transcript.as_deref_mut() {
+                    let recomputed = normalized.unwrap_or(&expected_intent_hash);
+                    t.record(
+                        &format!("intent_hash[{}]", i),
+                        "/bundle/intent",
+                        &canonicalize(intent),
+                        Some(ih),
+                        Some(recomputed),
+                        matched,
+                    );
+                }
+                if !matched {
+                    note(checks.as_deref_mut(), "intent_hash", CheckStatus::Failed);
+                    fail!(VerificationFailure::new(
+                        VerifyError::IntentHashMismatch { entry: Some(i) },
+                        format!("expected {}, got {}", expected_intent_hash, ih),
+                        format!("/bundle/audit_entries/{}/intent_hash", i),
+                    )
+                    .expected(expected_intent_hash.as_str())
+                    .actual(ih));
                 }
-                if let Some(ph) = entry.get("prev_hash").and_then(|v| v.as_str()) {
-                    if ph != prev_expected {
-                        return VerificationResult::fail(vec![
-                            format!("prev_hash chain (entry {}): expected {}, got {}", i, prev_expected, ph),
-                        ]);
+                intent_hash_status = Ok(());
+            }
+            if let Some(ph) = entry.get("prev_hash").and_then(|v| v.as_str()) {
+                if let Some(t) = transcript.as_deref_mut() {
+                    let (pointer, input) = if i == 0 {
+                        ("/bundle/audit_entries".to_string(), String::new())
+                    } else {
+                        (format!("/bundle/audit_entries/{}", i - 1), canonicalize(&entries[i - 1]))
+                    };
+                    t.record(&format!("prev_hash[{}]", i), &pointer, &input, Some(ph), Some(prev_expected), ph == prev_expected);
+                }
+                if ph != prev_expected {
+                    note(checks.as_deref_mut(), "prev_hash", CheckStatus::Failed);
+                    fail!(VerificationFailure::new(
+                        VerifyError::ChainBreak { entry: i },
+                        format!("expected {}, got {}", prev_expected, ph),
+                        format!("/bundle/audit_entries/{}/prev_hash", i),
+                    )
+                    .expected(prev_expected)
+                    .actual(ph));
+                }
+                prev_hash_status = Ok(());
+            }
+            if entry.get("outcome").and_then(|v| v.as_str()) == Some(AMENDED_OUTCOME) {
+                let announced = entry.pointer("/evidence/result_ref").and_then(|v| v.as_str());
+                match amendments.next_if(|(id, _)| Some(id.as_str()) == announced) {
+                    // Amended revisions carry the hash their amendment declares.
+                    Some((_, new_hash)) => {
+                        normalized_intent_hash = None;
+                        expected_intent_hash = new_hash;
+                    }
+                    None => {
+                        note(checks.as_deref_mut(), "intent_hash", CheckStatus::Failed);
+                        fail!(VerificationFailure::new(
+                            VerifyError::AmendmentInvalid { entry: Some(i) },
+                            "announces an amendment out of order",
+                            format!("/bundle/audit_entries/{}/evidence/result_ref", i),
+                        )
+                        .expected(amendments.peek().map(|(id, _)| id.as_str()).unwrap_or("no further amendment"))
+                        .actual(announced.unwrap_or("null")));
                     }
                 }
-                prev_expected = hash_object(entry);
             }
+            prev_expected = entry_hash.as_str();
+        }
+        if let Some((id, _)) = amendments.next() {
+            note(checks.as_deref_mut(), "intent_hash", CheckStatus::Failed);
+            fail!(VerificationFailure::new(
+                VerifyError::AmendmentInvalid { entry: None },
+                format!("amendment {} is not announced in the audit chain", id),
+                "/bundle/audit_entries",
+            ));
+        }
+    }
+    if let (true, Err(reason)) = (opts.require_expiry_check, expiry_status) {
+        note(checks.as_deref_mut(), "expiry", CheckStatus::Failed);
+        fail!(VerificationFailure::new(VerifyError::missing("valid_until"), reason, "/bundle/intent/valid_until"));
+    }
+    note_outcome(checks.as_deref_mut(), "expiry", expiry_status);
+    note_outcome(checks.as_deref_mut(), "intent_hash", intent_hash_status);
+    note_outcome(checks.as_deref_mut(), "prev_hash", prev_hash_status);
+
+    // 4b) Sequence numbers
+    match check_sequence(entries) {
+        Err(reason) => note_outcome(checks.as_deref_mut(), "sequence", Err(reason)),
+        Ok(Some(failure)) => {
+            note(checks.as_deref_mut(), "sequence", CheckStatus::Failed);
+            fail!(failure);
+        }
+        Ok(None) => note(checks.as_deref_mut(), "sequence", CheckStatus::Passed),
+    }
+
+    // 4c) Trace context ids
+    match trace_context::check_entries(entries) {
+        Err(reason) => note_outcome(checks.as_deref_mut(), "trace_context", Err(reason)),
+        Ok(Some(failure)) => {
+            note(checks.as_deref_mut(), "trace_context", CheckStatus::Failed);
+            fail!(failure);
         }
+        Ok(None) => note(checks.as_deref_mut(), "trace_context", CheckStatus::Passed),
     }
 
-    VerificationResult::ok()
+    // 5) Cross-artifact references
+    match check_references(bundle) {
+        _ if !opts.strict_identifiers => note_outcome(checks.as_deref_mut(), "references", Err("disabled by options")),
+        Err(reason) => note_outcome(checks.as_deref_mut(), "references", Err(reason)),
+        Ok(Some(failure)) => {
+            note(checks.as_deref_mut(), "references", CheckStatus::Failed);
+            fail!(failure);
+        }
+        Ok(None) => note(checks.as_deref_mut(), "references", CheckStatus::Passed),
+    }
+
+    // 5b) Identifier uniqueness and consistency
+    if opts.strict_identifiers {
+        if let Some(failure) = check_identifiers(bundle, entries) {
+            note(checks.as_deref_mut(), "identifiers", CheckStatus::Failed);
+            fail!(failure);
+        }
+        note(checks.as_deref_mut(), "identifiers", CheckStatus::Passed);
+    } else {
+        note_outcome(checks.as_deref_mut(), "identifiers", Err("disabled by options"));
+    }
+
+    // 6) Decision coverage
+    if opts.require_decision_coverage {
+        if let Some(failure) = check_decision_coverage(bundle, entries) {
+            note(checks.as_deref_mut(), "decision_coverage", CheckStatus::Failed);
+            fail!(failure);
+        }
+        note(checks.as_deref_mut(), "decision_coverage", CheckStatus::Passed);
+    } else {
+        note_outcome(checks.as_deref_mut(), "decision_coverage", Err("disabled by options"));
+    }
+
+    // 7) Hashed targets
+    let target_declares_salt = bundle.pointer("/intent/target/salt_id").is_some();
+    if let Some(failure) = check_hashed_target(bundle, opts.require_hashed_targets) {
+        note(checks.as_deref_mut(), "hashed_targets", CheckStatus::Failed);
+        fail!(failure);
+    }
+    if opts.require_hashed_targets || target_declares_salt {
+        note(checks.as_deref_mut(), "hashed_targets", CheckStatus::Passed);
+    } else {
+        note_outcome(checks.as_deref_mut(), "hashed_targets", Err("not required and target has no salt_id"));
+    }
+
+    // 8) Policy snapshot
+    if opts.approved_policy_snapshots.is_empty() {
+        note_outcome(checks.as_deref_mut(), "policy_snapshot", Err("no approved snapshots configured"));
+    } else {
+        let snapshot = bundle.pointer("/policy_decision/policy_snapshot_hash").and_then(|v| v.as_str());
+        if !snapshot.is_some_and(|h| opts.approved_policy_snapshots.iter().any(|a| a == h)) {
+            note(checks.as_deref_mut(), "policy_snapshot", CheckStatus::Failed);
+            fail!(VerificationFailure::new(
+                VerifyError::PolicySnapshotUnapproved,
+                snapshot.unwrap_or("decision names no policy snapshot"),
+                "/bundle/policy_decision/policy_snapshot_hash",
+            )
+            .actual(snapshot.unwrap_or("null")));
+        }
+        note(checks, "policy_snapshot", CheckStatus::Passed);
+    }
+
+    if !failures.is_empty() {
+        return VerificationResult::fail_at(failures).with_matched_key(matched_key).with_warnings(warnings);
+    }
+    VerificationResult::ok().with_matched_key(matched_key).with_warnings(warnings)
+}
+
+/// `signature.signer` must be a valid [`SignerIdentity`], and
+/// `signing_key` the passport key, or a trusted key of a signer that
+/// does not claim to be the agent.
+fn check_signer_binding(
+    bundle: &Value,
+    signature: &Value,
+    signing_key: &str,
+    trusted: &[String],
+) -> Option<VerificationFailure> {
+    let signer = match SignerIdentity::from_value(signature.get("signer").unwrap_or(&Value::Null)) {
+        Ok(signer) => signer,
+        Err(e) => return Some(VerificationFailure::new(VerifyError::SignerInvalid, e.to_string(), "/signature/signer")),
+    };
+    let passport_key = bundle.pointer("/agent_passport/public_key").and_then(|v| v.as_str());
+    if passport_key == Some(signing_key) {
+        return None;
+    }
+    if signer.signer_type != SignerType::Agent && trusted.iter().any(|k| k == signing_key) {
+        return None;
+    }
+    let agent_id = bundle.pointer("/agent_passport/agent_id").and_then(|v| v.as_str()).unwrap_or("null");
+    Some(
+        VerificationFailure::new(
+            VerifyError::KeyNotBound,
+            format!(
+                "{} signing key is neither the passport key of agent {} nor a trusted signer key",
+                signer.signer_type, agent_id
+            ),
+            "/signature/signer/public_key_b64",
+        )
+        .expected(passport_key.unwrap_or("null"))
+        .actual(signing_key),
+    )
+}
+
+/// Latest audit entry `timestamp`: the latest time the signed bundle
+/// vouches for. An entry timestamp that does not parse fails.
+fn latest_entry_secs(bundle: &Value) -> Result<Option<i64>, VerificationFailure> {
+    let entries = bundle.get("audit_entries").and_then(Value::as_array).map(Vec::as_slice).unwrap_or(&[]);
+    let mut latest = None;
+    for (i, ts) in entries.iter().enumerate().filter_map(|(i, e)| Some((i, e.get("timestamp")?.as_str()?))) {
+        let Some(secs) = parse_iso_epoch_secs(ts) else {
+            let pointer = format!("/bundle/audit_entries/{}/timestamp", i);
+            let detail = format!("audit entry timestamp {} is unparseable", ts);
+            return Err(VerificationFailure::new(VerifyError::BindingExpired, detail, pointer).actual(ts));
+        };
+        latest = latest.max(Some(secs));
+    }
+    Ok(latest)
+}
+
+/// `Err` if the binding had expired at `at_secs` by more than `grace`,
+/// `Ok(Some(warning))` if it had expired within `grace`.
+fn check_binding_expiry(expires_at: &str, at_secs: i64, grace: Duration) -> Result<Option<String>, VerificationFailure> {
+    let pointer = "/bundle/responsible_principal_record/expires_at";
+    let Some(expires) = parse_iso_epoch_secs(expires_at) else {
+        let failure = VerificationFailure::new(VerifyError::BindingExpired, "expires_at is unparseable", pointer);
+        return Err(failure.actual(expires_at));
+    };
+    let overdue = at_secs.saturating_sub(expires);
+    if overdue <= 0 {
+        return Ok(None);
+    }
+    if overdue <= duration_secs(grace) {
+        return Ok(Some(format!(
+            "BINDING IN GRACE PERIOD: binding expired at {}, {}s earlier; grace is {}s",
+            expires_at,
+            overdue,
+            grace.as_secs()
+        )));
+    }
+    Err(VerificationFailure::new(
+        VerifyError::BindingExpired,
+        format!("binding expired at {}, {}s earlier", expires_at, overdue),
+        pointer,
+    )
+    .expected(format!("expired at most {}s earlier", grace.as_secs()))
+    .actual(expires_at))
+}
+
+/// Whether ISO-8601 UTC time `a` is more than `skew` before `b`, or
+/// `None` if either does not parse.
+fn skewed_before(a: &str, b: &str, skew: Duration) -> Option<bool> {
+    Some(parse_iso_epoch_secs(a)?.saturating_add(duration_secs(skew)) < parse_iso_epoch_secs(b)?)
+}
+
+/// The record is issued before the passport is created, and the passport
+/// before the first audit entry; the last entry is not from after
+/// `now_secs`. Each within `skew`. Only signed timestamps are compared; one
+/// that is present but does not parse fails.
+fn check_temporal_order(bundle: &Value, skew: Duration, now_secs: i64) -> Result<(), VerificationFailure> {
+    // (what happened, when, its epoch seconds, pointer)
+    type Stamp<'a> = (&'static str, &'a str, i64, String);
+    fn stamp<'a>(
+        what: &'static str,
+        value: Option<&'a Value>,
+        pointer: String,
+    ) -> Result<Option<Stamp<'a>>, VerificationFailure> {
+        let Some(text) = value.and_then(Value::as_str) else {
+            return Ok(None);
+        };
+        let Some(secs) = parse_iso_epoch_secs(text) else {
+            let detail = format!("{} at {} is unparseable", what, text);
+            return Err(VerificationFailure::new(VerifyError::TimestampOrder, detail, pointer).actual(text));
+        };
+        Ok(Some((what, text, secs, pointer)))
+    }
+    let skew = duration_secs(skew);
+    let issued = stamp(
+        "record issued",
+        bundle.pointer("/responsible_principal_record/issued_at"),
+        "/bundle/responsible_principal_record/issued_at".into(),
+    )?;
+    let created = stamp(
+        "passport created",
+        bundle.pointer("/agent_passport/created_at"),
+        "/bundle/agent_passport/created_at".into(),
+    )?;
+    let entries = bundle.get("audit_entries").and_then(Value::as_array).map(Vec::as_slice).unwrap_or(&[]);
+    let logged: Vec<_> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, e)| {
+            stamp("audit entry", e.get("timestamp"), format!("/bundle/audit_entries/{}/timestamp", i)).transpose()
+        })
+        .collect::<Result<_, _>>()?;
+    let first = logged.iter().min_by_key(|s| s.2).cloned();
+    for (earlier, later) in [(issued, created.clone()), (created, first)] {
+        let (Some((first, first_at, first_secs, _)), Some((then, then_at, then_secs, pointer))) = (earlier, later)
+        else {
+            continue;
+        };
+        if then_secs.saturating_add(skew) < first_secs {
+            return Err(
+                VerificationFailure::new(
+                    VerifyError::TimestampOrder,
+                    format!("{} at {}, before {} at {}", then, then_at, first, first_at),
+                    pointer,
+                )
+                .expected(format!(">= {}", first_at))
+                .actual(then_at),
+            );
+        }
+    }
+    let Some((_, logged_at, logged_secs, pointer)) = logged.into_iter().max_by_key(|s| s.2) else {
+        return Ok(());
+    };
+    if logged_secs <= now_secs.saturating_add(skew) {
+        return Ok(());
+    }
+    let detail = format!("audit entry at {}, in the future", logged_at);
+    Err(VerificationFailure::new(VerifyError::TimestampOrder, detail, pointer)
+        .expected(format!("<= {}", iso_from_secs(now_secs)))
+        .actual(logged_at))
+}
+
+/// `signature.created_at` must be at most `max` before `now_secs`. A
+/// missing or unparseable timestamp fails too.
+fn check_signature_age(signature: &Value, max: Duration, now_secs: i64) -> Option<VerificationFailure> {
+    let pointer = "/signature/created_at";
+    let expected = format!("within {}s of now", max.as_secs());
+    let created_at = signature.get("created_at").and_then(|v| v.as_str());
+    let Some(created) = created_at.and_then(parse_iso_epoch_secs) else {
+        return Some(
+            VerificationFailure::new(VerifyError::SignatureTooOld, "created_at is missing or unparseable", pointer)
+                .expected(expected)
+                .actual(created_at.unwrap_or("")),
+        );
+    };
+    let age = now_secs.saturating_sub(created);
+    if age <= duration_secs(max) {
+        return None;
+    }
+    let detail = format!("signed {}s ago, limit {}s", age, max.as_secs());
+    Some(
+        VerificationFailure::new(VerifyError::SignatureTooOld, detail, pointer)
+            .expected(expected)
+            .actual(created_at.unwrap_or("")),
+    )
+}
+
+/// `STALE AUDIT ENTRY` warnings for entries timestamped more than `max`
+/// before `signed_at_secs`. Unparseable timestamps are left to the schema.
+fn stale_entries(bundle: &Value, max: Duration, signed_at_secs: i64) -> Vec<String> {
+    let entries = bundle.get("audit_entries").and_then(Value::as_array).map(Vec::as_slice).unwrap_or(&[]);
+    let window = duration_secs(max);
+    entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, e)| {
+            Some((i, signed_at_secs.saturating_sub(parse_iso_epoch_secs(e.get("timestamp")?.as_str()?)?)))
+        })
+        .filter(|(_, age)| *age > window)
+        .map(|(i, age)| format!("STALE AUDIT ENTRY: entry {} was {}s old when signed, window {}s", i, age, window))
+        .collect()
+}
+
+/// `policy_decision.intent_id` must name the bundle's intent. `Err` with
+/// a skip reason when either side is absent.
+fn check_references(bundle: &Value) -> Result<Option<VerificationFailure>, &'static str> {
+    let intent_id = bundle.pointer("/intent/intent_id").and_then(|v| v.as_str()).ok_or("bundle has no intent_id")?;
+    let decided = bundle
+        .pointer("/policy_decision/intent_id")
+        .and_then(|v| v.as_str())
+        .ok_or("bundle has no policy_decision")?;
+    if decided == intent_id {
+        return Ok(None);
+    }
+    Ok(Some(
+        VerificationFailure::new(
+            VerifyError::ReferenceMismatch,
+            format!("policy_decision names intent {}, bundle intent is {}", decided, intent_id),
+            "/bundle/policy_decision/intent_id",
+        )
+        .expected(intent_id)
+        .actual(decided),
+    ))
+}
+
+/// Record a check's status. The first one recorded stands, so a check
+/// that failed stays failed when exhaustive verification goes on past it.
+fn note(checks: Option<&mut Vec<CheckResult>>, name: &str, status: CheckStatus) {
+    if let Some(c) = checks.filter(|c| c.iter().all(|r| r.name != name)) {
+        c.push(CheckResult::new(name, status));
+    }
+}
+
+/// Record `Ok` as passed and `Err(reason)` as skipped, unless the check
+/// already has a status.
+fn note_outcome(checks: Option<&mut Vec<CheckResult>>, name: &str, outcome: Result<(), &str>) {
+    if let Some(c) = checks.filter(|c| c.iter().all(|r| r.name != name)) {
+        c.push(match outcome {
+            Ok(()) => CheckResult::new(name, CheckStatus::Passed),
+            Err(reason) => CheckResult::skipped(name, reason),
+        });
+    }
+}
+
+/// A target that declares a `salt_id`, or any target when `required`,
+/// must not carry plaintext `to`/`domain`/`url`.
+fn check_hashed_target(bundle: &Value, required: bool) -> Option<VerificationFailure> {
+    let target = bundle.get("intent")?.get("target")?;
+    if !required && target.get("salt_id").is_none() {
+        return None;
+    }
+    let field = ["to", "domain", "url"].into_iter().find(|f| {
+        target
+            .get(*f)
+            .and_then(|v| v.as_str())
+            .is_some_and(|v| !crate::privacy::is_hashed_value(v))
+    })?;
+    Some(
+        VerificationFailure::new(
+            VerifyError::TargetNotHashed,
+            format!("intent target {} is plaintext", field),
+            format!("/bundle/intent/target/{}", field),
+        )
+        .expected("sha256:<hex>"),
+    )
+}
+
+/// v1.1 `seq`: all entries carry one or none do, and each is greater than
+/// the one before. `Err` when no entry is sequenced.
+fn check_sequence(entries: &[Value]) -> Result<Option<VerificationFailure>, &'static str> {
+    if entries.iter().all(|e| e.get("seq").is_none()) {
+        return Err("no audit entry carries seq");
+    }
+    let mut prev: Option<u64> = None;
+    for (i, entry) in entries.iter().enumerate() {
+        let pointer = format!("/bundle/audit_entries/{}/seq", i);
+        let Some(seq) = entry.get("seq").and_then(Value::as_u64) else {
+            let actual = entry.get("seq").map_or_else(|| "absent".to_string(), |v| v.to_string());
+            let kind = VerifyError::SequenceOutOfOrder { entry: i };
+            return Ok(Some(VerificationFailure::new(kind, "seq is not an unsigned integer", pointer).actual(actual)));
+        };
+        if let Some(p) = prev.filter(|p| seq <= *p) {
+            let kind = VerifyError::SequenceOutOfOrder { entry: i };
+            return Ok(Some(
+                VerificationFailure::new(kind, format!("seq {} follows {}", seq, p), pointer)
+                    .expected(format!("> {}", p)).actual(seq.to_string()),
+            ));
+        }
+        prev = Some(seq);
+    }
+    Ok(None)
+}
+
+/// Audit ids must be unique and every entry must name the bundle's intent.
+fn check_identifiers(bundle: &Value, entries: &[Value]) -> Option<VerificationFailure> {
+    let intent_id = bundle.pointer("/intent/intent_id").and_then(|v| v.as_str());
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    for (i, entry) in entries.iter().enumerate() {
+        if let Some(audit_id) = entry.get("audit_id").and_then(|v| v.as_str()) {
+            if let Some(first) = seen.insert(audit_id, i) {
+                return Some(
+                    VerificationFailure::new(
+                        VerifyError::DuplicateAuditId { entry: i },
+                        format!("{} is already used by entry {}", audit_id, first),
+                        format!("/bundle/audit_entries/{}/audit_id", i),
+                    )
+                    .actual(audit_id),
+                );
+            }
+        }
+        let named = entry.get("intent_id").and_then(|v| v.as_str());
+        if named != intent_id {
+            let failure = VerificationFailure::new(
+                VerifyError::IntentIdMismatch { entry: i },
+                format!(
+                    "entry names intent {}, bundle intent is {}",
+                    named.unwrap_or("null"),
+                    intent_id.unwrap_or("null")
+                ),
+                format!("/bundle/audit_entries/{}/intent_id", i),
+            )
+            .actual(named.unwrap_or("null"));
+            return Some(match intent_id {
+                Some(id) => failure.expected(id),
+                None => failure,
+            });
+        }
+    }
+    None
+}
+
+/// An `approve` decision needs an `approved` entry for its intent whose
+/// outcome is terminal.
+fn check_decision_coverage(bundle: &Value, entries: &[Value]) -> Option<VerificationFailure> {
+    let decision = bundle.get("policy_decision")?;
+    if decision.get("decision").and_then(|v| v.as_str()) != Some("approve") {
+        return None;
+    }
+    let intent_id = decision.get("intent_id").and_then(|v| v.as_str()).unwrap_or("");
+    let covered = entries.iter().any(|e| {
+        e.get("intent_id").and_then(|v| v.as_str()) == Some(intent_id)
+            && e.get("policy_decision").and_then(|v| v.as_str()) == Some("approved")
+            && e.get("outcome")
+                .and_then(|v| v.as_str())
+                .and_then(Outcome::classify)
+                .is_some_and(|o| o.is_terminal())
+    });
+    if covered {
+        return None;
+    }
+    Some(
+        VerificationFailure::new(
+            VerifyError::DecisionNotAudited,
+            format!("intent {} was approved but no audit entry has a terminal outcome", intent_id),
+            "/bundle/policy_decision",
+        )
+        .expected("approved entry with terminal outcome"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contain_turns_panics_into_internal_errors() {
+        let err = contain(|| panic!("index {} out of range", 7)).unwrap_err();
+        assert_eq!(err.message, "index 7 out of range");
+        assert_eq!(err.into_result().errors.unwrap()[0], "VERIFIER INTERNAL ERROR: index 7 out of range");
+        assert!(contain(VerificationResult::ok).unwrap().verified);
+    }
 }
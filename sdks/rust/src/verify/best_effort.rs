@@ -0,0 +1,185 @@
+//! Best-effort verification of partly corrupt bundles.
+//!
+//! Deserializing a [`crate::types::SignedBundle`] fails as a whole when a
+//! single audit entry is malformed, and [`verify_with_report`] stops at
+//! the first failing check. Auditors handed such a bundle still want the
+//! rest of it checked. [`verify_best_effort`] runs whole-bundle
+//! verification, then parses and checks every artifact on its own: a
+//! corrupt entry is reported against its own pointer while its
+//! neighbours' `prev_hash` and `intent_hash` links are still verified.
+//!
+//! ```ignore
+//! let report = verify_best_effort(&signed, None, &VerifyOptions::default());
+//! for artifact in report.failed() {
+//!     eprintln!("{}: {:?} {:?}", artifact.pointer, artifact.parse_error, artifact.failures);
+//! }
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::amendment::check_lineage_in;
+use crate::crypto::{Canon, Canonicalizer};
+use crate::types::{
+    AgentPassport, AuditEntry, BundleSignature, Intent, PolicyDecision, ResponsiblePrincipalRecord, VerificationFailure,
+    VerificationReport, VerificationResult, VerifyError,
+};
+use crate::verify::{verify_with_report, VerifyOptions};
+
+/// What was found for one artifact of the bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactReport {
+    /// JSON Pointer of the artifact, e.g. `/bundle/audit_entries/3`.
+    pub pointer: String,
+    /// Why the artifact does not deserialize as its DCP type, if it does not.
+    pub parse_error: Option<String>,
+    /// Checks on this artifact that failed.
+    pub failures: Vec<VerificationFailure>,
+}
+
+impl ArtifactReport {
+    pub fn is_ok(&self) -> bool {
+        self.parse_error.is_none() && self.failures.is_empty()
+    }
+}
+
+/// Result of [`verify_best_effort`].
+#[derive(Debug, Clone)]
+pub struct BestEffortReport {
+    /// Whole-bundle verification, which stops at the first failure.
+    pub bundle: VerificationReport,
+    /// The signature block, each top-level bundle artifact, then each
+    /// audit entry, in order. Missing artifacts are reported as parse errors.
+    pub artifacts: Vec<ArtifactReport>,
+}
+
+impl BestEffortReport {
+    /// Whether every artifact parsed.
+    pub fn is_complete(&self) -> bool {
+        self.artifacts.iter().all(|a| a.parse_error.is_none())
+    }
+
+    /// Artifacts that did not parse or failed a check.
+    pub fn failed(&self) -> impl Iterator<Item = &ArtifactReport> {
+        self.artifacts.iter().filter(|a| !a.is_ok())
+    }
+
+    /// Every failure found, whole-bundle first, as one result. Parse
+    /// errors are `ARTIFACT UNPARSEABLE` failures at the artifact.
+    pub fn to_result(&self) -> VerificationResult {
+        let mut failures: Vec<VerificationFailure> = self.bundle.result.failures.clone().unwrap_or_default();
+        for artifact in &self.artifacts {
+            if let Some(e) = &artifact.parse_error {
+                failures.push(VerificationFailure::new(VerifyError::ArtifactUnparseable, e, artifact.pointer.as_str()));
+            }
+            for failure in &artifact.failures {
+                if !failures.contains(failure) {
+                    failures.push(failure.clone());
+                }
+            }
+        }
+        if failures.is_empty() {
+            VerificationResult::ok().with_warnings(self.bundle.result.warnings.clone())
+        } else {
+            VerificationResult::fail_at(failures)
+        }
+    }
+}
+
+/// Verify `signed_bundle` as far as it can be: whole-bundle
+/// verification, then each artifact parsed and linked on its own.
+pub fn verify_best_effort(signed_bundle: &Value, public_key_b64: Option<&str>, opts: &VerifyOptions) -> BestEffortReport {
+    let report = verify_with_report(signed_bundle, public_key_b64, opts);
+    let mut artifacts = vec![parsed::<BundleSignature>(signed_bundle, "/signature")];
+    let bundle = signed_bundle.get("bundle").unwrap_or(&Value::Null);
+    artifacts.push(parsed::<ResponsiblePrincipalRecord>(signed_bundle, "/bundle/responsible_principal_record"));
+    artifacts.push(parsed::<AgentPassport>(signed_bundle, "/bundle/agent_passport"));
+    artifacts.push(parsed::<Intent>(signed_bundle, "/bundle/intent"));
+    artifacts.push(parsed::<PolicyDecision>(signed_bundle, "/bundle/policy_decision"));
+
+    // Every revision of the intent an entry may carry the hash of; the
+    // whole-bundle check already covers the order they are announced in.
+    // Absent `canon` means v1; an unknown one already failed the
+    // whole-bundle check.
+    let canon = signed_bundle.pointer("/signature/canon").and_then(Value::as_str).and_then(Canon::parse);
+    let canon = canon.unwrap_or_default();
+    let mut buffers = Canonicalizer::new();
+    let mut intent_hashes = Vec::new();
+    if let Some(intent) = bundle.get("intent") {
+        intent_hashes.extend(buffers.accepted_intent_hashes_in(canon, intent));
+        let lineage = check_lineage_in(bundle, &mut buffers, canon);
+        intent_hashes.extend(lineage.into_iter().flatten().map(|(_, hash)| hash));
+    }
+
+    let entries = bundle.get("audit_entries").and_then(Value::as_array).map(Vec::as_slice).unwrap_or(&[]);
+    let volatile = &opts.volatile_audit_fields;
+    for (i, entry) in entries.iter().enumerate() {
+        let mut artifact = parsed::<AuditEntry>(signed_bundle, &format!("/bundle/audit_entries/{}", i));
+        if let Some(ih) = entry.get("intent_hash").and_then(Value::as_str) {
+            if !intent_hashes.is_empty() && !intent_hashes.iter().any(|h| h == ih) {
+                artifact.failures.push(
+                    VerificationFailure::new(
+                        VerifyError::IntentHashMismatch { entry: Some(i) },
+                        format!("expected {}, got {}", intent_hashes[0], ih),
+                        format!("{}/intent_hash", artifact.pointer),
+                    )
+                    .expected(intent_hashes[0].as_str())
+                    .actual(ih),
+                );
+            }
+        }
+        if let Some(ph) = entry.get("prev_hash").and_then(Value::as_str) {
+            let expected = match i {
+                0 => "GENESIS".to_string(),
+                _ => volatile.entry_hash_in(&mut buffers, canon, &entries[i - 1]),
+            };
+            if ph != expected {
+                artifact.failures.push(
+                    VerificationFailure::new(
+                        VerifyError::ChainBreak { entry: i },
+                        format!("expected {}, got {}", expected, ph),
+                        format!("{}/prev_hash", artifact.pointer),
+                    )
+                    .expected(expected)
+                    .actual(ph),
+                );
+            }
+        }
+        artifacts.push(artifact);
+    }
+    BestEffortReport { bundle: report, artifacts }
+}
+
+fn parsed<T: DeserializeOwned>(signed_bundle: &Value, pointer: &str) -> ArtifactReport {
+    let parse_error = match signed_bundle.pointer(pointer) {
+        None => Some("missing".to_string()),
+        Some(value) => T::deserialize(value).err().map(|e| e.to_string()),
+    };
+    ArtifactReport { pointer: pointer.to_string(), parse_error, failures: Vec::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+
+    #[test]
+    fn test_corrupt_entry_is_isolated() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let mut signed = set.get("citizenship_bundle.signed").unwrap().value.clone();
+        assert!(verify_best_effort(&signed, None, &VerifyOptions::default()).to_result().verified);
+
+        let entries = signed["bundle"]["audit_entries"].as_array().unwrap().len();
+        signed["bundle"]["audit_entries"][0]["outcome"] = Value::Null;
+        let report = verify_best_effort(&signed, None, &VerifyOptions::default());
+        assert!(!report.bundle.verified() && !report.is_complete());
+        let failed: Vec<&str> = report.failed().map(|a| a.pointer.as_str()).collect();
+        // The corrupt entry, and the next entry, whose prev_hash no longer matches it.
+        let mut expected = vec!["/bundle/audit_entries/0"];
+        if entries > 1 {
+            expected.push("/bundle/audit_entries/1");
+        }
+        assert_eq!(failed, expected);
+        assert!(report.to_result().errors.unwrap().iter().any(|e| e.starts_with("ARTIFACT UNPARSEABLE")));
+    }
+}
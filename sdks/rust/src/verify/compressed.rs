@@ -0,0 +1,134 @@
+//! Verify gzip- or zstd-compressed signed bundles (feature = "compressed").
+//!
+//! [`verify_compressed`] decompresses incrementally and stops as soon as
+//! the output passes [`CompressedOptions::max_decompressed_bytes`] or
+//! [`CompressedOptions::max_ratio`] times the input size, so a small
+//! hostile payload cannot expand into gigabytes before the SDK sees it.
+//!
+//! ```ignore
+//! let result = verify_compressed(&body, Encoding::Zstd, &CompressedOptions::default());
+//! ```
+
+use std::io::Read;
+
+use serde_json::Value;
+
+use crate::types::{VerificationFailure, VerificationResult, VerifyError};
+use crate::verify::{verify_signed_bundle_with_options, VerifyOptions};
+
+/// Compression of the payload, as named by e.g. `Content-Encoding`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Encoding {
+    Gzip,
+    Zstd,
+}
+
+impl Encoding {
+    /// The encoding for a `Content-Encoding` token (`gzip`, `x-gzip`, `zstd`).
+    pub fn from_content_encoding(token: &str) -> Option<Self> {
+        match token.trim().to_ascii_lowercase().as_str() {
+            "gzip" | "x-gzip" => Some(Encoding::Gzip),
+            "zstd" => Some(Encoding::Zstd),
+            _ => None,
+        }
+    }
+}
+
+/// Options for [`verify_compressed`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct CompressedOptions {
+    /// Largest decompressed payload accepted. Default 64 MiB.
+    pub max_decompressed_bytes: u64,
+    /// Largest decompressed-to-compressed size ratio accepted. JSON
+    /// bundles compress around 5-20x; default 100.
+    pub max_ratio: u64,
+    pub verify: VerifyOptions,
+}
+
+impl Default for CompressedOptions {
+    fn default() -> Self {
+        Self { max_decompressed_bytes: 64 << 20, max_ratio: 100, verify: VerifyOptions::default() }
+    }
+}
+
+/// Decompress `bytes` within the limits of `opts`, then verify the signed
+/// bundle under `opts.verify`. A payload over a limit fails with
+/// `LIMIT EXCEEDED`; one that does not decompress or parse fails with
+/// `DECOMPRESSION FAILED`.
+pub fn verify_compressed(bytes: &[u8], encoding: Encoding, opts: &CompressedOptions) -> VerificationResult {
+    let decompressed = match encoding {
+        Encoding::Gzip => decompress(flate2::read::GzDecoder::new(bytes), bytes.len(), opts),
+        Encoding::Zstd => match zstd::stream::read::Decoder::new(bytes) {
+            Ok(decoder) => decompress(decoder, bytes.len(), opts),
+            Err(e) => Err(failed(e)),
+        },
+    };
+    let signed_bundle: Value = match decompressed.and_then(|json| serde_json::from_slice(&json).map_err(failed)) {
+        Ok(value) => value,
+        Err(failure) => return VerificationResult::fail_at(vec![failure]),
+    };
+    verify_signed_bundle_with_options(&signed_bundle, None, &opts.verify)
+}
+
+fn failed(e: impl std::fmt::Display) -> VerificationFailure {
+    VerificationFailure::new(VerifyError::DecompressionFailed, e.to_string(), "")
+}
+
+fn decompress(
+    mut reader: impl Read,
+    compressed_len: usize,
+    opts: &CompressedOptions,
+) -> Result<Vec<u8>, VerificationFailure> {
+    let ratio_limit = (compressed_len as u64).saturating_mul(opts.max_ratio);
+    let limit = opts.max_decompressed_bytes.min(ratio_limit);
+    let mut out = Vec::new();
+    let mut chunk = [0u8; 64 << 10];
+    loop {
+        let n = reader.read(&mut chunk).map_err(failed)?;
+        if n == 0 {
+            return Ok(out);
+        }
+        if (out.len() + n) as u64 > limit {
+            let detail = if limit == opts.max_decompressed_bytes {
+                format!("payload decompresses past {} bytes", limit)
+            } else {
+                let (ratio, len) = (opts.max_ratio, compressed_len);
+                format!("payload decompresses past {}x its {} compressed bytes", ratio, len)
+            };
+            let failure = VerificationFailure::new(VerifyError::LimitExceeded, detail, "");
+            return Err(failure.expected(format!("<= {} bytes", limit)));
+        }
+        out.extend_from_slice(&chunk[..n]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+    use std::io::Write;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_verifies_within_limits_and_stops_bombs() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let signed = serde_json::to_vec(&set.get("citizenship_bundle.signed").unwrap().value).unwrap();
+        let opts = CompressedOptions::default();
+        assert!(verify_compressed(&gzip(&signed), Encoding::Gzip, &opts).verified);
+        let zstd_bytes = zstd::encode_all(signed.as_slice(), 3).unwrap();
+        assert!(verify_compressed(&zstd_bytes, Encoding::Zstd, &opts).verified);
+
+        let bomb = gzip(&vec![b' '; 8 << 20]);
+        let errors = verify_compressed(&bomb, Encoding::Gzip, &opts).errors.unwrap();
+        assert!(errors[0].starts_with("LIMIT EXCEEDED: payload decompresses past 100x"), "{}", errors[0]);
+        let errors = verify_compressed(b"not gzip", Encoding::Gzip, &opts).errors.unwrap();
+        assert!(errors[0].starts_with("DECOMPRESSION FAILED"));
+    }
+}
@@ -0,0 +1,217 @@
+//! Replay a bundle's audit trail against an independent event source.
+//!
+//! A valid signature proves the bundle is what the agent wrote, not that
+//! it wrote down everything it did. [`cross_check`] pairs each event from
+//! an external log (API gateway, mail relay, proxy) with an audit entry
+//! of the same agent close enough in time, and reports the events no
+//! entry accounts for: actions taken outside the audited bundle.
+//!
+//! ```ignore
+//! let report = cross_check(&signed.bundle, gateway_log.iter().map(to_event));
+//! for event in &report.unaudited_events {
+//!     eprintln!("unaudited call to {} at {}", event.target, event.timestamp);
+//! }
+//! ```
+
+use std::time::Duration;
+
+use crate::privacy::TargetSalt;
+use crate::types::{CitizenshipBundle, IntentTarget, VerificationFailure, VerificationResult, VerifyError};
+use crate::v2::advisory::parse_iso_epoch_secs;
+
+/// One action observed by a system outside the agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ExternalEvent {
+    /// Identifier in the external system, for the report.
+    pub event_id: String,
+    /// ISO-8601 UTC time the action was observed.
+    pub timestamp: String,
+    /// Agent the source attributes the action to, if it knows.
+    pub agent_id: Option<String>,
+    /// Recipient, domain, or URL the action was aimed at.
+    pub target: String,
+}
+
+impl ExternalEvent {
+    pub fn new(event_id: impl Into<String>, timestamp: impl Into<String>, target: impl Into<String>) -> Self {
+        Self { event_id: event_id.into(), timestamp: timestamp.into(), agent_id: None, target: target.into() }
+    }
+
+    pub fn agent(mut self, agent_id: impl Into<String>) -> Self {
+        self.agent_id = Some(agent_id.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct CrossCheckOptions {
+    /// Largest clock difference between an event and the audit entry
+    /// recording it. Default five minutes.
+    pub window: Duration,
+    /// Salt of a hashed intent target ([`crate::privacy`]), so plaintext
+    /// event targets can be compared with it. Without it, events against
+    /// a bundle with a hashed target match on agent and time alone.
+    pub salt: Option<TargetSalt>,
+}
+
+impl Default for CrossCheckOptions {
+    fn default() -> Self {
+        Self { window: Duration::from_secs(300), salt: None }
+    }
+}
+
+/// An event and the audit entry that accounts for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventMatch {
+    pub event_id: String,
+    pub audit_id: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CrossCheckReport {
+    pub matched: Vec<EventMatch>,
+    /// Events of the bundle's agent, or aimed at its target, that no
+    /// audit entry accounts for.
+    pub unaudited_events: Vec<ExternalEvent>,
+    /// `audit_id`s with no external event. Informational: not every
+    /// entry (a policy approval, say) is visible to an outside system.
+    pub unobserved_entries: Vec<String>,
+    /// Events outside the check: another agent's, or another target with
+    /// no agent attribution.
+    pub unrelated_events: usize,
+}
+
+impl CrossCheckReport {
+    /// Whether every related event is accounted for.
+    pub fn is_consistent(&self) -> bool {
+        self.unaudited_events.is_empty()
+    }
+
+    /// One `UNAUDITED ACTION` failure per unaudited event.
+    pub fn to_result(&self) -> VerificationResult {
+        if self.is_consistent() {
+            return VerificationResult::ok();
+        }
+        VerificationResult::fail_at(
+            self.unaudited_events
+                .iter()
+                .map(|e| {
+                    VerificationFailure::new(
+                        VerifyError::UnauditedAction,
+                        format!("event {} at {} targeting {}", e.event_id, e.timestamp, e.target),
+                        "/bundle/audit_entries",
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+/// [`cross_check_with`] under default options.
+pub fn cross_check(
+    bundle: &CitizenshipBundle,
+    external_log: impl IntoIterator<Item = ExternalEvent>,
+) -> CrossCheckReport {
+    cross_check_with(bundle, external_log, &CrossCheckOptions::default())
+}
+
+/// Match `external_log` against `bundle`'s audit entries.
+///
+/// An event is related to the bundle when it names the bundle's agent, or
+/// names no agent and is aimed at the intent's target. Each related event
+/// is matched to the nearest-in-time unmatched audit entry of that agent
+/// within `opts.window`; an event that finds none is unaudited, as is one
+/// with an unparseable timestamp. Each entry accounts for at most one
+/// event.
+pub fn cross_check_with(
+    bundle: &CitizenshipBundle,
+    external_log: impl IntoIterator<Item = ExternalEvent>,
+    opts: &CrossCheckOptions,
+) -> CrossCheckReport {
+    let agent = bundle.agent_passport.agent_id.as_str();
+    let window = opts.window.as_secs() as i64;
+    let mut entries: Vec<(Option<i64>, &str, bool)> = bundle
+        .audit_entries
+        .iter()
+        .filter(|e| e.agent_id == agent)
+        .map(|e| (parse_iso_epoch_secs(&e.timestamp), e.audit_id.as_str(), false))
+        .collect();
+
+    let mut events: Vec<(Option<i64>, ExternalEvent)> =
+        external_log.into_iter().map(|e| (parse_iso_epoch_secs(&e.timestamp), e)).collect();
+    events.sort_by_key(|(t, _)| *t);
+
+    let mut report = CrossCheckReport::default();
+    for (at, event) in events {
+        let related = match event.agent_id.as_deref() {
+            Some(a) => a == agent,
+            None => targets(&bundle.intent.target, &event.target, opts.salt.as_ref()),
+        };
+        if !related {
+            report.unrelated_events += 1;
+            continue;
+        }
+        let nearest = at.and_then(|at| {
+            entries
+                .iter_mut()
+                .filter(|(t, _, used)| !*used && t.is_some_and(|t| (t - at).abs() <= window))
+                .min_by_key(|(t, _, _)| t.map(|t| (t - at).abs()))
+        });
+        match nearest {
+            Some((_, audit_id, used)) => {
+                *used = true;
+                report.matched.push(EventMatch { event_id: event.event_id, audit_id: audit_id.to_string() });
+            }
+            None => report.unaudited_events.push(event),
+        }
+    }
+    report.unobserved_entries =
+        entries.into_iter().filter(|(_, _, used)| !used).map(|(_, id, _)| id.to_string()).collect();
+    report
+}
+
+/// Whether `event_target` is the intent's `to`, `domain`, or `url`, or,
+/// for a hashed target, whether it hashes to one of them under `salt`.
+fn targets(target: &IntentTarget, event_target: &str, salt: Option<&TargetSalt>) -> bool {
+    let fields = [("to", &target.to), ("domain", &target.domain), ("url", &target.url)];
+    match (&target.salt_id, salt) {
+        (Some(_), Some(salt)) => {
+            fields.iter().any(|(name, v)| v.as_deref() == Some(salt.hash(name, event_target).as_str()))
+        }
+        (Some(_), None) => true,
+        (None, _) => fields.iter().any(|(_, v)| v.as_deref() == Some(event_target)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+
+    fn bundle() -> CitizenshipBundle {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        serde_json::from_value(set.get("citizenship_bundle").unwrap().value.clone()).unwrap()
+    }
+
+    #[test]
+    fn test_reports_events_outside_the_audit_trail() {
+        let b = bundle();
+        let entry = &b.audit_entries[0];
+        let agent = b.agent_passport.agent_id.clone();
+        let log = vec![
+            ExternalEvent::new("gw-1", entry.timestamp.clone(), "ignored").agent(agent.clone()),
+            ExternalEvent::new("gw-2", "2031-06-01T00:00:00Z", "ignored").agent(agent),
+            ExternalEvent::new("gw-3", entry.timestamp.clone(), "x").agent("did:agent:other"),
+        ];
+
+        let report = cross_check(&b, log);
+        assert_eq!(report.matched, [EventMatch { event_id: "gw-1".into(), audit_id: entry.audit_id.clone() }]);
+        assert_eq!(report.unaudited_events.len(), 1);
+        assert_eq!(report.unaudited_events[0].event_id, "gw-2");
+        assert_eq!(report.unrelated_events, 1);
+        assert!(report.to_result().errors.unwrap()[0].starts_with("UNAUDITED ACTION: event gw-2"));
+    }
+}
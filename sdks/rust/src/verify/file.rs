@@ -0,0 +1,202 @@
+//! Memory-mapped verification of large bundle files (feature = "mmap").
+//!
+//! Archived bundles with long audit logs and embedded evidence can run to
+//! gigabytes, and parsing one into a `serde_json::Value` takes several
+//! times its size in RAM. [`verify_file`] maps the file instead and walks
+//! the audit entries one at a time: each is canonicalized and hashed, then
+//! kept only as the fields the chain checks read. Peak memory is roughly
+//! one canonical copy of the bundle, which Ed25519 needs as a single
+//! message, plus the slimmed entries.
+//!
+//! Results match [`verify_signed_bundle_with_options`] on the same bundle.
+//! Files that are small enough are read into memory instead; see
+//! [`verify_file`] for why mapped files must not change.
+//!
+//! ```ignore
+//! let result = verify_file_with_progress("archive/2025-q4.json", &VerifyOptions::default(), |p| {
+//!     eprintln!("{}/{} entries", p.entries_hashed, p.entries_total);
+//! })?;
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use memmap2::Mmap;
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use serde_json::{Map, Value};
+
+use super::{verify_prepared, verify_signed_bundle_with_options, Depth, Prepared, VerifyOptions};
+use crate::crypto::{canonicalize, hash_canonical};
+use crate::retention::tombstoned_hash;
+use crate::types::VerificationResult;
+
+/// Files up to this many bytes are read into memory instead of mapped.
+const READ_LIMIT: u64 = 64 * 1024 * 1024;
+
+/// Reported after each audit entry is hashed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FileProgress {
+    pub bytes_total: u64,
+    pub entries_total: usize,
+    pub entries_hashed: usize,
+}
+
+#[derive(Deserialize)]
+struct RawSigned<'a> {
+    #[serde(borrow)]
+    bundle: Option<&'a RawValue>,
+    signature: Option<Value>,
+}
+
+/// Verify the signed bundle stored at `path`. Keys come from
+/// `opts.candidate_keys` or the signer's embedded key. Fails only if the
+/// file cannot be read or is not JSON.
+///
+/// Only verify files nothing writes to while they are verified, such as
+/// sealed archive objects. Files over 64 MiB are memory-mapped: truncating
+/// one mid-verification makes the process fault with `SIGBUS`, and one
+/// rewritten in place can yield a verdict about bytes other than the ones
+/// a later read returns. Copy a file that may change to private storage
+/// first.
+pub fn verify_file(path: impl AsRef<Path>, opts: &VerifyOptions) -> io::Result<VerificationResult> {
+    verify_file_with_progress(path, opts, |_| {})
+}
+
+/// [`verify_file`], calling `on_progress` after each audit entry.
+pub fn verify_file_with_progress(
+    path: impl AsRef<Path>,
+    opts: &VerifyOptions,
+    mut on_progress: impl FnMut(&FileProgress),
+) -> io::Result<VerificationResult> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let (read, mapped);
+    let map: &[u8] = if len <= READ_LIMIT {
+        let mut buf = Vec::with_capacity(len as usize);
+        file.read_to_end(&mut buf)?;
+        read = buf;
+        &read
+    } else {
+        // SAFETY: the map is read-only and dropped before returning. It is
+        // sound only while no other process truncates or rewrites the file,
+        // which callers must guarantee (see the module docs); truncation
+        // raises SIGBUS on access.
+        mapped = unsafe { Mmap::map(&file)? };
+        &mapped
+    };
+    let bytes_total = map.len() as u64;
+
+    let raw: RawSigned<'_> = serde_json::from_slice(map)?;
+    let mut signed = Map::new();
+    if let Some(signature) = raw.signature {
+        signed.insert("signature".into(), signature);
+    }
+    let Some(bundle) = raw.bundle else {
+        return Ok(verify_prepared(&Value::Object(signed), None, opts, Depth::Full, Prepared::default(), None, None));
+    };
+
+    // Odd shapes are rare and small; verify them the ordinary way.
+    let (fields, entries) = match split_bundle(bundle) {
+        Some(parts) => parts,
+        None => {
+            signed.insert("bundle".into(), serde_json::from_str(bundle.get())?);
+            return Ok(verify_signed_bundle_with_options(&Value::Object(signed), None, opts));
+        }
+    };
+
+    // Canonical form, built field by field in sorted key order exactly as
+    // `canonicalize` would produce it for the whole bundle.
+    let mut canon = String::with_capacity(map.len());
+    let mut entry_hashes = Vec::with_capacity(entries.len());
+    let mut slim = Map::new();
+    canon.push('{');
+    for (i, (key, raw)) in fields.iter().enumerate() {
+        if i > 0 {
+            canon.push(',');
+        }
+        canon.push_str(&serde_json::to_string(key)?);
+        canon.push(':');
+        if key == "audit_entries" {
+            let mut slimmed = Vec::with_capacity(entries.len());
+            canon.push('[');
+            for (j, raw_entry) in entries.iter().enumerate() {
+                if j > 0 {
+                    canon.push(',');
+                }
+                let entry: Value = serde_json::from_str(raw_entry.get())?;
+                let entry_canon = canonicalize(&entry);
+                let volatile = &opts.volatile_audit_fields;
+                entry_hashes.push(match tombstoned_hash(&entry) {
+                    Some(h) => h.to_string(),
+                    None if volatile.is_empty() => hash_canonical(&entry_canon),
+                    None => volatile.entry_hash(&entry),
+                });
+                canon.push_str(&entry_canon);
+                slimmed.push(slim_entry(entry));
+                on_progress(&FileProgress { bytes_total, entries_total: entries.len(), entries_hashed: j + 1 });
+            }
+            canon.push(']');
+            slim.insert(key.clone(), Value::Array(slimmed));
+        } else {
+            let value: Value = serde_json::from_str(raw.get())?;
+            canon.push_str(&canonicalize(&value));
+            slim.insert(key.clone(), value);
+        }
+    }
+    canon.push('}');
+
+    signed.insert("bundle".into(), Value::Object(slim));
+    let prepared = Prepared { canon: Some(canon), entry_hashes: Some(entry_hashes), ..Prepared::default() };
+    Ok(verify_prepared(&Value::Object(signed), None, opts, Depth::Full, prepared, None, None))
+}
+
+/// The bundle's fields and its audit entries, still unparsed. `None`
+/// unless the bundle is an object whose `audit_entries` is an array.
+#[allow(clippy::type_complexity)]
+fn split_bundle(bundle: &RawValue) -> Option<(BTreeMap<String, &RawValue>, Vec<&RawValue>)> {
+    let fields: BTreeMap<String, &RawValue> = serde_json::from_str(bundle.get()).ok()?;
+    let entries: Vec<&RawValue> = serde_json::from_str(fields.get("audit_entries")?.get()).ok()?;
+    Some((fields, entries))
+}
+
+/// Keep only `evidence.result_ref` and any retention tombstone, the
+/// evidence fields the checks read; embedded evidence is what makes
+/// archives large.
+fn slim_entry(mut entry: Value) -> Value {
+    if let Some(evidence) = entry.get_mut("evidence").and_then(Value::as_object_mut) {
+        evidence.retain(|k, _| k == "result_ref" || k == "tombstone");
+    }
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+
+    #[test]
+    fn test_file_result_matches_in_memory_verification() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let mut signed = set.get("citizenship_bundle.signed").unwrap().value.clone();
+        let path = std::env::temp_dir().join(format!("dcp-verify-file-{}.json", std::process::id()));
+
+        std::fs::write(&path, serde_json::to_vec(&signed).unwrap()).unwrap();
+        let mut seen = Vec::new();
+        let result = verify_file_with_progress(&path, &VerifyOptions::default(), |p| seen.push(p.entries_hashed)).unwrap();
+        assert!(result.verified, "{:?}", result.errors);
+        let total = signed["bundle"]["audit_entries"].as_array().unwrap().len();
+        assert_eq!(seen, (1..=total).collect::<Vec<_>>());
+
+        signed["bundle"]["audit_entries"][0]["evidence"]["tool"] = "tampered".into();
+        std::fs::write(&path, serde_json::to_vec(&signed).unwrap()).unwrap();
+        let result = verify_file(&path, &VerifyOptions::default()).unwrap();
+        let expected = verify_signed_bundle_with_options(&signed, None, &VerifyOptions::default());
+        assert!(!result.verified);
+        assert_eq!(result.errors, expected.errors);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
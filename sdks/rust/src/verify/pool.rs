@@ -0,0 +1,111 @@
+//! Bounded verification pool (feature = "pool").
+//!
+//! Signature and hash checks are CPU-bound. Running them directly on an
+//! async runtime lets a bundle flood starve every other task; the pool
+//! moves them onto tokio's blocking threads and caps how many run at once.
+//!
+//! ```ignore
+//! let pool = VerifierPool::new(4);
+//! let result = pool.submit(signed_bundle).await;
+//! ```
+
+use std::sync::Arc;
+
+use serde_json::Value;
+use tokio::sync::Semaphore;
+
+use crate::types::{VerificationResult, VerifyError};
+use crate::verify::{verify_signed_bundle_with_options, VerifyOptions};
+
+/// Bounds concurrent CPU-heavy verifications. Cheap to clone; clones share
+/// the same limit.
+#[derive(Clone)]
+pub struct VerifierPool {
+    permits: Arc<Semaphore>,
+    opts: Arc<VerifyOptions>,
+    workers: usize,
+}
+
+impl VerifierPool {
+    /// Pool allowing at most `n_workers` verifications at a time
+    /// (minimum 1), using default [`VerifyOptions`].
+    pub fn new(n_workers: usize) -> Self {
+        Self::with_options(n_workers, VerifyOptions::default())
+    }
+
+    pub fn with_options(n_workers: usize, opts: VerifyOptions) -> Self {
+        let workers = n_workers.max(1);
+        Self {
+            permits: Arc::new(Semaphore::new(workers)),
+            opts: Arc::new(opts),
+            workers,
+        }
+    }
+
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    /// Worker slots free right now.
+    pub fn available(&self) -> usize {
+        self.permits.available_permits()
+    }
+
+    /// Verify `signed_bundle` once a worker slot is free. Must be awaited
+    /// inside a tokio runtime.
+    pub async fn submit(&self, signed_bundle: Value) -> VerificationResult {
+        self.submit_with_key(signed_bundle, None).await
+    }
+
+    /// [`submit`](Self::submit) with an explicit public key.
+    pub async fn submit_with_key(&self, signed_bundle: Value, public_key_b64: Option<String>) -> VerificationResult {
+        let permit = match self.permits.clone().acquire_owned().await {
+            Ok(p) => p,
+            Err(_) => return VerificationResult::fail(VerifyError::Internal, "verifier pool closed"),
+        };
+        let opts = self.opts.clone();
+        // The blocking task owns the permit, so a caller that stops waiting
+        // does not free a slot while verification is still running.
+        let job = tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            verify_signed_bundle_with_options(&signed_bundle, public_key_b64.as_deref(), &opts)
+        });
+        match job.await {
+            Ok(result) => result,
+            Err(e) => VerificationResult::fail(VerifyError::Internal, format!("verification task failed: {}", e)),
+        }
+    }
+
+    /// Stop accepting work; pending and future submissions fail.
+    pub fn close(&self) {
+        self.permits.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_submit_returns_result() {
+        let pool = VerifierPool::new(2);
+        let result = pool.submit(json!({"signature": {}})).await;
+        assert!(!result.verified);
+        assert_eq!(result.errors.unwrap()[0], "Missing bundle");
+        assert_eq!(pool.available(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_closed_pool_rejects() {
+        let pool = VerifierPool::new(1);
+        pool.close();
+        let result = pool.submit(json!({})).await;
+        assert_eq!(result.errors.unwrap()[0], "VERIFIER INTERNAL ERROR: verifier pool closed");
+    }
+
+    #[test]
+    fn test_zero_workers_clamped() {
+        assert_eq!(VerifierPool::new(0).workers(), 1);
+    }
+}
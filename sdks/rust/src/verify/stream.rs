@@ -0,0 +1,124 @@
+//! Verify bundles arriving on a tokio channel (feature = "stream").
+//!
+//! [`stream_verifier`] turns a `Receiver<Value>` into a stream of
+//! `(bundle, report)` pairs. Several bundles are verified at once on
+//! tokio's blocking threads while results come out in arrival order; at
+//! most `in_flight` bundles are held between the channel and the
+//! consumer, so a slow consumer stops the verifier reading and the
+//! producers' `send` waits.
+//!
+//! ```ignore
+//! let (tx, rx) = tokio::sync::mpsc::channel(64);
+//! let mut reports = std::pin::pin!(stream_verifier(rx, VerifyOptions::default()));
+//! while let Some((bundle, report)) = reports.next().await {
+//!     store(bundle, report.verified());
+//! }
+//! ```
+
+use std::sync::Arc;
+
+use serde_json::Value;
+use tokio::sync::mpsc::{self, Receiver};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+
+use crate::types::{CheckResult, VerificationReport};
+use crate::verify::{contain, verify_with_report, VerifyOptions, CHECK_NAMES};
+
+/// [`stream_verifier_with_concurrency`] with one bundle in flight per
+/// available CPU.
+pub fn stream_verifier(rx: Receiver<Value>, opts: VerifyOptions) -> impl Stream<Item = (Value, VerificationReport)> {
+    let cpus = std::thread::available_parallelism().map_or(1, |n| n.get());
+    stream_verifier_with_concurrency(rx, opts, cpus)
+}
+
+/// Verify every bundle received on `rx` under `opts`, up to `in_flight`
+/// (minimum 1) at a time, yielding each with its
+/// [`verify_with_report`] report in the order received. The stream ends
+/// once `rx` is closed and drained. Must be called inside a tokio
+/// runtime.
+///
+/// A panic while verifying one bundle yields a `VERIFIER INTERNAL ERROR`
+/// report for it and does not end the stream.
+pub fn stream_verifier_with_concurrency(
+    mut rx: Receiver<Value>,
+    opts: VerifyOptions,
+    in_flight: usize,
+) -> impl Stream<Item = (Value, VerificationReport)> {
+    let in_flight = in_flight.max(1);
+    let opts = Arc::new(opts);
+    let (jobs_tx, mut jobs_rx) = mpsc::channel(in_flight);
+    let (out_tx, out_rx) = mpsc::channel(in_flight);
+
+    tokio::spawn(async move {
+        while let Some(signed_bundle) = rx.recv().await {
+            let opts = opts.clone();
+            let job = tokio::task::spawn_blocking(move || {
+                let report = report_contained(&signed_bundle, &opts);
+                (signed_bundle, report)
+            });
+            if jobs_tx.send(job).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    tokio::spawn(async move {
+        while let Some(job) = jobs_rx.recv().await {
+            // Panics are contained inside the job, so it only fails if
+            // the runtime is shutting down.
+            let Ok(item) = job.await else {
+                break;
+            };
+            if out_tx.send(item).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    ReceiverStream::new(out_rx)
+}
+
+/// [`verify_with_report`], with a panic reported as `VERIFIER INTERNAL
+/// ERROR` and every check skipped.
+pub(crate) fn report_contained(signed_bundle: &Value, opts: &VerifyOptions) -> VerificationReport {
+    contain(|| verify_with_report(signed_bundle, None, opts)).unwrap_or_else(|e| {
+        let checks = CHECK_NAMES.iter().map(|name| CheckResult::skipped(*name, "verifier panicked")).collect();
+        VerificationReport { result: e.into_result(), checks }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::{sign_bundle, SignOptions};
+    use crate::crypto::generate_keypair;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+    use crate::types::CitizenshipBundle;
+    use serde_json::json;
+    use tokio_stream::StreamExt;
+
+    #[tokio::test]
+    async fn test_reports_come_out_in_order() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let bundle: CitizenshipBundle =
+            serde_json::from_value(set.get("citizenship_bundle").unwrap().value.clone()).unwrap();
+        let (_, sk) = generate_keypair();
+        let valid = serde_json::to_value(sign_bundle(bundle, &SignOptions::new(&sk)).unwrap()).unwrap();
+
+        let (tx, rx) = mpsc::channel(1);
+        tokio::spawn(async move {
+            for i in 0..8 {
+                let sb = if i % 2 == 0 { valid.clone() } else { json!({"n": i}) };
+                tx.send(sb).await.unwrap();
+            }
+        });
+        let results: Vec<_> = stream_verifier_with_concurrency(rx, VerifyOptions::default(), 3).collect().await;
+        assert_eq!(results.len(), 8);
+        for (i, (sb, report)) in results.iter().enumerate() {
+            assert_eq!(report.verified(), i % 2 == 0, "{}: {:?}", i, report.result.errors);
+            assert_eq!(sb.get("n").is_some(), i % 2 == 1);
+            assert_eq!(report.checks.len(), CHECK_NAMES.len());
+        }
+    }
+}
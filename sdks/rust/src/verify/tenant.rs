@@ -0,0 +1,195 @@
+//! One verifier for many customer organizations.
+//!
+//! A [`MultiTenantVerifier`] holds a [`TenantConfig`] per tenant id: the
+//! keys that tenant trusts, its [`VerifyOptions`] profile, optional input
+//! limits, and its own [`VerifierStatus`] counters. Selecting a tenant per
+//! request is a read-locked map lookup; tenants can be added, replaced,
+//! or removed while requests are in flight.
+//!
+//! ```
+//! use dcp_ai::limits::EmbeddedLimits;
+//! use dcp_ai::verify::{MultiTenantVerifier, TenantConfig, VerifyOptions};
+//!
+//! let verifier = MultiTenantVerifier::new();
+//! verifier.insert_tenant("acme", TenantConfig::new(VerifyOptions::default()).limits(EmbeddedLimits::check));
+//! let result = verifier.verify("acme", &serde_json::json!({}), None).unwrap();
+//! assert!(!result.verified);
+//! assert!(verifier.verify("globex", &serde_json::json!({}), None).is_err());
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use serde_json::Value;
+
+use crate::health::VerifierStatus;
+use crate::limits::LimitExceeded;
+use crate::types::{VerificationFailure, VerificationResult, VerifyError};
+use crate::verify::{Verifier, VerifyOptions};
+
+/// A [`crate::limits::Limits`] profile's `check`, e.g. `EmbeddedLimits::check`.
+pub type LimitCheck = fn(&Value) -> Result<(), LimitExceeded>;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum TenantError {
+    #[error("unknown tenant {0}")]
+    UnknownTenant(String),
+}
+
+/// Trust and policy for one tenant.
+#[derive(Debug, Clone)]
+pub struct TenantConfig {
+    opts: VerifyOptions,
+    limits: Option<LimitCheck>,
+}
+
+impl TenantConfig {
+    pub fn new(opts: VerifyOptions) -> Self {
+        Self { opts, limits: None }
+    }
+
+    /// Keys this tenant trusts. Bundles are verified against these
+    /// ([`VerifyOptions::candidate_keys`]) instead of their embedded
+    /// signer key.
+    pub fn trust_keys<I, S>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.opts.candidate_keys.extend(keys.into_iter().map(Into::into));
+        self
+    }
+
+    /// Reject bundles over these limits before verifying them.
+    pub fn limits(mut self, check: LimitCheck) -> Self {
+        self.limits = Some(check);
+        self
+    }
+
+    pub fn options(&self) -> &VerifyOptions {
+        &self.opts
+    }
+}
+
+struct Tenant {
+    verifier: Verifier,
+    limits: Option<LimitCheck>,
+    status: Arc<VerifierStatus>,
+}
+
+/// Verifiers for many tenants behind one handle. Cheap to clone; clones
+/// share tenants.
+#[derive(Clone, Default)]
+pub struct MultiTenantVerifier {
+    tenants: Arc<RwLock<HashMap<String, Arc<Tenant>>>>,
+}
+
+impl std::fmt::Debug for MultiTenantVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultiTenantVerifier").field("tenants", &self.tenant_ids()).finish()
+    }
+}
+
+impl MultiTenantVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `tenant_id`, or replace its configuration. Replacing resets
+    /// the tenant's counters.
+    pub fn insert_tenant(&self, tenant_id: impl Into<String>, config: TenantConfig) {
+        let tenant = Tenant {
+            verifier: Verifier::new(config.opts),
+            limits: config.limits,
+            status: Arc::new(VerifierStatus::new()),
+        };
+        self.write().insert(tenant_id.into(), Arc::new(tenant));
+    }
+
+    /// Whether `tenant_id` was present.
+    pub fn remove_tenant(&self, tenant_id: &str) -> bool {
+        self.write().remove(tenant_id).is_some()
+    }
+
+    /// Tenant ids, sorted.
+    pub fn tenant_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.read().keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    /// The tenant's verification counters.
+    pub fn status(&self, tenant_id: &str) -> Option<Arc<VerifierStatus>> {
+        self.read().get(tenant_id).map(|t| t.status.clone())
+    }
+
+    /// Verify `signed_bundle` under `tenant_id`'s trust and policy, and
+    /// count the result against that tenant.
+    pub fn verify(
+        &self,
+        tenant_id: &str,
+        signed_bundle: &Value,
+        public_key_b64: Option<&str>,
+    ) -> Result<VerificationResult, TenantError> {
+        let tenant = self
+            .read()
+            .get(tenant_id)
+            .cloned()
+            .ok_or_else(|| TenantError::UnknownTenant(tenant_id.to_string()))?;
+        let result = match tenant.limits.map(|check| check(signed_bundle)) {
+            Some(Err(e)) => VerificationResult::fail_at(vec![VerificationFailure::new(
+                VerifyError::LimitExceeded,
+                e.to_string(),
+                e.pointer().to_string(),
+            )]),
+            _ => tenant.verifier.verify(signed_bundle, public_key_b64),
+        };
+        tenant.status.record(&result);
+        Ok(result)
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, Arc<Tenant>>> {
+        self.tenants.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<String, Arc<Tenant>>> {
+        self.tenants.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::{sign_bundle, SignOptions};
+    use crate::crypto::generate_keypair;
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+    use crate::limits::Limits;
+    use crate::types::CitizenshipBundle;
+
+    #[test]
+    fn test_tenants_apply_their_own_trust_and_limits() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let bundle: CitizenshipBundle =
+            serde_json::from_value(set.get("citizenship_bundle").unwrap().value.clone()).unwrap();
+        let (pk, sk) = generate_keypair();
+        let sb = serde_json::to_value(sign_bundle(bundle, &SignOptions::new(&sk)).unwrap()).unwrap();
+
+        let verifier = MultiTenantVerifier::new();
+        let config = || TenantConfig::new(VerifyOptions::default());
+        verifier.insert_tenant("acme", config().trust_keys([pk]));
+        verifier.insert_tenant("globex", config().trust_keys([generate_keypair().0]));
+        verifier.insert_tenant("tiny", config().limits(Limits::<1, 8, 2>::check));
+
+        let first_error = |tenant: &str| verifier.verify(tenant, &sb, None).unwrap().errors.unwrap()[0].clone();
+        assert!(verifier.verify("acme", &sb, None).unwrap().verified);
+        assert!(first_error("globex").starts_with("SIGNATURE INVALID"));
+        assert!(first_error("tiny").starts_with("LIMIT EXCEEDED"));
+        assert!(matches!(verifier.verify("initech", &sb, None), Err(TenantError::UnknownTenant(_))));
+
+        assert_eq!(verifier.status("acme").unwrap().snapshot().bundles_verified, 1);
+        assert_eq!(verifier.status("globex").unwrap().snapshot().bundles_failed, 1);
+        assert!(verifier.remove_tenant("tiny"));
+        assert_eq!(verifier.tenant_ids(), ["acme", "globex"]);
+    }
+}
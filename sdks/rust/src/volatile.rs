@@ -0,0 +1,155 @@
+//! Audit entry fields excluded from entry hashing.
+//!
+//! Some producers annotate audit entries with runtime-only fields
+//! (`latency_ms`, `retries`) after the entry was hashed into the
+//! `prev_hash` chain. Those fields then break the chain and the merkle
+//! root even though nothing the protocol protects changed.
+//!
+//! A [`VolatileFields`] set marks such top-level fields as volatile: the
+//! serde-level equivalent of a `#[dcp(volatile)]` attribute on the
+//! producer's entry type. [`VolatileFields::entry_hash`] hashes an entry
+//! without them, and verification with the same set in
+//! [`crate::verify::VerifyOptions::volatile_audit_fields`] does the same
+//! for `prev_hash` and `merkle_root`. The bundle signature and
+//! `bundle_hash` still cover the whole bundle, volatile fields included,
+//! so they must be added before the bundle is signed.
+//!
+//! Fields the protocol defines ([`PROTECTED_FIELDS`]) cannot be volatile.
+//!
+//! ```
+//! use dcp_ai::volatile::VolatileFields;
+//!
+//! let volatile = VolatileFields::new(["latency_ms", "retries"]).unwrap();
+//! let entry = serde_json::json!({"audit_id": "a-1", "outcome": "ok"});
+//! let mut annotated = entry.clone();
+//! annotated["latency_ms"] = 42.into();
+//! assert_eq!(volatile.entry_hash(&annotated), volatile.entry_hash(&entry));
+//! assert!(VolatileFields::new(["outcome"]).is_err());
+//! ```
+
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+
+use serde_json::Value;
+
+use crate::crypto::{Canon, Canonicalizer};
+use crate::retention;
+
+/// Audit entry fields defined by the protocol, which are always hashed.
+pub const PROTECTED_FIELDS: &[&str] = &[
+    "dcp_version",
+    "audit_id",
+    "prev_hash",
+    "timestamp",
+    "agent_id",
+    "human_id",
+    "intent_id",
+    "intent_hash",
+    "policy_decision",
+    "outcome",
+    "evidence",
+    "seq",
+    "trace_id",
+    "span_id",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum VolatileError {
+    #[error("{0} is a protocol field and cannot be volatile")]
+    Protected(String),
+    #[error("volatile field name is empty")]
+    Empty,
+}
+
+/// Top-level audit entry fields left out of entry hashes. The default
+/// set is empty: every field is hashed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct VolatileFields(BTreeSet<String>);
+
+impl VolatileFields {
+    pub fn new<I, S>(names: I) -> Result<Self, VolatileError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut set = BTreeSet::new();
+        for name in names {
+            let name = name.into();
+            if name.is_empty() {
+                return Err(VolatileError::Empty);
+            }
+            if PROTECTED_FIELDS.contains(&name.as_str()) {
+                return Err(VolatileError::Protected(name));
+            }
+            set.insert(name);
+        }
+        Ok(Self(set))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    /// `entry` without the volatile fields; borrowed when it has none.
+    pub fn strip<'a>(&self, entry: &'a Value) -> Cow<'a, Value> {
+        match entry.as_object() {
+            Some(map) if self.0.iter().any(|name| map.contains_key(name)) => {
+                let kept = map.iter().filter(|(k, _)| !self.0.contains(*k));
+                Cow::Owned(Value::Object(kept.map(|(k, v)| (k.clone(), v.clone())).collect()))
+            }
+            _ => Cow::Borrowed(entry),
+        }
+    }
+
+    /// [`retention::entry_hash`] of `entry` without the volatile fields:
+    /// the value for the next entry's `prev_hash` and for merkle leaves.
+    pub fn entry_hash(&self, entry: &Value) -> String {
+        self.entry_hash_in(&mut Canonicalizer::new(), Canon::V1, entry)
+    }
+
+    /// [`Self::entry_hash`] under `canon`, reusing `buffers`.
+    pub fn entry_hash_in(&self, buffers: &mut Canonicalizer, canon: Canon, entry: &Value) -> String {
+        match retention::tombstoned_hash(entry) {
+            Some(h) => h.to_string(),
+            None => buffers.hash_in(canon, &self.strip(entry)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{canonicalize, generate_keypair, hash_canonical, sign_canonical};
+    use crate::fixtures::{generate_fixtures, GOLDEN_SEED};
+    use crate::verify::{verify_signed_bundle_with_options, VerifyOptions};
+
+    #[test]
+    fn test_verification_respects_volatile_fields() {
+        let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+        let mut signed = set.get("citizenship_bundle.signed").unwrap().value.clone();
+        let volatile = VolatileFields::new(["latency_ms"]).unwrap();
+
+        // Annotated after the chain was hashed, before the bundle was signed.
+        signed["bundle"]["audit_entries"][0]["latency_ms"] = 42.into();
+        let canon = canonicalize(&signed["bundle"]);
+        let (pk, sk) = generate_keypair();
+        signed["signature"]["sig_b64"] = sign_canonical(&canon, &sk).unwrap().into();
+        signed["signature"]["bundle_hash"] = format!("sha256:{}", hash_canonical(&canon)).into();
+        signed["signature"]["signer"]["public_key_b64"] = pk.into();
+
+        let mut opts = VerifyOptions::default();
+        assert!(!verify_signed_bundle_with_options(&signed, None, &opts).verified);
+        opts.volatile_audit_fields = volatile;
+        let result = verify_signed_bundle_with_options(&signed, None, &opts);
+        assert!(result.verified, "{:?}", result.errors);
+    }
+}
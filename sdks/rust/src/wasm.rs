@@ -0,0 +1,506 @@
+//! WebAssembly bindings.
+//!
+//! Two feature sets:
+//!
+//! - `wasm-verify-only` exports verification, hashing, and
+//!   canonicalization only. No key generation, signing, KEM, or key
+//!   storage code is linked, which keeps the artifact small enough to
+//!   embed in browser extensions.
+//! - `wasm-full` (and its alias `wasm`) adds keypair generation,
+//!   composite signing, bundle building, proof-of-possession, and
+//!   ML-KEM-768. `wasm-keystore` adds IndexedDB key storage on top.
+//!
+//! Exports return JSON strings; input errors are reported as
+//! `{"error": ...}`, and nothing here panics on bad input. Key and nonce
+//! generation read the system RNG (`crypto.getRandomValues` in a
+//! browser) and, like the rest of the SDK, panic if it is unavailable;
+//! they keep `String` returns so their TypeScript types stay stable.
+//!
+//! Build the smallest verify-only artifact with the `wasm-release`
+//! profile:
+//!
+//! ```text
+//! cargo build --target wasm32-unknown-unknown --profile wasm-release \
+//!     --no-default-features --features wasm-verify-only
+//! ```
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::b64;
+use crate::crypto;
+use crate::hash::{HashAlgorithm, Hasher};
+use crate::providers::ed25519::Ed25519Provider;
+use crate::providers::ml_dsa_65::MlDsa65Provider;
+use crate::v2::canonicalize::canonicalize_v2;
+use crate::v2::composite_ops::composite_verify;
+use crate::v2::composite_sig::CompositeSignature;
+use crate::v2::crypto_provider::CryptoProvider;
+use crate::v2::dual_hash;
+use crate::verify;
+
+#[cfg(feature = "wasm-full")]
+mod full;
+#[cfg(feature = "wasm-full")]
+pub use full::*;
+
+#[cfg(feature = "wasm-keystore")]
+mod keystore;
+#[cfg(feature = "wasm-keystore")]
+pub use keystore::*;
+
+fn json_err(msg: &str) -> String {
+    format!("{{\"error\":\"{}\"}}", msg.replace('"', "'"))
+}
+
+/// Serialize a `json!` value. Infallible, unlike `serde_json::to_string`
+/// on arbitrary `Serialize` types, so there is no `unwrap` to panic.
+fn to_json(value: &Value) -> String {
+    value.to_string()
+}
+
+// ── V1 Compatibility ──────────────────────────────────────────────────
+
+#[wasm_bindgen]
+pub fn wasm_verify_signed_bundle(signed_bundle_json: &str, public_key_b64: Option<String>) -> String {
+    let sb: Value = match serde_json::from_str(signed_bundle_json) {
+        Ok(v) => v,
+        Err(e) => return json_err(&format!("JSON parse error: {}", e)),
+    };
+    let result = verify::verify_signed_bundle(&sb, public_key_b64.as_deref());
+    serde_json::to_string(&result).unwrap_or_else(|_| "{\"verified\":false}".to_string())
+}
+
+#[wasm_bindgen]
+pub fn wasm_hash_object(json_str: &str) -> String {
+    let obj: Value = match serde_json::from_str(json_str) {
+        Ok(v) => v,
+        Err(e) => return json_err(&format!("JSON parse: {}", e)),
+    };
+    crypto::hash_object(&obj)
+}
+
+#[wasm_bindgen]
+pub fn wasm_detect_version(json_str: &str) -> String {
+    let val: Value = match serde_json::from_str(json_str) {
+        Ok(v) => v,
+        Err(_) => return "null".to_string(),
+    };
+    match crate::detect_dcp_version(&val) {
+        Some(v) => format!("\"{}\"", v),
+        None => "null".to_string(),
+    }
+}
+
+// ── Composite Verification ────────────────────────────────────────────
+
+/// Verify a composite signature cryptographically.
+#[wasm_bindgen]
+pub fn wasm_composite_verify(
+    context: &str,
+    payload_json: &str,
+    composite_sig_json: &str,
+    classical_pk_b64: &str,
+    pq_pk_b64: Option<String>,
+) -> String {
+    let val: Value = match serde_json::from_str(payload_json) {
+        Ok(v) => v,
+        Err(e) => return json_err(&format!("JSON parse: {}", e)),
+    };
+    let sig: CompositeSignature = match serde_json::from_str(composite_sig_json) {
+        Ok(s) => s,
+        Err(e) => return json_err(&format!("Signature parse: {}", e)),
+    };
+    let canonical = match canonicalize_v2(&val) {
+        Ok(c) => c,
+        Err(e) => return json_err(&e),
+    };
+
+    let ed = Ed25519Provider;
+    let pq = MlDsa65Provider;
+    let pq_ref: Option<&dyn CryptoProvider> = if pq_pk_b64.is_some() { Some(&pq) } else { None };
+
+    match composite_verify(
+        &ed, pq_ref, context, canonical.as_bytes(),
+        &sig, classical_pk_b64, pq_pk_b64.as_deref(),
+    ) {
+        Ok(result) => to_json(&json!({
+            "valid": result.valid,
+            "classical_valid": result.classical_valid,
+            "pq_valid": result.pq_valid
+        })),
+        Err(e) => json_err(&e.to_string()),
+    }
+}
+
+/// Full V2 bundle verification with cryptographic signature checks.
+#[wasm_bindgen]
+pub fn wasm_verify_signed_bundle_v2(signed_bundle_json: &str) -> String {
+    let val: Value = match serde_json::from_str(signed_bundle_json) {
+        Ok(v) => v,
+        Err(e) => return to_json(&json!({
+            "verified": false, "errors": [format!("JSON parse error: {}", e)]
+        })),
+    };
+
+    let version = crate::detect_dcp_version(&val);
+
+    match version {
+        Some("1.0") => {
+            let result = verify::verify_signed_bundle(&val, None);
+            return serde_json::to_string(&result).unwrap_or_else(|_| "{\"verified\":false}".to_string());
+        },
+        Some("2.0") => {},
+        _ => {
+            return to_json(&json!({
+                "verified": false, "errors": ["Unknown DCP version"]
+            }));
+        }
+    }
+
+    let mut errors: Vec<String> = Vec::new();
+    let mut warnings: Vec<String> = Vec::new();
+    let mut classical_valid = false;
+    let mut pq_valid = false;
+
+    let bundle = match val.get("bundle") {
+        Some(b) => b,
+        None => return to_json(&json!({
+            "verified": false, "errors": ["Missing bundle field"]
+        })),
+    };
+    let signature = match val.get("signature") {
+        Some(s) => s,
+        None => return to_json(&json!({
+            "verified": false, "errors": ["Missing signature field"]
+        })),
+    };
+
+    if bundle.get("dcp_bundle_version").and_then(|v| v.as_str()) != Some("2.0") {
+        errors.push("Invalid dcp_bundle_version".to_string());
+    }
+    if bundle.get("manifest").is_none() {
+        errors.push("Missing manifest in bundle".to_string());
+    }
+    for field in &["responsible_principal_record", "agent_passport", "intent", "policy_decision"] {
+        if bundle.get(*field).is_none() {
+            errors.push(format!("Missing {} in bundle", field));
+        }
+    }
+
+    let manifest_nonce = bundle.get("manifest")
+        .and_then(|m| m.get("session_nonce"))
+        .and_then(|n| n.as_str())
+        .unwrap_or("");
+    if manifest_nonce.is_empty() {
+        errors.push("Missing session_nonce in manifest".to_string());
+    }
+
+    // Verify manifest hashes against actual artifact hashes
+    if let Some(manifest) = bundle.get("manifest") {
+        for (field, hash_key) in &[
+            ("responsible_principal_record", "rpr_hash"),
+            ("agent_passport", "passport_hash"),
+            ("intent", "intent_hash"),
+            ("policy_decision", "policy_hash"),
+        ] {
+            if let (Some(artifact), Some(expected)) = (
+                bundle.get(*field).and_then(|a| a.get("payload")),
+                manifest.get(*hash_key).and_then(|h| h.as_str()),
+            ) {
+                if let Ok(canonical) = canonicalize_v2(artifact) {
+                    let dh = dual_hash::dual_hash_canonical(&canonical);
+                    let computed = format!("sha256:{}", dh.sha256);
+                    if computed != expected {
+                        errors.push(format!("Manifest {} mismatch", hash_key));
+                    }
+                }
+            }
+        }
+    }
+
+    // Session nonce consistency across artifacts
+    if !manifest_nonce.is_empty() {
+        for field in &["responsible_principal_record", "agent_passport", "intent", "policy_decision"] {
+            if let Some(nonce) = bundle.get(*field)
+                .and_then(|a| a.get("payload"))
+                .and_then(|p| p.get("session_nonce"))
+                .and_then(|n| n.as_str())
+            {
+                if nonce != manifest_nonce {
+                    errors.push(format!("Session nonce mismatch in {}", field));
+                    break;
+                }
+            }
+        }
+    }
+
+    // Cryptographic signature verification on the bundle-level composite_sig
+    if let Some(cs_val) = signature.get("composite_sig") {
+        if let Ok(cs) = serde_json::from_value::<CompositeSignature>(cs_val.clone()) {
+            let binding = cs.binding.as_str();
+
+            // We need public keys from the passport
+            let passport_keys = bundle.get("agent_passport")
+                .and_then(|a| a.get("payload"))
+                .and_then(|p| p.get("keys"))
+                .and_then(|k| k.as_array());
+
+            let mut classical_pk: Option<String> = None;
+            let mut pq_pk: Option<String> = None;
+
+            if let Some(keys) = passport_keys {
+                for key_entry in keys {
+                    let alg = key_entry.get("alg").and_then(|a| a.as_str()).unwrap_or("");
+                    let pk = key_entry.get("public_key_b64").and_then(|p| p.as_str());
+                    match alg {
+                        "ed25519" => classical_pk = pk.map(|s| s.to_string()),
+                        "ml-dsa-65" => pq_pk = pk.map(|s| s.to_string()),
+                        _ => {}
+                    }
+                }
+            }
+
+            // Verify bundle manifest signature
+            if let Some(manifest) = bundle.get("manifest") {
+                if let Ok(canonical) = canonicalize_v2(manifest) {
+                    let ed = Ed25519Provider;
+                    let pq_prov = MlDsa65Provider;
+
+                    if let Some(ref cpk) = classical_pk {
+                        let pq_ref: Option<&dyn CryptoProvider> = if pq_pk.is_some() && binding == "pq_over_classical" {
+                            Some(&pq_prov)
+                        } else {
+                            None
+                        };
+                        match composite_verify(
+                            &ed, pq_ref,
+                            crate::v2::domain_separation::CTX_BUNDLE,
+                            canonical.as_bytes(), &cs, cpk,
+                            pq_pk.as_deref(),
+                        ) {
+                            Ok(result) => {
+                                classical_valid = result.classical_valid;
+                                pq_valid = result.pq_valid;
+                                if !result.valid {
+                                    errors.push("Bundle signature verification failed".to_string());
+                                }
+                            },
+                            Err(e) => errors.push(format!("Signature verify error: {}", e)),
+                        }
+                    } else {
+                        warnings.push("No classical public key found in passport".to_string());
+                    }
+                }
+            }
+
+            if binding == "classical_only" {
+                warnings.push("Bundle uses classical_only binding (no PQ protection)".to_string());
+            }
+        } else {
+            errors.push("Invalid composite_sig structure".to_string());
+        }
+    } else {
+        errors.push("Missing composite_sig in signature".to_string());
+    }
+
+    // Verify audit entry hash chain
+    if let Some(entries) = bundle.get("audit_entries").and_then(|e| e.as_array()) {
+        let mut expected_prev = "sha256:".to_string() + &"0".repeat(64);
+        for (i, entry) in entries.iter().enumerate() {
+            if let Some(prev) = entry.get("prev_hash").and_then(|p| p.as_str()) {
+                if i > 0 && prev != expected_prev {
+                    errors.push(format!("Audit hash chain broken at entry {}", i));
+                    break;
+                }
+            }
+            if let Ok(canonical) = canonicalize_v2(entry) {
+                let dh = dual_hash::dual_hash_canonical(&canonical);
+                expected_prev = format!("sha256:{}", dh.sha256);
+            }
+        }
+    }
+
+    let verified = errors.is_empty();
+    to_json(&json!({
+        "verified": verified,
+        "dcp_version": "2.0",
+        "errors": errors,
+        "warnings": warnings,
+        "classical_valid": classical_valid,
+        "pq_valid": pq_valid,
+        "session_binding_valid": !manifest_nonce.is_empty(),
+        "manifest_valid": bundle.get("manifest").is_some()
+    }))
+}
+
+// ── Canonicalization & Domain Separation ───────────────────────────────
+
+#[wasm_bindgen]
+pub fn wasm_derive_kid(alg: &str, public_key_b64: &str) -> String {
+    let pk_bytes = match b64::decode(public_key_b64) {
+        Ok(b) => b,
+        Err(e) => return json_err(&format!("base64 decode: {}", e)),
+    };
+    crate::v2::crypto_provider::derive_kid(alg, &pk_bytes)
+}
+
+#[wasm_bindgen]
+pub fn wasm_canonicalize_v2(json_str: &str) -> String {
+    let val: Value = match serde_json::from_str(json_str) {
+        Ok(v) => v,
+        Err(e) => return json_err(&format!("JSON parse: {}", e)),
+    };
+    match canonicalize_v2(&val) {
+        Ok(s) => s,
+        Err(e) => json_err(&e),
+    }
+}
+
+#[wasm_bindgen]
+pub fn wasm_domain_separated_message(context: &str, payload_hex: &str) -> String {
+    let payload = match hex::decode(payload_hex) {
+        Ok(b) => b,
+        Err(e) => return json_err(&format!("hex decode: {}", e)),
+    };
+    match crate::v2::domain_separation::domain_separated_message(context, &payload) {
+        Ok(dsm) => hex::encode(dsm),
+        Err(e) => json_err(&e),
+    }
+}
+
+// ── Incremental Hashing ───────────────────────────────────────────────
+
+/// Streaming hasher for input that arrives in chunks (file reads, fetch
+/// bodies). `new WasmHasher("sha256")`, `update(chunk)` per chunk, then
+/// `finalize()` for the hex digest; the object is consumed.
+#[wasm_bindgen]
+pub struct WasmHasher {
+    inner: Hasher,
+}
+
+#[wasm_bindgen]
+impl WasmHasher {
+    /// `alg` is `sha256` (the default) or `sha3-256`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(alg: Option<String>) -> Result<WasmHasher, JsError> {
+        let algorithm = match alg.as_deref() {
+            None => HashAlgorithm::Sha256,
+            Some(name) => HashAlgorithm::parse(name)
+                .ok_or_else(|| JsError::new(&format!("unsupported hash algorithm: {}", name)))?,
+        };
+        Ok(WasmHasher { inner: Hasher::new(algorithm) })
+    }
+
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.inner.update(chunk);
+    }
+
+    pub fn update_string(&mut self, chunk: &str) {
+        self.inner.update(chunk.as_bytes());
+    }
+
+    pub fn finalize(self) -> String {
+        self.inner.finalize_hex()
+    }
+}
+
+// ── Dual Hash ─────────────────────────────────────────────────────────
+
+/// Compute SHA-256 + SHA3-256 dual hash of a string.
+#[wasm_bindgen]
+pub fn wasm_dual_hash(data: &str) -> String {
+    let dh = dual_hash::dual_hash(data.as_bytes());
+    serde_json::to_string(&dh).unwrap_or_else(|e| json_err(&e.to_string()))
+}
+
+/// Compute SHA3-256 hash of a string (hex-encoded).
+#[wasm_bindgen]
+pub fn wasm_sha3_256(data: &str) -> String {
+    dual_hash::sha3_256_hex(data.as_bytes())
+}
+
+/// Compute dual Merkle root from an array of dual-hash leaves.
+/// Input: JSON array of {"sha256":"...","sha3_256":"..."} objects.
+#[wasm_bindgen]
+pub fn wasm_dual_merkle_root(leaves_json: &str) -> String {
+    let leaves: Vec<dual_hash::DualHash> = match serde_json::from_str(leaves_json) {
+        Ok(v) => v,
+        Err(e) => return json_err(&format!("JSON parse: {}", e)),
+    };
+    if leaves.is_empty() {
+        return json_err("Empty leaves array");
+    }
+
+    fn merkle_reduce(hashes: Vec<String>, use_sha3: bool) -> String {
+        if hashes.len() == 1 {
+            return hashes[0].clone();
+        }
+        let mut next = Vec::new();
+        let mut i = 0;
+        while i < hashes.len() {
+            if i + 1 < hashes.len() {
+                let combined = format!("{}{}", hashes[i], hashes[i + 1]);
+                if use_sha3 {
+                    next.push(dual_hash::sha3_256_hex(combined.as_bytes()));
+                } else {
+                    next.push(dual_hash::sha256_hex(combined.as_bytes()));
+                }
+                i += 2;
+            } else {
+                next.push(hashes[i].clone());
+                i += 1;
+            }
+        }
+        merkle_reduce(next, use_sha3)
+    }
+
+    let sha256_leaves: Vec<String> = leaves.iter().map(|l| l.sha256.clone()).collect();
+    let sha3_leaves: Vec<String> = leaves.iter().map(|l| l.sha3_256.clone()).collect();
+
+    let sha256_root = merkle_reduce(sha256_leaves, false);
+    let sha3_root = merkle_reduce(sha3_leaves, true);
+
+    to_json(&json!({
+        "sha256": sha256_root,
+        "sha3_256": sha3_root
+    }))
+}
+
+// ── Session Binding ───────────────────────────────────────────────────
+
+/// Verify session nonce consistency across artifacts.
+/// Input: JSON array of objects, each optionally containing "session_nonce".
+#[wasm_bindgen]
+pub fn wasm_verify_session_binding(artifacts_json: &str) -> String {
+    let artifacts: Vec<Value> = match serde_json::from_str(artifacts_json) {
+        Ok(v) => v,
+        Err(e) => return to_json(&json!({
+            "valid": false, "error": format!("JSON parse: {}", e)
+        })),
+    };
+
+    let mut found_nonce: Option<String> = None;
+    for art in &artifacts {
+        if let Some(nonce) = art.get("session_nonce").and_then(|n| n.as_str()) {
+            match &found_nonce {
+                None => found_nonce = Some(nonce.to_string()),
+                Some(expected) => {
+                    if nonce != expected {
+                        return to_json(&json!({
+                            "valid": false,
+                            "error": "Session nonce mismatch",
+                            "expected": expected,
+                            "got": nonce
+                        }));
+                    }
+                }
+            }
+        }
+    }
+
+    to_json(&json!({
+        "valid": true,
+        "nonce": found_nonce
+    }))
+}
@@ -0,0 +1,555 @@
+//! Signing-side bindings (`wasm-full`).
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use super::{json_err, to_json};
+use crate::b64;
+use crate::crypto;
+use crate::providers::ed25519::Ed25519Provider;
+use crate::providers::ml_dsa_65::MlDsa65Provider;
+use crate::providers::slh_dsa_192f::SlhDsa192fProvider;
+use crate::v2::canonicalize::canonicalize_v2;
+use crate::v2::composite_ops::{classical_only_sign, composite_sign, CompositeKeyInfo};
+use crate::v2::composite_sig::SignatureEntry;
+use crate::v2::crypto_provider::CryptoProvider;
+use crate::v2::dual_hash;
+use crate::v2::proof_of_possession::{generate_registration_pop, verify_registration_pop, PopChallenge};
+use crate::v2::signed_payload;
+
+fn provider_for_alg(alg: &str) -> Result<Box<dyn CryptoProvider>, String> {
+    match alg {
+        "ed25519" => Ok(Box::new(Ed25519Provider)),
+        "ml-dsa-65" => Ok(Box::new(MlDsa65Provider)),
+        "slh-dsa-192f" => Ok(Box::new(SlhDsa192fProvider)),
+        _ => Err(format!("Unknown algorithm: {}", alg)),
+    }
+}
+
+// ── Keypair Generation ────────────────────────────────────────────────
+
+#[wasm_bindgen]
+pub fn wasm_generate_keypair() -> String {
+    let (pub_key, sec_key) = crypto::generate_keypair();
+    to_json(&json!({
+        "alg": "ed25519",
+        "public_key_b64": pub_key,
+        "secret_key_b64": sec_key
+    }))
+}
+
+#[wasm_bindgen]
+pub fn wasm_generate_ml_dsa_65_keypair() -> String {
+    let provider = MlDsa65Provider;
+    match provider.generate_keypair() {
+        Ok(kp) => to_json(&json!({
+            "alg": "ml-dsa-65",
+            "kid": kp.kid,
+            "public_key_b64": kp.public_key_b64,
+            "secret_key_b64": kp.secret_key_b64
+        })),
+        Err(e) => json_err(&e.to_string()),
+    }
+}
+
+#[wasm_bindgen]
+pub fn wasm_generate_slh_dsa_192f_keypair() -> String {
+    let provider = SlhDsa192fProvider;
+    match provider.generate_keypair() {
+        Ok(kp) => to_json(&json!({
+            "alg": "slh-dsa-192f",
+            "kid": kp.kid,
+            "public_key_b64": kp.public_key_b64,
+            "secret_key_b64": kp.secret_key_b64
+        })),
+        Err(e) => json_err(&e.to_string()),
+    }
+}
+
+/// Generate an Ed25519 + ML-DSA-65 hybrid keypair in a single call.
+#[wasm_bindgen]
+pub fn wasm_generate_hybrid_keypair() -> String {
+    let ed = Ed25519Provider;
+    let pq = MlDsa65Provider;
+    let ed_kp = match ed.generate_keypair() {
+        Ok(kp) => kp,
+        Err(e) => return json_err(&e.to_string()),
+    };
+    let pq_kp = match pq.generate_keypair() {
+        Ok(kp) => kp,
+        Err(e) => return json_err(&e.to_string()),
+    };
+    to_json(&json!({
+        "classical": {
+            "alg": "ed25519",
+            "kid": ed_kp.kid,
+            "public_key_b64": ed_kp.public_key_b64,
+            "secret_key_b64": ed_kp.secret_key_b64
+        },
+        "pq": {
+            "alg": "ml-dsa-65",
+            "kid": pq_kp.kid,
+            "public_key_b64": pq_kp.public_key_b64,
+            "secret_key_b64": pq_kp.secret_key_b64
+        }
+    }))
+}
+
+// ── Composite Signing ─────────────────────────────────────────────────
+
+/// Composite sign: Ed25519 + ML-DSA-65 with pq_over_classical binding.
+#[wasm_bindgen]
+pub fn wasm_composite_sign(
+    context: &str,
+    payload_json: &str,
+    classical_sk_b64: &str,
+    classical_kid: &str,
+    pq_sk_b64: &str,
+    pq_kid: &str,
+) -> String {
+    let val: Value = match serde_json::from_str(payload_json) {
+        Ok(v) => v,
+        Err(e) => return json_err(&format!("JSON parse: {}", e)),
+    };
+    let canonical = match canonicalize_v2(&val) {
+        Ok(c) => c,
+        Err(e) => return json_err(&e),
+    };
+
+    let ed = Ed25519Provider;
+    let pq = MlDsa65Provider;
+    let classical_key = CompositeKeyInfo {
+        kid: classical_kid.to_string(),
+        alg: "ed25519".to_string(),
+        secret_key_b64: classical_sk_b64.to_string(),
+        public_key_b64: String::new(),
+    };
+    let pq_key = CompositeKeyInfo {
+        kid: pq_kid.to_string(),
+        alg: "ml-dsa-65".to_string(),
+        secret_key_b64: pq_sk_b64.to_string(),
+        public_key_b64: String::new(),
+    };
+
+    match composite_sign(&ed, &pq, context, canonical.as_bytes(), &classical_key, &pq_key) {
+        Ok(sig) => serde_json::to_string(&sig).unwrap_or_else(|_| json_err("serialize failed")),
+        Err(e) => json_err(&e.to_string()),
+    }
+}
+
+/// Classical-only signing (Ed25519 transition mode).
+#[wasm_bindgen]
+pub fn wasm_classical_only_sign(
+    context: &str,
+    payload_json: &str,
+    sk_b64: &str,
+    kid: &str,
+) -> String {
+    let val: Value = match serde_json::from_str(payload_json) {
+        Ok(v) => v,
+        Err(e) => return json_err(&format!("JSON parse: {}", e)),
+    };
+    let canonical = match canonicalize_v2(&val) {
+        Ok(c) => c,
+        Err(e) => return json_err(&e),
+    };
+
+    let ed = Ed25519Provider;
+    let key = CompositeKeyInfo {
+        kid: kid.to_string(),
+        alg: "ed25519".to_string(),
+        secret_key_b64: sk_b64.to_string(),
+        public_key_b64: String::new(),
+    };
+
+    match classical_only_sign(&ed, context, canonical.as_bytes(), &key) {
+        Ok(sig) => serde_json::to_string(&sig).unwrap_or_else(|_| json_err("serialize failed")),
+        Err(e) => json_err(&e.to_string()),
+    }
+}
+
+/// Sign a payload and return a SignedPayload envelope (payload + hash + composite_sig).
+#[wasm_bindgen]
+pub fn wasm_sign_payload(
+    context: &str,
+    payload_json: &str,
+    classical_sk_b64: &str,
+    classical_kid: &str,
+    pq_sk_b64: &str,
+    pq_kid: &str,
+) -> String {
+    let val: Value = match serde_json::from_str(payload_json) {
+        Ok(v) => v,
+        Err(e) => return json_err(&format!("JSON parse: {}", e)),
+    };
+    let (canonical_bytes, payload_hash) = match signed_payload::prepare_payload(&val) {
+        Ok(r) => r,
+        Err(e) => return json_err(&e),
+    };
+
+    let ed = Ed25519Provider;
+    let pq = MlDsa65Provider;
+    let classical_key = CompositeKeyInfo {
+        kid: classical_kid.to_string(),
+        alg: "ed25519".to_string(),
+        secret_key_b64: classical_sk_b64.to_string(),
+        public_key_b64: String::new(),
+    };
+    let pq_key = CompositeKeyInfo {
+        kid: pq_kid.to_string(),
+        alg: "ml-dsa-65".to_string(),
+        secret_key_b64: pq_sk_b64.to_string(),
+        public_key_b64: String::new(),
+    };
+
+    match composite_sign(&ed, &pq, context, &canonical_bytes, &classical_key, &pq_key) {
+        Ok(sig) => to_json(&json!({
+            "payload": val,
+            "payload_hash": payload_hash,
+            "composite_sig": sig
+        })),
+        Err(e) => json_err(&e.to_string()),
+    }
+}
+
+// ── Session Nonce ─────────────────────────────────────────────────────
+
+/// Generate a 256-bit random session nonce (64 hex chars).
+#[wasm_bindgen]
+pub fn wasm_generate_session_nonce() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+// ── Security Tier ─────────────────────────────────────────────────────
+
+/// Compute adaptive security tier from an intent's risk profile.
+/// Input: JSON intent with risk_score, data_classes, action_type.
+#[wasm_bindgen]
+pub fn wasm_compute_security_tier(intent_json: &str) -> String {
+    let val: Value = match serde_json::from_str(intent_json) {
+        Ok(v) => v,
+        Err(e) => return json_err(&format!("JSON parse: {}", e)),
+    };
+
+    let risk_score = val.get("risk_score").and_then(|r| r.as_u64()).unwrap_or(0);
+    let data_classes: Vec<&str> = val.get("data_classes")
+        .and_then(|d| d.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+    let action_type = val.get("action_type").and_then(|a| a.as_str()).unwrap_or("");
+
+    let has_high_sensitivity = data_classes.iter().any(|c|
+        *c == "credentials" || *c == "children_data" || *c == "biometric"
+    );
+    let has_medium_sensitivity = data_classes.iter().any(|c|
+        *c == "pii" || *c == "financial" || *c == "health" || *c == "legal"
+    );
+    let is_payment = action_type == "payment" || action_type == "transfer";
+
+    let tier = if risk_score >= 800 || has_high_sensitivity {
+        "maximum"
+    } else if risk_score >= 500 || has_medium_sensitivity || is_payment {
+        "elevated"
+    } else if risk_score >= 200 {
+        "standard"
+    } else {
+        "routine"
+    };
+
+    let (verification_mode, checkpoint_interval) = match tier {
+        "maximum" => ("hybrid_required", 1),
+        "elevated" => ("hybrid_required", 1),
+        "standard" => ("hybrid_preferred", 10),
+        _ => ("classical_only", 50),
+    };
+
+    to_json(&json!({
+        "tier": tier,
+        "verification_mode": verification_mode,
+        "checkpoint_interval": checkpoint_interval
+    }))
+}
+
+// ── Payload Preparation ───────────────────────────────────────────────
+
+/// Canonicalize a payload and compute its hash.
+/// Returns { canonical: "...", payload_hash: "sha256:..." }.
+#[wasm_bindgen]
+pub fn wasm_prepare_payload(payload_json: &str) -> String {
+    let val: Value = match serde_json::from_str(payload_json) {
+        Ok(v) => v,
+        Err(e) => return json_err(&format!("JSON parse: {}", e)),
+    };
+    match signed_payload::prepare_payload(&val) {
+        Ok((canonical_bytes, hash)) => {
+            let canonical_str = String::from_utf8_lossy(&canonical_bytes);
+            to_json(&json!({
+                "canonical": canonical_str,
+                "payload_hash": hash
+            }))
+        },
+        Err(e) => json_err(&e),
+    }
+}
+
+// ── Bundle Building & Signing ─────────────────────────────────────────
+
+/// Build a complete V2 CitizenshipBundle with manifest.
+#[wasm_bindgen]
+pub fn wasm_build_bundle(
+    rpr_json: &str,
+    passport_json: &str,
+    intent_json: &str,
+    policy_json: &str,
+    audit_entries_json: &str,
+    session_nonce: &str,
+) -> String {
+    let rpr: Value = match serde_json::from_str(rpr_json) {
+        Ok(v) => v, Err(e) => return json_err(&format!("RPR parse: {}", e)),
+    };
+    let passport: Value = match serde_json::from_str(passport_json) {
+        Ok(v) => v, Err(e) => return json_err(&format!("Passport parse: {}", e)),
+    };
+    let intent: Value = match serde_json::from_str(intent_json) {
+        Ok(v) => v, Err(e) => return json_err(&format!("Intent parse: {}", e)),
+    };
+    let policy: Value = match serde_json::from_str(policy_json) {
+        Ok(v) => v, Err(e) => return json_err(&format!("Policy parse: {}", e)),
+    };
+    let audit_entries: Vec<Value> = match serde_json::from_str(audit_entries_json) {
+        Ok(v) => v, Err(e) => return json_err(&format!("Audit entries parse: {}", e)),
+    };
+
+    let hash_val = |v: &Value| -> String {
+        match canonicalize_v2(v) {
+            Ok(c) => {
+                let dh = dual_hash::dual_hash_canonical(&c);
+                format!("sha256:{}", dh.sha256)
+            },
+            Err(_) => "sha256:error".to_string(),
+        }
+    };
+
+    let rpr_hash = hash_val(&rpr);
+    let passport_hash = hash_val(&passport);
+    let intent_hash = hash_val(&intent);
+    let policy_hash = hash_val(&policy);
+
+    // Compute dual Merkle root over audit entries
+    let audit_hashes: Vec<dual_hash::DualHash> = audit_entries.iter()
+        .filter_map(|e| canonicalize_v2(e).ok())
+        .map(|c| dual_hash::dual_hash_canonical(&c))
+        .collect();
+
+    let (audit_merkle_sha256, audit_merkle_sha3) = if audit_hashes.is_empty() {
+        ("sha256:".to_string() + &"0".repeat(64), "sha3-256:".to_string() + &"0".repeat(64))
+    } else {
+        let sha256_leaves: Vec<String> = audit_hashes.iter().map(|h| h.sha256.clone()).collect();
+        let sha3_leaves: Vec<String> = audit_hashes.iter().map(|h| h.sha3_256.clone()).collect();
+        (
+            format!("sha256:{}", crypto::merkle_root_from_hex_leaves(&sha256_leaves).unwrap_or_default()),
+            format!("sha3-256:{}", crypto::merkle_root_from_hex_leaves(&sha3_leaves).unwrap_or_default()),
+        )
+    };
+
+    let manifest = json!({
+        "session_nonce": session_nonce,
+        "rpr_hash": rpr_hash,
+        "passport_hash": passport_hash,
+        "intent_hash": intent_hash,
+        "policy_hash": policy_hash,
+        "audit_merkle_root": audit_merkle_sha256,
+        "audit_merkle_root_secondary": audit_merkle_sha3,
+        "audit_count": audit_entries.len(),
+        "canonicalization_profile": "dcp-jcs-v1"
+    });
+
+    let bundle = json!({
+        "dcp_bundle_version": "2.0",
+        "manifest": manifest,
+        "responsible_principal_record": { "payload": rpr, "payload_hash": rpr_hash },
+        "agent_passport": { "payload": passport, "payload_hash": passport_hash },
+        "intent": { "payload": intent, "payload_hash": intent_hash },
+        "policy_decision": { "payload": policy, "payload_hash": policy_hash },
+        "audit_entries": audit_entries
+    });
+
+    serde_json::to_string(&bundle).unwrap_or_else(|_| json_err("serialize failed"))
+}
+
+/// Sign a V2 bundle with composite signature (Ed25519 + ML-DSA-65).
+#[wasm_bindgen]
+pub fn wasm_sign_bundle(
+    bundle_json: &str,
+    classical_sk_b64: &str,
+    classical_kid: &str,
+    pq_sk_b64: &str,
+    pq_kid: &str,
+) -> String {
+    let bundle: Value = match serde_json::from_str(bundle_json) {
+        Ok(v) => v,
+        Err(e) => return json_err(&format!("Bundle parse: {}", e)),
+    };
+
+    let manifest = match bundle.get("manifest") {
+        Some(m) => m,
+        None => return json_err("Missing manifest in bundle"),
+    };
+
+    let canonical = match canonicalize_v2(manifest) {
+        Ok(c) => c,
+        Err(e) => return json_err(&e),
+    };
+
+    let manifest_hash = {
+        let dh = dual_hash::dual_hash_canonical(&canonical);
+        format!("sha256:{}", dh.sha256)
+    };
+
+    let ed = Ed25519Provider;
+    let pq = MlDsa65Provider;
+    let classical_key = CompositeKeyInfo {
+        kid: classical_kid.to_string(),
+        alg: "ed25519".to_string(),
+        secret_key_b64: classical_sk_b64.to_string(),
+        public_key_b64: String::new(),
+    };
+    let pq_key = CompositeKeyInfo {
+        kid: pq_kid.to_string(),
+        alg: "ml-dsa-65".to_string(),
+        secret_key_b64: pq_sk_b64.to_string(),
+        public_key_b64: String::new(),
+    };
+
+    let sig = match composite_sign(
+        &ed, &pq,
+        crate::v2::domain_separation::CTX_BUNDLE,
+        canonical.as_bytes(), &classical_key, &pq_key,
+    ) {
+        Ok(s) => s,
+        Err(e) => return json_err(&e.to_string()),
+    };
+
+    let signed_bundle = json!({
+        "bundle": bundle,
+        "signature": {
+            "hash_alg": "sha256",
+            "created_at": "",
+            "signer": {
+                "type": "human",
+                "kids": [classical_kid, pq_kid]
+            },
+            "manifest_hash": manifest_hash,
+            "composite_sig": sig
+        }
+    });
+
+    serde_json::to_string(&signed_bundle).unwrap_or_else(|_| json_err("serialize failed"))
+}
+
+// ── Proof of Possession ───────────────────────────────────────────────
+
+/// Generate a proof-of-possession for key registration.
+#[wasm_bindgen]
+pub fn wasm_generate_registration_pop(
+    challenge_json: &str,
+    sk_b64: &str,
+    alg: &str,
+) -> String {
+    let challenge: PopChallenge = match serde_json::from_str(challenge_json) {
+        Ok(c) => c,
+        Err(e) => return json_err(&format!("Challenge parse: {}", e)),
+    };
+    let provider = match provider_for_alg(alg) {
+        Ok(p) => p,
+        Err(e) => return json_err(&e),
+    };
+
+    match generate_registration_pop(provider.as_ref(), &challenge, sk_b64) {
+        Ok(entry) => serde_json::to_string(&entry).unwrap_or_else(|_| json_err("serialize failed")),
+        Err(e) => json_err(&e.to_string()),
+    }
+}
+
+// ── ML-KEM-768 Key Encapsulation ─────────────────────────────────────
+
+/// Generate an ML-KEM-768 keypair (encapsulation key + decapsulation key).
+#[wasm_bindgen]
+pub fn wasm_ml_kem_768_keygen() -> String {
+    use crate::providers::ml_kem_768::MlKem768Provider;
+    use crate::v2::crypto_provider::KemProvider;
+    let provider = MlKem768Provider;
+    match provider.generate_keypair() {
+        Ok(kp) => to_json(&json!({
+            "alg": "ml-kem-768",
+            "kid": kp.kid,
+            "public_key_b64": kp.public_key_b64,
+            "secret_key_b64": kp.secret_key_b64
+        })),
+        Err(e) => json_err(&e.to_string()),
+    }
+}
+
+/// Encapsulate a shared secret using an ML-KEM-768 public key.
+/// Returns { shared_secret_hex, ciphertext_b64 }.
+#[wasm_bindgen]
+pub fn wasm_ml_kem_768_encapsulate(public_key_b64: &str) -> String {
+    use crate::providers::ml_kem_768::MlKem768Provider;
+    use crate::v2::crypto_provider::KemProvider;
+    let provider = MlKem768Provider;
+    match provider.encapsulate(public_key_b64) {
+        Ok((ss, ct)) => to_json(&json!({
+            "shared_secret_hex": hex::encode(&ss),
+            "ciphertext_b64": b64::encode(&ct)
+        })),
+        Err(e) => json_err(&e.to_string()),
+    }
+}
+
+/// Decapsulate a shared secret from ciphertext using an ML-KEM-768 secret key.
+/// Returns the shared secret as hex.
+#[wasm_bindgen]
+pub fn wasm_ml_kem_768_decapsulate(ciphertext_b64: &str, secret_key_b64: &str) -> String {
+    use crate::providers::ml_kem_768::MlKem768Provider;
+    use crate::v2::crypto_provider::KemProvider;
+    let provider = MlKem768Provider;
+    let ct = match b64::decode(ciphertext_b64) {
+        Ok(b) => b,
+        Err(e) => return json_err(&format!("base64 decode: {}", e)),
+    };
+    match provider.decapsulate(&ct, secret_key_b64) {
+        Ok(ss) => hex::encode(ss),
+        Err(e) => json_err(&e.to_string()),
+    }
+}
+
+/// Verify a proof-of-possession for key registration.
+#[wasm_bindgen]
+pub fn wasm_verify_registration_pop(
+    challenge_json: &str,
+    pop_json: &str,
+    pk_b64: &str,
+    alg: &str,
+) -> String {
+    let challenge: PopChallenge = match serde_json::from_str(challenge_json) {
+        Ok(c) => c,
+        Err(e) => return json_err(&format!("Challenge parse: {}", e)),
+    };
+    let pop: SignatureEntry = match serde_json::from_str(pop_json) {
+        Ok(p) => p,
+        Err(e) => return json_err(&format!("PoP parse: {}", e)),
+    };
+    let provider = match provider_for_alg(alg) {
+        Ok(p) => p,
+        Err(e) => return json_err(&e),
+    };
+
+    match verify_registration_pop(provider.as_ref(), &challenge, &pop, pk_b64) {
+        Ok(valid) => to_json(&json!({ "valid": valid })),
+        Err(e) => json_err(&e.to_string()),
+    }
+}
@@ -0,0 +1,115 @@
+//! IndexedDB key storage bindings (`wasm-keystore`).
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use super::{json_err, to_json};
+
+// ── Key Storage (IndexedDB) ───────────────────────────────────────────
+
+#[wasm_bindgen(inline_js = r#"
+const DB = 'dcp-ai-keystore';
+const STORE = 'keys';
+function openDb() {
+  return new Promise((resolve, reject) => {
+    const req = indexedDB.open(DB, 1);
+    req.onupgradeneeded = () => req.result.createObjectStore(STORE);
+    req.onsuccess = () => resolve(req.result);
+    req.onerror = () => reject(req.error);
+  });
+}
+function run(mode, op) {
+  return openDb().then((db) => new Promise((resolve, reject) => {
+    const tx = db.transaction(STORE, mode);
+    const req = op(tx.objectStore(STORE));
+    tx.oncomplete = () => { db.close(); resolve(req.result === undefined ? null : req.result); };
+    tx.onerror = () => { db.close(); reject(tx.error); };
+  }));
+}
+export function dcp_idb_put(name, value) { return run('readwrite', (s) => s.put(value, name)); }
+export function dcp_idb_get(name) { return run('readonly', (s) => s.get(name)); }
+export function dcp_idb_delete(name) { return run('readwrite', (s) => s.delete(name)); }
+"#)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    async fn dcp_idb_put(name: &str, value: &str) -> Result<JsValue, JsValue>;
+    #[wasm_bindgen(catch)]
+    async fn dcp_idb_get(name: &str) -> Result<JsValue, JsValue>;
+    #[wasm_bindgen(catch)]
+    async fn dcp_idb_delete(name: &str) -> Result<JsValue, JsValue>;
+}
+
+fn js_err(e: JsValue) -> String {
+    json_err(&e.as_string().unwrap_or_else(|| format!("{:?}", e)))
+}
+
+/// Seal a keypair under a passphrase (Argon2id + AES-256-GCM) and persist
+/// it in IndexedDB under `name`. Input: `{alg, public_key_b64, secret_key_b64}`
+/// as returned by the keypair generators. The secret never touches
+/// localStorage or any other plaintext store.
+#[wasm_bindgen]
+pub async fn wasm_store_keypair(name: String, keypair_json: String, passphrase: String) -> String {
+    use crate::keystore::{seal_secret_key, KdfParams};
+    let kp: Value = match serde_json::from_str(&keypair_json) {
+        Ok(v) => v,
+        Err(e) => return json_err(&format!("JSON parse: {}", e)),
+    };
+    let field = |k: &str| kp.get(k).and_then(|v| v.as_str()).unwrap_or("").to_string();
+    let (alg, pk, sk) = (field("alg"), field("public_key_b64"), field("secret_key_b64"));
+    if pk.is_empty() || sk.is_empty() {
+        return json_err("keypair must contain public_key_b64 and secret_key_b64");
+    }
+    let sealed = match seal_secret_key(&alg, &pk, &sk, &passphrase, KdfParams::default()) {
+        Ok(s) => s,
+        Err(e) => return json_err(&e.to_string()),
+    };
+    let record = match serde_json::to_string(&sealed) {
+        Ok(r) => r,
+        Err(e) => return json_err(&e.to_string()),
+    };
+    match dcp_idb_put(&name, &record).await {
+        Ok(_) => to_json(&json!({
+            "stored": true,
+            "name": name,
+            "alg": alg,
+            "public_key_b64": pk
+        })),
+        Err(e) => js_err(e),
+    }
+}
+
+/// Load and unseal a keypair stored with `wasm_store_keypair`.
+/// Returns `{alg, public_key_b64, secret_key_b64}` or `{error}`. Records
+/// whose KDF parameters exceed `KdfParams::WASM_MAX` are refused.
+#[wasm_bindgen]
+pub async fn wasm_load_keypair(name: String, passphrase: String) -> String {
+    use crate::keystore::{open_sealed_key_with_max, KdfParams, SealedKey};
+    let record = match dcp_idb_get(&name).await {
+        Ok(v) => match v.as_string() {
+            Some(s) => s,
+            None => return json_err(&format!("No key stored under {}", name)),
+        },
+        Err(e) => return js_err(e),
+    };
+    let sealed: SealedKey = match serde_json::from_str(&record) {
+        Ok(s) => s,
+        Err(e) => return json_err(&format!("Keystore record parse: {}", e)),
+    };
+    match open_sealed_key_with_max(&sealed, &passphrase, KdfParams::WASM_MAX) {
+        Ok(sk) => to_json(&json!({
+            "alg": sealed.alg,
+            "public_key_b64": sealed.public_key_b64,
+            "secret_key_b64": sk.as_str()
+        })),
+        Err(e) => json_err(&e.to_string()),
+    }
+}
+
+/// Delete a stored keypair. Returns `{"deleted": true}` or `{error}`.
+#[wasm_bindgen]
+pub async fn wasm_delete_keypair(name: String) -> String {
+    match dcp_idb_delete(&name).await {
+        Ok(_) => "{\"deleted\":true}".to_string(),
+        Err(e) => js_err(e),
+    }
+}
@@ -0,0 +1,299 @@
+//! v1 bundle builder + signer, canonicalization version tagging (Rust).
+
+mod common;
+
+use serde_json::json;
+
+use dcp_ai::bundle::BundleBuilder;
+use dcp_ai::crypto::CANON_V1;
+use dcp_ai::verify::verify_signed_bundle;
+
+use common::signed_value;
+
+#[test]
+fn builder_output_verifies() {
+    let sb = signed_value();
+    let result = verify_signed_bundle(&sb, None);
+    assert!(result.verified, "{:?}", result.errors);
+    assert_eq!(sb["bundle"]["audit_entries"][0]["prev_hash"], "GENESIS");
+}
+
+#[test]
+fn builder_requires_all_artifacts() {
+    assert_eq!(BundleBuilder::new().build().unwrap_err(), "Missing responsible_principal_record");
+}
+
+#[test]
+fn signer_records_canonicalization_version() {
+    let sb = signed_value();
+    assert_eq!(sb["signature"]["canon"], CANON_V1);
+}
+
+#[test]
+fn declared_canonicalization_hashes_every_artifact() {
+    use dcp_ai::crypto::{hash_object, intent_hash, Canon, Canonicalizer};
+
+    let sb = signed_value();
+    let canon = Canon::parse(sb["signature"]["canon"].as_str().unwrap()).unwrap();
+    assert_eq!((canon, canon.as_str()), (Canon::V1, CANON_V1));
+    assert_eq!(Canon::parse("dcp-c14n/99"), None);
+
+    let mut buffers = Canonicalizer::new();
+    let intent = &sb["bundle"]["intent"];
+    assert_eq!(buffers.intent_hash_in(canon, intent), intent_hash(intent));
+    assert_eq!(sb["bundle"]["audit_entries"][0]["intent_hash"], intent_hash(intent));
+    let entry = &sb["bundle"]["audit_entries"][0];
+    assert_eq!(sb["bundle"]["audit_entries"][1]["prev_hash"], buffers.hash_in(canon, entry));
+    assert_eq!(buffers.hash_in(canon, entry), hash_object(entry));
+}
+
+#[test]
+fn bundles_without_canon_field_still_verify() {
+    let mut sb = signed_value();
+    sb["signature"].as_object_mut().unwrap().remove("canon");
+    assert!(verify_signed_bundle(&sb, None).verified);
+}
+
+#[test]
+fn unknown_canonicalization_is_rejected() {
+    let mut sb = signed_value();
+    sb["signature"]["canon"] = json!("dcp-c14n/99");
+    let result = verify_signed_bundle(&sb, None);
+    assert!(!result.verified);
+    assert_eq!(result.errors.unwrap()[0], "UNSUPPORTED CANONICALIZATION: dcp-c14n/99");
+}
+
+#[test]
+fn declared_leaf_order_sets_merkle_root() {
+    use dcp_ai::bundle::{apply_patch, SignOptions};
+    use dcp_ai::crypto::generate_keypair;
+    use dcp_ai::merkle::LeafOrder;
+    use dcp_ai::types::SignedBundle;
+
+    let typed: SignedBundle = serde_json::from_value(signed_value()).unwrap();
+    let (_, sk) = generate_keypair();
+    let mut sign = SignOptions::new(&sk);
+    sign.leaf_order = Some(LeafOrder::AuditId);
+    // The first entry in the chain becomes the last by audit_id.
+    let rename = json!([{"op": "replace", "path": "/audit_entries/0/audit_id", "value": "audit999"}]);
+    let sb = serde_json::to_value(apply_patch(&typed, &rename, &sign).unwrap()).unwrap();
+    assert_eq!(sb["signature"]["leaf_order"], "audit_id");
+    let result = verify_signed_bundle(&sb, None);
+    assert!(result.verified, "{:?}", result.errors);
+
+    let mut relabelled = sb.clone();
+    relabelled["signature"]["leaf_order"] = json!("chain");
+    assert_eq!(verify_signed_bundle(&relabelled, None).errors.unwrap()[0], "MERKLE ROOT MISMATCH");
+    relabelled["signature"]["leaf_order"] = json!("random");
+    assert_eq!(verify_signed_bundle(&relabelled, None).errors.unwrap()[0], "UNSUPPORTED LEAF ORDER: random");
+}
+
+#[test]
+fn normalize_converges_producer_variants() {
+    use dcp_ai::bundle::normalize;
+    use dcp_ai::crypto::hash_object;
+
+    let mut a = json!({
+        "intent_id": " intent-1 ",
+        "timestamp": "2026-01-01T02:30:00.250+02:00",
+        "expires_at": null,
+        "target": { "channel": "api", "to": null },
+    });
+    let mut b = json!({
+        "intent_id": "intent-1",
+        "timestamp": "2026-01-01T00:30:00.250Z",
+        "target": { "channel": "api" },
+    });
+    normalize(&mut a);
+    normalize(&mut b);
+    assert_eq!(a, b);
+    assert_eq!(hash_object(&a), hash_object(&b));
+}
+
+#[test]
+fn normalize_handles_day_rollover_and_keeps_nulls_when_asked() {
+    use dcp_ai::bundle::{normalize_with, NormalizeOptions};
+
+    let mut opts = NormalizeOptions::default();
+    opts.drop_nulls = false;
+    let mut v = json!({ "created_at": "2025-12-31T22:00:00-05:00", "not_before": null, "note": " x " });
+    normalize_with(&mut v, &opts);
+    assert_eq!(v["created_at"], "2026-01-01T03:00:00Z");
+    assert!(v["not_before"].is_null());
+    assert_eq!(v["note"], " x ");
+}
+
+#[test]
+fn observers_see_entries_and_finalized_bundle() {
+    use std::sync::{Arc, Mutex};
+
+    use dcp_ai::bundle::AuditObserver;
+    use dcp_ai::types::{AuditEntry, CitizenshipBundle};
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl AuditObserver for Recorder {
+        fn on_entry_appended(&self, entry: &AuditEntry) {
+            self.0.lock().unwrap().push(entry.audit_id.clone());
+        }
+        fn on_bundle_finalized(&self, bundle: &CitizenshipBundle) {
+            self.0.lock().unwrap().push(format!("final:{}", bundle.audit_entries.len()));
+        }
+    }
+
+    let sample = common::sample_bundle(&common::any_key());
+    let recorder = Arc::new(Recorder::default());
+    let mut builder = BundleBuilder::new()
+        .observer(recorder.clone())
+        .responsible_principal_record(sample.responsible_principal_record)
+        .agent_passport(sample.agent_passport)
+        .intent(sample.intent)
+        .policy_decision(sample.policy_decision);
+    for entry in sample.audit_entries {
+        builder = builder.add_audit_entry(entry);
+    }
+    builder.build().unwrap();
+
+    assert_eq!(*recorder.0.lock().unwrap(), ["audit001", "audit002", "audit003", "final:3"]);
+}
+
+#[test]
+fn replaying_events_rebuilds_the_bundle() {
+    use dcp_ai::bundle::{from_events, AuditEntryFields, DcpEvent};
+
+    let original = common::sample_bundle(&common::any_key());
+    let mut events = vec![
+        DcpEvent::PrincipalRegistered(original.responsible_principal_record.clone()),
+        DcpEvent::PassportIssued(original.agent_passport.clone()),
+        DcpEvent::IntentDeclared(original.intent.clone()),
+        DcpEvent::DecisionMade(original.policy_decision.clone()),
+    ];
+    events.extend(original.audit_entries.iter().map(|e| {
+        DcpEvent::ActionExecuted(AuditEntryFields {
+            audit_id: e.audit_id.clone(),
+            timestamp: e.timestamp.clone(),
+            agent_id: e.agent_id.clone(),
+            human_id: e.human_id.clone(),
+            policy_decision: e.policy_decision.clone(),
+            outcome: e.outcome.clone(),
+            evidence: e.evidence.clone(),
+        })
+    }));
+
+    let replayed = from_events(events.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&replayed).unwrap(), serde_json::to_value(&original).unwrap());
+
+    events.swap(2, 4);
+    let err = from_events(events).unwrap_err();
+    assert!(err.contains("Intent must be set"), "{}", err);
+}
+
+#[test]
+fn keys_and_signatures_are_validated_on_deserialize() {
+    use dcp_ai::types::{PublicKeyB64, SignatureB64, SignedBundle};
+
+    let sb = signed_value();
+    let typed: SignedBundle = serde_json::from_value(sb.clone()).unwrap();
+    let key: &PublicKeyB64 = &typed.signature.signer.public_key_b64;
+    assert!(key.verify(&sb["bundle"], &typed.signature.sig_b64).unwrap());
+
+    // A signature where the key belongs is rejected before any crypto runs.
+    let mut swapped = sb.clone();
+    swapped["signature"]["signer"]["public_key_b64"] = sb["signature"]["sig_b64"].clone();
+    assert!(serde_json::from_value::<SignedBundle>(swapped).is_err());
+
+    assert!("not base64!".parse::<SignatureB64>().is_err());
+    assert_eq!(SignatureB64::default().to_string(), "");
+}
+
+#[test]
+fn amended_intent_lineage_verifies() {
+    use dcp_ai::bundle::{sign_bundle, AuditEntryFields, SignOptions};
+    use dcp_ai::crypto::generate_keypair;
+    use dcp_ai::types::AuditEvidence;
+
+    let (pk, sk) = generate_keypair();
+    let base = common::sample_bundle(&pk);
+    let fields = |id: &str, minute: u32| AuditEntryFields {
+        audit_id: id.into(),
+        timestamp: format!("2026-01-01T01:{:02}:00Z", minute),
+        agent_id: "did:agent:a".into(),
+        human_id: "did:human:alice".into(),
+        policy_decision: "approved".into(),
+        outcome: "email_sent".into(),
+        evidence: AuditEvidence { tool: Some("smtp".into()), result_ref: None, tombstone: None },
+    };
+    let changes = json!({ "estimated_impact": "medium" }).as_object().unwrap().clone();
+    let bundle = BundleBuilder::new()
+        .responsible_principal_record(base.responsible_principal_record)
+        .agent_passport(base.agent_passport)
+        .intent(base.intent)
+        .policy_decision(base.policy_decision)
+        .create_audit_entry(fields("audit001", 1))
+        .unwrap()
+        .amend_intent("amend001", changes, fields("audit002", 2), &sk)
+        .unwrap()
+        .create_audit_entry(fields("audit003", 3))
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(bundle.intent_amendments.len(), 1);
+    assert_ne!(bundle.audit_entries[1].intent_hash, bundle.audit_entries[2].intent_hash);
+
+    let signed = serde_json::to_value(sign_bundle(bundle, &SignOptions::new(&sk)).unwrap()).unwrap();
+    let result = verify_signed_bundle(&signed, None);
+    assert!(result.verified, "{:?}", result.errors);
+
+    let mut forged = signed["bundle"].clone();
+    forged["intent_amendments"][0]["changes"]["estimated_impact"] = "low".into();
+    let errors = verify_signed_bundle(&common::resign(&forged), None).errors.unwrap();
+    assert!(errors[0].starts_with("AMENDMENT INVALID"), "{:?}", errors);
+
+    let mut unannounced = signed["bundle"].clone();
+    unannounced["audit_entries"][1]["evidence"]["result_ref"] = json!(null);
+    let errors = verify_signed_bundle(&common::resign(&unannounced), None).errors.unwrap();
+    assert!(errors[0].contains("out of order"), "{:?}", errors);
+}
+
+#[test]
+fn patched_bundle_relinks_hashes_and_records_lineage() {
+    use dcp_ai::bundle::{apply_patch, sign_bundle, SignOptions};
+    use dcp_ai::crypto::generate_keypair;
+
+    let (pk, sk) = generate_keypair();
+    let signed = sign_bundle(common::sample_bundle(&pk), &SignOptions::new(&sk)).unwrap();
+
+    let fix = json!([
+        {"op": "add", "path": "/responsible_principal_record/contact", "value": "alice@example.com"},
+        {"op": "replace", "path": "/intent/estimated_impact", "value": "medium"},
+        {"op": "replace", "path": "/audit_entries/0/outcome", "value": "policy_checked"},
+    ]);
+    let patched = apply_patch(&signed, &fix, &SignOptions::new(&sk)).unwrap();
+    let result = verify_signed_bundle(&serde_json::to_value(&patched).unwrap(), None);
+    assert!(result.verified, "{:?}", result.errors);
+    assert_ne!(patched.bundle.audit_entries[0].intent_hash, signed.bundle.audit_entries[0].intent_hash);
+    assert_eq!(patched.bundle.patches.len(), 1);
+    assert_eq!(patched.bundle.patches[0].prev_bundle_hash, signed.signature.bundle_hash);
+    assert_eq!(patched.bundle.patches[0].operations, fix);
+
+    let rewrite = json!([{"op": "remove", "path": "/patches/0"}]);
+    assert!(apply_patch(&patched, &rewrite, &SignOptions::new(&sk)).is_err());
+    let failing = json!([{"op": "test", "path": "/intent/estimated_impact", "value": "low"}]);
+    assert!(apply_patch(&patched, &failing, &SignOptions::new(&sk)).unwrap_err().contains("test of"));
+}
+
+#[test]
+fn typed_bundle_verifies_like_its_json() {
+    use dcp_ai::types::SignedBundle;
+    use dcp_ai::verify::verify_signed_bundle_typed;
+
+    let mut typed: SignedBundle = serde_json::from_value(signed_value()).unwrap();
+    assert!(verify_signed_bundle_typed(&typed, None).verified);
+
+    typed.bundle.intent.action_type = "send_payment".into();
+    let result = verify_signed_bundle_typed(&typed, None);
+    let value = serde_json::to_value(&typed).unwrap();
+    assert_eq!(result.errors, verify_signed_bundle(&value, None).errors);
+    assert!(!result.verified);
+}
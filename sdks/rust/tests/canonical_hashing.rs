@@ -0,0 +1,107 @@
+//! Typed canonical hashing with set-valued field normalization (Rust).
+
+use serde_json::json;
+
+use dcp_ai::crypto::{hash_object, hash_object_normalized, hash_typed, normalize_set_fields};
+use dcp_ai::types::{Intent, IntentTarget};
+
+fn intent(data_classes: &[&str]) -> Intent {
+    Intent::new(
+        "intent001",
+        "did:agent:agent123",
+        "did:human:alice123",
+        "2026-01-01T01:00:00Z",
+        "send_email",
+        IntentTarget {
+            channel: "email".into(),
+            to: Some("bob@example.com".into()),
+            domain: None,
+            url: None,
+            salt_id: None,
+        },
+        "medium",
+    )
+    .with_data_classes(data_classes.iter().map(|s| s.to_string()).collect())
+}
+
+#[test]
+fn data_classes_order_does_not_affect_typed_hash() {
+    let a = hash_typed(&intent(&["pii", "financial"])).unwrap();
+    let b = hash_typed(&intent(&["financial", "pii"])).unwrap();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn raw_hash_remains_order_sensitive() {
+    let a = json!({"data_classes": ["pii", "financial"]});
+    let b = json!({"data_classes": ["financial", "pii"]});
+    assert_ne!(hash_object(&a), hash_object(&b));
+    assert_eq!(hash_object_normalized(&a), hash_object_normalized(&b));
+}
+
+#[test]
+fn normalization_dedups_and_only_touches_set_fields() {
+    let mut v = json!({
+        "data_classes": ["pii", "contact_info", "pii"],
+        "reasons": ["b", "a"],
+        "nested": {"data_classes": ["z", "a"]}
+    });
+    normalize_set_fields(&mut v);
+    assert_eq!(v["data_classes"], json!(["contact_info", "pii"]));
+    assert_eq!(v["reasons"], json!(["b", "a"]));
+    assert_eq!(v["nested"]["data_classes"], json!(["a", "z"]));
+}
+
+#[test]
+fn estimated_impact_accepts_text_and_structured_forms() {
+    use dcp_ai::types::{Impact, ImpactAssessment, ImpactCategory, ImpactMagnitude};
+    use dcp_ai::v2::security_tier::{compute_security_tier, SecurityTier};
+
+    // v1.0 text round-trips unchanged, so its hash does too.
+    let text = serde_json::to_value(intent(&[])).unwrap();
+    assert_eq!(text["estimated_impact"], json!("medium"));
+    let reparsed: Intent = serde_json::from_value(text.clone()).unwrap();
+    assert_eq!(hash_object(&serde_json::to_value(&reparsed).unwrap()), hash_object(&text));
+    assert_eq!(reparsed.estimated_impact.magnitude(), Some(ImpactMagnitude::Medium));
+
+    let mut structured = intent(&[]);
+    structured.estimated_impact = Impact::from(ImpactAssessment {
+        categories: vec![ImpactCategory::Financial, ImpactCategory::Safety],
+        magnitude: ImpactMagnitude::High,
+        affected_parties: Some(12),
+    });
+    let value = serde_json::to_value(&structured).unwrap();
+    assert_eq!(value["estimated_impact"]["categories"], json!(["financial", "safety"]));
+    let reparsed: Intent = serde_json::from_value(value.clone()).unwrap();
+    assert_eq!(reparsed.estimated_impact, structured.estimated_impact);
+    assert_eq!(reparsed.estimated_impact.to_string(), "high (financial, safety; 12 parties)");
+
+    assert_eq!(compute_security_tier(&text), SecurityTier::Routine);
+    assert_eq!(compute_security_tier(&value), SecurityTier::Maximum);
+}
+
+#[test]
+fn null_members_are_kept_unless_treated_as_absent() {
+    use dcp_ai::crypto::{canonicalize, canonicalize_opts, CanonicalizeOptions};
+
+    let v = json!({ "a": 1, "contact": null, "list": [1, null] });
+    assert_eq!(canonicalize(&v), r#"{"a":1,"contact":null,"list":[1,null]}"#);
+    let mut opts = CanonicalizeOptions::default();
+    opts.treat_null_as_absent = true;
+    assert_eq!(canonicalize_opts(&v, &opts), r#"{"a":1,"list":[1,null]}"#);
+}
+
+#[test]
+fn buffered_canonicalizer_matches_canonicalize() {
+    use dcp_ai::crypto::{canonicalize, Canonicalizer};
+
+    let mut c = Canonicalizer::new();
+    for v in [
+        json!({ "b": [1, { "z": null, "a": "é\n" }], "a": 2.5 }),
+        serde_json::to_value(intent(&["pii"])).unwrap(),
+        json!([]),
+    ] {
+        assert_eq!(c.canonicalize(&v), canonicalize(&v));
+        assert_eq!(c.hash(&v), hash_object(&v));
+    }
+}
@@ -0,0 +1,118 @@
+//! Shared fixtures for integration tests: a freshly built and signed v1
+//! bundle whose keys are known to the test, and the conformance fixtures
+//! shared with the other SDKs.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde_json::Value;
+
+use dcp_ai::bundle::{sign_bundle, AuditEntryFields, BundleBuilder, SignOptions};
+use dcp_ai::crypto::generate_keypair;
+use dcp_ai::types::*;
+
+/// `tests/conformance` at the repository root.
+pub fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("tests")
+        .join("conformance")
+}
+
+/// The signed v1 conformance bundle, verifiable with its embedded key.
+pub fn load_signed_bundle() -> Value {
+    let path = fixtures_dir()
+        .join("examples")
+        .join("citizenship_bundle.signed.json");
+    let data = fs::read_to_string(&path).expect("signed bundle not found");
+    serde_json::from_str(&data).expect("invalid JSON in signed bundle")
+}
+
+/// A well-formed public key for tests that never verify with it.
+pub fn any_key() -> String {
+    generate_keypair().0
+}
+
+pub fn sample_bundle(agent_pk: &str) -> CitizenshipBundle {
+    build_sample(agent_pk, false)
+}
+
+/// [`sample_bundle`] with every optional field the fixture leaves empty
+/// filled in, so its serialized form carries no `null` members.
+pub fn sample_bundle_without_nulls(agent_pk: &str) -> CitizenshipBundle {
+    build_sample(agent_pk, true)
+}
+
+fn build_sample(agent_pk: &str, without_nulls: bool) -> CitizenshipBundle {
+    let mut rpr = ResponsiblePrincipalRecord::new(
+        "did:human:alice",
+        "Alice Example",
+        "natural_person",
+        "US",
+        "owner_responsible",
+        true,
+        "2026-01-01T00:00:00Z",
+    );
+    if without_nulls {
+        rpr = rpr.with_expires_at("2027-01-01T00:00:00Z");
+    }
+    let passport = AgentPassport::new(
+        "did:agent:a",
+        agent_pk.parse::<PublicKeyB64>().expect("agent public key"),
+        "did:human:alice",
+        "2026-01-01T00:10:00Z",
+    )
+    .with_capabilities(vec!["email".into()])
+    .with_risk_tier("low");
+    let intent = Intent::new(
+        "intent001",
+        "did:agent:a",
+        "did:human:alice",
+        "2026-01-01T01:00:00Z",
+        "send_email",
+        IntentTarget { channel: "email".into(), to: Some("bob@example.com".into()), domain: None, url: None, salt_id: None },
+        "low",
+    )
+    .with_data_classes(vec!["contact_info".into()]);
+    let decision = PolicyDecision::new("intent001", "approve", 0.1, vec!["low_risk".into()]);
+    let mut builder = BundleBuilder::new()
+        .responsible_principal_record(rpr)
+        .agent_passport(passport)
+        .intent(intent)
+        .policy_decision(decision);
+    for (i, outcome) in ["policy_approved", "email_sent", "done"].iter().enumerate() {
+        builder = builder
+            .create_audit_entry(AuditEntryFields {
+                audit_id: format!("audit{:03}", i + 1),
+                timestamp: format!("2026-01-01T01:0{}:00Z", i + 1),
+                agent_id: "did:agent:a".into(),
+                human_id: "did:human:alice".into(),
+                policy_decision: "approved".into(),
+                outcome: outcome.to_string(),
+                evidence: AuditEvidence {
+                    tool: Some("smtp".into()),
+                    result_ref: without_nulls.then(|| format!("smtp:msg-{}", i + 1)),
+                    tombstone: None,
+                },
+            })
+            .unwrap();
+    }
+    builder.build().unwrap()
+}
+
+pub fn signed_value() -> Value {
+    let (pk, sk) = generate_keypair();
+    let signed = sign_bundle(sample_bundle(&pk), &SignOptions::new(&sk)).unwrap();
+    serde_json::to_value(&signed).unwrap()
+}
+
+/// Re-sign a (possibly tampered) bundle value so only the targeted check
+/// fails, not the signature.
+pub fn resign(bundle: &Value) -> Value {
+    let (_, sk) = generate_keypair();
+    let typed: CitizenshipBundle = serde_json::from_value(bundle.clone()).unwrap();
+    serde_json::to_value(sign_bundle(typed, &SignOptions::new(&sk)).unwrap()).unwrap()
+}
@@ -4,25 +4,20 @@
 //! 2. Golden canonical vectors match across all SDKs
 //! 3. Dual-hash chain (SHA-256 + SHA3-256) produces expected results
 
+mod common;
+
 use std::fs;
-use std::path::PathBuf;
 
 use serde_json::{json, Value};
 use sha2::{Digest as Sha2Digest, Sha256};
-use sha3::{Digest as Sha3Digest, Sha3_256};
+use sha3::Sha3_256;
 
-use dcp_ai::crypto::{canonicalize, hash_object, merkle_root_from_hex_leaves, verify_object};
+use dcp_ai::crypto::{canonicalize, hash_object, merkle_root_from_hex_leaves};
 use dcp_ai::v2::canonicalize::{assert_no_floats, canonicalize_v2};
 use dcp_ai::v2::dual_hash::{dual_hash, dual_hash_canonical, sha256_hex, sha3_256_hex};
 use dcp_ai::verify::verify_signed_bundle;
 
-fn fixtures_dir() -> PathBuf {
-    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("..")
-        .join("..")
-        .join("tests")
-        .join("conformance")
-}
+use common::{fixtures_dir, load_signed_bundle};
 
 fn load_golden_vectors() -> Value {
     let path = fixtures_dir().join("v2").join("golden_vectors.json");
@@ -30,14 +25,6 @@ fn load_golden_vectors() -> Value {
     serde_json::from_str(&data).expect("invalid JSON in golden_vectors.json")
 }
 
-fn load_signed_bundle() -> Value {
-    let path = fixtures_dir()
-        .join("examples")
-        .join("citizenship_bundle.signed.json");
-    let data = fs::read_to_string(&path).expect("signed bundle not found");
-    serde_json::from_str(&data).expect("invalid JSON in signed bundle")
-}
-
 // ---------------------------------------------------------------------------
 // 1. V1 Bundle Verification (backward compatibility)
 // ---------------------------------------------------------------------------
@@ -107,7 +94,7 @@ fn v1_merkle_root_matches_golden_vector() {
         .as_str()
         .unwrap();
     let entries = sb["bundle"]["audit_entries"].as_array().unwrap();
-    let leaves: Vec<String> = entries.iter().map(|e| hash_object(e)).collect();
+    let leaves: Vec<String> = entries.iter().map(hash_object).collect();
     let root = merkle_root_from_hex_leaves(&leaves).unwrap();
     assert_eq!(format!("sha256:{}", root), expected);
 }
@@ -220,7 +207,7 @@ fn v2_canonical_rejects_floats() {
 
 #[test]
 fn v2_canonical_rejects_nested_floats() {
-    let val = json!({"outer": {"inner": 3.14}});
+    let val = json!({"outer": {"inner": 2.5}});
     assert!(canonicalize_v2(&val).is_err());
 }
 
@@ -291,6 +278,32 @@ fn audit_entry_hashes_match_golden() {
     }
 }
 
+/// The checked-in `golden/` fixtures are what `generate_fixtures` builds
+/// from the golden seed, byte for byte. A protocol change that alters them
+/// must regenerate them with `dcp gen-fixtures` and commit the diff.
+#[test]
+fn golden_fixtures_match_checked_in_files() {
+    use dcp_ai::fixtures::{generate_fixtures, GOLDEN_SEED};
+
+    let set = generate_fixtures(&GOLDEN_SEED).unwrap();
+    let dir = fixtures_dir().join("golden");
+    let read = |file: &str| fs::read_to_string(dir.join(file)).unwrap_or_else(|e| panic!("{}: {}", file, e));
+    let pretty = |value: &Value| serde_json::to_string_pretty(value).unwrap() + "\n";
+    for fixture in &set.fixtures {
+        let file = format!("{}.json", fixture.name);
+        let text = read(&file);
+        assert_eq!(text, pretty(&fixture.value), "{} changed; rerun `dcp gen-fixtures`", file);
+        let parsed: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(hash_object(&parsed), fixture.canonical_sha256, "{}", file);
+    }
+    let expected: Value = serde_json::from_str(&read("expected_hashes.json")).unwrap();
+    assert_eq!(expected, set.expected_hashes(), "expected_hashes.json changed; rerun `dcp gen-fixtures`");
+
+    let signed: Value = serde_json::from_str(&read("citizenship_bundle.signed.json")).unwrap();
+    let result = verify_signed_bundle(&signed, expected["human_public_key_b64"].as_str());
+    assert!(result.verified, "{:?}", result.errors);
+}
+
 // ---------------------------------------------------------------------------
 // 3. Dual-Hash Chain Tests
 // ---------------------------------------------------------------------------
@@ -398,16 +411,14 @@ fn dual_hash_chain_integrity() {
     let entries = sb["bundle"]["audit_entries"].as_array().unwrap();
 
     let mut prev_sha256 = "GENESIS".to_string();
-    let mut prev_sha3 = "GENESIS".to_string();
 
     for (i, entry) in entries.iter().enumerate() {
         assert_eq!(entry["prev_hash"].as_str().unwrap(), prev_sha256);
         let canon = canonicalize(entry);
         let dh = dual_hash_canonical(&canon);
         prev_sha256 = dh.sha256;
-        prev_sha3 = dh.sha3_256;
         assert_eq!(prev_sha256, expected_chain[i + 1].as_str().unwrap());
-        assert_eq!(prev_sha3, dual_hashes[i]["sha3_256"].as_str().unwrap());
+        assert_eq!(dh.sha3_256, dual_hashes[i]["sha3_256"].as_str().unwrap());
     }
 }
 
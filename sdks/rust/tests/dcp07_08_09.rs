@@ -347,7 +347,7 @@ fn significance_scoring_bounds() {
 #[test]
 fn should_notify_human_rules() {
     let rule = json!({"dimension": "significance", "operator": "gt", "value": 500, "action_if_triggered": "notify"});
-    let trigger = should_notify_human(600.0, &[rule.clone()]);
+    let trigger = should_notify_human(600.0, std::slice::from_ref(&rule));
     assert!(trigger.notify);
     assert_eq!(trigger.actions, vec!["notify"]);
 
@@ -0,0 +1,31 @@
+//! Signatures over externally computed artifact digests.
+
+use sha2::{Digest, Sha256};
+
+use dcp_ai::crypto::{generate_keypair, sign_digest};
+use dcp_ai::{DetachedSignature, PublicKeyB64, Signer};
+
+#[test]
+fn detached_signature_round_trip() {
+    let (pk, sk) = generate_keypair();
+    let artifact = vec![7u8; 1 << 20];
+    let digest: [u8; 32] = Sha256::digest(&artifact).into();
+    let signer = Signer {
+        signer_type: "agent".into(),
+        id: "agent:storage-sync".into(),
+        public_key_b64: PublicKeyB64::parse(&pk).unwrap(),
+    };
+
+    let detached = DetachedSignature::sign(&digest, signer, &sk).unwrap();
+    assert_eq!(detached.hash_ref, format!("sha256:{}", hex::encode(digest)));
+    let json = serde_json::to_value(&detached).unwrap();
+    let parsed: DetachedSignature = serde_json::from_value(json).unwrap();
+    assert!(parsed.verify(&digest, None).unwrap());
+
+    let mut other = digest;
+    other[0] ^= 1;
+    assert!(!parsed.verify(&other, None).unwrap());
+    let (stranger, _) = generate_keypair();
+    assert!(!parsed.verify(&digest, Some(&stranger)).unwrap());
+    assert_eq!(parsed.sig_b64, sign_digest(&digest, &sk).unwrap().as_str());
+}
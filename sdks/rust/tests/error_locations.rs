@@ -0,0 +1,88 @@
+//! JSON Pointer locations on verification failures (Rust).
+
+mod common;
+
+use serde_json::json;
+
+use dcp_ai::verify::verify_signed_bundle;
+
+use common::{resign, signed_value};
+
+#[test]
+fn chain_break_points_at_prev_hash() {
+    let mut sb = signed_value();
+    sb["bundle"]["audit_entries"][2]["prev_hash"] = json!("deadbeef");
+    let sb = resign(&sb["bundle"]);
+    let result = verify_signed_bundle(&sb, None);
+    assert!(!result.verified);
+    let failure = &result.failures.unwrap()[0];
+    assert_eq!(failure.pointer, "/bundle/audit_entries/2/prev_hash");
+    assert_eq!(failure.actual.as_deref(), Some("deadbeef"));
+    assert_eq!(failure.expected.as_ref().map(String::len), Some(64));
+}
+
+#[test]
+fn intent_hash_mismatch_points_at_entry() {
+    let mut sb = signed_value();
+    sb["bundle"]["audit_entries"][1]["intent_hash"] = json!("00");
+    let sb = resign(&sb["bundle"]);
+    let failure = &verify_signed_bundle(&sb, None).failures.unwrap()[0];
+    assert_eq!(failure.pointer, "/bundle/audit_entries/1/intent_hash");
+}
+
+#[test]
+fn signature_and_hash_failures_point_into_signature_block() {
+    let mut sb = signed_value();
+    sb["bundle"]["intent"]["action_type"] = json!("tampered");
+    let failure = &verify_signed_bundle(&sb, None).failures.unwrap()[0];
+    assert_eq!(failure.pointer, "/signature/sig_b64");
+
+    let mut sb = signed_value();
+    sb["signature"]["merkle_root"] = json!(format!("sha256:{}", "0".repeat(64)));
+    let failure = &verify_signed_bundle(&sb, None).failures.unwrap()[0];
+    assert_eq!(failure.pointer, "/signature/merkle_root");
+    assert_eq!(failure.actual.as_deref(), Some("0".repeat(64).as_str()));
+}
+
+#[test]
+fn string_errors_are_kept() {
+    let result = verify_signed_bundle(&json!({"signature": {}}), None);
+    assert_eq!(result.errors.unwrap(), vec!["Missing bundle".to_string()]);
+    assert_eq!(result.failures.unwrap()[0].pointer, "/bundle");
+}
+
+#[test]
+fn chain_break_has_structured_kind() {
+    use dcp_ai::types::VerifyError;
+
+    let mut sb = signed_value();
+    sb["bundle"]["audit_entries"][2]["prev_hash"] = json!("deadbeef");
+    let result = verify_signed_bundle(&resign(&sb["bundle"]), None);
+    assert_eq!(result.error_kinds.unwrap()[0], VerifyError::ChainBreak { entry: 2 });
+    let failure = &result.failures.unwrap()[0];
+    assert_eq!(failure.message, failure.kind.message(failure.message.split_once(": ").unwrap().1));
+    let wire = serde_json::to_value(failure).unwrap();
+    assert_eq!((wire["kind"].as_str(), wire["entry"].as_u64()), (Some("chain_break"), Some(2)));
+    assert_eq!(&serde_json::from_value::<dcp_ai::VerificationFailure>(wire).unwrap(), failure);
+
+    let mut sb = signed_value();
+    sb["bundle"]["intent"]["action_type"] = json!("tampered");
+    let kinds = verify_signed_bundle(&sb, None).error_kinds.unwrap();
+    assert_eq!(kinds[0], VerifyError::SignatureInvalid);
+    assert_eq!(serde_json::to_value(&kinds[0]).unwrap(), json!({"kind": "signature_invalid"}));
+}
+
+#[test]
+fn exhaustive_mode_reports_every_failure() {
+    use dcp_ai::verify::{verify_signed_bundle_exhaustive, VerifyOptions};
+
+    let mut sb = signed_value();
+    sb["bundle"]["audit_entries"][1]["intent_hash"] = json!("00");
+    sb["bundle"]["audit_entries"][2]["prev_hash"] = json!("deadbeef");
+    let sb = resign(&sb["bundle"]);
+    assert_eq!(verify_signed_bundle(&sb, None).failures.unwrap().len(), 1);
+
+    let failures = verify_signed_bundle_exhaustive(&sb, None, &VerifyOptions::default()).failures.unwrap();
+    let pointers: Vec<&str> = failures.iter().map(|f| f.pointer.as_str()).collect();
+    assert_eq!(pointers, ["/bundle/audit_entries/1/intent_hash", "/bundle/audit_entries/2/prev_hash"]);
+}
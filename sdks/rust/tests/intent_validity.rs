@@ -0,0 +1,96 @@
+mod common;
+
+use dcp_ai::bundle::{AuditEntryFields, BundleBuilder};
+use dcp_ai::crypto::generate_keypair;
+use dcp_ai::types::AuditEvidence;
+use dcp_ai::verify_signed_bundle;
+
+fn failure(signed: &serde_json::Value) -> (String, String) {
+    let result = verify_signed_bundle(signed, None);
+    assert!(!result.verified);
+    let f = &result.failures.unwrap()[0];
+    (f.message.clone(), f.pointer.clone())
+}
+
+#[test]
+fn entries_inside_window_verify() {
+    let mut v = common::signed_value();
+    v["bundle"]["intent"]["not_before"] = "2026-01-01T00:00:00Z".into();
+    v["bundle"]["intent"]["valid_until"] = "2026-01-01T02:00:00Z".into();
+    let bundle = rehash_intent(v["bundle"].clone());
+    let result = verify_signed_bundle(&common::resign(&bundle), None);
+    assert!(result.verified, "{:?}", result.errors);
+}
+
+#[test]
+fn entry_after_valid_until_is_rejected() {
+    let mut v = common::signed_value();
+    v["bundle"]["intent"]["valid_until"] = "2026-01-01T01:02:00Z".into();
+    let bundle = rehash_intent(v["bundle"].clone());
+    let (message, pointer) = failure(&common::resign(&bundle));
+    assert!(message.starts_with("INTENT EXPIRED (entry 2)"), "{}", message);
+    assert_eq!(pointer, "/bundle/audit_entries/2/timestamp");
+}
+
+#[test]
+fn entry_before_not_before_is_rejected() {
+    let mut v = common::signed_value();
+    v["bundle"]["intent"]["not_before"] = "2026-01-01T01:02:00Z".into();
+    let bundle = rehash_intent(v["bundle"].clone());
+    let (message, _) = failure(&common::resign(&bundle));
+    assert!(message.starts_with("INTENT NOT YET VALID (entry 0)"), "{}", message);
+}
+
+#[test]
+fn builder_refuses_entries_outside_window() {
+    let (pk, _) = generate_keypair();
+    let bundle = common::sample_bundle(&pk);
+    let intent = bundle
+        .intent
+        .clone()
+        .with_validity_window(None, Some("2026-01-01T01:00:00Z".into()));
+    let err = BundleBuilder::new()
+        .intent(intent)
+        .create_audit_entry(AuditEntryFields {
+            audit_id: "late".into(),
+            timestamp: "2026-01-01T05:00:00Z".into(),
+            agent_id: "did:agent:a".into(),
+            human_id: "did:human:alice".into(),
+            policy_decision: "approved".into(),
+            outcome: "ok".into(),
+            evidence: AuditEvidence { tool: None, result_ref: None, tombstone: None },
+        })
+        .unwrap_err();
+    assert!(err.contains("outside the validity window"));
+}
+
+/// Rebuild the audit chain after editing the intent, so only the window
+/// check can fail.
+fn rehash_intent(mut bundle: serde_json::Value) -> serde_json::Value {
+    let intent_hash = dcp_ai::hash_object(&bundle["intent"]);
+    let mut prev = "GENESIS".to_string();
+    for entry in bundle["audit_entries"].as_array_mut().unwrap() {
+        entry["intent_hash"] = intent_hash.clone().into();
+        entry["prev_hash"] = prev.into();
+        prev = dcp_ai::hash_object(entry);
+    }
+    bundle
+}
+
+#[test]
+fn clock_skew_widens_window_and_expiry_check_can_be_required() {
+    use dcp_ai::verify::{verify_signed_bundle_with_options, VerificationOptions};
+
+    let mut opts = VerificationOptions::default();
+    opts.require_expiry_check = true;
+    let result = verify_signed_bundle_with_options(&common::signed_value(), None, &opts);
+    assert!(result.errors.unwrap()[0].starts_with("Missing valid_until"));
+
+    let mut v = common::signed_value();
+    v["bundle"]["intent"]["valid_until"] = "2026-01-01T01:02:30Z".into();
+    let signed = common::resign(&rehash_intent(v["bundle"].clone()));
+    assert!(!verify_signed_bundle_with_options(&signed, None, &opts).verified);
+    opts.clock_skew = std::time::Duration::from_secs(60);
+    let result = verify_signed_bundle_with_options(&signed, None, &opts);
+    assert!(result.verified, "{:?}", result.errors);
+}
@@ -96,6 +96,40 @@ fn ed25519_rfc8032_tampered_fails() {
     }
 }
 
+/// The same vectors through the SDK's sign/verify API, so each
+/// `crypto::CRYPTO_BACKEND` is held to them (`cargo test --features ring-backend`).
+/// Only vectors whose message is UTF-8 can pass through the string API.
+#[test]
+fn ed25519_rfc8032_active_backend() {
+    let data = fs::read_to_string(kat_path("ed25519")).expect("read KAT");
+    let kat: Value = serde_json::from_str(&data).expect("parse KAT");
+    let backend = dcp_ai::crypto::CRYPTO_BACKEND;
+
+    let mut checked = 0;
+    for vec in kat["test_vectors"].as_array().unwrap() {
+        let name = vec["name"].as_str().unwrap();
+        let Ok(msg) = String::from_utf8(hex_to_bytes(vec["message_hex"].as_str().unwrap())) else {
+            continue;
+        };
+        let sk_b64 = BASE64.encode(hex_to_bytes(vec["secret_key_hex"].as_str().unwrap()));
+        let pk_b64 = BASE64.encode(hex_to_bytes(vec["public_key_hex"].as_str().unwrap()));
+        let expected_sig = BASE64.encode(hex_to_bytes(vec["signature_hex"].as_str().unwrap()));
+
+        let sig = dcp_ai::crypto::sign_canonical(&msg, &sk_b64).unwrap();
+        assert_eq!(sig, expected_sig, "{} ({}): sig mismatch", name, backend);
+        let verified = dcp_ai::crypto::verify_canonical(&msg, &sig, &pk_b64).unwrap();
+        assert!(verified, "{} ({}): verify failed", name, backend);
+        assert!(
+            !dcp_ai::crypto::verify_canonical(&format!("{}x", msg), &sig, &pk_b64).unwrap(),
+            "{} ({}): wrong message verified",
+            name,
+            backend
+        );
+        checked += 1;
+    }
+    assert_eq!(checked, 2);
+}
+
 // ---------------------------------------------------------------------------
 // Ed25519 Provider KAT
 // ---------------------------------------------------------------------------
@@ -170,7 +204,7 @@ fn ml_dsa_65_roundtrip() {
     let kp = p.generate_keypair().unwrap();
     let msg = b"ML-DSA-65 KAT round-trip";
     let sig = p.sign(msg, &kp.secret_key_b64).unwrap();
-    assert!(sig.len() > 0);
+    assert!(!sig.is_empty());
     assert!(p.verify(msg, &sig, &kp.public_key_b64).unwrap());
 }
 
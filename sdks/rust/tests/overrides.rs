@@ -0,0 +1,104 @@
+mod common;
+
+use dcp_ai::bundle::BundleBuilder;
+use dcp_ai::crypto::generate_keypair;
+use dcp_ai::issue::sign_record;
+use dcp_ai::overrides::{create_override, verify_overrides, OverrideParams, OverrideRecord};
+use dcp_ai::types::CitizenshipBundle;
+
+fn halt(human_sk: &str) -> OverrideRecord {
+    create_override(
+        OverrideParams {
+            override_id: "ovr-1",
+            intent_id: "intent001",
+            human_id: "did:human:alice",
+            agent_id: "did:agent:a",
+            action_taken: "halt",
+            reason: "recipient looked wrong",
+            timestamp: "2026-01-01T01:05:00Z",
+        },
+        human_sk,
+    )
+    .unwrap()
+}
+
+/// A bundle whose RPR is signed by `human_sk`, with `record` appended as
+/// an override entry.
+fn with_override(record: &OverrideRecord, human_sk: &str) -> CitizenshipBundle {
+    let (agent_pk, _) = generate_keypair();
+    let base = common::sample_bundle(&agent_pk);
+    let mut rpr = base.responsible_principal_record;
+    rpr.signature = sign_record(&rpr, human_sk).unwrap();
+    let mut builder = BundleBuilder::new()
+        .responsible_principal_record(rpr)
+        .agent_passport(base.agent_passport)
+        .intent(base.intent)
+        .policy_decision(base.policy_decision);
+    for entry in base.audit_entries {
+        builder = builder.add_audit_entry(entry);
+    }
+    builder.create_override_entry("audit004", record).unwrap().build().unwrap()
+}
+
+#[test]
+fn override_from_bound_human_verifies() {
+    let (human_pk, human_sk) = generate_keypair();
+    let record = halt(&human_sk);
+    let bundle = with_override(&record, &human_sk);
+    assert_eq!(bundle.audit_entries[3].policy_decision, "human_override");
+    let result = verify_overrides(&bundle, &[record], &human_pk);
+    assert!(result.verified, "{:?}", result.errors);
+}
+
+#[test]
+fn override_signed_by_other_key_is_rejected() {
+    let (human_pk, human_sk) = generate_keypair();
+    let (_, intruder_sk) = generate_keypair();
+    let record = halt(&intruder_sk);
+    let bundle = with_override(&record, &human_sk);
+    let result = verify_overrides(&bundle, &[record], &human_pk);
+    assert!(result.errors.unwrap()[0].contains("not from the bound human's key"));
+}
+
+#[test]
+fn override_key_must_have_signed_the_rpr() {
+    // The intruder signs the override and offers their own key; that key
+    // signed nothing else in the bundle, so it is not the bound human's.
+    let (_, human_sk) = generate_keypair();
+    let (intruder_pk, intruder_sk) = generate_keypair();
+    let record = halt(&intruder_sk);
+    let bundle = with_override(&record, &human_sk);
+    let result = verify_overrides(&bundle, &[record], &intruder_pk);
+    assert_eq!(result.errors.unwrap(), ["OVERRIDE INVALID: override key did not sign the record of did:human:alice"]);
+}
+
+#[test]
+fn missing_override_record_is_rejected() {
+    let (human_pk, human_sk) = generate_keypair();
+    let bundle = with_override(&halt(&human_sk), &human_sk);
+    assert!(!verify_overrides(&bundle, &[], &human_pk).verified);
+}
+
+#[test]
+fn override_without_rights_is_rejected() {
+    let (human_pk, human_sk) = generate_keypair();
+    let record = halt(&human_sk);
+    let mut bundle = with_override(&record, &human_sk);
+    let rpr = &mut bundle.responsible_principal_record;
+    rpr.override_rights = false;
+    rpr.signature = sign_record(rpr, &human_sk).unwrap();
+    let result = verify_overrides(&bundle, &[record], &human_pk);
+    assert!(result.errors.unwrap()[0].contains("no override_rights"));
+}
+
+#[test]
+fn override_entries_reference_records_by_uri_and_legacy_hash() {
+    let (human_pk, human_sk) = generate_keypair();
+    let record = halt(&human_sk);
+    let mut bundle = with_override(&record, &human_sk);
+    let uri = bundle.audit_entries[3].evidence.result_ref.clone().unwrap();
+    assert!(uri.starts_with("dcp:record:sha256:"), "{}", uri);
+
+    bundle.audit_entries[3].evidence.result_ref = Some(uri.trim_start_matches("dcp:record:").to_string());
+    assert!(verify_overrides(&bundle, &[record], &human_pk).verified);
+}
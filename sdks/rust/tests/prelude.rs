@@ -0,0 +1,15 @@
+mod common;
+
+use dcp_ai::prelude::*;
+
+#[test]
+fn prelude_covers_sign_and_verify() {
+    let (pk, sk) = generate_keypair();
+    let signed: SignedBundle = sign_bundle(common::sample_bundle(&pk), &SignOptions::new(&sk)).unwrap();
+    assert!(signed.verify(None).verified);
+
+    let value = serde_json::to_value(&signed).unwrap();
+    assert_eq!(value.dcp_version(), Some("1.0"));
+    assert!(value.verify_signed_bundle(Some(&pk)).verified);
+    assert!(Verifier::new(VerifyOptions::default()).verify(&value, None).verified);
+}
@@ -0,0 +1,78 @@
+//! Hash-only intent targets (hard privacy mode).
+
+mod common;
+
+use serde_json::Value;
+
+use dcp_ai::bundle::{AuditEntryFields, BundleBuilder};
+use dcp_ai::privacy::TargetSalt;
+use dcp_ai::types::{CitizenshipBundle, IntentTarget};
+use dcp_ai::verify::{verify_signed_bundle, verify_signed_bundle_with_options, VerifyOptions};
+
+/// The sample bundle with its intent target replaced and the audit chain
+/// rebuilt over the new intent.
+fn signed_with_target(target: IntentTarget) -> Value {
+    let sample = common::sample_bundle(&common::any_key());
+    let mut intent = sample.intent;
+    intent.target = target;
+    let mut builder = BundleBuilder::new()
+        .responsible_principal_record(sample.responsible_principal_record)
+        .agent_passport(sample.agent_passport)
+        .intent(intent)
+        .policy_decision(sample.policy_decision);
+    for e in sample.audit_entries {
+        builder = builder
+            .create_audit_entry(AuditEntryFields {
+                audit_id: e.audit_id,
+                timestamp: e.timestamp,
+                agent_id: e.agent_id,
+                human_id: e.human_id,
+                policy_decision: e.policy_decision,
+                outcome: e.outcome,
+                evidence: e.evidence,
+            })
+            .unwrap();
+    }
+    common::resign(&serde_json::to_value(builder.build().unwrap()).unwrap())
+}
+
+fn plaintext_target() -> IntentTarget {
+    common::sample_bundle(&common::any_key()).intent.target
+}
+
+#[test]
+fn hashed_target_verifies_and_confirms_against_plaintext() {
+    let salt = TargetSalt::generate();
+    let sb = signed_with_target(plaintext_target().hashed(&salt));
+    assert!(!sb.to_string().contains("bob@example.com"));
+
+    let mut opts = VerifyOptions::default();
+    opts.require_hashed_targets = true;
+    let result = verify_signed_bundle_with_options(&sb, None, &opts);
+    assert!(result.verified, "{:?}", result.errors);
+
+    let bundle: CitizenshipBundle = serde_json::from_value(sb["bundle"].clone()).unwrap();
+    assert!(bundle.intent.target.matches_plaintext(&plaintext_target(), &salt));
+}
+
+#[test]
+fn plaintext_target_rejected_in_privacy_mode() {
+    let sb = signed_with_target(plaintext_target());
+    assert!(verify_signed_bundle(&sb, None).verified);
+
+    let mut opts = VerifyOptions::default();
+    opts.require_hashed_targets = true;
+    let result = verify_signed_bundle_with_options(&sb, None, &opts);
+    assert!(!result.verified);
+    let failure = &result.failures.unwrap()[0];
+    assert!(failure.message.starts_with("TARGET NOT HASHED"));
+    assert_eq!(failure.pointer, "/bundle/intent/target/to");
+}
+
+#[test]
+fn declared_salt_with_plaintext_field_is_rejected() {
+    let mut target = plaintext_target();
+    target.salt_id = Some("salt:0000000000000000".into());
+    let result = verify_signed_bundle(&signed_with_target(target), None);
+    assert!(!result.verified);
+}
@@ -0,0 +1,80 @@
+//! Adversarial Ed25519 vectors: every conforming verifier must reach the
+//! same verdict on these bytes.
+
+mod common;
+
+use serde_json::{json, Value};
+
+use dcp_ai::b64;
+use dcp_ai::bundle::{sign_bundle, SignOptions};
+use dcp_ai::crypto::{generate_keypair, sign_object, verify_canonical_lenient, verify_object};
+use dcp_ai::verify::{verify_signed_bundle_with_options, VerifyOptions};
+use dcp_ai::{canonicalize, verify_canonical};
+
+/// Group order l, little-endian.
+const ORDER: [u8; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0x10,
+];
+
+/// Compressed identity point: a small-order public key and `R`.
+fn identity() -> [u8; 32] {
+    let mut p = [0u8; 32];
+    p[0] = 1;
+    p
+}
+
+/// `R = identity, S = 0` verifies for every message under the identity
+/// key with the cofactorless equation.
+fn weak_key_signature() -> (String, String) {
+    let mut sig = [0u8; 64];
+    sig[..32].copy_from_slice(&identity());
+    (b64::encode(sig), b64::encode(identity()))
+}
+
+#[test]
+fn non_canonical_s_is_rejected_in_every_mode() {
+    let (pk, sk) = generate_keypair();
+    let msg = json!({"action": "send_email", "amount": 1});
+    let sig = b64::decode(&sign_object(&msg, &sk).unwrap()).unwrap();
+
+    // S' = S + l is the same scalar mod l but a second encoding.
+    let mut forged = sig.clone();
+    let mut carry = 0u16;
+    for (byte, l) in forged[32..].iter_mut().zip(ORDER) {
+        let sum = *byte as u16 + l as u16 + carry;
+        *byte = sum as u8;
+        carry = sum >> 8;
+    }
+    assert_eq!(carry, 0);
+    let forged = b64::encode(forged);
+
+    assert!(verify_object(&msg, &b64::encode(sig), &pk).unwrap());
+    assert!(!verify_object(&msg, &forged, &pk).unwrap());
+    assert!(!verify_canonical_lenient(&canonicalize(&msg), &forged, &pk).unwrap());
+}
+
+#[test]
+fn small_order_key_is_rejected_by_strict_verification() {
+    let (sig, pk) = weak_key_signature();
+    let canon = canonicalize(&json!({"anything": "at all"}));
+    assert!(verify_canonical_lenient(&canon, &sig, &pk).unwrap());
+    assert!(!verify_canonical(&canon, &sig, &pk).unwrap());
+}
+
+#[test]
+fn strict_signatures_option_governs_bundle_verification() {
+    let (sig, pk) = weak_key_signature();
+    let (_, sk) = generate_keypair();
+    let mut sb: Value = serde_json::to_value(sign_bundle(common::sample_bundle(&pk), &SignOptions::new(&sk)).unwrap()).unwrap();
+    sb["signature"]["sig_b64"] = json!(sig);
+    sb["signature"]["signer"]["public_key_b64"] = json!(pk);
+
+    let strict = verify_signed_bundle_with_options(&sb, None, &VerifyOptions::default());
+    assert_eq!(strict.errors.unwrap()[0], "SIGNATURE INVALID");
+
+    let mut opts = VerifyOptions::default();
+    opts.strict_signatures = false;
+    let lenient = verify_signed_bundle_with_options(&sb, None, &opts);
+    assert!(lenient.verified, "{:?}", lenient.errors);
+}
@@ -0,0 +1,103 @@
+//! Signed verification transcripts (Rust).
+
+mod common;
+
+use serde_json::json;
+
+use dcp_ai::crypto::generate_keypair;
+use dcp_ai::verify::{verify_with_transcript, VerifyOptions};
+
+use common::load_signed_bundle;
+
+#[test]
+fn transcript_records_every_comparison() {
+    let sb = load_signed_bundle();
+    let (result, transcript) = verify_with_transcript(&sb, None, &VerifyOptions::default());
+    assert!(result.verified);
+    assert!(transcript.verified);
+
+    let checks: Vec<&str> = transcript.steps.iter().map(|s| s.check.as_str()).collect();
+    assert_eq!(
+        checks,
+        vec!["signature", "bundle_hash", "merkle_root", "intent_hash[0]", "prev_hash[0]", "intent_hash[1]", "prev_hash[1]"]
+    );
+    assert!(transcript.steps.iter().all(|s| s.matched));
+    assert_eq!(transcript.steps[4].pointer, "/bundle/audit_entries");
+    assert_eq!(transcript.steps[6].pointer, "/bundle/audit_entries/0");
+}
+
+#[test]
+fn transcript_replays_deterministically() {
+    let sb = load_signed_bundle();
+    let opts = VerifyOptions::default();
+    let (_, transcript) = verify_with_transcript(&sb, None, &opts);
+    assert!(transcript.replay(&sb, &opts));
+
+    let mut other = VerifyOptions::default();
+    other.require_merkle_root = true;
+    assert!(!transcript.replay(&sb, &other));
+
+    let mut tampered = sb.clone();
+    tampered["bundle"]["audit_entries"][1]["outcome"] = json!("tampered");
+    assert!(!transcript.replay(&tampered, &opts));
+}
+
+#[test]
+fn transcript_records_options_and_merkle_leaves() {
+    use dcp_ai::crypto::hash_object;
+    use dcp_ai::volatile::VolatileFields;
+
+    let sb = load_signed_bundle();
+    let mut opts = VerifyOptions::default();
+    opts.require_bundle_hash = true;
+    let (_, transcript) = verify_with_transcript(&sb, None, &opts);
+    assert_eq!(transcript.options["require_bundle_hash"], json!(true));
+    assert_eq!(transcript.options["volatile_audit_fields"], json!([]));
+
+    let step = transcript.steps.iter().find(|s| s.check == "merkle_root").unwrap();
+    let entries = sb["bundle"]["audit_entries"].as_array().unwrap();
+    let expected: Vec<String> = entries.iter().map(|e| VolatileFields::default().entry_hash(e)).collect();
+    assert_eq!(step.leaves.as_ref(), Some(&expected));
+    assert_eq!(step.input_sha256, hash_object(&json!(expected)));
+    assert!(transcript.steps.iter().filter(|s| s.check != "merkle_root").all(|s| s.leaves.is_none()));
+}
+
+#[test]
+fn failed_verification_is_recorded() {
+    let mut sb = load_signed_bundle();
+    sb["bundle"]["intent"]["action_type"] = json!("transfer_funds");
+    let (result, transcript) = verify_with_transcript(&sb, None, &VerifyOptions::default());
+    assert!(!result.verified);
+    assert!(!transcript.verified);
+    assert!(!transcript.steps[0].matched);
+}
+
+#[test]
+fn signed_transcript_verifies() {
+    let sb = load_signed_bundle();
+    let (_, transcript) = verify_with_transcript(&sb, None, &VerifyOptions::default());
+    let (pk, sk) = generate_keypair();
+    let signed = transcript.sign("did:verifier:gateway-1", &sk).unwrap();
+    assert_eq!(signed.verifier_public_key_b64, pk);
+    assert!(signed.verify_signature(&pk).unwrap());
+
+    let mut forged = signed.clone();
+    forged.transcript.verified = false;
+    assert!(!forged.verify_signature(&pk).unwrap());
+
+    let mut renamed = signed.clone();
+    renamed.verifier_id = "did:verifier:someone-else".into();
+    assert!(!renamed.verify_signature(&pk).unwrap());
+}
+
+#[test]
+fn transcript_signed_by_another_key_is_rejected() {
+    let sb = load_signed_bundle();
+    let (_, transcript) = verify_with_transcript(&sb, None, &VerifyOptions::default());
+    let (pk, _) = generate_keypair();
+    let (other_pk, other_sk) = generate_keypair();
+    // Self-consistent, but not signed by the verifier the caller trusts.
+    let signed = transcript.sign("did:verifier:gateway-1", &other_sk).unwrap();
+    assert!(signed.verify_signature(&other_pk).unwrap());
+    assert!(!signed.verify_signature(&pk).unwrap());
+}
@@ -100,7 +100,7 @@ fn apply_advisories_filters_and_warns() {
     );
     let eval = evaluate_advisories(&[adv_deprecate, adv_warn], None);
     let result = apply_advisories_to_policy(
-        &vec!["ed25519".into(), "ml-dsa-65".into(), "slh-dsa-192f".into()],
+        &["ed25519".into(), "ml-dsa-65".into(), "slh-dsa-192f".into()],
         &eval,
     );
     assert_eq!(result.removed_algs, vec!["ed25519"]);
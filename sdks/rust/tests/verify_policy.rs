@@ -0,0 +1,627 @@
+//! Verifier policy options (Rust).
+
+mod common;
+
+use dcp_ai::verify::{quick_verify, verify_signed_bundle, verify_signed_bundle_with_options, VerifyOptions};
+
+use common::load_signed_bundle;
+
+fn first_error(result: &dcp_ai::VerificationResult) -> String {
+    result.errors.as_ref().and_then(|e| e.first()).cloned().unwrap_or_default()
+}
+
+#[test]
+fn default_options_accept_legacy_sha256_bundle() {
+    let sb = load_signed_bundle();
+    let result = verify_signed_bundle_with_options(&sb, None, &VerifyOptions::default());
+    assert!(result.verified, "{:?}", result.errors);
+}
+
+#[test]
+fn phased_out_digest_is_rejected() {
+    let sb = load_signed_bundle();
+    let mut opts = VerifyOptions::default();
+    opts.accepted_hash_algs = vec!["sha3-256".into()];
+    let result = verify_signed_bundle_with_options(&sb, None, &opts);
+    assert!(!result.verified);
+    assert!(first_error(&result).starts_with("WEAK ALGORITHM REJECTED"));
+}
+
+#[test]
+fn minimum_signature_alg_is_enforced() {
+    let sb = load_signed_bundle();
+    let mut ok = VerifyOptions::default();
+    ok.minimum_signature_alg = Some("ed25519".into());
+    assert!(verify_signed_bundle_with_options(&sb, None, &ok).verified);
+
+    let mut strict = VerifyOptions::default();
+    strict.minimum_signature_alg = Some("ml-dsa-65".into());
+    let result = verify_signed_bundle_with_options(&sb, None, &strict);
+    assert!(!result.verified);
+    assert!(first_error(&result).contains("below minimum ml-dsa-65"));
+}
+
+#[test]
+fn relabelled_ed25519_signature_fails_algorithm_policy() {
+    let mut sb = load_signed_bundle();
+    sb["signature"]["alg"] = "ml-dsa-65".into();
+    // The label is not signed; without a policy it is not consulted.
+    assert!(verify_signed_bundle(&sb, None).verified);
+    let mut strict = VerifyOptions::default();
+    strict.minimum_signature_alg = Some("ml-dsa-65".into());
+    let err = first_error(&verify_signed_bundle_with_options(&sb, None, &strict));
+    assert!(err.starts_with("UNSUPPORTED SIGNATURE ALG"), "{}", err);
+}
+
+#[test]
+fn unverified_alg_is_reported_once_under_both_policies() {
+    use dcp_ai::types::VerifyError;
+    use dcp_ai::verify::verify_signed_bundle_exhaustive;
+
+    let mut sb = load_signed_bundle();
+    sb["signature"]["alg"] = "ml-dsa-65".into();
+    let mut strict = VerifyOptions::default();
+    strict.minimum_signature_alg = Some("ml-dsa-65".into());
+    strict.allowed_algs = vec!["ml-dsa-65".into()];
+    let kinds = verify_signed_bundle_exhaustive(&sb, None, &strict).error_kinds.unwrap();
+    let unsupported = kinds.iter().filter(|k| **k == VerifyError::UnsupportedSignatureAlg).count();
+    assert_eq!(unsupported, 1, "{:?}", kinds);
+}
+
+#[test]
+fn sha3_merkle_root_is_not_accepted_by_default() {
+    let mut sb = load_signed_bundle();
+    sb["signature"]["merkle_root"] = format!("sha3-256:{}", "0".repeat(64)).into();
+    let err = first_error(&verify_signed_bundle(&sb, None));
+    assert!(err.starts_with("WEAK ALGORITHM REJECTED: merkle_root uses sha3-256"), "{}", err);
+}
+
+#[test]
+fn accepted_non_sha256_merkle_root_is_not_skipped() {
+    let mut sb = load_signed_bundle();
+    sb["signature"]["merkle_root"] = format!("sha3-256:{}", "0".repeat(64)).into();
+    let mut opts = VerifyOptions::default();
+    opts.accepted_merkle_algs.push("sha3-256".into());
+    let err = first_error(&verify_signed_bundle_with_options(&sb, None, &opts));
+    assert!(err.starts_with("UNSUPPORTED HASH ALG: merkle_root"), "{}", err);
+}
+
+#[test]
+fn merkle_root_over_no_entries_fails() {
+    let mut sb = common::signed_value();
+    sb["bundle"]["audit_entries"] = serde_json::json!([]);
+    let mut sb = common::resign(&sb["bundle"]);
+    sb["signature"]["merkle_root"] = format!("sha256:{}", "0".repeat(64)).into();
+    let err = first_error(&verify_signed_bundle(&sb, None));
+    assert_eq!(err, "MERKLE ROOT MISMATCH: declared over no audit entries");
+}
+
+#[test]
+fn approved_decision_requires_terminal_audit_outcome() {
+    let mut opts = VerifyOptions::default();
+    opts.require_decision_coverage = true;
+
+    let free_form = common::signed_value();
+    let result = verify_signed_bundle_with_options(&free_form, None, &opts);
+    assert!(first_error(&result).starts_with("DECISION NOT AUDITED"));
+
+    let mut bundle = free_form["bundle"].clone();
+    bundle["audit_entries"][2]["outcome"] = "success".into();
+    let covered = common::resign(&bundle);
+    let result = verify_signed_bundle_with_options(&covered, None, &opts);
+    assert!(result.verified, "{:?}", result.errors);
+}
+
+#[test]
+fn quick_verify_skips_chain_but_not_signature() {
+    let sb = common::signed_value();
+    assert!(quick_verify(&sb, None).verified);
+
+    let mut bundle = sb["bundle"].clone();
+    bundle["audit_entries"][1]["prev_hash"] = "GENESIS".into();
+    let broken_chain = common::resign(&bundle);
+    assert!(quick_verify(&broken_chain, None).verified);
+    assert!(!verify_signed_bundle(&broken_chain, None).verified);
+
+    let mut tampered = sb.clone();
+    tampered["bundle"]["intent"]["action_type"] = "wire_funds".into();
+    assert_eq!(first_error(&quick_verify(&tampered, None)), "SIGNATURE INVALID");
+}
+
+#[test]
+fn candidate_keys_report_which_key_matched() {
+    use dcp_ai::crypto::{generate_keypair, sign_object, verify_object_multi};
+
+    let sb = common::signed_value();
+    let signer_key = sb["signature"]["signer"]["public_key_b64"].as_str().unwrap().to_string();
+    let (old_key, _) = generate_keypair();
+
+    let mut opts = VerifyOptions::default();
+    opts.candidate_keys = vec![old_key.clone(), signer_key.clone()];
+    let result = verify_signed_bundle_with_options(&sb, None, &opts);
+    assert!(result.verified, "{:?}", result.errors);
+    assert_eq!(result.matched_key.as_deref(), Some(signer_key.as_str()));
+
+    opts.candidate_keys = vec![old_key.clone(), "not-a-key".into()];
+    let result = verify_signed_bundle_with_options(&sb, None, &opts);
+    let failure = &result.failures.unwrap()[0];
+    assert!(failure.message.starts_with("SIGNATURE INVALID: none of 2 candidate keys"));
+    assert!(failure.actual.as_deref().unwrap().contains("key 1: public key"));
+
+    let (pk, sk) = generate_keypair();
+    let obj = serde_json::json!({ "a": 1 });
+    let sig = sign_object(&obj, &sk).unwrap();
+    assert_eq!(verify_object_multi(&obj, &sig, &[old_key.as_str(), pk.as_str()]), Ok(1));
+    assert_eq!(verify_object_multi(&obj, &sig, &[old_key]).unwrap_err(), ["key 0: signature mismatch"]);
+}
+
+#[test]
+fn report_lists_every_check_and_why_it_was_skipped() {
+    use dcp_ai::verify::{verify_with_report, CHECK_NAMES};
+    use dcp_ai::CheckStatus;
+
+    let sb = common::signed_value();
+    let report = verify_with_report(&sb, None, &VerifyOptions::default());
+    assert!(report.verified());
+    let names: Vec<&str> = report.checks.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, CHECK_NAMES);
+    for name in ["signature", "bundle_hash", "merkle_root", "intent_hash", "prev_hash"] {
+        assert_eq!(report.check(name).unwrap().status, CheckStatus::Passed, "{}", name);
+    }
+    assert_eq!(report.check("references").unwrap().skipped_reason.as_deref(), Some("disabled by options"));
+    let expiry = report.check("expiry").unwrap();
+    assert_eq!(expiry.status, CheckStatus::Skipped);
+    assert_eq!(expiry.skipped_reason.as_deref(), Some("intent has no validity window"));
+    assert_eq!(report.check("decision_coverage").unwrap().skipped_reason.as_deref(), Some("disabled by options"));
+
+    let mut bundle = sb["bundle"].clone();
+    bundle["policy_decision"]["intent_id"] = "intent999".into();
+    let foreign = common::resign(&bundle);
+    assert!(verify_with_report(&foreign, None, &VerifyOptions::default()).verified());
+    let mut strict = VerifyOptions::default();
+    strict.strict_identifiers = true;
+    let report = verify_with_report(&foreign, None, &strict);
+    assert!(first_error(&report.result).starts_with("REFERENCE MISMATCH"));
+    let references = report.check("references").unwrap();
+    assert_eq!(references.status, CheckStatus::Failed);
+    assert_eq!(references.failure.as_ref().unwrap().pointer, "/bundle/policy_decision/intent_id");
+    assert_eq!(report.failed().count(), 1);
+    assert_eq!(
+        report.check("hashed_targets").unwrap().skipped_reason.as_deref(),
+        Some("not reached: an earlier check failed")
+    );
+}
+
+#[test]
+fn report_attributes_policy_failures_to_signature_policy() {
+    use dcp_ai::verify::verify_with_report;
+    use dcp_ai::CheckStatus;
+
+    let sb = load_signed_bundle();
+    let report = verify_with_report(&sb, None, &VerifyOptions::default());
+    assert_eq!(report.check("signature_policy").unwrap().status, CheckStatus::Passed);
+
+    let mut strict = VerifyOptions::default();
+    strict.minimum_signature_alg = Some("ml-dsa-65".into());
+    let report = verify_with_report(&sb, None, &strict);
+    let policy = report.check("signature_policy").unwrap();
+    assert_eq!(policy.status, CheckStatus::Failed);
+    assert_eq!(policy.failure.as_ref().unwrap().pointer, "/signature/alg");
+    assert_eq!(
+        report.check("signature").unwrap().skipped_reason.as_deref(),
+        Some("not reached: an earlier check failed")
+    );
+
+    let mut unknown_canon = sb.clone();
+    unknown_canon["signature"]["canon"] = "dcp-jcs-v9".into();
+    let report = verify_with_report(&unknown_canon, None, &VerifyOptions::default());
+    let policy = report.check("signature_policy").unwrap();
+    assert_eq!(policy.status, CheckStatus::Failed);
+    assert!(policy.failure.as_ref().unwrap().message.starts_with("UNSUPPORTED CANONICALIZATION"));
+}
+
+#[test]
+fn max_signature_age_rejects_stale_signatures() {
+    use std::time::Duration;
+
+    use dcp_ai::bundle::{sign_bundle, SignOptions};
+    use dcp_ai::crypto::generate_keypair;
+
+    let mut opts = VerifyOptions::default();
+    opts.max_signature_age = Some(Duration::from_secs(300));
+
+    let fresh = common::signed_value();
+    assert!(verify_signed_bundle_with_options(&fresh, None, &opts).verified);
+
+    let (pk, sk) = generate_keypair();
+    let mut sign = SignOptions::new(&sk);
+    sign.created_at = Some("2020-01-01T00:00:00Z");
+    let stale = serde_json::to_value(sign_bundle(common::sample_bundle(&pk), &sign).unwrap()).unwrap();
+    let result = verify_signed_bundle_with_options(&stale, None, &opts);
+    let failure = &result.failures.unwrap()[0];
+    assert!(failure.message.starts_with("SIGNATURE TOO OLD"), "{}", failure.message);
+    assert_eq!(failure.pointer, "/signature/created_at");
+
+    // Archival verification ignores age.
+    assert!(verify_signed_bundle(&stale, None).verified);
+}
+
+#[test]
+fn temporal_order_with_injected_now() {
+    use std::time::Duration;
+
+    use dcp_ai::bundle::{sign_bundle, SignOptions};
+    use dcp_ai::crypto::generate_keypair;
+
+    let mut opts = VerifyOptions::default();
+    opts.require_temporal_order = true;
+    assert!(verify_signed_bundle_with_options(&common::signed_value(), None, &opts).verified);
+
+    // Entries are logged at 01:01..01:03; a passport created after the
+    // first of them is out of order.
+    let mut late = common::signed_value()["bundle"].clone();
+    late["agent_passport"]["created_at"] = "2026-01-01T01:01:30Z".into();
+    let late = common::resign(&late);
+    let result = verify_signed_bundle_with_options(&late, None, &opts);
+    let failure = &result.failures.unwrap()[0];
+    assert!(failure.message.starts_with("TIMESTAMP ORDER: audit entry"), "{}", failure.message);
+    assert_eq!(failure.pointer, "/bundle/audit_entries/0/timestamp");
+    opts.clock_skew = Duration::from_secs(60);
+    assert!(verify_signed_bundle_with_options(&late, None, &opts).verified);
+
+    // The unsigned created_at is not compared, however it is forged.
+    let (pk, sk) = generate_keypair();
+    let mut sign = SignOptions::new(&sk);
+    sign.created_at = Some("2030-01-01T00:00:00Z");
+    let forged = serde_json::to_value(sign_bundle(common::sample_bundle(&pk), &sign).unwrap()).unwrap();
+    opts.now_secs = Some(1_767_232_800); // 2026-01-01T02:00:00Z
+    assert!(verify_signed_bundle_with_options(&forged, None, &opts).verified);
+    opts.now_secs = Some(1_767_225_600); // 2026-01-01T00:00:00Z
+    let err = first_error(&verify_signed_bundle_with_options(&forged, None, &opts));
+    assert!(err.starts_with("TIMESTAMP ORDER: audit entry at 2026-01-01T01:03:00Z"), "{}", err);
+    assert!(err.ends_with("in the future"), "{}", err);
+}
+
+#[test]
+fn malformed_timestamps_fail_temporal_checks_without_panicking() {
+    use std::time::Duration;
+
+    let mut opts = VerifyOptions::default();
+    opts.require_temporal_order = true;
+    let mut bad = common::signed_value()["bundle"].clone();
+    bad["agent_passport"]["created_at"] = "2026-13-45T99:99:99Z".into();
+    let result = verify_signed_bundle_with_options(&common::resign(&bad), None, &opts);
+    let failure = &result.failures.unwrap()[0];
+    assert!(failure.message.starts_with("TIMESTAMP ORDER: passport created"), "{}", failure.message);
+    assert_eq!(failure.pointer, "/bundle/agent_passport/created_at");
+
+    let mut huge = common::signed_value()["bundle"].clone();
+    huge["audit_entries"][2]["timestamp"] = "99999999999999999-01-01T00:00:00Z".into();
+    huge["responsible_principal_record"]["expires_at"] = "2027-01-01T00:00:00Z".into();
+    let huge = common::resign(&huge);
+    let failure = &verify_signed_bundle_with_options(&huge, None, &opts).failures.unwrap()[0];
+    assert_eq!(failure.pointer, "/bundle/audit_entries/2/timestamp");
+
+    let mut window = common::signed_value()["bundle"].clone();
+    window["intent"]["valid_until"] = "2026-02-30T00:00:00Z".into();
+    let err = first_error(&verify_signed_bundle(&common::resign(&window), None));
+    assert!(err.starts_with("INTENT EXPIRED"), "{}", err);
+    assert!(err.contains("unparseable"), "{}", err);
+
+    // Extreme clocks and windows saturate instead of overflowing.
+    let mut extreme = VerifyOptions::default();
+    extreme.require_temporal_order = true;
+    extreme.clock_skew = Duration::MAX;
+    extreme.max_signature_age = Some(Duration::MAX);
+    extreme.max_entry_age = Some(Duration::MAX);
+    for now in [i64::MIN, i64::MAX] {
+        extreme.now_secs = Some(now);
+        verify_signed_bundle_with_options(&common::signed_value(), None, &extreme);
+    }
+}
+
+#[test]
+fn strict_identifiers_reject_duplicate_and_foreign_ids() {
+    let mut strict = VerifyOptions::default();
+    strict.strict_identifiers = true;
+    assert!(verify_signed_bundle_with_options(&common::signed_value(), None, &strict).verified);
+
+    let sb = common::signed_value();
+    let mut duplicate = sb["bundle"].clone();
+    duplicate["audit_entries"][2]["audit_id"] = "audit001".into();
+    let duplicate = common::resign(&duplicate);
+    // Only strict mode notices; the chain itself is intact.
+    assert!(verify_signed_bundle(&duplicate, None).verified);
+    let result = verify_signed_bundle_with_options(&duplicate, None, &strict);
+    let failure = &result.failures.unwrap()[0];
+    assert!(failure.message.starts_with("DUPLICATE AUDIT ID (entry 2)"), "{}", failure.message);
+    assert_eq!(failure.pointer, "/bundle/audit_entries/2/audit_id");
+
+    let mut foreign = sb["bundle"].clone();
+    foreign["audit_entries"][2]["intent_id"] = "intent999".into();
+    let result = verify_signed_bundle_with_options(&common::resign(&foreign), None, &strict);
+    assert!(first_error(&result).starts_with("INTENT ID MISMATCH (entry 2)"));
+}
+
+#[test]
+fn null_as_absent_shim_accepts_bundles_signed_without_nulls() {
+    use dcp_ai::bundle::{sign_bundle, SignOptions};
+    use dcp_ai::crypto::generate_keypair;
+
+    // Signed by a producer that skips `None`, shipped with explicit nulls.
+    let (pk, sk) = generate_keypair();
+    let signed = sign_bundle(common::sample_bundle_without_nulls(&pk), &SignOptions::new(&sk)).unwrap();
+    let mut sb = serde_json::to_value(&signed).unwrap();
+    sb["bundle"]["responsible_principal_record"]["contact"] = serde_json::Value::Null;
+    assert!(first_error(&verify_signed_bundle(&sb, None)).starts_with("SIGNATURE INVALID"));
+
+    let mut opts = VerifyOptions::default();
+    opts.accept_null_as_absent = true;
+    let result = verify_signed_bundle_with_options(&sb, None, &opts);
+    assert!(result.verified, "{:?}", result.errors);
+}
+
+#[test]
+fn expiry_grace_turns_recently_expired_binding_into_warning() {
+    use std::time::Duration;
+
+    use dcp_ai::bundle::{sign_bundle, SignOptions};
+    use dcp_ai::crypto::generate_keypair;
+
+    // Audit entries run 01:01-01:03, after the binding expired.
+    let (pk, sk) = generate_keypair();
+    let mut bundle = common::sample_bundle(&pk);
+    bundle.responsible_principal_record.expires_at = Some("2026-01-01T00:30:00Z".into());
+    let mut sign = SignOptions::new(&sk);
+    // Backdating the unsigned created_at changes nothing.
+    sign.created_at = Some("2026-01-01T00:00:00Z");
+    let sb = serde_json::to_value(sign_bundle(bundle, &sign).unwrap()).unwrap();
+    assert!(verify_signed_bundle(&sb, None).verified);
+
+    let mut opts = VerifyOptions::default();
+    opts.require_unexpired_binding = true;
+    let result = verify_signed_bundle_with_options(&sb, None, &opts);
+    assert!(first_error(&result).starts_with("BINDING EXPIRED"), "{:?}", result.errors);
+
+    opts.expiry_grace = Duration::from_secs(86_400);
+    let result = verify_signed_bundle_with_options(&sb, None, &opts);
+    assert!(result.verified, "{:?}", result.errors);
+    assert_eq!(result.warnings.len(), 1);
+    assert!(result.warnings[0].starts_with("BINDING IN GRACE PERIOD"));
+}
+
+#[test]
+fn sequence_numbers_order_entries_sharing_a_timestamp() {
+    use dcp_ai::bundle::{AuditEntryFields, BundleBuilder};
+    use dcp_ai::verify::verify_with_report;
+    use dcp_ai::CheckStatus;
+
+    let base = common::sample_bundle(&common::any_key());
+    let mut builder = BundleBuilder::new()
+        .responsible_principal_record(base.responsible_principal_record)
+        .agent_passport(base.agent_passport)
+        .intent(base.intent)
+        .policy_decision(base.policy_decision)
+        .sequenced();
+    for entry in base.audit_entries {
+        builder = builder
+            .create_audit_entry(AuditEntryFields {
+                audit_id: entry.audit_id,
+                timestamp: "2026-01-01T01:00:00Z".into(),
+                agent_id: entry.agent_id,
+                human_id: entry.human_id,
+                policy_decision: entry.policy_decision,
+                outcome: entry.outcome,
+                evidence: entry.evidence,
+            })
+            .unwrap();
+    }
+    let bundle = builder.build().unwrap();
+    let seqs: Vec<Option<u64>> = bundle.audit_entries.iter().map(|e| e.seq).collect();
+    assert_eq!(seqs, [Some(0), Some(1), Some(2)]);
+
+    let sb = common::resign(&serde_json::to_value(&bundle).unwrap());
+    let report = verify_with_report(&sb, None, &VerifyOptions::default());
+    assert_eq!(report.check("sequence").unwrap().status, CheckStatus::Passed);
+
+    let mut replayed = sb["bundle"].clone();
+    replayed["audit_entries"][2]["seq"] = 1.into();
+    let result = verify_signed_bundle(&common::resign(&replayed), None);
+    let error = first_error(&result);
+    assert!(error.starts_with("SEQUENCE OUT OF ORDER (entry 2): seq 1 follows 1"), "{}", error);
+}
+
+#[test]
+fn signer_binding_requires_passport_key_or_trusted_notary() {
+    use dcp_ai::bundle::{sign_bundle, SignOptions};
+    use dcp_ai::crypto::generate_keypair;
+
+    let mut strict = VerifyOptions::default();
+    strict.require_signer_binding = true;
+    assert!(verify_signed_bundle_with_options(&common::signed_value(), None, &strict).verified);
+
+    let (notary_pk, notary_sk) = generate_keypair();
+    let signed_as = |signer_type: &'static str| {
+        let mut sign = SignOptions::new(&notary_sk);
+        sign.signer_type = signer_type;
+        serde_json::to_value(sign_bundle(common::sample_bundle(&common::any_key()), &sign).unwrap()).unwrap()
+    };
+    let notarized = signed_as("notary");
+    // Self-verifies, so only strict mode notices.
+    assert!(verify_signed_bundle(&notarized, None).verified);
+    let result = verify_signed_bundle_with_options(&notarized, None, &strict);
+    let failure = &result.failures.unwrap()[0];
+    assert!(failure.message.starts_with("KEY NOT BOUND: notary signing key"), "{}", failure.message);
+    assert_eq!(failure.pointer, "/signature/signer/public_key_b64");
+
+    strict.trusted_signer_keys = vec![notary_pk];
+    assert!(verify_signed_bundle_with_options(&notarized, None, &strict).verified);
+    // A trusted key cannot stand in for the agent's own.
+    let impersonated = signed_as("agent");
+    assert!(first_error(&verify_signed_bundle_with_options(&impersonated, None, &strict)).starts_with("KEY NOT BOUND"));
+    // An unknown signer type fails before any key lookup.
+    let unknown = signed_as("robot");
+    assert!(first_error(&verify_signed_bundle_with_options(&unknown, None, &strict)).starts_with("SIGNER INVALID"));
+}
+
+#[test]
+fn verifier_presets() {
+    use dcp_ai::limits::InputLimits;
+    use dcp_ai::verify::{Preset, Verifier};
+
+    let fresh = common::signed_value();
+    let gateway = Verifier::builder().preset(Preset::ApiGateway).build();
+    assert!(gateway.options().strict_identifiers && gateway.options().require_signer_binding);
+    for preset in [Preset::ApiGateway, Preset::Archival] {
+        let opts = Verifier::builder().preset(preset).build().options().clone();
+        assert!(opts.require_unexpired_binding && opts.require_temporal_order, "{:?}", preset);
+    }
+    assert!(gateway.verify(&fresh, None).verified);
+    // The archived example is signed by its human principal, long ago.
+    let archived = load_signed_bundle();
+    assert!(first_error(&gateway.verify(&archived, None)).starts_with("KEY NOT BOUND"));
+    let unbound =
+        Verifier::builder().preset(Preset::ApiGateway).configure(|o| o.require_signer_binding = false).build();
+    assert!(first_error(&unbound.verify(&archived, None)).starts_with("SIGNATURE TOO OLD"));
+    let archival = Verifier::builder().preset(Preset::Archival).build();
+    assert!(archival.limits().is_none());
+    assert!(archival.verify(&archived, None).verified);
+
+    let tiny = InputLimits { max_entries: 2, ..Preset::BROWSER_LIMITS };
+    let browser = Verifier::builder().preset(Preset::Browser).limits(tiny).build();
+    assert!(first_error(&browser.verify(&fresh, None)).starts_with("LIMIT EXCEEDED"));
+}
+
+#[test]
+fn approved_policy_snapshots_require_a_matching_decision() {
+    use dcp_ai::policy::{snapshot_hash, PolicySet};
+
+    let policy = PolicySet { policy_id: "outbound-email".into(), version: "1".into(), rules: Vec::new() };
+    let approved = snapshot_hash(&policy).unwrap();
+    let mut opts = VerifyOptions::default();
+    opts.approved_policy_snapshots = vec![approved.clone()];
+
+    let sb = common::signed_value();
+    let err = first_error(&verify_signed_bundle_with_options(&sb, None, &opts));
+    assert!(err.starts_with("POLICY SNAPSHOT UNAPPROVED"), "{}", err);
+
+    let mut bundle = sb["bundle"].clone();
+    bundle["policy_decision"]["policy_snapshot_hash"] = approved.into();
+    let result = verify_signed_bundle_with_options(&common::resign(&bundle), None, &opts);
+    assert!(result.verified, "{:?}", result.errors);
+}
+
+#[test]
+fn required_hashes_and_allowed_algs() {
+    let mut sb = common::signed_value();
+    sb["signature"].as_object_mut().unwrap().remove("merkle_root");
+    assert!(verify_signed_bundle(&sb, None).verified);
+
+    let mut opts = VerifyOptions::default();
+    opts.require_bundle_hash = true;
+    assert!(verify_signed_bundle_with_options(&sb, None, &opts).verified);
+    opts.require_merkle_root = true;
+    let err = first_error(&verify_signed_bundle_with_options(&sb, None, &opts));
+    assert_eq!(err, "Missing merkle_root: no merkle_root in signature");
+
+    let mut opts = VerifyOptions::default();
+    opts.allowed_algs = vec!["ed25519".into()];
+    assert!(verify_signed_bundle_with_options(&sb, None, &opts).verified);
+    opts.allowed_algs = vec!["ml-dsa-65".into()];
+    let err = first_error(&verify_signed_bundle_with_options(&sb, None, &opts));
+    assert!(err.starts_with("WEAK ALGORITHM REJECTED"), "{}", err);
+    // Relabelling the Ed25519 signature does not satisfy the list.
+    opts.allowed_algs = vec!["slh-dsa-192f".into()];
+    sb["signature"]["alg"] = "slh-dsa-192f".into();
+    let err = first_error(&verify_signed_bundle_with_options(&sb, None, &opts));
+    assert!(err.starts_with("UNSUPPORTED SIGNATURE ALG"), "{}", err);
+}
+
+#[test]
+fn entry_freshness_window_on_builder_and_verifier() {
+    use std::time::Duration;
+
+    use dcp_ai::bundle::{sign_bundle, BundleBuilder, SignOptions};
+    use dcp_ai::crypto::generate_keypair;
+
+    let (pk, sk) = generate_keypair();
+    let base = common::sample_bundle(&pk);
+    let builder = || {
+        let mut builder = BundleBuilder::new()
+            .responsible_principal_record(base.responsible_principal_record.clone())
+            .agent_passport(base.agent_passport.clone())
+            .intent(base.intent.clone())
+            .policy_decision(base.policy_decision.clone());
+        for entry in &base.audit_entries {
+            builder = builder.add_audit_entry(entry.clone());
+        }
+        builder
+    };
+    let err = builder().finalize_with_freshness(Duration::from_secs(3600)).unwrap_err();
+    assert!(err.contains("audit001"), "{}", err);
+    let bundle = builder().finalize_with_freshness(Duration::from_secs(100 * 365 * 86_400)).unwrap();
+
+    // Entries at 01:01-01:03, signed at 01:33: two are past a 30 minute window.
+    let mut sign = SignOptions::new(&sk);
+    sign.created_at = Some("2026-01-01T01:33:00Z");
+    let sb = serde_json::to_value(sign_bundle(bundle, &sign).unwrap()).unwrap();
+    let mut opts = VerifyOptions::default();
+    opts.max_entry_age = Some(Duration::from_secs(1800));
+    let result = verify_signed_bundle_with_options(&sb, None, &opts);
+    assert!(result.verified, "{:?}", result.errors);
+    assert_eq!(result.warnings.len(), 2, "{:?}", result.warnings);
+    assert!(result.warnings.iter().all(|w| w.starts_with("STALE AUDIT ENTRY")));
+}
+
+#[test]
+fn both_intent_hash_forms_are_accepted() {
+    use dcp_ai::bundle::{sign_bundle, AuditEntryFields, BundleBuilder, SignOptions};
+    use dcp_ai::crypto::{generate_keypair, hash_object, hash_object_normalized, intent_hash};
+
+    let (pk, sk) = generate_keypair();
+    let mut base = common::sample_bundle(&pk);
+    base.intent.data_classes = vec!["pii".into(), "contact_info".into()];
+    let intent = serde_json::to_value(&base.intent).unwrap();
+    let (spec, normalized) = (intent_hash(&intent), hash_object_normalized(&intent));
+    assert_eq!(spec, hash_object(&intent));
+    assert_ne!(spec, normalized);
+
+    // The builder emits the spec hash, as the other SDKs do.
+    let entry = base.audit_entries[0].clone();
+    let built = BundleBuilder::new()
+        .responsible_principal_record(base.responsible_principal_record.clone())
+        .agent_passport(base.agent_passport.clone())
+        .intent(base.intent.clone())
+        .policy_decision(base.policy_decision.clone())
+        .create_audit_entry(AuditEntryFields {
+            audit_id: entry.audit_id,
+            timestamp: entry.timestamp,
+            agent_id: entry.agent_id,
+            human_id: entry.human_id,
+            policy_decision: entry.policy_decision,
+            outcome: entry.outcome,
+            evidence: entry.evidence,
+        })
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(built.audit_entries[0].intent_hash, spec);
+    let sb = serde_json::to_value(sign_bundle(built, &SignOptions::new(&sk)).unwrap()).unwrap();
+    assert!(verify_signed_bundle(&sb, None).verified);
+
+    // Producers that sort data_classes first emit the normalized hash;
+    // it verifies by default too, and any other hash does not.
+    for (hash, verifies) in [(normalized.as_str(), true), ("sha256:00", false)] {
+        let mut value = serde_json::to_value(&base).unwrap();
+        let mut prev = "GENESIS".to_string();
+        for entry in value["audit_entries"].as_array_mut().unwrap() {
+            entry["intent_hash"] = hash.into();
+            entry["prev_hash"] = prev.into();
+            prev = hash_object(entry);
+        }
+        let typed = serde_json::from_value(value).unwrap();
+        let sb = serde_json::to_value(sign_bundle(typed, &SignOptions::new(&sk)).unwrap()).unwrap();
+        let result = verify_signed_bundle(&sb, None);
+        assert_eq!(result.verified, verifies, "{:?}", result.errors);
+    }
+}